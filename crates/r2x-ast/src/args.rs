@@ -0,0 +1,817 @@
+//! Keyword arguments of plugin registration calls
+//!
+//! Registration decorators such as `@expose_plugin(tags=["reeds"])` carry
+//! metadata that is read statically from the source next to the decorated
+//! symbol.
+
+use crate::naming::find_matching_paren;
+use r2x_manifest::types::DecoratorRegistration;
+use std::sync::Arc;
+
+/// Decorators that register a plugin and may carry keyword metadata
+const REGISTRATION_DECORATORS: &[&str] = &["expose_plugin"];
+
+/// Registration keyword arguments that map onto plugin metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KwArgRole {
+    /// `tags=[...]`: free-form labels used for filtering
+    Tags,
+    /// `description="..."`: one-line summary shown in plugin listings
+    Description,
+    /// `aliases=[...]`: former names that still resolve to the plugin
+    Aliases,
+    /// `requires_core="0.5"`: oldest r2x-core the plugin runs on
+    RequiresCore,
+}
+
+impl KwArgRole {
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "tags" => Some(Self::Tags),
+            "description" => Some(Self::Description),
+            "aliases" => Some(Self::Aliases),
+            "requires_core" => Some(Self::RequiresCore),
+            _ => None,
+        }
+    }
+}
+
+/// Metadata parsed from a registration call's keyword arguments
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistrationArgs {
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub aliases: Vec<String>,
+    pub requires_core: Option<String>,
+}
+
+/// Parse the text between a registration call's parentheses
+pub fn parse_registration_args(args_text: &str) -> RegistrationArgs {
+    let mut args = RegistrationArgs::default();
+
+    for arg in split_top_level(args_text) {
+        let Some((keyword, value)) = arg.split_once('=') else {
+            continue;
+        };
+        match KwArgRole::from_keyword(keyword.trim()) {
+            Some(KwArgRole::Tags) => args.tags = parse_string_list(value),
+            Some(KwArgRole::Description) => args.description = unquote(value.trim()),
+            Some(KwArgRole::Aliases) => args.aliases = parse_string_list(value),
+            Some(KwArgRole::RequiresCore) => args.requires_core = unquote(value.trim()),
+            None => {}
+        }
+    }
+
+    args
+}
+
+/// Find the registration decorator applied to `symbol` and parse its arguments
+///
+/// Returns `None` when the symbol is not defined in `content` or has no
+/// registration decorator. A bare decorator yields empty arguments.
+pub fn find_registration_args(content: &str, symbol: &str) -> Option<RegistrationArgs> {
+    let definition_start = find_definition_line(content, symbol)?;
+    let before = &content[..definition_start];
+
+    let (decorator_start, decorator) = REGISTRATION_DECORATORS
+        .iter()
+        .filter_map(|name| find_decorator(before, name).map(|pos| (pos, *name)))
+        .max_by_key(|(pos, _)| *pos)?;
+
+    // Another definition between the decorator and the symbol means the
+    // decorator belongs to something else.
+    let between = &before[decorator_start..];
+    if between
+        .lines()
+        .skip(1)
+        .any(|line| is_definition_line(line.trim_start()))
+    {
+        return None;
+    }
+
+    let after_name = between[1 + decorator.len()..].trim_start();
+    let Some(call) = after_name.strip_prefix('(') else {
+        return Some(RegistrationArgs::default());
+    };
+    let end = find_matching_paren(call)?;
+    Some(parse_registration_args(&call[..end]))
+}
+
+/// Decorator methods that register an upgrade step across r2x-core versions
+pub const DEFAULT_UPGRADER_STEP_DECORATORS: &[&str] = &["register_step", "step", "upgrade"];
+
+/// Decorator methods that register steps on the upgrader `class_name`
+///
+/// An upgrader can name its own with a `step_decorators` class attribute
+/// holding a tuple or list of strings, e.g. `step_decorators = ("migrate",)`;
+/// otherwise [`DEFAULT_UPGRADER_STEP_DECORATORS`] apply.
+pub fn upgrader_step_decorators(content: &str, class_name: &str) -> Vec<String> {
+    let declared = find_class_attribute(content, class_name, "step_decorators").and_then(|value| {
+        let inner = value
+            .strip_prefix('(')
+            .and_then(|v| v.strip_suffix(')'))
+            .or_else(|| value.strip_prefix('[').and_then(|v| v.strip_suffix(']')))?;
+        split_top_level(inner)
+            .iter()
+            .map(|item| unquote(item.trim()))
+            .collect::<Option<Vec<_>>>()
+    });
+    declared.unwrap_or_else(|| {
+        DEFAULT_UPGRADER_STEP_DECORATORS
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    })
+}
+
+/// Functions registered through `@<owner>.<method>(...)` decorators
+///
+/// This links a plugin class such as an upgrader with the steps registered
+/// on it. Only decorators whose method is in `methods` are recognized, and
+/// each registration records the method it used. `target_version=`,
+/// `min_version=`, `max_version=` and `priority=` are read from the
+/// decorator call; decorators that are not applied to a function are ignored.
+pub fn find_decorator_registrations(
+    content: &str,
+    owner: &str,
+    methods: &[&str],
+) -> Vec<DecoratorRegistration> {
+    let needle = format!("@{}.", owner);
+    let mut registrations = Vec::new();
+
+    for (pos, _) in content.match_indices(&needle) {
+        let line_start = content[..pos].rfind('\n').map_or(0, |i| i + 1);
+        if !content[line_start..pos].trim().is_empty() {
+            continue;
+        }
+
+        let rest = &content[pos + needle.len()..];
+        let method_len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if method_len == 0 {
+            continue;
+        }
+        let (method, after) = rest.split_at(method_len);
+        if !methods.contains(&method) {
+            continue;
+        }
+
+        let (args_text, tail) = match after.strip_prefix('(') {
+            Some(call) => {
+                let Some(end) = find_matching_paren(call) else {
+                    continue;
+                };
+                (&call[..end], &call[end + 1..])
+            }
+            None => ("", after),
+        };
+
+        let Some(function) = decorated_function_name(tail) else {
+            continue;
+        };
+
+        let mut registration = DecoratorRegistration {
+            function: Arc::from(function),
+            decorator: Arc::from(method),
+            target_version: None,
+            min_version: None,
+            max_version: None,
+            priority: None,
+        };
+        for arg in split_top_level(args_text) {
+            let Some((keyword, value)) = arg.split_once('=') else {
+                continue;
+            };
+            match keyword.trim() {
+                "target_version" => {
+                    registration.target_version = unquote(value.trim()).map(Arc::from);
+                }
+                "min_version" => {
+                    registration.min_version = unquote(value.trim()).map(Arc::from);
+                }
+                "max_version" => {
+                    registration.max_version = unquote(value.trim()).map(Arc::from);
+                }
+                "priority" => registration.priority = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+        registrations.push(registration);
+    }
+
+    registrations
+}
+
+/// Name of the function defined after a decorator, skipping stacked decorators
+fn decorated_function_name(tail: &str) -> Option<&str> {
+    let line = tail
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with(['@', '#']))?;
+    let rest = line
+        .strip_prefix("async def ")
+        .or_else(|| line.strip_prefix("def "))?;
+    let name = rest[..rest.find('(')?].trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// First paragraph of the docstring of the class or function `symbol`
+///
+/// The docstring is the first statement of the body when it is a
+/// triple-quoted string. Lines are trimmed and joined with spaces.
+pub fn find_docstring(content: &str, symbol: &str) -> Option<String> {
+    let definition_start = find_definition_line(content, symbol)?;
+    let definition = &content[definition_start..];
+    let body_start = find_body_start(definition)?;
+    let body = definition[body_start..].trim_start();
+
+    let body = body
+        .strip_prefix(['r', 'R', 'u', 'U'])
+        .filter(|rest| rest.starts_with(['"', '\'']))
+        .unwrap_or(body);
+    let (quote, rest) = ["\"\"\"", "'''"]
+        .iter()
+        .find_map(|quote| body.strip_prefix(quote).map(|rest| (*quote, rest)))?;
+    let docstring = &rest[..rest.find(quote)?];
+
+    let paragraph: Vec<&str> = docstring
+        .trim()
+        .lines()
+        .map(str::trim)
+        .take_while(|line| !line.is_empty())
+        .collect();
+    if paragraph.is_empty() {
+        None
+    } else {
+        Some(paragraph.join(" "))
+    }
+}
+
+/// Source text assigned to a class-level attribute of `class_name`
+///
+/// Matches `attribute = value` and `attribute: Type = value` directly in the
+/// class body (not in methods). String literals are unquoted; any other
+/// expression is returned as written. Only single-line values are read.
+pub fn find_class_attribute(content: &str, class_name: &str, attribute: &str) -> Option<String> {
+    let definition_start = find_definition_line(content, class_name)?;
+    let definition = &content[definition_start..];
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let class_indent = indent_of(definition.lines().next()?);
+    let body_start = find_body_start(definition)?;
+
+    let mut body_indent = None;
+    for line in definition[body_start..].lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = indent_of(line);
+        if indent <= class_indent {
+            // A one-line class (`class A: x = 1`) or the end of the body
+            if body_indent.is_some() {
+                break;
+            }
+            continue;
+        }
+        if *body_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+
+        let Some(rest) = trimmed.strip_prefix(attribute) else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let value = match rest.strip_prefix(':') {
+            Some(annotated) => annotated.split_once('=').map(|(_, value)| value),
+            None => rest
+                .strip_prefix('=')
+                .filter(|value| !value.starts_with('=')),
+        };
+        let Some(value) = value.map(str::trim) else {
+            continue;
+        };
+        let value = match value.find(" #") {
+            Some(comment) if !value.starts_with(['"', '\'']) => value[..comment].trim_end(),
+            _ => value,
+        };
+        return Some(unquote(value).unwrap_or_else(|| value.to_string()));
+    }
+    None
+}
+
+/// Source text of the `if` conditions `symbol` is defined under
+///
+/// `if`/`elif` tests are kept as written and an `else` branch becomes the
+/// negation of the branches before it; nested blocks are joined with `and`,
+/// outermost first. Nothing is evaluated, so every platform-gated definition
+/// is still discovered. Only single-line conditions are read.
+pub fn find_definition_condition(content: &str, symbol: &str) -> Option<String> {
+    let definition_start = find_definition_line(content, symbol)?;
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let branch_test = |line: &str, keyword: &str| {
+        line.strip_prefix(keyword)
+            .and_then(|rest| rest.trim_end().strip_suffix(':'))
+            .map(|test| test.trim().to_string())
+    };
+
+    let mut indent = indent_of(content[definition_start..].lines().next()?);
+    // Innermost first; reversed before joining
+    let mut conditions: Vec<String> = Vec::new();
+    // Terms of the branch being completed by walking up its `elif`/`if` chain
+    let mut branch: Option<Vec<String>> = None;
+
+    for line in content[..definition_start].lines().rev() {
+        if indent == 0 && branch.is_none() {
+            break;
+        }
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let line_indent = indent_of(line);
+        if line_indent > indent || (line_indent == indent && branch.is_none()) {
+            continue;
+        }
+
+        if let Some(terms) = branch.as_mut() {
+            if line_indent == indent {
+                if let Some(test) = branch_test(trimmed, "elif ") {
+                    terms.push(format!("not ({})", test));
+                    continue;
+                }
+                if let Some(test) = branch_test(trimmed, "if ") {
+                    terms.push(format!("not ({})", test));
+                    terms.reverse();
+                    conditions.push(terms.join(" and "));
+                }
+                // `else` of a `for`/`while`/`try` block: not a condition
+                branch = None;
+                continue;
+            }
+            branch = None;
+        }
+
+        indent = line_indent;
+        if let Some(test) = branch_test(trimmed, "if ") {
+            conditions.push(test);
+        } else if let Some(test) = branch_test(trimmed, "elif ") {
+            branch = Some(vec![test]);
+        } else if branch_test(trimmed, "else").is_some() {
+            branch = Some(Vec::new());
+        }
+    }
+
+    if conditions.is_empty() {
+        return None;
+    }
+    conditions.reverse();
+    Some(conditions.join(" and "))
+}
+
+/// Offset just past the `:` that ends a definition's signature
+fn find_body_start(definition: &str) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+
+    for (i, ch) in definition.char_indices() {
+        if let Some(q) = quote {
+            if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '\'' | '"' => quote = Some(ch),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ':' if depth == 0 => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn find_definition_line(content: &str, symbol: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let rest = trimmed
+            .strip_prefix("async def ")
+            .or_else(|| trimmed.strip_prefix("def "))
+            .or_else(|| trimmed.strip_prefix("class "));
+        if let Some(rest) = rest {
+            if rest
+                .strip_prefix(symbol)
+                .is_some_and(|tail| tail.trim_start().starts_with(['(', ':']))
+            {
+                return Some(offset);
+            }
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn is_definition_line(line: &str) -> bool {
+    ["def ", "async def ", "class "]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+fn find_decorator(text: &str, name: &str) -> Option<usize> {
+    let needle = format!("@{}", name);
+    text.match_indices(&needle)
+        .filter(|(pos, _)| {
+            let next = text[pos + needle.len()..].chars().next();
+            !next.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+        })
+        .map(|(pos, _)| pos)
+        .last()
+}
+
+/// Split on commas that are not nested in brackets or quotes
+///
+/// `# ...` comments outside strings are dropped up to the end of the line,
+/// and empty segments, such as the one after a trailing comma, are skipped.
+fn split_top_level(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut chars = text.chars();
+
+    while let Some(ch) = chars.next() {
+        if let Some(q) = quote {
+            if ch == q {
+                quote = None;
+            }
+            part.push(ch);
+            continue;
+        }
+        match ch {
+            '#' => {
+                if chars.by_ref().any(|c| c == '\n') {
+                    part.push('\n');
+                }
+                continue;
+            }
+            ',' if depth == 0 => {
+                parts.push(part.trim().to_string());
+                part.clear();
+                continue;
+            }
+            '\'' | '"' => quote = Some(ch),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ => {}
+        }
+        part.push(ch);
+    }
+    parts.push(part.trim().to_string());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Parse a list or tuple literal of strings, ignoring non-literal elements
+fn parse_string_list(value: &str) -> Vec<String> {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .or_else(|| value.strip_prefix('(').and_then(|v| v.strip_suffix(')')))
+        .unwrap_or(value);
+
+    split_top_level(inner)
+        .iter()
+        .filter_map(|item| unquote(item))
+        .collect()
+}
+
+fn unquote(text: &str) -> Option<String> {
+    ['"', '\''].iter().find_map(|&q| {
+        text.strip_prefix(q)
+            .and_then(|t| t.strip_suffix(q))
+            .map(ToString::to_string)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::args::*;
+
+    #[test]
+    fn test_parse_multiple_tags_with_mixed_quotes() {
+        let args = parse_registration_args(r#"name="break-gens", tags=["experimental", 'reeds']"#);
+        assert_eq!(args.tags, vec!["experimental", "reeds"]);
+    }
+
+    #[test]
+    fn test_parse_empty_tags_list() {
+        assert!(parse_registration_args("tags=[]").tags.is_empty());
+        assert!(parse_registration_args("tags=( )").tags.is_empty());
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_trailing_comma() {
+        let args = parse_registration_args(
+            "\n    name=\"x\",  # the plugin's name, kept short\n    tags=[\"a\", \"b\",],  # (two)\n    description=\"Uses # in text\",\n    # trailing note\n",
+        );
+        assert_eq!(args.tags, vec!["a", "b"]);
+        assert_eq!(args.description.as_deref(), Some("Uses # in text"));
+    }
+
+    #[test]
+    fn test_find_registration_args_with_commented_multiline_call() {
+        let content = "@expose_plugin(\n    tags=[\"reeds\"],  # keep in sync (see docs)\n    description=\"Break generators\",\n    # closing comment\n)\ndef break_gens(system):\n    pass\n";
+        let args = find_registration_args(content, "break_gens");
+        assert_eq!(
+            args,
+            Some(RegistrationArgs {
+                tags: vec!["reeds".to_string()],
+                description: Some("Break generators".to_string()),
+                aliases: Vec::new(),
+                requires_core: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_without_tags_argument() {
+        assert_eq!(
+            parse_registration_args(r#"name="x""#),
+            RegistrationArgs::default()
+        );
+    }
+
+    #[test]
+    fn test_find_registration_args_for_decorated_function() {
+        let content = r#"
+from r2x_core import expose_plugin
+
+@expose_plugin(
+    tags=["sysmod", "reeds,pcm"],
+)
+def add_pcm_defaults(system, path: str = "defaults.json"):
+    return system
+
+@expose_plugin
+def break_gens(system):
+    return system
+
+def helper():
+    pass
+"#;
+        let args = find_registration_args(content, "add_pcm_defaults");
+        assert!(args.is_some_and(|a| a.tags == vec!["sysmod", "reeds,pcm"]));
+        assert_eq!(
+            find_registration_args(content, "break_gens"),
+            Some(RegistrationArgs::default())
+        );
+        assert_eq!(find_registration_args(content, "helper"), None);
+        assert_eq!(find_registration_args(content, "missing"), None);
+    }
+
+    #[test]
+    fn test_parse_description_argument() {
+        let args = parse_registration_args(r#"tags=["a"], description="Add PCM defaults""#);
+        assert_eq!(args.description.as_deref(), Some("Add PCM defaults"));
+    }
+
+    #[test]
+    fn test_parse_requires_core_argument() {
+        let args = parse_registration_args(r#"name="add-pcm", requires_core="0.5.1""#);
+        assert_eq!(args.requires_core.as_deref(), Some("0.5.1"));
+    }
+
+    #[test]
+    fn test_parse_aliases_argument() {
+        let args = parse_registration_args(r#"name="add-pcm", aliases=["pcm-defaults", 'pcm']"#);
+        assert_eq!(args.aliases, vec!["pcm-defaults", "pcm"]);
+    }
+
+    #[test]
+    fn test_find_decorator_registrations_for_upgrader_steps() {
+        let content = r#"
+class ReEDSUpgrader(BaseUpgrader):
+    pass
+
+@ReEDSUpgrader.register_step(target_version="2.0", priority=10)
+def rename_columns(folder):
+    pass
+
+@ReEDSUpgrader.register_step(
+    target_version='1.5',
+    min_version="1.0",
+    priority=5,
+)
+@log_step
+def move_files(folder):
+    pass
+
+@ReEDSUpgrader.register_step
+def drop_cache(folder):
+    pass
+
+@OtherUpgrader.register_step(priority=1)
+def unrelated(folder):
+    pass
+"#;
+        let registrations = find_decorator_registrations(
+            content,
+            "ReEDSUpgrader",
+            DEFAULT_UPGRADER_STEP_DECORATORS,
+        );
+        let summary: Vec<_> = registrations
+            .iter()
+            .map(|r| {
+                (
+                    r.function.as_ref(),
+                    r.decorator.as_ref(),
+                    r.target_version.as_deref(),
+                    r.priority,
+                )
+            })
+            .collect();
+        assert_eq!(registrations[1].min_version.as_deref(), Some("1.0"));
+        assert_eq!(
+            summary,
+            vec![
+                ("rename_columns", "register_step", Some("2.0"), Some(10)),
+                ("move_files", "register_step", Some("1.5"), Some(5)),
+                ("drop_cache", "register_step", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_decorator_registrations_accepts_configured_methods() {
+        let content = "
+@Upgrader.register_step(priority=1)
+def first(folder):
+    pass
+
+@Upgrader.step(priority=2)
+def second(folder):
+    pass
+
+@Upgrader.migrate(priority=3)
+def third(folder):
+    pass
+
+@Upgrader.helper
+def not_a_step(folder):
+    pass
+";
+        let found = |methods: &[&str]| -> Vec<(String, String)> {
+            find_decorator_registrations(content, "Upgrader", methods)
+                .into_iter()
+                .map(|r| (r.function.to_string(), r.decorator.to_string()))
+                .collect()
+        };
+        let pair = |function: &str, decorator: &str| (function.to_string(), decorator.to_string());
+
+        assert_eq!(
+            found(DEFAULT_UPGRADER_STEP_DECORATORS),
+            vec![pair("first", "register_step"), pair("second", "step")]
+        );
+        assert_eq!(
+            found(&["register_step", "step", "migrate"]),
+            vec![
+                pair("first", "register_step"),
+                pair("second", "step"),
+                pair("third", "migrate"),
+            ]
+        );
+        assert!(found(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_upgrader_step_decorators_reads_class_attribute() {
+        let content = r#"
+class Upgrader(PluginUpgrader):
+    step_decorators = ("register_step", 'migrate')
+
+class Other(PluginUpgrader):
+    step_decorators = ["migrate"]
+
+class Plain(PluginUpgrader):
+    version_strategy = "semver"
+"#;
+        assert_eq!(
+            upgrader_step_decorators(content, "Upgrader"),
+            ["register_step", "migrate"]
+        );
+        assert_eq!(upgrader_step_decorators(content, "Other"), ["migrate"]);
+        assert_eq!(
+            upgrader_step_decorators(content, "Plain"),
+            DEFAULT_UPGRADER_STEP_DECORATORS
+        );
+    }
+
+    #[test]
+    fn test_find_class_attribute() {
+        let content = r#"
+class ReEDSUpgrader(BaseUpgrader):
+    """Upgrade ReEDS runs."""
+
+    version_strategy = SemanticVersioningStrategy  # compare as semver
+    version_reader: VersionReader = ReEDSVersionReader()
+    label = "reeds"
+
+    def run(self):
+        version_reader = None
+        return version_reader
+
+class Other(BaseUpgrader):
+    version_reader = OtherReader()
+"#;
+        let attribute = |name: &str| find_class_attribute(content, "ReEDSUpgrader", name);
+        assert_eq!(
+            attribute("version_strategy").as_deref(),
+            Some("SemanticVersioningStrategy")
+        );
+        assert_eq!(
+            attribute("version_reader").as_deref(),
+            Some("ReEDSVersionReader()")
+        );
+        assert_eq!(attribute("label").as_deref(), Some("reeds"));
+        assert_eq!(attribute("missing"), None);
+        assert_eq!(
+            find_class_attribute(content, "Other", "version_strategy"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_docstring_takes_first_paragraph() {
+        let content = r#"
+class ReEDSParser(BaseParser[ReEDSConfig]):
+    """Parse ReEDS outputs
+    into an infrasys system.
+
+    Longer notes that should not be part of the description.
+    """
+
+    def build_system(self):
+        """Build it."""
+
+class NoDocs(BaseParser):
+    def build_system(self):
+        """Not a class docstring."""
+
+def add_pcm_defaults(
+    system: System,
+    path: str = "defaults.json",
+) -> System:
+    '''Apply PCM defaults.'''
+    return system
+"#;
+        assert_eq!(
+            find_docstring(content, "ReEDSParser").as_deref(),
+            Some("Parse ReEDS outputs into an infrasys system.")
+        );
+        assert_eq!(find_docstring(content, "NoDocs"), None);
+        assert_eq!(
+            find_docstring(content, "add_pcm_defaults").as_deref(),
+            Some("Apply PCM defaults.")
+        );
+    }
+
+    #[test]
+    fn definition_condition_captures_enclosing_branches() {
+        let content = r#"
+import sys
+
+if sys.platform == "win32":
+    class WinParser(Plugin[WinConfig]):
+        pass
+elif sys.platform == "darwin":
+    def mac_transform(system):
+        return system
+else:
+    if HAS_GDAL:
+        @expose_plugin
+        def gis_transform(system):
+            return system
+
+class Everywhere(Plugin[Config]):
+    pass
+"#;
+        assert_eq!(
+            find_definition_condition(content, "WinParser").as_deref(),
+            Some(r#"sys.platform == "win32""#)
+        );
+        assert_eq!(
+            find_definition_condition(content, "mac_transform").as_deref(),
+            Some(r#"not (sys.platform == "win32") and sys.platform == "darwin""#)
+        );
+        assert_eq!(
+            find_definition_condition(content, "gis_transform").as_deref(),
+            Some(
+                r#"not (sys.platform == "win32") and not (sys.platform == "darwin") and HAS_GDAL"#
+            )
+        );
+        assert_eq!(find_definition_condition(content, "Everywhere"), None);
+    }
+}
@@ -3,7 +3,7 @@
 //! These types are used during AST parsing and discovery. They are distinct
 //! from the manifest types and keep AST-specific details out of the manifest.
 
-use r2x_manifest::types::SchemaFields;
+use r2x_manifest::types::{DefaultValue, SchemaFields};
 use serde::{Deserialize, Serialize};
 
 /// Entry point information parsed from entry_points.txt
@@ -56,7 +56,7 @@ pub struct ConfigField {
     pub name: String,
     /// Array of type alternatives (for union types like int | str)
     pub types: Vec<String>,
-    pub default: Option<String>,
+    pub default: Option<DefaultValue>,
     pub required: bool,
     /// Description extracted from Field(description="...")
     pub description: Option<String>,
@@ -39,9 +39,12 @@ pub fn find_pyproject_toml_path(package_path: &Path, discovery_root: &Path) -> O
     None
 }
 
-/// Parse entry points from pyproject.toml content (PEP 621 format)
+/// Parse entry points from pyproject.toml content
 ///
-/// Looks for entry points in `[project.entry-points]` table.
+/// Looks for entry points in the PEP 621 `[project.entry-points]` table and
+/// the legacy Poetry `[tool.poetry.plugins]` table. Section names may be bare
+/// or quoted (`[project.entry-points."r2x_plugin"]`); TOML resolves both to
+/// the same key.
 pub fn parse_pyproject_entry_points(content: &str) -> Vec<EntryPointInfo> {
     let mut entries = Vec::new();
     let parsed: toml::Value = match toml::from_str(content) {
@@ -49,32 +52,39 @@ pub fn parse_pyproject_entry_points(content: &str) -> Vec<EntryPointInfo> {
         Err(_) => return entries,
     };
 
-    let entry_points = match parsed
+    let pep621 = parsed
         .get("project")
         .and_then(|project| project.get("entry-points"))
-        .and_then(|value| value.as_table())
-    {
-        Some(entry_points) => entry_points,
-        None => return entries,
-    };
-
-    for (section, values) in entry_points {
-        if !is_r2x_section(section) {
-            continue;
-        }
-
-        let Some(table) = values.as_table() else {
-            continue;
-        };
+        .and_then(|value| value.as_table());
+    let poetry = parsed
+        .get("tool")
+        .and_then(|tool| tool.get("poetry"))
+        .and_then(|poetry| poetry.get("plugins"))
+        .and_then(|value| value.as_table());
+
+    for entry_points in [pep621, poetry].into_iter().flatten() {
+        for (section, values) in entry_points {
+            if !is_r2x_section(section) {
+                continue;
+            }
 
-        for (name, target) in table {
-            let Some(target_str) = target.as_str() else {
+            let Some(table) = values.as_table() else {
                 continue;
             };
 
-            let line = format!("{} = {}", name, target_str);
-            if let Some(entry) = parse_entry_point_line(&line, section) {
-                entries.push(entry);
+            for (name, target) in table {
+                let Some(target_str) = target.as_str() else {
+                    continue;
+                };
+
+                let line = format!("{} = {}", name, target_str);
+                if let Some(entry) = parse_entry_point_line(&line, section) {
+                    if !entries.iter().any(|e: &EntryPointInfo| {
+                        e.section == entry.section && e.name == entry.name
+                    }) {
+                        entries.push(entry);
+                    }
+                }
             }
         }
     }
@@ -131,6 +141,52 @@ cli = "some_package:main"
         assert!(entries.is_empty());
     }
 
+    #[test]
+    fn test_parse_pyproject_entry_points_setuptools_style() {
+        let content = r#"
+[project.entry-points.r2x_plugin]
+reeds = "r2x_reeds:ReEDSParser"
+"#;
+        let entries = parse_pyproject_entry_points(content);
+        assert_eq!(entries.len(), 1);
+        assert!(entries
+            .first()
+            .is_some_and(|e| e.section == "r2x_plugin" && e.module == "r2x_reeds"));
+    }
+
+    #[test]
+    fn test_parse_pyproject_entry_points_quoted_section() {
+        let content = r#"
+[project.entry-points."r2x_plugin"]
+"plexos" = "r2x_plexos.plugins:register"
+"#;
+        let entries = parse_pyproject_entry_points(content);
+        assert_eq!(entries.len(), 1);
+        assert!(entries.first().is_some_and(|e| e.section == "r2x_plugin"
+            && e.name == "plexos"
+            && e.module == "r2x_plexos.plugins"
+            && e.symbol == "register"));
+    }
+
+    #[test]
+    fn test_parse_pyproject_entry_points_poetry_style() {
+        let content = r#"
+[tool.poetry]
+name = "r2x-sienna"
+
+[tool.poetry.plugins."r2x_plugin"]
+sienna = "r2x_sienna:SiennaParser"
+
+[tool.poetry.plugins."console_scripts"]
+sienna-cli = "r2x_sienna.cli:main"
+"#;
+        let entries = parse_pyproject_entry_points(content);
+        assert_eq!(entries.len(), 1);
+        assert!(entries
+            .first()
+            .is_some_and(|e| e.name == "sienna" && e.symbol == "SiennaParser"));
+    }
+
     #[test]
     fn test_parse_pyproject_entry_points_invalid_toml() {
         let content = "not valid toml {{{";
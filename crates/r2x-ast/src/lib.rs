@@ -9,12 +9,19 @@
 //! This approach is significantly faster than Python-based discovery and requires
 //! no Python interpreter startup.
 
+pub mod args;
 pub mod discovery_types;
 pub mod entry_points;
 pub mod naming;
 pub mod package_cache;
+mod patterns;
 pub mod schema_extractor;
+pub mod source;
 
+use crate::args::{
+    find_class_attribute, find_decorator_registrations, find_definition_condition, find_docstring,
+    find_registration_args, upgrader_step_decorators,
+};
 use crate::discovery_types::{ConfigField, ConfigSpec, EntryPointInfo};
 use crate::entry_points::{parser as entry_parser, pyproject as entry_pyproject};
 // Re-export for tests
@@ -22,11 +29,18 @@ use crate::entry_points::{parser as entry_parser, pyproject as entry_pyproject};
 use crate::entry_points::parser::is_r2x_section;
 use crate::naming::{camel_to_kebab, find_matching_paren, snake_to_kebab};
 use crate::package_cache::PackageAstCache;
+use crate::patterns::ANNOTATED_ASSIGNMENT;
 use crate::schema_extractor::TypeResolver;
+use crate::source::read_python_source;
 use anyhow::{anyhow, Result};
 use ast_grep_language::Python;
 use r2x_logger as logger;
-use r2x_manifest::types::{Parameter, Plugin, PluginType};
+use r2x_manifest::runtime::{
+    ambiguous_step_order, infer_call_method, infer_plugin_role, missing_call_method, PluginRole,
+};
+use r2x_manifest::types::{
+    DecoratorRegistration, DefaultValue, Parameter, Plugin, PluginType, UpgradeSpec,
+};
 use smallvec::SmallVec;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -103,11 +117,31 @@ impl AstDiscovery {
     /// # Returns
     /// Vector of discovered plugins
     pub fn discover_plugins(
+        package_path: &Path,
+        package_name_full: &str,
+        venv_path: Option<&str>,
+        package_version: Option<&str>,
+        dist_info_path: Option<&Path>,
+    ) -> Result<Vec<Plugin>> {
+        Self::discover_plugins_with_dev_dirs(
+            package_path,
+            package_name_full,
+            venv_path,
+            package_version,
+            dist_info_path,
+            false,
+        )
+    }
+
+    /// Like [`Self::discover_plugins`], also scanning top-level `tests/` and
+    /// `examples/` directories when `include_dev` is set (`--include-dev`)
+    pub fn discover_plugins_with_dev_dirs(
         package_path: &Path,
         package_name_full: &str,
         venv_path: Option<&str>,
         _package_version: Option<&str>,
         dist_info_path: Option<&Path>,
+        include_dev: bool,
     ) -> Result<Vec<Plugin>> {
         let total_start = Instant::now();
         logger::debug(&format!("AST discovery started for: {}", package_name_full));
@@ -188,7 +222,7 @@ impl AstDiscovery {
 
         if entry_point_entries.is_empty() {
             let ast_start = Instant::now();
-            let cache = PackageAstCache::build(&discovery_root);
+            let cache = PackageAstCache::build_with_dev_dirs(&discovery_root, include_dev);
             class_entries =
                 Self::discover_plugins_with_ast_grep(&cache, &discovery_root, package_name_full);
             function_entries =
@@ -239,6 +273,7 @@ impl AstDiscovery {
                 &mut file_cache,
                 &mut package_cache,
                 is_site_packages,
+                include_dev,
             ) {
                 Ok(plugin) => {
                     logger::debug(&format!(
@@ -612,6 +647,11 @@ impl AstDiscovery {
     }
 
     /// Infer module path from file path
+    ///
+    /// The module is taken relative to the outermost directory above the file
+    /// that still has an `__init__.py`, so flat and src layouts (and import
+    /// names that differ from the distribution name) resolve the same way.
+    /// Files outside a regular package fall back to prefixing `package_name`.
     fn infer_module_from_file_path(
         file_path: &str,
         package_path: &Path,
@@ -621,6 +661,18 @@ impl AstDiscovery {
         // Normalize package name: dashes to underscores for Python module names
         let normalized_package = package_name.replace('-', "_");
 
+        if let Some(rel) = import_root(path).and_then(|root| path.strip_prefix(root).ok()) {
+            let module_parts: Vec<&str> = rel
+                .components()
+                .filter_map(|c| c.as_os_str().to_str())
+                .map(|p| p.strip_suffix(".py").unwrap_or(p))
+                .filter(|p| *p != "__init__")
+                .collect();
+            if !module_parts.is_empty() {
+                return module_parts.join(".");
+            }
+        }
+
         // Try to get relative path from package_path
         if let Ok(rel) = path.strip_prefix(package_path) {
             let parts: Vec<&str> = rel
@@ -665,6 +717,7 @@ impl AstDiscovery {
     /// When `is_site_packages` is true, we skip expensive fallback operations like
     /// building a full PackageAstCache, since non-editable installs don't have
     /// accessible source files and scanning site-packages would be catastrophically slow.
+    #[allow(clippy::too_many_arguments)]
     fn discover_direct_entry_point(
         package_path: &Path,
         discovery_root: &Path,
@@ -673,6 +726,7 @@ impl AstDiscovery {
         file_cache: &mut HashMap<PathBuf, Arc<CachedFile>>,
         package_cache: &mut Option<PackageAstCache>,
         is_site_packages: bool,
+        include_dev: bool,
     ) -> Result<Plugin> {
         logger::debug(&format!(
             "Discovering direct entry point: {} = {}:{}",
@@ -684,6 +738,8 @@ impl AstDiscovery {
         let mut cached = source_file
             .as_ref()
             .and_then(|source_path| Self::read_file_cached(file_cache, source_path));
+        // Module that defines the symbol, once a re-export has been followed
+        let mut source_module = entry.module.clone();
 
         // Determine plugin type based on symbol naming convention
         let plugin_type = if entry.is_class() {
@@ -702,6 +758,7 @@ impl AstDiscovery {
                     if let Some(resolved_module) = Self::resolve_reexported_symbol(
                         &cached_file.content,
                         &entry.module,
+                        source_file.as_deref().is_some_and(is_package_init),
                         &entry.symbol,
                     ) {
                         if let Some(path) =
@@ -712,6 +769,7 @@ impl AstDiscovery {
                             has_class = cached.as_ref().is_some_and(|cached| {
                                 Self::ast_has_class(&cached.ast, &entry.symbol)
                             });
+                            source_module = resolved_module;
                         }
                     }
                 }
@@ -722,7 +780,10 @@ impl AstDiscovery {
             // scanning all of site-packages would parse thousands of unrelated files.
             if !has_class && !is_site_packages {
                 if package_cache.is_none() {
-                    *package_cache = Some(PackageAstCache::build(discovery_root));
+                    *package_cache = Some(PackageAstCache::build_with_dev_dirs(
+                        discovery_root,
+                        include_dev,
+                    ));
                 }
 
                 if let Some(cache) = package_cache.as_ref() {
@@ -744,6 +805,7 @@ impl AstDiscovery {
                     if let Some(resolved_module) = Self::resolve_reexported_symbol(
                         &cached_file.content,
                         &entry.module,
+                        source_file.as_deref().is_some_and(is_package_init),
                         &entry.symbol,
                     ) {
                         if let Some(path) =
@@ -751,6 +813,7 @@ impl AstDiscovery {
                         {
                             source_file = Some(path.clone());
                             cached = Self::read_file_cached(file_cache, &path);
+                            source_module = resolved_module;
                         }
                     }
                 }
@@ -763,23 +826,133 @@ impl AstDiscovery {
                 Self::extract_entry_metadata(
                     discovery_root,
                     source_path,
+                    &source_module,
                     cached.as_ref(),
                     entry,
                     plugin_type,
                     file_cache,
                     package_cache,
                     package_name,
+                    include_dev,
                 )
             } else {
                 (Vec::new(), None)
             };
 
-        Ok(Self::build_manifest_plugin(
-            entry,
-            plugin_type,
-            config.as_ref(),
-            &call_args,
-        ))
+        let mut plugin =
+            Self::build_manifest_plugin(entry, plugin_type, config.as_ref(), &call_args);
+        let registration = cached
+            .as_ref()
+            .and_then(|cached| find_registration_args(&cached.content, &entry.symbol))
+            .unwrap_or_default();
+        plugin.tags = registration
+            .tags
+            .iter()
+            .map(|tag| Arc::from(tag.as_str()))
+            .collect();
+        plugin.aliases = registration
+            .aliases
+            .iter()
+            .map(|alias| Arc::from(alias.as_str()))
+            .collect();
+        plugin.min_r2x_core_version = registration.requires_core.as_deref().map(Arc::from);
+        // An explicit `description=` wins over the entry symbol's docstring.
+        plugin.description = registration
+            .description
+            .or_else(|| {
+                cached
+                    .as_ref()
+                    .and_then(|cached| find_docstring(&cached.content, &entry.symbol))
+            })
+            .map(Arc::from);
+        plugin.condition = cached
+            .as_ref()
+            .and_then(|cached| find_definition_condition(&cached.content, &entry.symbol))
+            .map(Arc::from);
+        if matches!(plugin_type, PluginType::Class) {
+            // Prefer the file the class was resolved to; the package cache may
+            // hold a same-named class from elsewhere in the package.
+            let methods = cached
+                .as_ref()
+                .and_then(|cached| Self::ast_class_methods(&cached.ast, &entry.symbol))
+                .or_else(|| {
+                    package_cache
+                        .as_ref()
+                        .and_then(|cache| cache.find_class(&entry.symbol))
+                        .map(|class| class.methods.clone())
+                });
+            let role = infer_plugin_role(&entry.name);
+            if let Some(methods) = &methods {
+                if let Some(expected) = missing_call_method(role, methods) {
+                    logger::warn(&format!(
+                        "{}: class {} does not define {}() (inherited methods are not checked)",
+                        entry.name, entry.symbol, expected
+                    ));
+                }
+            }
+            plugin.call_method = methods
+                .and_then(|methods| infer_call_method(role, &methods))
+                .map(Arc::from);
+        }
+        if matches!(plugin_type, PluginType::Class)
+            && infer_plugin_role(&entry.name) == PluginRole::Upgrader
+        {
+            if let Some(source_path) = source_file.as_ref() {
+                let content = cached.as_ref().map_or("", |cached| cached.content.as_str());
+                let decorators = upgrader_step_decorators(content, &entry.symbol);
+                plugin.registrations =
+                    Self::find_upgrader_registrations(source_path, &entry.symbol, &decorators);
+                for warning in ambiguous_step_order(&plugin.registrations) {
+                    logger::warn(&format!("{}: {}", entry.name, warning));
+                }
+            }
+            plugin.upgrade = cached
+                .as_ref()
+                .and_then(|cached| Self::extract_upgrade_spec(&cached.content, &entry.symbol));
+        }
+
+        Ok(plugin)
+    }
+
+    /// `version_strategy`/`version_reader` class attributes of an upgrader
+    fn extract_upgrade_spec(content: &str, class_name: &str) -> Option<UpgradeSpec> {
+        let attribute =
+            |name: &str| find_class_attribute(content, class_name, name).map(Arc::<str>::from);
+        let spec = UpgradeSpec {
+            version_strategy: attribute("version_strategy"),
+            version_reader: attribute("version_reader"),
+        };
+        (spec != UpgradeSpec::default()).then_some(spec)
+    }
+
+    /// Steps registered on an upgrader class in its module or sibling modules
+    ///
+    /// Steps usually live next to the upgrader, so every `.py` file in the
+    /// directory of the class's source file is scanned for the `decorators`
+    /// the class accepts.
+    fn find_upgrader_registrations(
+        source_path: &Path,
+        class_name: &str,
+        decorators: &[String],
+    ) -> Vec<DecoratorRegistration> {
+        let decorators: Vec<&str> = decorators.iter().map(String::as_str).collect();
+        let needle = format!("@{}.", class_name);
+        let mut paths: Vec<PathBuf> = source_path
+            .parent()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "py"))
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .filter_map(|path| read_python_source(path).ok())
+            .filter(|content| content.contains(&needle))
+            .flat_map(|content| find_decorator_registrations(&content, class_name, &decorators))
+            .collect()
     }
 
     /// Read file content with caching to avoid re-reading the same file
@@ -791,8 +964,11 @@ impl AstDiscovery {
             return Some(Arc::clone(cached));
         }
 
-        let content = std::fs::read_to_string(path).ok()?;
-        let ast = PythonAst::new(&content, Python);
+        let content = read_python_source(path).ok()?;
+        let Ok(ast) = std::panic::catch_unwind(|| PythonAst::new(&content, Python)) else {
+            logger::warn(&format!("Failed to parse {}; skipping", path.display()));
+            return None;
+        };
         let cached = Arc::new(CachedFile { content, ast });
         file_cache.insert(path.to_path_buf(), Arc::clone(&cached));
         Some(cached)
@@ -803,12 +979,14 @@ impl AstDiscovery {
     fn extract_entry_metadata(
         discovery_root: &Path,
         source_path: &Path,
+        source_module: &str,
         cached: &CachedFile,
         entry: &EntryPointInfo,
         plugin_type: PluginType,
         file_cache: &mut HashMap<PathBuf, Arc<CachedFile>>,
         package_cache: &mut Option<PackageAstCache>,
         package_name: &str,
+        include_dev: bool,
     ) -> (Vec<ParsedArgument>, Option<ConfigSpec>) {
         let call_args = if matches!(plugin_type, PluginType::Function) {
             Self::extract_function_params(&cached.content, &entry.symbol)
@@ -820,12 +998,14 @@ impl AstDiscovery {
         let mut config = Self::extract_config_with_fields(
             discovery_root,
             source_path,
+            source_module,
             cached,
             entry,
             plugin_type,
             file_cache,
             package_cache,
             package_name,
+            include_dev,
         );
 
         // For functions without a config class, convert call_args to ConfigField format
@@ -840,7 +1020,7 @@ impl AstDiscovery {
                 .collect();
 
             config = Some(ConfigSpec {
-                module: entry.module.clone(),
+                module: source_module.to_string(),
                 name: format!("{}Params", entry.symbol),
                 fields,
                 config_schema: SchemaFields::default(),
@@ -856,7 +1036,7 @@ impl AstDiscovery {
         config: Option<&ConfigSpec>,
         call_args: &[ParsedArgument],
     ) -> Plugin {
-        use crate::schema_extractor::parse_union_types_from_annotation;
+        use r2x_manifest::runtime::parse_union_types_from_annotation;
 
         let module = Arc::from(entry.module.as_str());
         let (class_name, function_name) = match plugin_type {
@@ -882,7 +1062,7 @@ impl AstDiscovery {
                 types: field.types.iter().map(|t| Arc::from(t.as_str())).collect(),
                 module: None,
                 required: field.required,
-                default: field.default.as_ref().map(|d| Arc::from(d.as_str())),
+                default: field.default.clone(),
                 description: field.description.as_ref().map(|d| Arc::from(d.as_str())),
             })
             .collect();
@@ -916,7 +1096,7 @@ impl AstDiscovery {
                 types,
                 module: None,
                 required: arg.required,
-                default: arg.default.as_ref().map(|d| Arc::from(d.as_str())),
+                default: arg.default.as_deref().map(Self::parse_default_value),
                 description: None,
             });
         }
@@ -932,11 +1112,19 @@ impl AstDiscovery {
             module,
             class_name,
             function_name,
+            call_method: None,
             config_class,
             config_module,
+            description: None,
             hooks: SmallVec::new(),
+            tags: SmallVec::new(),
+            aliases: SmallVec::new(),
+            min_r2x_core_version: None,
             parameters,
             config_schema,
+            registrations: Vec::new(),
+            upgrade: None,
+            condition: None,
             content_hash: 0,
         }
     }
@@ -946,12 +1134,14 @@ impl AstDiscovery {
     fn extract_config_with_fields(
         discovery_root: &Path,
         source_path: &Path,
+        source_module: &str,
         cached: &CachedFile,
         entry: &EntryPointInfo,
         plugin_type: PluginType,
         file_cache: &mut HashMap<PathBuf, Arc<CachedFile>>,
         package_cache: &mut Option<PackageAstCache>,
         package_name: &str,
+        include_dev: bool,
     ) -> Option<ConfigSpec> {
         use crate::schema_extractor::SchemaExtractor;
         use r2x_manifest::types::SchemaFields;
@@ -964,18 +1154,22 @@ impl AstDiscovery {
         let mut config_file_path: Option<PathBuf> = None;
         let mut config_content: Option<String> = None;
 
-        // Try to extract fields from the same file first
+        // A config class defined in the plugin's own file (not imported) is
+        // read from that file, even when it declares no fields of its own
+        let defined_here = Self::ast_has_class(&cached.ast, &config_name);
         let mut fields = Self::extract_config_fields_from_ast(&cached.ast, &config_name);
-        if !fields.is_empty() {
-            // Config class is in the same file as the function
+        if defined_here || !fields.is_empty() {
             config_file_path = Some(source_path.to_path_buf());
             config_content = Some(cached.content.clone());
         }
 
-        // If no fields found in the same file, search the package using ast-grep
-        if fields.is_empty() {
+        // If the class is not in the same file, search the package using ast-grep
+        if config_content.is_none() {
             if package_cache.is_none() {
-                *package_cache = Some(PackageAstCache::build(discovery_root));
+                *package_cache = Some(PackageAstCache::build_with_dev_dirs(
+                    discovery_root,
+                    include_dev,
+                ));
             }
 
             if let Some(cache) = package_cache.as_ref() {
@@ -989,8 +1183,8 @@ impl AstDiscovery {
             }
         }
 
-        // If still no fields, try looking in common locations relative to source file
-        if fields.is_empty() {
+        // If still not found, try looking in common locations relative to source file
+        if config_content.is_none() {
             if let Some((path, content)) = Self::find_config_in_common_locations_with_path(
                 source_path,
                 &config_name,
@@ -1005,16 +1199,31 @@ impl AstDiscovery {
         }
 
         // Determine the config module path
-        let config_module = if let Some(ref path) = config_file_path {
+        let config_module = if defined_here {
+            source_module.to_string()
+        } else if let Some(ref path) = config_file_path {
             // Use the file path where we found the config class to infer its module
             let path_str = path.to_string_lossy();
             Self::infer_module_from_file_path(&path_str, discovery_root, package_name)
         } else {
             // Fallback: try to resolve from imports in the source file
-            Self::resolve_config_module_from_imports(&cached.content, &config_name, &entry.module)
-                .unwrap_or_else(|| entry.module.clone())
+            Self::resolve_config_module_from_imports(
+                &cached.content,
+                &config_name,
+                source_module,
+                is_package_init(source_path),
+            )
+            .unwrap_or_else(|| source_module.to_string())
         };
 
+        // Nested types may live in other files, so the schema needs the package cache
+        if config_content.is_some() && package_cache.is_none() {
+            *package_cache = Some(PackageAstCache::build_with_dev_dirs(
+                discovery_root,
+                include_dev,
+            ));
+        }
+
         // Extract schema with nested type resolution if we have the content and package cache
         let config_schema = if let (Some(content), Some(cache)) =
             (config_content.as_ref(), package_cache.as_ref())
@@ -1073,6 +1282,7 @@ impl AstDiscovery {
         content: &str,
         config_name: &str,
         current_module: &str,
+        is_package: bool,
     ) -> Option<String> {
         for line in content.lines() {
             let trimmed = line.trim();
@@ -1090,24 +1300,7 @@ impl AstDiscovery {
                         .collect();
 
                     if imports.contains(&config_name) {
-                        // Resolve relative imports
-                        if from_part.starts_with('.') {
-                            let dot_count = from_part.chars().take_while(|c| *c == '.').count();
-                            let relative_path = &from_part[dot_count..];
-
-                            let mut parts: Vec<&str> = current_module.split('.').collect();
-                            // Go up dot_count levels (one dot means same package)
-                            for _ in 0..dot_count {
-                                parts.pop();
-                            }
-
-                            if !relative_path.is_empty() {
-                                parts.push(relative_path);
-                            }
-
-                            return Some(parts.join("."));
-                        }
-                        return Some(from_part.to_string());
+                        return Some(resolve_import_module(from_part, current_module, is_package));
                     }
                 }
             }
@@ -1196,13 +1389,29 @@ impl AstDiscovery {
         found
     }
 
+    /// Methods defined in the body of `class_name`, if the class is in `ast`
+    fn ast_class_methods(ast: &PythonAst, class_name: &str) -> Option<Vec<String>> {
+        let pattern = format!("class {}($$$BASES): $$$BODY", class_name);
+        let node = ast.root().find_all(pattern.as_str()).next()?;
+        Some(PackageAstCache::extract_method_names(&node.text()))
+    }
+
     fn ast_has_function(ast: &PythonAst, function_name: &str) -> bool {
         let pattern = format!("def {}($$$PARAMS): $$$BODY", function_name);
         let found = ast.root().find_all(pattern.as_str()).next().is_some();
         found
     }
 
-    fn resolve_reexported_symbol(content: &str, base_module: &str, symbol: &str) -> Option<String> {
+    /// Module a `from ... import symbol` line in `content` imports `symbol` from
+    ///
+    /// `is_package` tells whether `content` is the `__init__.py` of
+    /// `base_module`, which decides what a leading `.` refers to.
+    fn resolve_reexported_symbol(
+        content: &str,
+        base_module: &str,
+        is_package: bool,
+        symbol: &str,
+    ) -> Option<String> {
         for line in content.lines() {
             let trimmed = line.trim();
             if !trimmed.starts_with("from ") {
@@ -1235,25 +1444,7 @@ impl AstDiscovery {
                     continue;
                 }
 
-                if from_part.starts_with('.') {
-                    let dot_count = from_part.chars().take_while(|c| *c == '.').count();
-                    let levels_up = dot_count.saturating_sub(1);
-                    let rel = &from_part[dot_count..];
-                    let mut base_parts: Vec<&str> = base_module.split('.').collect();
-                    if levels_up > 0 && base_parts.len() >= levels_up {
-                        base_parts.truncate(base_parts.len() - levels_up);
-                    }
-                    let base_prefix = base_parts.join(".");
-                    if rel.is_empty() {
-                        return Some(base_prefix);
-                    }
-                    if base_prefix.is_empty() {
-                        return Some(rel.to_string());
-                    }
-                    return Some(format!("{}.{}", base_prefix, rel));
-                }
-
-                return Some(from_part.to_string());
+                return Some(resolve_import_module(from_part, base_module, is_package));
             }
         }
 
@@ -1329,11 +1520,17 @@ impl AstDiscovery {
             return None;
         }
 
+        // `Optional[X]` / `X | None` without a default still resolves to None
+        let required = default.is_none()
+            && !annotation
+                .as_deref()
+                .is_some_and(r2x_manifest::runtime::is_optional_annotation);
+
         Some(ParsedArgument {
             name,
             annotation,
-            required: default.is_none(),
             default,
+            required,
         })
     }
 
@@ -1382,7 +1579,8 @@ impl AstDiscovery {
         root: &ast_grep_core::Node<'_, ast_grep_core::source::StrDoc<Python>>,
         class_name: &str,
     ) -> Vec<ConfigField> {
-        use schema_extractor::{extract_description_from_field, parse_union_types_from_annotation};
+        use r2x_manifest::runtime::{is_optional_annotation, parse_union_types_from_annotation};
+        use schema_extractor::extract_description_from_field;
 
         // Find the class definition
         let class_pattern = format!("class {}($$$): $$$BODY", class_name);
@@ -1391,7 +1589,7 @@ impl AstDiscovery {
         };
 
         // Pattern for annotated assignment: name: Type = value or name: Type
-        let matches: Vec<_> = class_match.find_all("$NAME: $TYPE").collect();
+        let matches: Vec<_> = class_match.find_all(&*ANNOTATED_ASSIGNMENT).collect();
 
         let mut fields = Vec::new();
         for m in matches {
@@ -1426,9 +1624,8 @@ impl AstDiscovery {
             // Check if there's a default value
             let has_default = full_text.contains(" = ") || full_text.contains("default=");
 
-            // Determine if required
-            let required =
-                !has_default && !type_text.contains("None") && !type_text.starts_with("Optional");
+            // Optional annotations are not required, even without a default
+            let required = !has_default && !is_optional_annotation(&type_text);
 
             // Try to extract default value
             let default = if has_default {
@@ -1450,7 +1647,7 @@ impl AstDiscovery {
     }
 
     /// Extract default value from field definition text
-    fn extract_default_value(text: &str) -> Option<String> {
+    fn extract_default_value(text: &str) -> Option<DefaultValue> {
         // Look for = value after the type annotation
         if let Some(eq_pos) = text.rfind(" = ") {
             let value_part = text[eq_pos + 3..].trim();
@@ -1481,40 +1678,39 @@ impl AstDiscovery {
                     }
                     if end > 0 {
                         let default_str = rest[..end].trim();
-                        return Some(Self::clean_default_value(default_str));
+                        return Some(Self::parse_default_value(default_str));
                     }
                 }
                 return None;
             }
-            return Some(Self::clean_default_value(value_part));
+            return Some(Self::parse_default_value(value_part));
         }
         None
     }
 
-    /// Clean up default value string (remove surrounding quotes if present)
-    fn clean_default_value(value: &str) -> String {
-        let value = value.trim();
-        // Remove surrounding quotes for string values
-        if (value.starts_with('"') && value.ends_with('"'))
-            || (value.starts_with('\'') && value.ends_with('\''))
-        {
-            value[1..value.len() - 1].to_string()
-        } else {
-            value.to_string()
-        }
+    /// Parse default value source text into a typed value.
+    ///
+    /// Literals (strings, bools, numbers, `[]`) become their typed variants;
+    /// `None` and any other expression are kept verbatim as `DefaultValue::Expr`.
+    fn parse_default_value(value: &str) -> DefaultValue {
+        DefaultValue::from_python_source(value)
     }
 
     /// Convert a parsed argument to a ConfigField for function parameter extraction
     fn argument_to_config_field(arg: &ParsedArgument) -> ConfigField {
-        use schema_extractor::parse_union_types_from_annotation;
+        use r2x_manifest::runtime::{is_optional_annotation, parse_union_types_from_annotation};
 
         let types = arg.annotation.as_deref().map_or_else(
             || vec!["Any".to_string()],
             parse_union_types_from_annotation,
         );
 
-        let default = arg.default.as_deref().map(Self::clean_default_value);
-        let required = arg.required && !types.iter().any(|t| t == "None");
+        let default = arg.default.as_deref().map(Self::parse_default_value);
+        let required = arg.required
+            && !arg
+                .annotation
+                .as_deref()
+                .is_some_and(is_optional_annotation);
 
         ConfigField {
             name: arg.name.clone(),
@@ -1526,6 +1722,53 @@ impl AstDiscovery {
     }
 }
 
+/// Whether `path` is a package's `__init__.py`
+fn is_package_init(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "__init__.py")
+}
+
+/// Directory a Python file is imported from: the parent of the outermost
+/// enclosing directory with an `__init__.py`
+///
+/// `None` when the file's own directory is not a regular package.
+fn import_root(path: &Path) -> Option<&Path> {
+    let mut root = None;
+    let mut dir = path.parent();
+    while let Some(current) = dir.filter(|dir| dir.join("__init__.py").is_file()) {
+        root = current.parent();
+        dir = current.parent();
+    }
+    root
+}
+
+/// Absolute module named by the `from` part of an import in `current_module`
+///
+/// Follows Python's rules: one leading dot is the package containing the
+/// module (the module itself for an `__init__.py`), each further dot goes up
+/// one package.
+fn resolve_import_module(from_part: &str, current_module: &str, is_package: bool) -> String {
+    let dot_count = from_part.chars().take_while(|c| *c == '.').count();
+    if dot_count == 0 {
+        return from_part.to_string();
+    }
+    let relative = &from_part[dot_count..];
+
+    let mut parts: Vec<&str> = current_module
+        .split('.')
+        .filter(|p| !p.is_empty())
+        .collect();
+    if !is_package {
+        parts.pop();
+    }
+    for _ in 1..dot_count {
+        parts.pop();
+    }
+    if !relative.is_empty() {
+        parts.push(relative);
+    }
+    parts.join(".")
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -1621,8 +1864,95 @@ some-gui = some_module:gui_main
 from .parser import ReEDSParser
 from .plugin_config import ReEDSConfig
 ";
-        let resolved = AstDiscovery::resolve_reexported_symbol(content, "r2x_reeds", "ReEDSParser");
+        let resolved =
+            AstDiscovery::resolve_reexported_symbol(content, "r2x_reeds", true, "ReEDSParser");
         assert_eq!(resolved, Some("r2x_reeds.parser".to_string()));
+
+        // In a plain module a single dot is the sibling package, not a child
+        let resolved = AstDiscovery::resolve_reexported_symbol(
+            content,
+            "r2x_reeds.plugins",
+            false,
+            "ReEDSConfig",
+        );
+        assert_eq!(resolved, Some("r2x_reeds.plugin_config".to_string()));
+    }
+
+    #[test]
+    fn test_infer_module_uses_enclosing_packages() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let root = temp_dir.path();
+        let write_package = |base: &Path| {
+            let steps_dir = base.join("r2x_reeds").join("upgrader");
+            assert!(fs::create_dir_all(&steps_dir).is_ok());
+            assert!(fs::write(base.join("r2x_reeds").join("__init__.py"), "").is_ok());
+            assert!(fs::write(steps_dir.join("__init__.py"), "").is_ok());
+            assert!(fs::write(steps_dir.join("steps.py"), "").is_ok());
+            steps_dir.join("steps.py")
+        };
+        let module = |file: &Path, package_path: &Path| {
+            AstDiscovery::infer_module_from_file_path(
+                &file.to_string_lossy(),
+                package_path,
+                "r2x-reeds",
+            )
+        };
+
+        let flat = root.join("flat");
+        let flat_file = write_package(&flat);
+        assert_eq!(module(&flat_file, &flat), "r2x_reeds.upgrader.steps");
+
+        let src_layout = root.join("src-layout");
+        let src_file = write_package(&src_layout.join("src"));
+        assert_eq!(module(&src_file, &src_layout), "r2x_reeds.upgrader.steps");
+        assert_eq!(
+            module(&src_file, &src_layout.join("src")),
+            "r2x_reeds.upgrader.steps"
+        );
+        assert_eq!(
+            module(&src_file.with_file_name("__init__.py"), &src_layout),
+            "r2x_reeds.upgrader"
+        );
+
+        // Without an __init__.py the package name is still prefixed.
+        let loose = root.join("loose");
+        assert!(fs::create_dir_all(loose.join("plugins")).is_ok());
+        assert_eq!(
+            module(&loose.join("plugins").join("parser.py"), &loose),
+            "r2x_reeds.plugins.parser"
+        );
+    }
+
+    #[test]
+    fn test_resolve_import_module_follows_python_rules() {
+        assert_eq!(
+            resolve_import_module("r2x_core", "r2x_demo", true),
+            "r2x_core"
+        );
+        assert_eq!(resolve_import_module(".", "r2x_demo", true), "r2x_demo");
+        assert_eq!(
+            resolve_import_module(".config", "r2x_demo", true),
+            "r2x_demo.config"
+        );
+        assert_eq!(
+            resolve_import_module(".config", "r2x_demo.parser", false),
+            "r2x_demo.config"
+        );
+        assert_eq!(
+            resolve_import_module("..shared", "r2x_demo.sub.parser", false),
+            "r2x_demo.shared"
+        );
+        assert_eq!(
+            AstDiscovery::resolve_config_module_from_imports(
+                "from .settings import DemoConfig\n",
+                "DemoConfig",
+                "r2x_demo",
+                true,
+            ),
+            Some("r2x_demo.settings".to_string())
+        );
     }
 
     #[test]
@@ -1720,10 +2050,111 @@ def add_pcm_defaults(
         let field = AstDiscovery::argument_to_config_field(&arg);
         assert_eq!(field.name, "pcm_defaults_fpath");
         assert_eq!(field.types, vec!["str".to_string(), "None".to_string()]);
-        assert_eq!(field.default, Some("None".to_string()));
+        assert!(field.default.as_ref().is_some_and(DefaultValue::is_none));
         assert!(!field.required);
     }
 
+    #[test]
+    fn test_parse_default_value_distinguishes_literal_types() {
+        assert_eq!(
+            AstDiscovery::parse_default_value("\"none\""),
+            DefaultValue::Str(Arc::from("none"))
+        );
+        assert_eq!(
+            AstDiscovery::parse_default_value("'x'"),
+            DefaultValue::Str(Arc::from("x"))
+        );
+        assert_eq!(
+            AstDiscovery::parse_default_value("True"),
+            DefaultValue::Bool(true)
+        );
+        assert_eq!(AstDiscovery::parse_default_value("0"), DefaultValue::Int(0));
+        assert!(AstDiscovery::parse_default_value("None").is_none());
+        assert_eq!(
+            AstDiscovery::parse_default_value("Path(\"data\")"),
+            DefaultValue::Expr {
+                expr: Arc::from("Path(\"data\")")
+            }
+        );
+    }
+
+    #[test]
+    fn test_function_params_produce_typed_defaults() {
+        let source = r#"
+def add_pcm_defaults(
+    label: str = "none",
+    enabled: bool = True,
+    weather_year: int = 0,
+    pcm_defaults_fpath: str | None = None,
+) -> System:
+    pass
+"#;
+        let entry = EntryPointInfo {
+            name: "add-pcm-defaults".to_string(),
+            module: "r2x_reeds.sysmod.pcm_defaults".to_string(),
+            symbol: "add_pcm_defaults".to_string(),
+            section: "r2x.transforms".to_string(),
+        };
+        let args = AstDiscovery::extract_function_params(source, "add_pcm_defaults");
+        let plugin = AstDiscovery::build_manifest_plugin(&entry, PluginType::Function, None, &args);
+
+        let default_of = |name: &str| {
+            plugin
+                .parameters
+                .iter()
+                .find(|p| p.name.as_ref() == name)
+                .and_then(|p| p.default.clone())
+        };
+        assert_eq!(
+            default_of("label"),
+            Some(DefaultValue::Str(Arc::from("none")))
+        );
+        assert_eq!(default_of("enabled"), Some(DefaultValue::Bool(true)));
+        assert_eq!(default_of("weather_year"), Some(DefaultValue::Int(0)));
+        assert!(default_of("pcm_defaults_fpath").is_some_and(|d| d.is_none()));
+        assert_eq!(
+            default_of("label").map(|d| d.to_string()).as_deref(),
+            Some("\"none\"")
+        );
+        assert_eq!(
+            default_of("pcm_defaults_fpath")
+                .map(|d| d.to_string())
+                .as_deref(),
+            Some("None")
+        );
+    }
+
+    #[test]
+    fn test_optional_function_params_are_not_required() {
+        let source = r"
+def break_gens(
+    system: System,
+    threshold: Optional[int],
+    limit: int | None,
+    count: int,
+) -> System:
+    pass
+";
+        let entry = EntryPointInfo {
+            name: "break-gens".to_string(),
+            module: "r2x_reeds.sysmod.break_gens".to_string(),
+            symbol: "break_gens".to_string(),
+            section: "r2x.transforms".to_string(),
+        };
+        let args = AstDiscovery::extract_function_params(source, "break_gens");
+        let plugin = AstDiscovery::build_manifest_plugin(&entry, PluginType::Function, None, &args);
+        let required = |name: &str| {
+            plugin
+                .parameters
+                .iter()
+                .find(|p| p.name.as_ref() == name)
+                .map(|p| p.required)
+        };
+        assert_eq!(required("threshold"), Some(false));
+        assert_eq!(required("limit"), Some(false));
+        assert_eq!(required("count"), Some(true));
+    }
+
     #[test]
     fn test_camel_to_kebab() {
         // Simple CamelCase
@@ -1983,6 +2414,381 @@ class MyPlugin(Plugin[MyConfig]):
         assert!(plugins.is_empty());
     }
 
+    #[test]
+    fn test_discover_plugins_uses_docstring_as_description() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let root = temp_dir.path();
+        let package_dir = root.join("src").join("r2x_demo");
+        assert!(fs::create_dir_all(&package_dir).is_ok());
+        assert!(fs::write(
+            root.join("pyproject.toml"),
+            r#"
+[project]
+name = "r2x-demo"
+
+[project.entry-points.r2x_plugin]
+demo-parser = "r2x_demo.parser:DemoParser"
+bare-parser = "r2x_demo.parser:BareParser"
+"#,
+        )
+        .is_ok());
+        assert!(fs::write(package_dir.join("__init__.py"), "").is_ok());
+        assert!(fs::write(
+            package_dir.join("parser.py"),
+            r#"
+from r2x_core import Plugin
+
+class DemoParser(Plugin[DemoConfig]):
+    """Build a demo system.
+
+    Details that stay out of the manifest.
+    """
+
+class BareParser(Plugin[DemoConfig]):
+    pass
+"#,
+        )
+        .is_ok());
+
+        let result = AstDiscovery::discover_plugins(root, "r2x-demo", None, None, None);
+        assert!(result.is_ok());
+        let Ok(plugins) = result else {
+            return;
+        };
+        let description = |name: &str| {
+            plugins
+                .iter()
+                .find(|plugin| plugin.name.as_ref() == name)
+                .map(|plugin| plugin.description.as_deref().map(str::to_string))
+        };
+        assert_eq!(
+            description("demo-parser"),
+            Some(Some("Build a demo system.".to_string()))
+        );
+        assert_eq!(description("bare-parser"), Some(None));
+    }
+
+    #[test]
+    fn test_discover_plugins_resolves_config_class_in_plugins_file() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let root = temp_dir.path();
+        let package_dir = root.join("src").join("r2x_demo");
+        assert!(fs::create_dir_all(&package_dir).is_ok());
+        assert!(fs::write(
+            root.join("pyproject.toml"),
+            r#"
+[project]
+name = "r2x-demo"
+
+[project.entry-points.r2x_plugin]
+demo-parser = "r2x_demo.plugins:DemoParser"
+"#,
+        )
+        .is_ok());
+        assert!(fs::write(package_dir.join("__init__.py"), "").is_ok());
+        assert!(fs::write(
+            package_dir.join("plugins.py"),
+            r"
+from r2x_core import Plugin, PluginConfig
+
+class DemoConfig(PluginConfig):
+    solve_year: int = 2030
+    weather_year: int
+
+class DemoParser(Plugin[DemoConfig]):
+    pass
+",
+        )
+        .is_ok());
+
+        let result = AstDiscovery::discover_plugins(root, "r2x-demo", None, None, None);
+        assert!(result.is_ok());
+        let Ok(plugins) = result else {
+            return;
+        };
+        let Some(plugin) = plugins.iter().find(|p| p.name.as_ref() == "demo-parser") else {
+            return;
+        };
+        assert_eq!(plugin.config_class.as_deref(), Some("DemoConfig"));
+        assert_eq!(plugin.config_module.as_deref(), Some("r2x_demo.plugins"));
+        assert!(plugin.config_schema.get("solve_year").is_some());
+        assert!(plugin
+            .config_schema
+            .get("weather_year")
+            .is_some_and(|field| field.required));
+    }
+
+    #[test]
+    fn test_discover_plugins_infers_call_method_from_class_methods() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let root = temp_dir.path();
+        let package_dir = root.join("src").join("r2x_demo");
+        assert!(fs::create_dir_all(&package_dir).is_ok());
+        assert!(fs::write(
+            root.join("pyproject.toml"),
+            r#"
+[project]
+name = "r2x-demo"
+
+[project.entry-points.r2x_plugin]
+demo-parser = "r2x_demo.plugins:DemoModel"
+demo-exporter = "r2x_demo.plugins:ParserLike"
+demo-upgrader = "r2x_demo.plugins:BareUpgrader"
+demo-tool = "r2x_demo.plugins:DemoTool"
+"#,
+        )
+        .is_ok());
+        assert!(fs::write(package_dir.join("__init__.py"), "").is_ok());
+        assert!(fs::write(
+            package_dir.join("plugins.py"),
+            r"
+from r2x_core import Plugin
+
+class DemoModel(Plugin):
+    def build_system(self):
+        return self._system
+
+class ParserLike(Plugin):
+    def build_system(self):
+        return self._system
+
+class BareUpgrader(Plugin):
+    def helper(self):
+        pass
+
+class DemoTool(Plugin):
+    def export(self):
+        pass
+",
+        )
+        .is_ok());
+
+        let result = AstDiscovery::discover_plugins(root, "r2x-demo", None, None, None);
+        assert!(result.is_ok());
+        let Ok(plugins) = result else {
+            return;
+        };
+        let call_method = |name: &str| {
+            plugins
+                .iter()
+                .find(|p| p.name.as_ref() == name)
+                .and_then(|p| p.call_method.as_deref().map(str::to_string))
+        };
+        assert_eq!(call_method("demo-parser").as_deref(), Some("build_system"));
+        assert_eq!(call_method("demo-exporter"), None);
+        assert_eq!(call_method("demo-upgrader"), None);
+        // No role in the name, so the runtime would call nothing; the class decides.
+        assert_eq!(call_method("demo-tool").as_deref(), Some("export"));
+    }
+
+    #[test]
+    fn test_discover_plugins_records_platform_condition() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let root = temp_dir.path();
+        let package_dir = root.join("src").join("r2x_demo");
+        assert!(fs::create_dir_all(&package_dir).is_ok());
+        assert!(fs::write(
+            root.join("pyproject.toml"),
+            r#"
+[project]
+name = "r2x-demo"
+
+[project.entry-points.r2x_plugin]
+win-parser = "r2x_demo.parser:WinParser"
+demo-parser = "r2x_demo.parser:DemoParser"
+"#,
+        )
+        .is_ok());
+        assert!(fs::write(package_dir.join("__init__.py"), "").is_ok());
+        assert!(fs::write(
+            package_dir.join("parser.py"),
+            r#"
+import sys
+from r2x_core import Plugin
+
+if sys.platform == "win32":
+    class WinParser(Plugin[DemoConfig]):
+        pass
+
+class DemoParser(Plugin[DemoConfig]):
+    pass
+"#,
+        )
+        .is_ok());
+
+        let result = AstDiscovery::discover_plugins(root, "r2x-demo", None, None, None);
+        assert!(result.is_ok());
+        let Ok(plugins) = result else {
+            return;
+        };
+        let condition = |name: &str| {
+            plugins
+                .iter()
+                .find(|plugin| plugin.name.as_ref() == name)
+                .map(|plugin| plugin.condition.as_deref().map(str::to_string))
+        };
+        assert_eq!(
+            condition("win-parser"),
+            Some(Some(r#"sys.platform == "win32""#.to_string()))
+        );
+        assert_eq!(condition("demo-parser"), Some(None));
+    }
+
+    #[test]
+    fn test_discover_plugins_follows_relative_reexport_at_package_root() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let root = temp_dir.path();
+        let package_dir = root.join("src").join("r2x_demo");
+        assert!(fs::create_dir_all(&package_dir).is_ok());
+        assert!(fs::write(
+            root.join("pyproject.toml"),
+            r#"
+[project]
+name = "r2x-demo"
+
+[project.entry-points.r2x_plugin]
+demo-parser = "r2x_demo:DemoParser"
+"#,
+        )
+        .is_ok());
+        assert!(fs::write(
+            package_dir.join("__init__.py"),
+            "from .parser import DemoParser\n",
+        )
+        .is_ok());
+        assert!(fs::write(
+            package_dir.join("parser.py"),
+            r#"
+from r2x_core import Plugin
+from .settings import DemoConfig
+
+class DemoParser(Plugin[DemoConfig]):
+    """Build a demo system."""
+"#,
+        )
+        .is_ok());
+        assert!(fs::write(
+            package_dir.join("settings.py"),
+            r"
+from r2x_core import PluginConfig
+
+class DemoConfig(PluginConfig):
+    year: int
+",
+        )
+        .is_ok());
+
+        let result = AstDiscovery::discover_plugins(root, "r2x-demo", None, None, None);
+        assert!(result.is_ok());
+        let Ok(plugins) = result else {
+            return;
+        };
+        let plugin = plugins.iter().find(|p| p.name.as_ref() == "demo-parser");
+        assert!(plugin.is_some());
+        let Some(plugin) = plugin else {
+            return;
+        };
+        assert_eq!(plugin.module.as_ref(), "r2x_demo");
+        assert_eq!(plugin.class_name.as_deref(), Some("DemoParser"));
+        assert_eq!(plugin.config_module.as_deref(), Some("r2x_demo.settings"));
+        assert_eq!(plugin.description.as_deref(), Some("Build a demo system."));
+    }
+
+    #[test]
+    fn test_discover_plugins_links_upgrader_steps() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let root = temp_dir.path();
+        let package_dir = root.join("src").join("r2x_demo");
+        assert!(fs::create_dir_all(&package_dir).is_ok());
+        assert!(fs::write(
+            root.join("pyproject.toml"),
+            r#"
+[project]
+name = "r2x-demo"
+
+[project.entry-points.r2x_plugin]
+demo-upgrader = "r2x_demo.upgrader:DemoUpgrader"
+"#,
+        )
+        .is_ok());
+        assert!(fs::write(package_dir.join("__init__.py"), "").is_ok());
+        assert!(fs::write(
+            package_dir.join("upgrader.py"),
+            r#"
+from r2x_core import BaseUpgrader
+
+class DemoUpgrader(BaseUpgrader):
+    version_strategy = SemanticVersioning
+    version_reader = DemoVersionReader()
+
+@DemoUpgrader.register_step(target_version="2.0", priority=20)
+def rename_columns(folder):
+    return folder
+"#,
+        )
+        .is_ok());
+        assert!(fs::write(
+            package_dir.join("steps.py"),
+            r#"
+from r2x_demo.upgrader import DemoUpgrader
+
+@DemoUpgrader.register_step(target_version="1.5", priority=10)
+def move_files(folder):
+    return folder
+
+@DemoUpgrader.step(target_version="2.1")
+def drop_cache(folder):
+    return folder
+"#,
+        )
+        .is_ok());
+
+        let result = AstDiscovery::discover_plugins(root, "r2x-demo", None, None, None);
+        assert!(result.is_ok());
+        let Ok(plugins) = result else {
+            return;
+        };
+        let upgrader = plugins
+            .iter()
+            .find(|plugin| plugin.name.as_ref() == "demo-upgrader");
+        assert!(upgrader.is_some());
+        let Some(upgrader) = upgrader else {
+            return;
+        };
+        let mut functions: Vec<&str> = upgrader
+            .registrations
+            .iter()
+            .map(|registration| registration.function.as_ref())
+            .collect();
+        functions.sort_unstable();
+        assert_eq!(
+            functions,
+            vec!["drop_cache", "move_files", "rename_columns"]
+        );
+
+        let upgrade = upgrader.upgrade.as_ref();
+        assert_eq!(
+            upgrade.and_then(|spec| spec.version_strategy.as_deref()),
+            Some("SemanticVersioning")
+        );
+        assert_eq!(
+            upgrade.and_then(|spec| spec.version_reader.as_deref()),
+            Some("DemoVersionReader()")
+        );
+    }
+
     #[test]
     fn test_nested_config_discovery_integration() {
         use crate::package_cache::PackageAstCache;
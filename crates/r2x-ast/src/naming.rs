@@ -58,10 +58,25 @@ pub fn snake_to_kebab(func_name: &str) -> String {
 ///
 /// Returns the index of the closing paren that matches the implicit opening paren
 /// at position -1 (i.e., we start at depth 0 looking for the first ')' at depth 0).
+/// Brackets inside string literals and `#` comments are ignored.
 pub fn find_matching_paren(text: &str) -> Option<usize> {
     let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut in_comment = false;
     for (i, ch) in text.char_indices() {
+        if in_comment {
+            in_comment = ch != '\n';
+            continue;
+        }
+        if let Some(q) = quote {
+            if ch == q {
+                quote = None;
+            }
+            continue;
+        }
         match ch {
+            '#' => in_comment = true,
+            '\'' | '"' => quote = Some(ch),
             '(' | '[' | '{' => depth += 1,
             ')' => {
                 if depth == 0 {
@@ -124,5 +139,9 @@ mod tests {
 
         // No match
         assert_eq!(find_matching_paren("no close paren"), None);
+
+        // Strings and comments
+        assert_eq!(find_matching_paren("name=\"a)\")"), Some(9));
+        assert_eq!(find_matching_paren("x=1,  # see (note\n)"), Some(18));
     }
 }
@@ -9,6 +9,8 @@
 //! - Single AST parse per file (vs multiple pattern searches)
 //! - Kind-based node matching (vs pattern string compilation)
 
+use crate::patterns::{CLASS_NO_BASES, CLASS_WITH_BASES, EXPOSE_PLUGIN_FUNCTIONS};
+use crate::source::read_python_source;
 use ast_grep_core::AstGrep;
 use ast_grep_language::Python;
 use r2x_logger as logger;
@@ -17,6 +19,9 @@ use std::path::{Path, PathBuf};
 use std::time::Instant;
 use walkdir::{DirEntry, WalkDir};
 
+/// Top-level test and example directories, skipped unless dev directories are included
+const DEV_DIRS: &[&str] = &["tests", "test", "examples", "example"];
+
 /// Extracted class definition from a Python file
 #[derive(Debug, Clone)]
 pub struct ClassDef {
@@ -71,16 +76,25 @@ pub struct PackageAstCache {
     files: HashMap<PathBuf, ParsedPyFile>,
     /// Index: class name -> file path
     class_index: HashMap<String, PathBuf>,
+    /// Files that could not be read or parsed
+    error_count: usize,
 }
 
 impl PackageAstCache {
-    fn is_ignored_dir(entry: &DirEntry) -> bool {
+    fn is_ignored_dir(entry: &DirEntry, include_dev: bool) -> bool {
         if !entry.file_type().is_dir() {
             return false;
         }
 
+        let name = entry.file_name().to_string_lossy();
+        // Only directly under the package root: the root itself is never a dev
+        // directory, and nested `tests` subpackages may hold real plugins.
+        if !include_dev && entry.depth() == 1 && DEV_DIRS.contains(&name.as_ref()) {
+            return true;
+        }
+
         matches!(
-            entry.file_name().to_string_lossy().as_ref(),
+            name.as_ref(),
             ".git"
                 | ".hg"
                 | ".svn"
@@ -101,29 +115,47 @@ impl PackageAstCache {
 
     /// Build cache by walking package once and parsing each .py file once
     pub fn build(package_root: &Path) -> Self {
+        Self::build_with_dev_dirs(package_root, false)
+    }
+
+    /// Like [`Self::build`], scanning test and example directories when `include_dev` is set
+    pub fn build_with_dev_dirs(package_root: &Path, include_dev: bool) -> Self {
         let start = Instant::now();
         let mut files = HashMap::new();
         let mut class_index = HashMap::new();
         let mut file_count = 0;
+        let mut error_count = 0;
 
         for entry in WalkDir::new(package_root)
             .into_iter()
-            .filter_entry(|entry| !Self::is_ignored_dir(entry))
+            .filter_entry(|entry| !Self::is_ignored_dir(entry, include_dev))
             .filter_map(|e| e.ok())
             .filter(|e| e.path().extension().is_some_and(|ext| ext == "py"))
         {
             let path = entry.path().to_path_buf();
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                let parsed = Self::parse_file(&path, content);
-
-                // Build class index
-                for class in &parsed.classes {
-                    class_index.insert(class.name.clone(), path.clone());
+            let content = match read_python_source(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    logger::debug(&format!("Skipping unreadable {}: {}", path.display(), e));
+                    error_count += 1;
+                    continue;
                 }
+            };
 
-                files.insert(path, parsed);
-                file_count += 1;
+            // A parser panic on one odd file must not abort the whole package scan.
+            let Ok(parsed) = std::panic::catch_unwind(|| Self::parse_file(&path, content)) else {
+                logger::warn(&format!("Failed to parse {}; skipping", path.display()));
+                error_count += 1;
+                continue;
+            };
+
+            // Build class index
+            for class in &parsed.classes {
+                class_index.insert(class.name.clone(), path.clone());
             }
+
+            files.insert(path, parsed);
+            file_count += 1;
         }
 
         logger::debug(&format!(
@@ -131,8 +163,19 @@ impl PackageAstCache {
             file_count,
             start.elapsed().as_secs_f64() * 1000.0
         ));
+        if error_count > 0 {
+            logger::warn(&format!(
+                "Skipped {} file(s) in {} that could not be read or parsed",
+                error_count,
+                package_root.display()
+            ));
+        }
 
-        PackageAstCache { files, class_index }
+        PackageAstCache {
+            files,
+            class_index,
+            error_count,
+        }
     }
 
     /// Parse a single file, extracting all needed data in one pass
@@ -168,8 +211,7 @@ impl PackageAstCache {
         let mut classes = Vec::new();
 
         // Pattern 1: Classes with base classes - class Name(BaseClasses): body
-        let pattern_with_bases = "class $NAME($$$BASES): $$$BODY";
-        for node in root.find_all(pattern_with_bases) {
+        for node in root.find_all(&*CLASS_WITH_BASES) {
             let env = node.get_env();
 
             // Try metavariable first, fallback to text extraction
@@ -212,8 +254,7 @@ impl PackageAstCache {
         }
 
         // Pattern 2: Classes without base classes - class Name: body
-        let pattern_no_bases = "class $NAME: $$$BODY";
-        for node in root.find_all(pattern_no_bases) {
+        for node in root.find_all(&*CLASS_NO_BASES) {
             let env = node.get_env();
 
             // Try metavariable first, fallback to text extraction
@@ -305,7 +346,7 @@ impl PackageAstCache {
     }
 
     /// Extract method names from class body text
-    fn extract_method_names(body_text: &str) -> Vec<String> {
+    pub(crate) fn extract_method_names(body_text: &str) -> Vec<String> {
         let mut methods = Vec::new();
 
         // Simple text-based extraction for method names
@@ -361,13 +402,7 @@ impl PackageAstCache {
         let mut results = Vec::new();
 
         // Try multiple patterns for @expose_plugin decorated functions
-        let patterns = [
-            "@expose_plugin\ndef $FUNC($$$PARAMS): $$$BODY",
-            "@expose_plugin()\ndef $FUNC($$$PARAMS): $$$BODY",
-            "@expose_plugin($$$ARGS)\ndef $FUNC($$$PARAMS): $$$BODY",
-        ];
-
-        for pattern in patterns {
+        for pattern in EXPOSE_PLUGIN_FUNCTIONS.iter() {
             for node in root.find_all(pattern) {
                 let env = node.get_env();
 
@@ -550,12 +585,18 @@ impl PackageAstCache {
     pub fn file_count(&self) -> usize {
         self.files.len()
     }
+
+    /// Get the number of files skipped because they could not be read or parsed
+    pub fn error_count(&self) -> usize {
+        self.error_count
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::package_cache::*;
     use std::fs;
+    use std::time::Duration;
     use tempfile::TempDir;
 
     #[test]
@@ -659,4 +700,131 @@ class PCMDefaultsConfig:
 
         Ok(())
     }
+
+    #[test]
+    fn test_build_cache_with_bom_and_latin1_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let mut bom_file = b"\xef\xbb\xbf".to_vec();
+        bom_file.extend_from_slice(b"class BomParser(Plugin[BomConfig]):\n    pass\n");
+        fs::write(temp_dir.path().join("bom.py"), bom_file)?;
+        fs::write(
+            temp_dir.path().join("legacy.py"),
+            b"# Auteur: Ren\xe9\nclass LegacyParser(Plugin[LegacyConfig]):\n    pass\n",
+        )?;
+
+        let cache = PackageAstCache::build(temp_dir.path());
+
+        assert_eq!(cache.file_count(), 2);
+        assert!(cache
+            .find_plugin_class("BomParser")
+            .is_some_and(|(_, config)| config == "BomConfig"));
+        assert!(cache
+            .find_plugin_class("LegacyParser")
+            .is_some_and(|(_, config)| config == "LegacyConfig"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_cache_skips_malformed_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        fs::write(
+            temp_dir.path().join("valid.py"),
+            "class GoodParser(Plugin[GoodConfig]):\n    pass\n\n@expose_plugin\ndef good_transform(system):\n    return system\n",
+        )?;
+        fs::write(
+            temp_dir.path().join("malformed.py"),
+            "class Broken(Plugin[\n    def (:\n@@@ ))) \"unterminated\n",
+        )?;
+        // Matches the `.py` filter but cannot be read as a file.
+        fs::create_dir(temp_dir.path().join("not_a_file.py"))?;
+
+        let cache = PackageAstCache::build(temp_dir.path());
+
+        assert_eq!(cache.error_count(), 1);
+        assert!(cache
+            .find_plugin_class("GoodParser")
+            .is_some_and(|(_, config)| config == "GoodConfig"));
+        assert!(cache
+            .get_all_decorated_functions()
+            .iter()
+            .any(|(_, func)| func.function_name == "good_transform"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_cache_many_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_count = 200;
+        for i in 0..file_count {
+            let content = format!(
+                "class Config{i}:\n    path: str = \"x\"\n\nclass Parser{i}(Plugin[Config{i}]):\n    def on_build(self, system):\n        return system\n\n@expose_plugin\ndef transform_{i}(system):\n    return system\n\n@expose_plugin(tags=[\"t\"])\ndef tagged_{i}(system, path: str):\n    return system\n"
+            );
+            fs::write(temp_dir.path().join(format!("module_{i}.py")), content)?;
+        }
+
+        let start = Instant::now();
+        let cache = PackageAstCache::build(temp_dir.path());
+        let elapsed = start.elapsed();
+
+        assert_eq!(cache.file_count(), file_count);
+        assert_eq!(cache.error_count(), 0);
+        for i in [0, file_count / 2, file_count - 1] {
+            assert!(cache
+                .find_plugin_class(&format!("Parser{i}"))
+                .is_some_and(|(_, config)| config == format!("Config{i}")));
+            assert!(cache
+                .find_config_class_content(&format!("Config{i}"))
+                .is_some());
+        }
+        let decorated = cache.get_all_decorated_functions();
+        assert_eq!(decorated.len(), file_count * 2);
+        assert!(decorated
+            .iter()
+            .any(|(_, func)| func.function_name == "tagged_7"));
+
+        // Generous bound: catches a regression to per-file pattern compilation
+        // on large packages without being flaky on slow CI machines.
+        assert!(
+            elapsed < Duration::from_secs(10),
+            "building the cache took {elapsed:?}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_cache_scans_examples_only_with_dev_dirs() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let examples = temp_dir.path().join("examples");
+        fs::create_dir(&examples)?;
+        fs::write(
+            examples.join("demo.py"),
+            "@expose_plugin\ndef demo_transform(system):\n    return system\n",
+        )?;
+
+        let cache = PackageAstCache::build_with_dev_dirs(temp_dir.path(), false);
+        assert_eq!(cache.file_count(), 0);
+
+        let cache = PackageAstCache::build_with_dev_dirs(temp_dir.path(), true);
+        let decorated = cache.get_all_decorated_functions();
+        assert_eq!(decorated.len(), 1);
+        assert_eq!(decorated[0].1.function_name, "demo_transform");
+
+        // A package rooted at a directory named like a dev dir is still scanned.
+        let cache = PackageAstCache::build_with_dev_dirs(&examples, false);
+        assert_eq!(cache.file_count(), 1);
+
+        // So are nested subpackages that happen to share the name.
+        let nested = temp_dir.path().join("r2x_demo").join("tests");
+        fs::create_dir_all(&nested)?;
+        fs::write(nested.join("fixtures.py"), "class Fixture:\n    pass\n")?;
+        let cache = PackageAstCache::build_with_dev_dirs(temp_dir.path(), false);
+        assert_eq!(cache.file_count(), 1);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,33 @@
+//! Precompiled ast-grep patterns
+//!
+//! Matching with a `&str` compiles the pattern on every call. Discovery runs
+//! the same handful of patterns against every file in a package, so they are
+//! compiled once on first use and shared.
+
+use ast_grep_core::Pattern;
+use ast_grep_language::Python;
+use once_cell::sync::Lazy;
+
+type PythonPattern = Pattern<Python>;
+
+/// `class Name(Bases): body`
+pub(crate) static CLASS_WITH_BASES: Lazy<PythonPattern> =
+    Lazy::new(|| Pattern::new("class $NAME($$$BASES): $$$BODY", Python));
+
+/// `class Name: body`
+pub(crate) static CLASS_NO_BASES: Lazy<PythonPattern> =
+    Lazy::new(|| Pattern::new("class $NAME: $$$BODY", Python));
+
+/// `@expose_plugin` in its bare, empty-call and argument forms
+pub(crate) static EXPOSE_PLUGIN_FUNCTIONS: Lazy<[PythonPattern; 3]> = Lazy::new(|| {
+    [
+        "@expose_plugin\ndef $FUNC($$$PARAMS): $$$BODY",
+        "@expose_plugin()\ndef $FUNC($$$PARAMS): $$$BODY",
+        "@expose_plugin($$$ARGS)\ndef $FUNC($$$PARAMS): $$$BODY",
+    ]
+    .map(|src| Pattern::new(src, Python))
+});
+
+/// Annotated assignment such as `name: Type = value` or `name: Type`
+pub(crate) static ANNOTATED_ASSIGNMENT: Lazy<PythonPattern> =
+    Lazy::new(|| Pattern::new("$NAME: $TYPE", Python));
@@ -8,9 +8,11 @@
 //! - Default values
 //! - Required/optional field detection
 
+use crate::patterns::ANNOTATED_ASSIGNMENT;
 use anyhow::{anyhow, Result};
 use ast_grep_core::AstGrep;
 use ast_grep_language::Python;
+use r2x_manifest::runtime::{find_first_arg_end, is_optional_annotation};
 use r2x_manifest::types::{
     Constraint, DefaultValue, FieldType, NestedInfo, SchemaField, SchemaFields,
 };
@@ -184,8 +186,7 @@ impl SchemaExtractor {
     ) -> Result<()> {
         // Find annotated assignments within the class match
         // Pattern for annotated assignment: name: Type = value or name: Type
-        let pattern = "$NAME: $TYPE";
-        let matches: Vec<_> = class_match.find_all(pattern).collect();
+        let matches: Vec<_> = class_match.find_all(&*ANNOTATED_ASSIGNMENT).collect();
 
         for m in matches {
             let full_text = m.text();
@@ -216,9 +217,8 @@ impl SchemaExtractor {
             // Check if there's a default value
             let has_default = full_text.contains(" = ") || full_text.contains("default=");
 
-            // Determine if required
-            let required =
-                !has_default && !type_text.contains("None") && !type_text.starts_with("Optional");
+            // Optional annotations are not required, even without a default
+            let required = !has_default && !is_optional_annotation(&type_text);
 
             // Try to extract default value
             let default = if has_default {
@@ -437,46 +437,8 @@ impl SchemaExtractor {
     }
 
     /// Parse a literal value
-    fn parse_literal_value(value: &str) -> Option<DefaultValue> {
-        let value = value.trim();
-
-        // Boolean
-        if value == "True" || value == "true" {
-            return Some(DefaultValue::Bool(true));
-        }
-        if value == "False" || value == "false" {
-            return Some(DefaultValue::Bool(false));
-        }
-
-        // None
-        if value == "None" {
-            return None;
-        }
-
-        // String
-        if (value.starts_with('"') && value.ends_with('"'))
-            || (value.starts_with('\'') && value.ends_with('\''))
-        {
-            let inner = &value[1..value.len() - 1];
-            return Some(DefaultValue::Str(Arc::from(inner)));
-        }
-
-        // Empty list
-        if value == "[]" {
-            return Some(DefaultValue::Array(Arc::from([])));
-        }
-
-        // Integer
-        if let Ok(i) = value.parse::<i64>() {
-            return Some(DefaultValue::Int(i));
-        }
-
-        // Float
-        if let Ok(f) = value.parse::<f64>() {
-            return Some(DefaultValue::Float(f));
-        }
-
-        None
+    pub(crate) fn parse_literal_value(value: &str) -> Option<DefaultValue> {
+        DefaultValue::parse_literal(value)
     }
 }
 
@@ -490,21 +452,6 @@ impl Default for SchemaExtractor {
 // HELPER FUNCTIONS FOR TYPE PARSING
 // =============================================================================
 
-/// Find the end of the first argument in a comma-separated list, handling nested brackets
-fn find_first_arg_end(text: &str) -> Option<usize> {
-    let mut depth = 0;
-    for (i, ch) in text.char_indices() {
-        match ch {
-            '[' | '(' | '{' => depth += 1,
-            ']' | ')' | '}' => depth -= 1,
-            ',' if depth == 0 => return Some(i),
-            _ => {}
-        }
-    }
-    // If no comma found, the whole text is the first argument
-    Some(text.len())
-}
-
 /// Extract description from Field(description="...") or Field(..., description="...")
 pub fn extract_description_from_field(text: &str) -> Option<String> {
     if let Some(start) = text.find("description=") {
@@ -521,37 +468,6 @@ pub fn extract_description_from_field(text: &str) -> Option<String> {
     }
 }
 
-/// Parse union types from a type annotation string
-/// Handles:
-/// - "int | str | None" -> vec!["int", "str", "None"]
-/// - "Annotated[int | str, Field(...)]" -> vec!["int", "str"]
-/// - "str" -> vec!["str"]
-pub fn parse_union_types_from_annotation(annotation: &str) -> Vec<String> {
-    let annotation = annotation.trim();
-
-    // Handle Annotated[X, Field(...)] - extract the actual type first
-    let actual_type = if annotation.starts_with("Annotated[") && annotation.ends_with(']') {
-        let inner = &annotation[10..annotation.len() - 1];
-        if let Some(type_end) = find_first_arg_end(inner) {
-            inner[..type_end].trim()
-        } else {
-            annotation
-        }
-    } else {
-        annotation
-    };
-
-    // Now parse union types
-    if actual_type.contains(" | ") {
-        actual_type
-            .split(" | ")
-            .map(|t| t.trim().to_string())
-            .collect()
-    } else {
-        vec![actual_type.to_string()]
-    }
-}
-
 #[cfg(test)]
 #[allow(clippy::expect_used)]
 mod tests {
@@ -606,6 +522,25 @@ class MyConfig(BaseModel):
             && n.constraints.contains(&Constraint::MaxLen(50))));
     }
 
+    #[test]
+    fn test_optional_fields_without_default_are_not_required() {
+        let source = r"
+class MyConfig(BaseModel):
+    threshold: Optional[int]
+    limit: int | None
+    weather: Annotated[Optional[int], Field(description='Weather year')]
+    count: int
+";
+
+        let extractor = SchemaExtractor::new();
+        let fields = extractor.extract(source, "MyConfig").unwrap_or_default();
+        let required = |name: &str| fields.get(name).map(|f| f.required);
+        assert_eq!(required("threshold"), Some(false));
+        assert_eq!(required("limit"), Some(false));
+        assert_eq!(required("weather"), Some(false));
+        assert_eq!(required("count"), Some(true));
+    }
+
     #[test]
     fn test_extract_literal_enum() {
         let source = r#"
@@ -0,0 +1,66 @@
+//! Reading Python source files for static analysis
+//!
+//! Discovery should not abort because one file was saved with a UTF-8 BOM or
+//! in a legacy encoding such as Latin-1. Sources are decoded leniently so the
+//! rest of the package can still be analyzed.
+
+use r2x_logger as logger;
+use std::path::Path;
+
+const UTF8_BOM: &str = "\u{feff}";
+
+/// Read a Python source file, stripping a BOM and decoding non-UTF-8 lossily
+pub fn read_python_source(path: &Path) -> std::io::Result<String> {
+    let bytes = std::fs::read(path)?;
+    Ok(decode_python_source(bytes, path))
+}
+
+/// Decode raw source bytes. Invalid UTF-8 is replaced and logged as a warning.
+pub fn decode_python_source(bytes: Vec<u8>, path: &Path) -> String {
+    let content = match String::from_utf8(bytes) {
+        Ok(content) => content,
+        Err(err) => {
+            logger::warn(&format!(
+                "{} is not valid UTF-8; decoding lossily",
+                path.display()
+            ));
+            String::from_utf8_lossy(err.as_bytes()).into_owned()
+        }
+    };
+
+    match content.strip_prefix(UTF8_BOM) {
+        Some(stripped) => stripped.to_string(),
+        None => content,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::source::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bom_is_stripped() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let path = dir.path().join("plugin.py");
+        let mut bytes = b"\xef\xbb\xbf".to_vec();
+        bytes.extend_from_slice(b"class Parser:\n    pass\n");
+        if std::fs::write(&path, bytes).is_err() {
+            return;
+        }
+
+        let content = read_python_source(&path);
+        assert!(content.is_ok_and(|c| c.starts_with("class Parser:")));
+    }
+
+    #[test]
+    fn test_latin1_is_decoded_lossily() {
+        // "# Café" in Latin-1: 0xE9 is not valid UTF-8 on its own.
+        let bytes = b"# Caf\xe9\nclass Parser:\n    pass\n".to_vec();
+        let content = decode_python_source(bytes, Path::new("plugin.py"));
+        assert!(content.starts_with("# Caf\u{fffd}\n"));
+        assert!(content.contains("class Parser:"));
+    }
+}
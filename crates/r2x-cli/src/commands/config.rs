@@ -1,14 +1,17 @@
+use crate::commands::plugins::sync::reinstall_packages;
 use crate::common::GlobalOpts;
 use crate::plugins::install::get_package_info;
 use clap::Subcommand;
 use colored::Colorize;
 use r2x_config::Config;
 use r2x_logger as logger;
-use r2x_python::python_bridge::configure_python_venv;
+use r2x_manifest::types::{Manifest, Package};
+use r2x_python::python_bridge::{configure_python_venv, Bridge};
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, SystemTime};
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum ConfigAction {
@@ -20,6 +23,9 @@ pub enum ConfigAction {
     Path {
         /// Optional new config path to set
         new_path: Option<String>,
+        /// Restore the default config location
+        #[arg(long, conflicts_with = "new_path")]
+        reset: bool,
     },
     /// Reset configuration back to defaults.
     Reset {
@@ -70,6 +76,15 @@ pub enum VenvAction {
 pub enum CacheAction {
     /// Clean the cache folder
     Clean,
+    /// Remove cache files last modified more than DAYS days ago
+    Prune {
+        /// Age in days; files modified more recently are kept
+        #[arg(long, value_name = "DAYS")]
+        older_than: u64,
+        /// List what would be freed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Get or set cache path
     Path {
         /// Optional new cache path to set
@@ -160,6 +175,12 @@ pub fn handle_config(action: Option<ConfigAction>, opts: GlobalOpts) {
                 if let Some(log_max_size) = config.log_max_size {
                     println!("  {}: {}", "log-max-size".cyan(), log_max_size);
                 }
+                if let Some(max_capture) = config.log_max_capture_bytes {
+                    println!("  {}: {}", "log-max-capture-bytes".cyan(), max_capture);
+                }
+                if let Some(timeout) = config.bridge_init_timeout {
+                    println!("  {}: {}", "bridge-init-timeout".cyan(), timeout);
+                }
 
                 // Show installed r2x-core version
                 let python_path = config.get_venv_python_path();
@@ -187,67 +208,61 @@ pub fn handle_config(action: Option<ConfigAction>, opts: GlobalOpts) {
         },
         ConfigAction::Set { key, value } => match Config::load() {
             Ok(mut config) => {
-                if config.get(&key).is_some()
-                    || matches!(
-                        key.as_str(),
-                        "cache-path"
-                            | "verbosity"
-                            | "python-version"
-                            | "venv-path"
-                            | "r2x-core-version"
-                            | "log-python"
-                            | "no-stdout"
-                            | "log-path"
-                            | "log-max-size"
-                    )
-                {
-                    config.set(&key, value.clone());
-                    match config.save() {
-                        Ok(()) => {
-                            logger::success(&format!("Set {} = {}", key, value));
-                        }
-                        Err(e) => {
-                            logger::error(&format!("Failed to save config: {}", e));
-                        }
+                if let Err(e) = config.try_set(&key, &value) {
+                    logger::error(&e.to_string());
+                    return;
+                }
+                match config.save() {
+                    Ok(()) => {
+                        logger::success(&format!("Set {} = {}", key, value));
+                    }
+                    Err(e) => {
+                        logger::error(&format!("Failed to save config: {}", e));
                     }
-                    println!(
-                        "{}",
-                        "Tip: run `r2x config show` to confirm the updated value.".dimmed()
-                    );
-                } else {
-                    logger::error(&format!(
-                        "Unknown config key: {}. Currently supported keys: cache-path, verbosity, python-version, venv-path, r2x-core-version, log-python, no-stdout, log-path, log-max-size",
-                        key
-                    ));
                 }
+                println!(
+                    "{}",
+                    "Tip: run `r2x config show` to confirm the updated value.".dimmed()
+                );
             }
             Err(e) => {
                 logger::error(&format!("Failed to load config: {}", e));
             }
         },
-        ConfigAction::Path { new_path } => {
-            // Show or set the configuration file path.
-            // When `new_path` is provided, write it to a pointer file next to the default config dir.
-            // When omitted, print the current resolved config path.
+        ConfigAction::Path { new_path, reset } => {
+            // Show, set, or reset the configuration file path.
+            // Custom locations are recorded in a pointer file next to the default config.
             let config_path = Config::path();
             logger::debug(&format!("Reading config from: {}", config_path.display()));
 
-            if let Some(p) = new_path {
-                // Pointer file path: same directory as default config, file named `.r2x_config_path`
-                let pointer_path = config_path
-                    .parent()
-                    .unwrap_or_else(|| std::path::Path::new("."))
-                    .join(".r2x_config_path");
-
-                // Ensure pointer directory exists
-                if let Some(parent) = pointer_path.parent() {
-                    if let Err(e) = std::fs::create_dir_all(parent) {
-                        logger::error(&format!("Failed to set config path: {}", e));
+            if reset {
+                let default_path = Config::default_path();
+                match Config::reset_path_override() {
+                    Ok(Some(previous)) => logger::success(&format!(
+                        "Config path reset to {} (was {})",
+                        default_path.display(),
+                        previous
+                    )),
+                    Ok(None) => logger::info(&format!(
+                        "Config path already uses the default location: {}",
+                        default_path.display()
+                    )),
+                    Err(e) => {
+                        logger::error(&format!("Failed to reset config path: {}", e));
                         return;
                     }
                 }
-
-                if let Err(e) = std::fs::write(&pointer_path, p.as_bytes()) {
+                if default_path.exists() {
+                    logger::info(&format!(
+                        "Existing config found at {}",
+                        default_path.display()
+                    ));
+                }
+                if std::env::var("R2X_CONFIG").is_ok_and(|v| !v.trim().is_empty()) {
+                    logger::warn("R2X_CONFIG is set and still overrides the config path");
+                }
+            } else if let Some(p) = new_path {
+                if let Err(e) = Config::set_path_override(&p) {
                     logger::error(&format!("Failed to set config path: {}", e));
                     return;
                 }
@@ -257,18 +272,9 @@ pub fn handle_config(action: Option<ConfigAction>, opts: GlobalOpts) {
                 // Print the resolved config path
                 println!("{}", config_path.display());
 
-                // If pointer file exists, also show the override
-                let pointer_path = config_path
-                    .parent()
-                    .unwrap_or_else(|| std::path::Path::new("."))
-                    .join(".r2x_config_path");
-                if pointer_path.exists() {
-                    if let Ok(contents) = std::fs::read_to_string(&pointer_path) {
-                        let trimmed = contents.trim();
-                        if !trimmed.is_empty() {
-                            println!("{} {}", "overridden-by".cyan(), trimmed);
-                        }
-                    }
+                // If a pointer file exists, also show the override
+                if let Some(pointer) = Config::path_override() {
+                    println!("{} {}", "overridden-by".cyan(), pointer);
                 }
             }
         }
@@ -362,6 +368,12 @@ fn handle_cache(action: CacheAction, opts: GlobalOpts) {
         CacheAction::Clean => {
             clean_cache(opts);
         }
+        CacheAction::Prune {
+            older_than,
+            dry_run,
+        } => {
+            prune_cache(older_than, dry_run);
+        }
         CacheAction::Path { new_path } => {
             handle_cache_path(new_path, opts);
         }
@@ -373,11 +385,12 @@ fn handle_python_install(version: Option<String>, _opts: GlobalOpts) {
     logger::debug("Handling Python install command");
     match Config::load() {
         Ok(mut config) => {
-            let version_str = version
-                .or_else(|| config.python_version.clone())
-                .unwrap_or_else(|| "3.12".to_string());
+            let version_str = version.unwrap_or_else(|| config.get_python_version().to_string());
 
-            config.python_version = Some(version_str.clone());
+            if let Err(e) = config.set_python_version(&version_str) {
+                logger::error(&e.to_string());
+                return;
+            }
             if let Err(e) = config.save() {
                 logger::error(&format!("Failed to save config: {}", e));
                 return;
@@ -452,29 +465,8 @@ fn handle_venv_create(skip_confirmation: bool) {
 
                 if should_skip {
                     logger::debug("Skipping confirmation (--yes flag or R2X_VENV_YES set)");
-                } else {
-                    print!(
-                        "{} A virtual environment already exists at `{}`. Do you want to replace it? {} ",
-                        "?".bold().cyan(),
-                        venv_path,
-                        "[y/n] ›".dimmed()
-                    );
-                    let _ = io::stdout().flush();
-                    logger::debug("Prompting user for venv replacement confirmation");
-
-                    let mut response = String::new();
-                    if io::stdin().read_line(&mut response).is_ok() {
-                        let response = response.trim().to_lowercase();
-                        if response != "y" && response != "yes" {
-                            logger::info("Operation cancelled by user");
-                            println!("Operation cancelled.");
-                            return;
-                        }
-                        logger::debug("User confirmed venv replacement");
-                    } else {
-                        logger::error("Failed to read input");
-                        return;
-                    }
+                } else if !confirm_venv_replacement(&venv_path) {
+                    return;
                 }
 
                 if let Err(e) = remove_existing_venv(&venv_path) {
@@ -509,6 +501,68 @@ fn handle_venv_create(skip_confirmation: bool) {
     }
 }
 
+/// Ask before replacing the venv at `venv_path`; false when declined or unreadable.
+fn confirm_venv_replacement(venv_path: &str) -> bool {
+    print!(
+        "{} A virtual environment already exists at `{}`. Do you want to replace it? {} ",
+        "?".bold().cyan(),
+        venv_path,
+        "[y/n] ›".dimmed()
+    );
+    let _ = io::stdout().flush();
+    logger::debug("Prompting user for venv replacement confirmation");
+
+    let mut response = String::new();
+    if io::stdin().read_line(&mut response).is_err() {
+        logger::error("Failed to read input");
+        return false;
+    }
+    let response = response.trim().to_lowercase();
+    if response != "y" && response != "yes" {
+        logger::info("Operation cancelled by user");
+        println!("Operation cancelled.");
+        return false;
+    }
+    logger::debug("User confirmed venv replacement");
+    true
+}
+
+/// Recreate the venv and reinstall the manifest's packages (`--refresh-venv`).
+///
+/// Prompts before removing an existing venv unless `skip_confirmation` or
+/// `R2X_VENV_YES` is set.
+pub fn refresh_venv(skip_confirmation: bool) -> Result<(), String> {
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+    let venv_path = config.get_venv_path();
+
+    let should_skip = skip_confirmation || std::env::var("R2X_VENV_YES").is_ok();
+    if Path::new(&venv_path).exists() && !should_skip && !confirm_venv_replacement(&venv_path) {
+        return Err("Venv refresh cancelled".to_string());
+    }
+
+    let manifest = Manifest::load().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let reinstalled = rebuild_venv(&config, &manifest.packages)?;
+    logger::success(&format!(
+        "Virtual environment refreshed at {} ({} package(s) reinstalled)",
+        venv_path, reinstalled
+    ));
+    Ok(())
+}
+
+fn rebuild_venv(config: &Config, packages: &[Package]) -> Result<usize, String> {
+    let venv_path = config.get_venv_path();
+    remove_existing_venv(&venv_path)?;
+    Bridge::create_venv(config, &PathBuf::from(&venv_path))
+        .map_err(|e| format!("Failed to create venv: {}", e))?;
+
+    let uv_path = config
+        .uv_path
+        .as_deref()
+        .ok_or_else(|| "uv path not configured".to_string())?;
+    reinstall_packages(uv_path, &config.get_venv_python_path(), packages)
+        .map_err(|e| format!("Failed to reinstall packages: {}", e))
+}
+
 fn handle_venv_path(new_path: Option<String>, _opts: GlobalOpts) {
     logger::debug("Handling venv path command");
     match Config::load() {
@@ -533,7 +587,10 @@ fn handle_venv_path(new_path: Option<String>, _opts: GlobalOpts) {
                     return;
                 }
 
-                config.venv_path = Some(path.clone());
+                if let Err(e) = config.set_venv_path(&path) {
+                    logger::error(&e.to_string());
+                    return;
+                }
                 if let Err(e) = config.save() {
                     logger::error(&format!("Failed to save config: {}", e));
                     return;
@@ -708,6 +765,106 @@ pub fn clean_cache_folder() {
     }
 }
 
+/// Plugin manifest kept in the cache directory, never pruned
+const MANIFEST_FILE_NAME: &str = "manifest.toml";
+
+/// Files removed from the cache by `r2x config cache prune`
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PruneSummary {
+    files: usize,
+    bytes: u64,
+}
+
+fn prune_cache(older_than_days: u64, dry_run: bool) {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            logger::error(&format!("Failed to load config: {}", e));
+            return;
+        }
+    };
+    let cache_dir = PathBuf::from(config.get_cache_path());
+    if !cache_dir.exists() {
+        println!(
+            "No cache found at: {}",
+            cache_dir.display().to_string().cyan()
+        );
+        return;
+    }
+
+    let age = Duration::from_secs(older_than_days.saturating_mul(24 * 60 * 60));
+    let cutoff = SystemTime::now()
+        .checked_sub(age)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    match prune_cache_dir(&cache_dir, cutoff, dry_run) {
+        Ok(summary) => logger::success(&format!(
+            "{} {} file{} ({}) older than {} day{} in {}",
+            if dry_run { "Would remove" } else { "Removed" },
+            summary.files,
+            if summary.files == 1 { "" } else { "s" },
+            format_bytes(summary.bytes),
+            older_than_days,
+            if older_than_days == 1 { "" } else { "s" },
+            cache_dir.display()
+        )),
+        Err(e) => logger::error(&format!("Failed to prune cache folder: {}", e)),
+    }
+}
+
+/// Remove files under `dir` last modified before `cutoff`, keeping directories
+///
+/// The plugin manifest and virtual environments (directories holding a
+/// `pyvenv.cfg`, such as the `--python` venvs) are left alone: removing
+/// part of a venv breaks it without it ever being rebuilt.
+///
+/// With `dry_run`, nothing is removed and the summary counts what would be.
+fn prune_cache_dir(dir: &Path, cutoff: SystemTime, dry_run: bool) -> io::Result<PruneSummary> {
+    let mut summary = PruneSummary::default();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_name() == MANIFEST_FILE_NAME {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            if path.join("pyvenv.cfg").exists() {
+                continue;
+            }
+            let nested = prune_cache_dir(&path, cutoff, dry_run)?;
+            summary.files += nested.files;
+            summary.bytes += nested.bytes;
+            continue;
+        }
+
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.modified()? >= cutoff {
+            continue;
+        }
+        if dry_run {
+            logger::info(&format!("Would remove {}", path.display()));
+        } else {
+            fs::remove_file(&path)?;
+        }
+        summary.files += 1;
+        summary.bytes += metadata.len();
+    }
+    Ok(summary)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
 fn handle_cache_path(new_path: Option<String>, _opts: GlobalOpts) {
     match Config::load() {
         Ok(mut config) => {
@@ -719,7 +876,10 @@ fn handle_cache_path(new_path: Option<String>, _opts: GlobalOpts) {
                     return;
                 }
 
-                config.cache_path = Some(path.clone());
+                if let Err(e) = config.set_cache_path(&path) {
+                    logger::error(&e.to_string());
+                    return;
+                }
                 if let Err(e) = config.save() {
                     logger::error(&format!("Failed to save config: {}", e));
                     return;
@@ -747,8 +907,7 @@ mod tests {
         GlobalOpts {
             quiet: 1,
             verbose: 0,
-            log_python: false,
-            no_stdout: false,
+            ..GlobalOpts::default()
         }
     }
 
@@ -756,8 +915,7 @@ mod tests {
         GlobalOpts {
             quiet: 0,
             verbose: 1,
-            log_python: false,
-            no_stdout: false,
+            ..GlobalOpts::default()
         }
     }
 
@@ -765,8 +923,7 @@ mod tests {
         GlobalOpts {
             quiet: 0,
             verbose: 0,
-            log_python: false,
-            no_stdout: false,
+            ..GlobalOpts::default()
         }
     }
 
@@ -851,4 +1008,127 @@ mod tests {
             assert!(!cache_dir.exists());
         });
     }
+
+    #[test]
+    fn prune_removes_only_files_older_than_the_cutoff() {
+        let Ok(cache) = tempfile::tempdir() else {
+            return;
+        };
+        let nested = cache.path().join("metadata").join("r2x-reeds");
+        let venv = cache.path().join("venvs").join("3.12");
+        assert!(fs::create_dir_all(&nested).is_ok());
+        assert!(fs::create_dir_all(venv.join("bin")).is_ok());
+        let old = [cache.path().join("old.json"), nested.join("old.pyc")];
+        let kept = [
+            cache.path().join("manifest.toml"),
+            venv.join("pyvenv.cfg"),
+            venv.join("bin").join("python"),
+        ];
+        let recent = nested.join("recent.json");
+        let cutoff = SystemTime::UNIX_EPOCH + Duration::from_secs(2_000_000);
+        let set_mtime = |path: &Path, contents: &str, mtime: SystemTime| {
+            fs::write(path, contents).is_ok()
+                && fs::File::options()
+                    .write(true)
+                    .open(path)
+                    .and_then(|file| file.set_modified(mtime))
+                    .is_ok()
+        };
+        for path in old.iter().chain(&kept) {
+            assert!(set_mtime(
+                path,
+                "0123456789",
+                cutoff - Duration::from_secs(60)
+            ));
+        }
+        assert!(set_mtime(
+            &recent,
+            "fresh",
+            cutoff + Duration::from_secs(60)
+        ));
+
+        let preview = prune_cache_dir(cache.path(), cutoff, true);
+        let expected = PruneSummary {
+            files: 2,
+            bytes: 20,
+        };
+        assert!(preview.is_ok_and(|summary| summary == expected));
+        assert!(old.iter().all(|path| path.exists()));
+
+        let pruned = prune_cache_dir(cache.path(), cutoff, false);
+        assert!(pruned.is_ok_and(|summary| summary == expected));
+        assert!(old.iter().all(|path| !path.exists()));
+        assert!(kept.iter().all(|path| path.exists()));
+        assert!(recent.exists());
+        assert!(nested.is_dir());
+    }
+
+    #[test]
+    fn format_bytes_picks_a_readable_unit() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rebuild_venv_recreates_venv_and_reinstalls_packages() {
+        use std::os::unix::fs::PermissionsExt;
+        use std::sync::Arc;
+
+        let Ok(dir) = tempfile::tempdir() else {
+            return;
+        };
+        let venv = dir.path().join("venv");
+        let source = dir.path().join("r2x-local");
+        let log = dir.path().join("uv.log");
+        assert!(fs::create_dir_all(&venv).is_ok());
+        assert!(fs::create_dir_all(&source).is_ok());
+        assert!(fs::write(venv.join("stale"), "").is_ok());
+
+        // Stand-in for uv: record every call and create the venv directory.
+        let uv = dir.path().join("uv");
+        let script = format!(
+            "#!/bin/sh\necho \"$@\" >> {}\nif [ \"$1\" = venv ]; then mkdir -p \"$2\"; fi\n",
+            log.display()
+        );
+        assert!(fs::write(&uv, script).is_ok());
+        assert!(fs::set_permissions(&uv, fs::Permissions::from_mode(0o755)).is_ok());
+
+        let config = Config {
+            uv_path: Some(uv.display().to_string()),
+            venv_path: Some(venv.display().to_string()),
+            ..Config::default()
+        };
+        let packages = vec![
+            Package {
+                name: Arc::from("r2x-reeds"),
+                ..Package::default()
+            },
+            Package {
+                name: Arc::from("r2x-local"),
+                editable_install: true,
+                source_uri: Some(Arc::from(source.display().to_string())),
+                ..Package::default()
+            },
+            Package {
+                name: Arc::from("r2x-core"),
+                install_type: r2x_manifest::types::InstallType::Dependency,
+                ..Package::default()
+            },
+        ];
+
+        assert_eq!(rebuild_venv(&config, &packages), Ok(2));
+        assert!(venv.is_dir());
+        assert!(!venv.join("stale").exists());
+
+        let calls = fs::read_to_string(&log).unwrap_or_default();
+        let calls: Vec<&str> = calls.lines().collect();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0].starts_with(&format!("venv {} --python", venv.display())));
+        assert!(calls[1].starts_with("pip install --python"));
+        assert!(calls[1].contains(&format!("-e {}", source.display())));
+        assert!(calls[1].ends_with(" r2x-reeds"));
+        assert!(!calls[1].contains("r2x-core"));
+    }
 }
@@ -1,6 +1,9 @@
+use crate::commands::plugins::docs::{format_constraints, format_field_type};
 use crate::common::GlobalOpts;
+use crate::manifest_lookup::resolve_plugin_ref;
 use colored::Colorize;
 use r2x_logger as logger;
+use r2x_manifest::types::{DefaultValue, Manifest, Plugin};
 use std::fs;
 use std::io::{self, Write};
 use std::path::Path;
@@ -148,9 +151,263 @@ pub fn handle_init(filename: Option<String>, _opts: GlobalOpts) {
     }
 }
 
+/// Scaffold a plugin's `config:` block from its extracted config schema
+///
+/// Merged into `filename` (default: pipeline.yaml) when the file exists,
+/// otherwise printed so it can be pasted or redirected.
+pub fn handle_init_for_plugin(filename: Option<String>, plugin_ref: &str) -> Result<(), String> {
+    let manifest = Manifest::load().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let resolved = resolve_plugin_ref(&manifest, plugin_ref).map_err(|e| e.to_string())?;
+    if resolved.plugin.config_schema.is_empty() {
+        logger::warn(&format!(
+            "{} has no extracted config schema; the scaffold is empty",
+            plugin_ref
+        ));
+    }
+    let entry = render_plugin_config(plugin_ref, resolved.plugin);
+
+    let target_filename = filename.unwrap_or_else(|| DEFAULT_FILENAME.to_string());
+    let target_path = Path::new(&target_filename);
+    if !target_path.exists() {
+        print!("config:\n{}", entry);
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(target_path)
+        .map_err(|e| format!("Failed to read {}: {}", target_filename, e))?;
+    let merged = merge_plugin_config(&existing, plugin_ref, &entry)?;
+    fs::write(target_path, merged)
+        .map_err(|e| format!("Failed to write {}: {}", target_filename, e))?;
+    logger::success(&format!(
+        "Added config for {} to {}",
+        plugin_ref, target_filename
+    ));
+    Ok(())
+}
+
+/// `  <plugin>:` followed by a line per config field, indented to sit under `config:`
+///
+/// Fields with a default start at that default; required fields start at
+/// `null`. A trailing comment gives the type, whether the field is required,
+/// and its constraints.
+fn render_plugin_config(plugin_ref: &str, plugin: &Plugin) -> String {
+    let mut fields: Vec<_> = plugin.config_schema.iter().collect();
+    fields.sort_by_key(|(name, _)| *name);
+
+    let mut out = format!("  {}:\n", plugin_ref);
+    if fields.is_empty() {
+        out.push_str("    {}\n");
+        return out;
+    }
+    for (name, field) in fields {
+        let mut notes = vec![format_field_type(field)];
+        if field.required {
+            notes.push("required".to_string());
+        }
+        let constraints = format_constraints(field);
+        if !constraints.is_empty() {
+            notes.push(constraints);
+        }
+        let placeholder = match &field.default {
+            Some(default) => yaml_placeholder(default, &mut notes),
+            None => "null".to_string(),
+        };
+        out.push_str(&format!(
+            "    {}: {}  # {}\n",
+            name,
+            placeholder,
+            notes.join(", ")
+        ));
+    }
+    out
+}
+
+/// YAML for a Python default; expressions other than `None` become `null`
+/// with the expression noted in the comment
+fn yaml_placeholder(default: &DefaultValue, notes: &mut Vec<String>) -> String {
+    if let Some(value) = default_to_json(default) {
+        return value.to_string();
+    }
+    notes.push(format!("default: {}", default));
+    "null".to_string()
+}
+
+fn default_to_json(default: &DefaultValue) -> Option<serde_json::Value> {
+    use serde_json::Value;
+    Some(match default {
+        DefaultValue::Bool(value) => Value::Bool(*value),
+        DefaultValue::Int(value) => Value::from(*value),
+        DefaultValue::Float(value) => Value::from(*value),
+        DefaultValue::Str(value) => Value::String(value.to_string()),
+        DefaultValue::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(default_to_json)
+                .collect::<Option<Vec<_>>>()?,
+        ),
+        DefaultValue::Expr { .. } if default.is_none() => Value::Null,
+        DefaultValue::Expr { .. } => return None,
+    })
+}
+
+/// Insert `entry` under the top-level `config:` of a pipeline file, keeping its comments
+fn merge_plugin_config(existing: &str, plugin_ref: &str, entry: &str) -> Result<String, String> {
+    let parsed: serde_yaml::Value = serde_yaml::from_str(existing)
+        .map_err(|e| format!("Failed to parse existing pipeline file: {}", e))?;
+    if parsed
+        .get("config")
+        .and_then(|config| config.get(plugin_ref))
+        .is_some()
+    {
+        return Err(format!(
+            "The pipeline file already configures {}; edit that block instead",
+            plugin_ref
+        ));
+    }
+
+    let mut lines: Vec<String> = existing.lines().map(str::to_string).collect();
+    let config_line = lines.iter().position(|line| {
+        line.strip_prefix("config:")
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '#']))
+    });
+    let merged =
+        if let Some(index) = config_line {
+            let (value, comment) = split_comment(&lines[index]["config:".len()..]);
+            match value.trim() {
+                "" => {}
+                "{}" => lines[index] = format!("config:{}", comment),
+                _ => return Err(
+                    "The pipeline file's `config:` is written inline; turn it into a block first"
+                        .to_string(),
+                ),
+            }
+            let indent = block_indent(&lines[index + 1..]).unwrap_or_else(|| file_indent(&lines));
+            lines.insert(
+                index + 1,
+                reindent(entry, indent).trim_end_matches('\n').to_string(),
+            );
+            format!("{}\n", lines.join("\n"))
+        } else {
+            let mut merged = existing.to_string();
+            if !merged.is_empty() && !merged.ends_with('\n') {
+                merged.push('\n');
+            }
+            merged.push_str("\nconfig:\n");
+            merged.push_str(&reindent(entry, file_indent(&lines)));
+            merged
+        };
+
+    // The text edit must add the entry and leave everything else as it was.
+    let mut reparsed: serde_yaml::Value = serde_yaml::from_str(&merged)
+        .map_err(|e| format!("Could not merge the config block: {}", e))?;
+    let added = reparsed
+        .get_mut("config")
+        .and_then(serde_yaml::Value::as_mapping_mut)
+        .and_then(|config| config.remove(plugin_ref));
+    let mut expected = parsed;
+    if expected.is_null() {
+        expected = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+    }
+    if let serde_yaml::Value::Mapping(doc) = &mut expected {
+        let config = doc
+            .entry("config".into())
+            .or_insert(serde_yaml::Value::Null);
+        if config.is_null() {
+            *config = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        }
+    }
+    if added.is_none() || reparsed != expected {
+        return Err(
+            "Could not merge the config block into the pipeline file; add it by hand".to_string(),
+        );
+    }
+    Ok(merged)
+}
+
+/// `line` split before a `#` comment, leaving the comment with the spaces before it
+fn split_comment(line: &str) -> (&str, &str) {
+    let hash = line
+        .char_indices()
+        .find(|&(i, c)| c == '#' && (i == 0 || line[..i].ends_with([' ', '\t'])));
+    match hash {
+        Some((index, _)) => line.split_at(line[..index].trim_end().len()),
+        None => (line, ""),
+    }
+}
+
+/// Indentation of the first entry under a block, if it has one
+fn block_indent(lines: &[String]) -> Option<usize> {
+    let first = lines
+        .iter()
+        .find(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))?;
+    let indent = first.len() - first.trim_start_matches(' ').len();
+    (indent > 0).then_some(indent)
+}
+
+/// Smallest indentation used in the file, defaulting to two spaces
+fn file_indent(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|line| line.len() - line.trim_start_matches(' ').len())
+        .filter(|&indent| indent > 0)
+        .min()
+        .unwrap_or(2)
+}
+
+/// `entry`, rendered with two-space steps, re-indented to `indent` spaces per level
+fn reindent(entry: &str, indent: usize) -> String {
+    let mut out = String::with_capacity(entry.len());
+    for line in entry.lines() {
+        let body = line.trim_start_matches(' ');
+        let level = (line.len() - body.len()) / 2;
+        out.push_str(&" ".repeat(level * indent));
+        out.push_str(body);
+        out.push('\n');
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use crate::commands::init::*;
+    use r2x_manifest::types::{Constraint, FieldType, SchemaField, SchemaFields};
+    use std::sync::Arc;
+
+    fn reeds_parser() -> Plugin {
+        let mut schema = SchemaFields::default();
+        schema.insert(
+            Arc::from("solve_year"),
+            SchemaField {
+                field_type: FieldType::Int,
+                required: true,
+                constraints: smallvec::smallvec![Constraint::Ge(2020.0)],
+                ..SchemaField::default()
+            },
+        );
+        schema.insert(
+            Arc::from("weather_year"),
+            SchemaField {
+                field_type: FieldType::Int,
+                default: Some(DefaultValue::Int(2012)),
+                ..SchemaField::default()
+            },
+        );
+        schema.insert(
+            Arc::from("folder"),
+            SchemaField {
+                default: Some(DefaultValue::Expr {
+                    expr: Arc::from("Path(\"inputs\")"),
+                }),
+                ..SchemaField::default()
+            },
+        );
+        Plugin {
+            name: Arc::from("parser"),
+            config_schema: schema,
+            ..Plugin::default()
+        }
+    }
 
     #[test]
     fn test_default_filename() {
@@ -171,4 +428,85 @@ mod tests {
     fn test_template_contains_config() {
         assert!(PIPELINE_TEMPLATE.contains("config:"));
     }
+
+    #[test]
+    fn plugin_scaffold_lists_each_field_with_its_default() {
+        let entry = render_plugin_config("reeds-parser", &reeds_parser());
+        assert_eq!(
+            entry,
+            "  reeds-parser:\n\
+             \x20   folder: null  # str, default: Path(\"inputs\")\n\
+             \x20   solve_year: null  # int, required, >= 2020\n\
+             \x20   weather_year: 2012  # int\n"
+        );
+    }
+
+    #[test]
+    fn plugin_scaffold_merges_under_existing_config() {
+        let entry = render_plugin_config("reeds-parser", &reeds_parser());
+        let existing = "pipelines:\n  nightly:\n    - reeds-parser\n\n# Plugin settings\nconfig:\n  exporter:\n    format: json\n";
+        let merged = merge_plugin_config(existing, "reeds-parser", &entry);
+        assert!(merged.is_ok());
+        let Ok(merged) = merged else {
+            return;
+        };
+        assert!(merged.contains("# Plugin settings\nconfig:\n  reeds-parser:\n    folder:"));
+        assert!(merged.ends_with("  exporter:\n    format: json\n"));
+
+        let parsed: Result<serde_yaml::Value, _> = serde_yaml::from_str(&merged);
+        let weather_year = parsed.ok().and_then(|doc| {
+            doc.get("config")?
+                .get("reeds-parser")?
+                .get("weather_year")?
+                .as_i64()
+        });
+        assert_eq!(weather_year, Some(2012));
+
+        assert!(merge_plugin_config(&merged, "reeds-parser", &entry).is_err());
+        let appended = merge_plugin_config("pipelines: {}\n", "reeds-parser", &entry);
+        assert_eq!(
+            appended.ok().as_deref(),
+            Some(format!("pipelines: {{}}\n\nconfig:\n{}", entry).as_str())
+        );
+    }
+
+    #[test]
+    fn plugin_scaffold_follows_the_file_layout() {
+        let entry = render_plugin_config("reeds-parser", &reeds_parser());
+        let weather_year = |merged: &str| {
+            serde_yaml::from_str::<serde_yaml::Value>(merged)
+                .ok()
+                .and_then(|doc| {
+                    doc.get("config")?
+                        .get("reeds-parser")?
+                        .get("weather_year")?
+                        .as_i64()
+                })
+        };
+
+        let four_spaces = "pipelines:\n    nightly:\n        - reeds-parser\nconfig:  # settings\n    # exporter first\n    exporter:\n        format: json\n";
+        let merged = merge_plugin_config(four_spaces, "reeds-parser", &entry);
+        assert!(merged.as_deref().is_ok_and(
+            |merged| merged.contains("config:  # settings\n    reeds-parser:\n        folder:")
+        ));
+        assert_eq!(merged.as_deref().ok().and_then(weather_year), Some(2012));
+
+        let empty = merge_plugin_config(
+            "pipelines: {}\nconfig: {}  # none yet\n",
+            "reeds-parser",
+            &entry,
+        );
+        assert!(empty
+            .as_deref()
+            .is_ok_and(|merged| merged.contains("config:  # none yet\n  reeds-parser:\n")));
+        assert_eq!(empty.as_deref().ok().and_then(weather_year), Some(2012));
+
+        assert!(merge_plugin_config("", "reeds-parser", &entry).is_ok());
+        assert!(merge_plugin_config(
+            "pipelines: {}\nconfig: {exporter: {format: json}}\n",
+            "reeds-parser",
+            &entry
+        )
+        .is_err());
+    }
 }
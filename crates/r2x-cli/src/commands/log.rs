@@ -27,6 +27,11 @@ pub enum LogSetAction {
         /// Maximum size in bytes
         bytes: u64,
     },
+    /// Set how many bytes of each subprocess's stdout/stderr are logged (default: 65536)
+    MaxCaptureBytes {
+        /// Maximum bytes per stream
+        bytes: u64,
+    },
     /// Enable or disable Python logs on console by default
     LogPython {
         /// true or false
@@ -78,6 +83,13 @@ fn show_logging_config() {
                 "max-size".cyan(),
                 format_max_size(config.log_max_size)
             );
+            println!(
+                "  {}: {} bytes",
+                "max-capture-bytes".cyan(),
+                config
+                    .log_max_capture_bytes
+                    .unwrap_or(logger::DEFAULT_MAX_CAPTURE_BYTES)
+            );
             println!("  {}: {}", "path".cyan(), resolve_log_path(&config));
         }
         Err(e) => {
@@ -90,17 +102,18 @@ fn handle_log_path(new_path: Option<String>) {
     match Config::load() {
         Ok(mut config) => {
             if let Some(path) = new_path {
-                if path.trim().is_empty() {
-                    logger::error("Log path cannot be empty.");
+                if let Err(e) = config.set_log_path(&path) {
+                    logger::error(&e.to_string());
                     return;
                 }
-
-                config.log_path = Some(path.clone());
                 if let Err(e) = config.save() {
                     logger::error(&format!("Failed to save config: {}", e));
                     return;
                 }
                 logger::success(&format!("Set log path to {}", path));
+                // This process keeps logging to the file it opened at startup.
+                println!("{}", path);
+                return;
             }
 
             println!("{}", resolve_log_path(&config));
@@ -116,15 +129,19 @@ fn set_logging_config(setting: LogSetAction) {
         Ok(mut config) => {
             let (key, value_display) = match setting {
                 LogSetAction::MaxSize { bytes } => {
-                    config.log_max_size = Some(bytes);
+                    config.set_log_max_size(bytes);
                     ("max-size", bytes.to_string())
                 }
+                LogSetAction::MaxCaptureBytes { bytes } => {
+                    config.set_log_max_capture_bytes(bytes);
+                    ("max-capture-bytes", bytes.to_string())
+                }
                 LogSetAction::LogPython { enabled } => {
-                    config.log_python = Some(enabled);
+                    config.set_log_python(enabled);
                     ("log-python", enabled.to_string())
                 }
                 LogSetAction::NoStdout { enabled } => {
-                    config.no_stdout = Some(enabled);
+                    config.set_no_stdout(enabled);
                     ("no-stdout", enabled.to_string())
                 }
             };
@@ -146,10 +163,11 @@ fn set_logging_config(setting: LogSetAction) {
     }
 }
 
+/// The file this process logs to (`--log-file` included), else the configured one
 fn resolve_log_path(config: &Config) -> String {
-    config
-        .log_path
-        .clone()
+    logger::get_log_path()
+        .map(|path| path.to_string_lossy().to_string())
+        .or_else(|| config.log_path.clone())
         .unwrap_or_else(logger::get_log_path_string)
 }
 
@@ -195,4 +213,22 @@ mod tests {
             assert_eq!(config.log_max_size, Some(10 * 1024 * 1024));
         });
     }
+
+    #[test]
+    fn test_log_set_max_capture_bytes() {
+        with_temp_config(|| {
+            handle_log(Some(LogAction::Set {
+                setting: LogSetAction::MaxCaptureBytes { bytes: 4096 },
+            }));
+
+            let Ok(config) = Config::load() else {
+                return;
+            };
+            assert_eq!(config.log_max_capture_bytes, Some(4096));
+            assert_eq!(
+                config.get("log-max-capture-bytes"),
+                Some("4096".to_string())
+            );
+        });
+    }
 }
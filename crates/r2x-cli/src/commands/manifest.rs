@@ -0,0 +1,38 @@
+use clap::Subcommand;
+use r2x_logger as logger;
+use r2x_manifest::schema::manifest_schema;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ManifestAction {
+    /// Print the JSON Schema of the manifest file
+    Schema {
+        /// Write the schema to a file instead of stdout
+        #[arg(short = 'o', long, value_name = "FILE")]
+        output: Option<String>,
+    },
+}
+
+pub fn handle_manifest(action: ManifestAction) {
+    match action {
+        ManifestAction::Schema { output } => {
+            if let Err(e) = write_manifest_schema(output.as_deref()) {
+                logger::error(&e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn write_manifest_schema(output: Option<&str>) -> Result<(), String> {
+    let rendered = serde_json::to_string_pretty(&manifest_schema())
+        .map_err(|e| format!("Failed to serialize manifest schema: {}", e))?;
+
+    if let Some(path) = output {
+        std::fs::write(path, format!("{}\n", rendered))
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        logger::success(&format!("Manifest schema written to: {}", path));
+    } else {
+        println!("{}", rendered);
+    }
+    Ok(())
+}
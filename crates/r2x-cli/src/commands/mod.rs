@@ -1,6 +1,7 @@
 pub mod config;
 pub mod init;
 pub mod log;
+pub mod manifest;
 pub mod plugins;
 pub mod read;
 pub mod run;
@@ -0,0 +1,194 @@
+use crate::manifest_lookup::resolve_plugin_ref;
+use crate::plugins::error::PluginError;
+use r2x_logger as logger;
+use r2x_manifest::types::{Constraint, FieldType, Manifest, Plugin, SchemaField};
+
+/// Render a plugin's config reference as Markdown, printing it or writing it to `output`.
+pub fn show_plugin_docs(plugin_name: &str, output: Option<&str>) -> Result<(), PluginError> {
+    let manifest = Manifest::load()?;
+    let resolved = resolve_plugin_ref(&manifest, plugin_name)
+        .map_err(|e| PluginError::InvalidArgs(e.to_string()))?;
+
+    let markdown = render_plugin_docs(plugin_name, resolved.plugin);
+
+    if let Some(path) = output {
+        std::fs::write(path, &markdown)?;
+        logger::success(&format!("Plugin docs written to: {}", path));
+    } else {
+        print!("{}", markdown);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn render_plugin_docs(plugin_name: &str, plugin: &Plugin) -> String {
+    let mut lines = vec![format!("## {}", plugin_name), String::new()];
+
+    if let Some(config_class) = &plugin.config_class {
+        match &plugin.config_module {
+            Some(module) => lines.push(format!("Config class: `{}.{}`", module, config_class)),
+            None => lines.push(format!("Config class: `{}`", config_class)),
+        }
+        lines.push(String::new());
+    }
+
+    if plugin.config_schema.is_empty() {
+        lines.push("_No configuration fields._".to_string());
+        lines.push(String::new());
+        return lines.join("\n");
+    }
+
+    lines.push("| Field | Type | Required | Default | Constraints | Description |".to_string());
+    lines.push("| --- | --- | --- | --- | --- | --- |".to_string());
+
+    let mut fields: Vec<_> = plugin.config_schema.iter().collect();
+    fields.sort_by_key(|(name, _)| *name);
+
+    for (name, field) in fields {
+        let description = plugin
+            .parameters
+            .iter()
+            .find(|param| param.name == *name)
+            .and_then(|param| param.description.as_deref())
+            .unwrap_or("");
+        let default = field
+            .default
+            .as_ref()
+            .map(|value| format!("`{}`", value))
+            .unwrap_or_default();
+
+        lines.push(format!(
+            "| `{}` | {} | {} | {} | {} | {} |",
+            name,
+            escape_cell(&format_field_type(field)),
+            if field.required { "yes" } else { "no" },
+            escape_cell(&default),
+            escape_cell(&format_constraints(field)),
+            escape_cell(description),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub(crate) fn format_field_type(field: &SchemaField) -> String {
+    let base = match field.field_type {
+        FieldType::Str => "str",
+        FieldType::Int => "int",
+        FieldType::Float => "float",
+        FieldType::Bool => "bool",
+        FieldType::Array => "array",
+        FieldType::Object => "object",
+        FieldType::Datetime => "datetime",
+        FieldType::Any => "any",
+    };
+
+    if let Some(items) = &field.items {
+        return format!("{}[{}]", base, items);
+    }
+    if let Some(class) = field
+        .nested
+        .as_ref()
+        .and_then(|nested| nested.class.as_ref())
+    {
+        return format!("{} ({})", base, class);
+    }
+    base.to_string()
+}
+
+pub(crate) fn format_constraints(field: &SchemaField) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    if let Some(values) = &field.enum_values {
+        let options: Vec<String> = values.iter().map(|v| format!("`{}`", v)).collect();
+        parts.push(format!("one of {}", options.join(", ")));
+    }
+
+    for constraint in &field.constraints {
+        parts.push(match constraint {
+            Constraint::Ge(v) => format!(">= {}", v),
+            Constraint::Le(v) => format!("<= {}", v),
+            Constraint::Gt(v) => format!("> {}", v),
+            Constraint::Lt(v) => format!("< {}", v),
+            Constraint::MinLen(v) => format!("min length {}", v),
+            Constraint::MaxLen(v) => format!("max length {}", v),
+            Constraint::Pattern(p) => format!("matches `{}`", p),
+            Constraint::MultipleOf(v) => format!("multiple of {}", v),
+        });
+    }
+
+    parts.join(", ")
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::plugins::docs::*;
+    use r2x_manifest::types::{DefaultValue, Parameter, SchemaFields};
+    use std::sync::Arc;
+
+    fn sample_plugin() -> Plugin {
+        let mut schema = SchemaFields::default();
+        schema.insert(
+            Arc::from("solve_year"),
+            SchemaField {
+                field_type: FieldType::Int,
+                required: true,
+                constraints: smallvec::smallvec![Constraint::Ge(2020.0), Constraint::Le(2050.0)],
+                ..SchemaField::default()
+            },
+        );
+        schema.insert(
+            Arc::from("mode"),
+            SchemaField {
+                enum_values: Some(Arc::from(vec![Arc::from("fast"), Arc::from("slow")])),
+                default: Some(DefaultValue::Str(Arc::from("fast"))),
+                ..SchemaField::default()
+            },
+        );
+
+        Plugin {
+            name: Arc::from("reeds-parser"),
+            config_class: Some(Arc::from("ReEDSConfig")),
+            config_module: Some(Arc::from("r2x_reeds.config")),
+            parameters: smallvec::smallvec![Parameter {
+                name: Arc::from("solve_year"),
+                types: smallvec::smallvec![Arc::from("int")],
+                module: None,
+                required: true,
+                default: None,
+                description: Some(Arc::from("Model year to translate")),
+            }],
+            config_schema: schema,
+            ..Plugin::default()
+        }
+    }
+
+    #[test]
+    fn docs_render_a_row_per_field() {
+        let markdown = render_plugin_docs("reeds-parser", &sample_plugin());
+
+        assert!(
+            markdown.contains("| Field | Type | Required | Default | Constraints | Description |")
+        );
+        assert!(markdown.contains(
+            "| `solve_year` | int | yes |  | >= 2020, <= 2050 | Model year to translate |"
+        ));
+        assert!(markdown.contains("| `mode` | str | no | `\"fast\"` | one of `fast`, `slow` |  |"));
+        assert_eq!(markdown.matches("\n| `").count(), 2);
+    }
+
+    #[test]
+    fn docs_note_plugins_without_config() {
+        let plugin = Plugin {
+            name: Arc::from("break-gens"),
+            ..Plugin::default()
+        };
+        let markdown = render_plugin_docs("break-gens", &plugin);
+        assert!(markdown.contains("_No configuration fields._"));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::commands::plugins::utils::parse_kind;
+use crate::plugins::error::PluginError;
+use r2x_logger as logger;
+use r2x_manifest::runtime::{build_runtime_bindings, IoSlot, PluginRole};
+use r2x_manifest::types::Manifest;
+
+/// Output format for `r2x plugin graph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+struct GraphNode {
+    /// Qualified `package.plugin` name
+    id: String,
+    role: PluginRole,
+}
+
+struct GraphEdge {
+    from: usize,
+    to: usize,
+    slots: Vec<IoSlot>,
+}
+
+/// Plugins as nodes, with an edge wherever a producer's slot is consumed by another plugin
+struct PluginGraph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+/// Print or write the IO graph of installed plugins.
+pub fn show_plugin_graph(
+    format: GraphFormat,
+    kind: Option<&str>,
+    output: Option<&str>,
+) -> Result<(), PluginError> {
+    let kind = kind.map(parse_kind).transpose()?;
+
+    let manifest = Manifest::load()?;
+    let graph = build_plugin_graph(&manifest, kind);
+    let rendered = match format {
+        GraphFormat::Dot => render_dot(&graph),
+        GraphFormat::Mermaid => render_mermaid(&graph),
+    };
+
+    if let Some(path) = output {
+        std::fs::write(path, &rendered)?;
+        logger::success(&format!("Plugin graph written to: {}", path));
+    } else {
+        print!("{}", rendered);
+    }
+
+    Ok(())
+}
+
+/// Build the graph, keeping only plugins of `kind` and the plugins they connect to.
+fn build_plugin_graph(manifest: &Manifest, kind: Option<PluginRole>) -> PluginGraph {
+    let mut nodes: Vec<GraphNode> = manifest
+        .packages
+        .iter()
+        .flat_map(|package| {
+            package.plugins.iter().map(move |plugin| GraphNode {
+                id: format!("{}.{}", package.name, plugin.name),
+                role: build_runtime_bindings(plugin).role,
+            })
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut edges = Vec::new();
+    for (from, producer) in nodes.iter().enumerate() {
+        let produces = producer.role.io_contract().produces;
+        for (to, consumer) in nodes.iter().enumerate() {
+            if from == to {
+                continue;
+            }
+            let slots: Vec<IoSlot> = consumer
+                .role
+                .io_contract()
+                .consumes
+                .iter()
+                .copied()
+                .filter(|slot| produces.contains(slot))
+                .collect();
+            if !slots.is_empty() {
+                edges.push(GraphEdge { from, to, slots });
+            }
+        }
+    }
+
+    let Some(kind) = kind else {
+        return PluginGraph { nodes, edges };
+    };
+
+    let mut keep: Vec<bool> = nodes.iter().map(|node| node.role == kind).collect();
+    edges.retain(|edge| keep[edge.from] || keep[edge.to]);
+    for edge in &edges {
+        keep[edge.from] = true;
+        keep[edge.to] = true;
+    }
+
+    // Re-number the surviving nodes so edges keep pointing at the right ones.
+    let mut remap = vec![None; nodes.len()];
+    let mut kept_nodes = Vec::new();
+    for (idx, node) in nodes.into_iter().enumerate() {
+        if keep[idx] {
+            remap[idx] = Some(kept_nodes.len());
+            kept_nodes.push(node);
+        }
+    }
+    let edges = edges
+        .into_iter()
+        .filter_map(|edge| {
+            Some(GraphEdge {
+                from: remap[edge.from]?,
+                to: remap[edge.to]?,
+                slots: edge.slots,
+            })
+        })
+        .collect();
+
+    PluginGraph {
+        nodes: kept_nodes,
+        edges,
+    }
+}
+
+fn slot_label(slots: &[IoSlot]) -> String {
+    slots
+        .iter()
+        .map(|slot| slot.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_dot(graph: &PluginGraph) -> String {
+    let mut lines = vec!["digraph plugins {".to_string(), "  rankdir=LR;".to_string()];
+    for node in &graph.nodes {
+        lines.push(format!(
+            "  \"{}\" [label=\"{}\\n({})\"];",
+            node.id,
+            node.id,
+            node.role.as_str()
+        ));
+    }
+    for edge in &graph.edges {
+        lines.push(format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\"];",
+            graph.nodes[edge.from].id,
+            graph.nodes[edge.to].id,
+            slot_label(&edge.slots)
+        ));
+    }
+    lines.push("}".to_string());
+    lines.join("\n") + "\n"
+}
+
+fn render_mermaid(graph: &PluginGraph) -> String {
+    let mut lines = vec!["flowchart LR".to_string()];
+    for (idx, node) in graph.nodes.iter().enumerate() {
+        lines.push(format!(
+            "  n{}[\"{} ({})\"]",
+            idx,
+            node.id,
+            node.role.as_str()
+        ));
+    }
+    for edge in &graph.edges {
+        lines.push(format!(
+            "  n{} -->|{}| n{}",
+            edge.from,
+            slot_label(&edge.slots),
+            edge.to
+        ));
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::plugins::graph::*;
+    use r2x_manifest::types::{Package, Plugin, PluginType};
+    use std::sync::Arc;
+
+    fn sample_manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        let mut package = Package {
+            name: Arc::from("r2x-reeds"),
+            ..Default::default()
+        };
+        for (name, class_name) in [
+            ("reeds-parser", "ReEDSParser"),
+            ("reeds-exporter", "ReEDSExporter"),
+        ] {
+            package.plugins.push(Plugin {
+                name: Arc::from(name),
+                plugin_type: PluginType::Class,
+                module: Arc::from("r2x_reeds"),
+                class_name: Some(Arc::from(class_name)),
+                ..Default::default()
+            });
+        }
+        package.plugins.push(Plugin {
+            name: Arc::from("reeds-upgrader"),
+            plugin_type: PluginType::Class,
+            module: Arc::from("r2x_reeds.upgrader"),
+            class_name: Some(Arc::from("ReEDSUpgrader")),
+            ..Default::default()
+        });
+        manifest.packages.push(package);
+        manifest.rebuild_indexes();
+        manifest
+    }
+
+    #[test]
+    fn dot_has_node_per_plugin_and_producer_consumer_edge() {
+        let dot = render_dot(&build_plugin_graph(&sample_manifest(), None));
+
+        assert!(dot.starts_with("digraph plugins {"));
+        for id in [
+            "r2x-reeds.reeds-parser",
+            "r2x-reeds.reeds-exporter",
+            "r2x-reeds.reeds-upgrader",
+        ] {
+            assert!(
+                dot.contains(&format!("  \"{}\" [label=", id)),
+                "missing node {id}"
+            );
+        }
+        assert!(dot.contains(
+            "\"r2x-reeds.reeds-parser\" -> \"r2x-reeds.reeds-exporter\" [label=\"system\"];"
+        ));
+        assert!(dot.contains(
+            "\"r2x-reeds.reeds-upgrader\" -> \"r2x-reeds.reeds-parser\" [label=\"store_folder\"];"
+        ));
+        assert!(!dot.contains("\"r2x-reeds.reeds-exporter\" ->"));
+    }
+
+    #[test]
+    fn kind_filter_keeps_connected_plugins() {
+        let graph = build_plugin_graph(&sample_manifest(), Some(PluginRole::Exporter));
+        let ids: Vec<&str> = graph.nodes.iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["r2x-reeds.reeds-exporter", "r2x-reeds.reeds-parser"]
+        );
+
+        let mermaid = render_mermaid(&graph);
+        assert!(mermaid.contains("  n1 -->|system| n0"));
+    }
+}
@@ -6,6 +6,7 @@ use crate::plugins::{
     package_spec::{build_package_spec, extract_package_name},
 };
 use colored::Colorize;
+use r2x_config::Config;
 use r2x_logger as logger;
 use r2x_manifest::package_discovery::PackageDiscoverer;
 use std::fs;
@@ -20,11 +21,61 @@ pub struct GitOptions {
     pub commit: Option<String>,
 }
 
+/// Print the uv command and discovery plan for `r2x install --dry-run`
+///
+/// Reads the saved config without setting anything up: a missing uv or
+/// venv is shown by its expected path rather than installed or created.
+pub fn print_install_plan(
+    package: &str,
+    editable: bool,
+    no_cache: bool,
+    git_opts: &GitOptions,
+) -> Result<(), PluginError> {
+    let config =
+        Config::load().map_err(|e| PluginError::Config(format!("Failed to load config: {e}")))?;
+    let uv_path = config
+        .uv_path
+        .clone()
+        .filter(|path| Path::new(path).exists())
+        .or_else(|| which::which("uv").ok().map(|p| p.display().to_string()))
+        .unwrap_or_else(|| "uv".to_string());
+    let python_path = config.get_venv_python_path();
+
+    let package_spec = build_package_spec(
+        package,
+        git_opts.host.clone(),
+        git_opts.branch.clone(),
+        git_opts.tag.clone(),
+        git_opts.commit.clone(),
+    )?;
+    let discovery = if is_workspace_package(&package_spec)? {
+        DiscoveryPlan::AllInstalled
+    } else {
+        DiscoveryPlan::Package(extract_package_name(package)?)
+    };
+    print!(
+        "{}",
+        render_install_plan(
+            &uv_path,
+            &python_path,
+            &package_spec,
+            editable,
+            no_cache,
+            &discovery
+        )
+    );
+    Ok(())
+}
+
 /// Install a plugin package
+///
+/// With `include_dev`, plugins under top-level `tests/` and `examples/` are
+/// discovered too, and already registered packages are rediscovered.
 pub fn install_plugin(
     package: &str,
     editable: bool,
     no_cache: bool,
+    include_dev: bool,
     git_opts: GitOptions,
     ctx: &mut PluginContext,
 ) -> Result<(), PluginError> {
@@ -38,9 +89,10 @@ pub fn install_plugin(
         git_opts.tag.clone(),
         git_opts.commit.clone(),
     )?;
+    let is_workspace = is_workspace_package(&package_spec)?;
 
     // Check if this is a workspace installation
-    if is_workspace_package(&package_spec)? {
+    if is_workspace {
         logger::info("Detected workspace repository, installing all members...");
         // Just install the workspace - uv will handle all members
         run_pip_install(
@@ -54,13 +106,13 @@ pub fn install_plugin(
 
         // Now discover all packages with entry points (like sync command)
         logger::info("Discovering plugins from installed packages...");
-        return discover_all_installed_packages(ctx, no_cache, total_start);
+        return discover_all_installed_packages(ctx, no_cache, include_dev, total_start);
     }
 
     let package_name_for_query = extract_package_name(package)?;
 
     let check_start = std::time::Instant::now();
-    let is_already_installed = if no_cache {
+    let is_already_installed = if no_cache || include_dev {
         None
     } else {
         match get_package_info(&ctx.uv_path, &ctx.python_path, &package_name_for_query) {
@@ -118,16 +170,15 @@ pub fn install_plugin(
     // locator cache so entry_points.txt discovery can see newly installed packages.
     ctx.refresh_locator()?;
 
-    let start = std::time::Instant::now();
-    let (package_version, dependencies) =
+    let (package_version, dependencies) = logger::timed("get_package_info", || {
         match get_package_info(&ctx.uv_path, &ctx.python_path, &package_name_for_query) {
             Ok((version, deps)) => (version, deps),
             Err(e) => {
                 logger::debug(&format!("Failed to get package info: {}", e));
                 (None, Vec::new())
             }
-        };
-    logger::debug(&format!("get_package_info took: {:?}", start.elapsed()));
+        }
+    });
 
     // source_path: local filesystem path for AST discovery (editable installs only)
     let source_path = if editable && Path::new(package).exists() {
@@ -159,6 +210,7 @@ pub fn install_plugin(
             editable,
             source_path,
             source_uri,
+            include_dev,
         },
     )?;
     logger::debug(&format!(
@@ -189,10 +241,14 @@ pub fn show_install_help() -> Result<(), PluginError> {
     println!("{}", "Options:".bold());
     println!("  -e, --editable     Install in editable mode (for development)");
     println!("  --no-cache         Skip metadata cache and force rebuild");
-    println!("  --host <HOST>      Git host (default: github.com)");
+    println!("  --include-dev      Also discover plugins under tests/ and examples/");
+    println!(
+        "  --host <HOST>      Git host for gh:owner/repo (default: github.com; git@host for SSH)"
+    );
     println!("  --branch <BRANCH>  Install from a git branch");
     println!("  --tag <TAG>        Install from a git tag");
     println!("  --commit <COMMIT>  Install from a git commit hash");
+    println!("  --dry-run          Print the uv command without installing");
     println!();
     println!("{}", "Examples:".bold());
     println!("  Install from PyPI:\n    r2x install r2x-reeds");
@@ -216,6 +272,61 @@ pub fn show_install_help() -> Result<(), PluginError> {
     Ok(())
 }
 
+/// Plugin discovery that follows a successful install
+enum DiscoveryPlan {
+    /// Workspace installs register every package with entry points
+    AllInstalled,
+    /// Register the named package and the r2x packages it depends on
+    Package(String),
+}
+
+/// What `r2x install --dry-run` prints
+fn render_install_plan(
+    uv_path: &str,
+    python_path: &str,
+    package_spec: &str,
+    editable: bool,
+    no_cache: bool,
+    discovery: &DiscoveryPlan,
+) -> String {
+    let install_args = build_pip_install_args(python_path, package_spec, editable, no_cache);
+    let discovery = match discovery {
+        DiscoveryPlan::AllInstalled => {
+            "Discover r2x_plugin entry points of every installed package (workspace)".to_string()
+        }
+        DiscoveryPlan::Package(name) => format!(
+            "Discover r2x_plugin entry points of '{}' and its r2x dependencies",
+            name
+        ),
+    };
+    format!(
+        "Would run:\n  {}\nThen:\n  {}\n",
+        format_command(uv_path, &install_args),
+        discovery
+    )
+}
+
+/// A command line that can be pasted into a POSIX shell
+fn format_command(program: &str, args: &[String]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .map(shell_quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn shell_quote(arg: &str) -> String {
+    let is_plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=+@,%".contains(c));
+    if is_plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
 fn run_pip_install(
     uv_path: &str,
     python_path: &str,
@@ -352,6 +463,7 @@ fn is_workspace_package(package_spec: &str) -> Result<bool, PluginError> {
 fn discover_all_installed_packages(
     ctx: &mut PluginContext,
     no_cache: bool,
+    include_dev: bool,
     total_start: std::time::Instant,
 ) -> Result<(), PluginError> {
     let discoverer = PackageDiscoverer::new(&ctx.locator);
@@ -407,6 +519,7 @@ fn discover_all_installed_packages(
                 editable: package.is_editable,
                 source_path: source_path.clone(),
                 source_uri: source_path,
+                include_dev,
             },
         ) {
             if entry_count > 0 {
@@ -441,7 +554,8 @@ fn discover_all_installed_packages(
 
 #[cfg(test)]
 mod tests {
-    use super::build_pip_install_args;
+    use super::{build_pip_install_args, render_install_plan, shell_quote, DiscoveryPlan};
+    use crate::plugins::package_spec::build_package_spec;
 
     #[test]
     fn build_pip_install_args_supports_editable_and_no_cache() {
@@ -449,4 +563,47 @@ mod tests {
         assert!(args.iter().any(|arg| arg == "-e"));
         assert!(args.iter().any(|arg| arg == "--no-cache"));
     }
+
+    #[test]
+    fn dry_run_prints_git_branch_command() {
+        let spec = build_package_spec(
+            "gh:NREL/r2x-reeds",
+            Some("git.example.org".to_string()),
+            Some("develop".to_string()),
+            None,
+            None,
+        );
+        assert!(spec.is_ok());
+        let spec = spec.unwrap_or_default();
+        let plan = render_install_plan(
+            "/usr/bin/uv",
+            "/venv/bin/python",
+            &spec,
+            false,
+            false,
+            &DiscoveryPlan::Package("r2x-reeds".to_string()),
+        );
+        assert_eq!(
+            plan,
+            "Would run:\n  /usr/bin/uv pip install --python /venv/bin/python --prerelease=allow --no-progress git+https://git.example.org/NREL/r2x-reeds@develop\nThen:\n  Discover r2x_plugin entry points of 'r2x-reeds' and its r2x dependencies\n"
+        );
+    }
+
+    #[test]
+    fn dry_run_prints_pypi_command() {
+        let plan = render_install_plan(
+            "uv",
+            "/home/me/My Envs/python",
+            "r2x-reeds",
+            false,
+            true,
+            &DiscoveryPlan::Package("r2x-reeds".to_string()),
+        );
+        let command = plan.lines().nth(1).unwrap_or_default();
+        assert_eq!(
+            command,
+            "  uv pip install --python '/home/me/My Envs/python' --prerelease=allow --no-progress --no-cache r2x-reeds"
+        );
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
 }
@@ -8,6 +8,7 @@ use colored::Colorize;
 use r2x_manifest::package_discovery::PackageLocator;
 use r2x_manifest::types::{Manifest, Package, PackageSource, Plugin};
 use std::collections::BTreeMap;
+use std::io::Write;
 
 fn source_kind(pkg: &Package, locator: Option<&PackageLocator>) -> PackageSource {
     if pkg.source_kind != PackageSource::Pypi {
@@ -145,6 +146,7 @@ pub fn list_plugins(
     opts: &GlobalOpts,
     plugin_filter: Option<String>,
     module_filter: Option<String>,
+    tag_filter: Option<&str>,
     ctx: &PluginContext,
 ) -> Result<(), PluginError> {
     let manifest = &ctx.manifest;
@@ -166,20 +168,26 @@ pub fn list_plugins(
             manifest,
             plugin_name,
             module_filter.as_deref(),
+            tag_filter,
             opts.verbose,
             ctx,
         );
     }
 
     // Otherwise, show the standard list view
-    let mut packages: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    for pkg in &manifest.packages {
-        if pkg.plugins.is_empty() {
-            continue;
+    let packages = list_groups(manifest, tag_filter);
+
+    if opts.verbose > 0 {
+        if let Some(provenance) = manifest_provenance(manifest) {
+            println!("{}", provenance.dimmed());
+        }
+    }
+
+    if let Some(tag) = tag_filter {
+        if packages.is_empty() {
+            println!("No plugins tagged '{}'.", tag);
+            return Ok(());
         }
-        let mut names: Vec<String> = pkg.plugins.iter().map(|p| p.name.to_string()).collect();
-        names.sort();
-        packages.insert(pkg.name.to_string(), names);
     }
 
     if has_plugins {
@@ -220,10 +228,119 @@ pub fn list_plugins(
     Ok(())
 }
 
+/// Write each plugin as a JSON object on its own line (`r2x list --json-lines`)
+///
+/// Objects are the manifest plugin entry plus its `package`, in the order of
+/// the standard list, and each line is flushed as it is written. Returns the
+/// number of plugins written.
+pub fn list_plugins_json_lines<W: Write>(
+    manifest: &Manifest,
+    package_filter: Option<&str>,
+    plugin_filter: Option<&str>,
+    tag_filter: Option<&str>,
+    mut out: W,
+) -> Result<usize, PluginError> {
+    let mut written = 0;
+    for (pkg, plugins) in sorted_plugins(manifest, tag_filter) {
+        if package_filter.is_some_and(|name| pkg.name.as_ref() != name) {
+            continue;
+        }
+        let plugins = plugins
+            .into_iter()
+            .filter(|p| plugin_filter.map_or(true, |name| p.name.as_ref() == name));
+        for plugin in plugins {
+            let mut entry = serde_json::to_value(plugin).map_err(|e| {
+                PluginError::Config(format!("Failed to serialize '{}': {}", plugin.name, e))
+            })?;
+            if let Some(object) = entry.as_object_mut() {
+                object.insert("package".to_string(), pkg.name.as_ref().into());
+            }
+            writeln!(out, "{}", entry)?;
+            out.flush()?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+/// When and by which r2x the manifest was last written, for `r2x list -v`
+fn manifest_provenance(manifest: &Manifest) -> Option<String> {
+    let written_by = manifest
+        .r2x_version
+        .as_deref()
+        .map(|version| format!("r2x {}", version));
+    match (manifest.generated_at.is_empty(), written_by) {
+        (true, None) => None,
+        (true, Some(written_by)) => Some(format!("Manifest written by {}", written_by)),
+        (false, None) => Some(format!("Manifest written {}", manifest.generated_at)),
+        (false, Some(written_by)) => Some(format!(
+            "Manifest written {} by {}",
+            manifest.generated_at, written_by
+        )),
+    }
+}
+
+/// Packages with their plugins matching `tag_filter`, both sorted by name
+///
+/// Packages left without plugins are skipped. The order never depends on
+/// discovery or manifest order, so captured `r2x list` output is stable
+/// across runs and machines.
+fn sorted_plugins<'a>(
+    manifest: &'a Manifest,
+    tag_filter: Option<&str>,
+) -> Vec<(&'a Package, Vec<&'a Plugin>)> {
+    let mut packages: Vec<(&Package, Vec<&Plugin>)> = manifest
+        .packages
+        .iter()
+        .filter_map(|pkg| {
+            let mut plugins: Vec<&Plugin> = pkg
+                .plugins
+                .iter()
+                .filter(|p| has_tag(p, tag_filter))
+                .collect();
+            if plugins.is_empty() {
+                return None;
+            }
+            plugins.sort_by(|a, b| a.name.cmp(&b.name));
+            Some((pkg, plugins))
+        })
+        .collect();
+    packages.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    packages
+}
+
+/// Packages of the standard list view and their plugin entries, see [`sorted_plugins`]
+fn list_groups(manifest: &Manifest, tag_filter: Option<&str>) -> BTreeMap<String, Vec<String>> {
+    let mut packages: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (pkg, plugins) in sorted_plugins(manifest, tag_filter) {
+        packages
+            .entry(pkg.name.to_string())
+            .or_default()
+            .extend(plugins.into_iter().map(list_entry));
+    }
+    packages
+}
+
+/// Plugin name as shown in the standard list, noting registrations behind an `if`
+fn list_entry(plugin: &Plugin) -> String {
+    if plugin.condition.is_some() {
+        format!("{} (platform-gated)", plugin.name)
+    } else {
+        plugin.name.to_string()
+    }
+}
+
+fn has_tag(plugin: &Plugin, tag_filter: Option<&str>) -> bool {
+    tag_filter.map_or(true, |tag| {
+        plugin.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    })
+}
+
 fn show_plugin_details(
     manifest: &Manifest,
     plugin_filter: &str,
     module_filter: Option<&str>,
+    tag_filter: Option<&str>,
     verbose_level: u8,
     ctx: &PluginContext,
 ) -> Result<(), PluginError> {
@@ -252,7 +369,7 @@ fn show_plugin_details(
     println!();
 
     // Filter plugins by module name if provided
-    let plugins_to_show: Vec<_> = if let Some(module_name) = module_filter {
+    let mut plugins_to_show: Vec<_> = if let Some(module_name) = module_filter {
         package
             .plugins
             .iter()
@@ -267,6 +384,8 @@ fn show_plugin_details(
     } else {
         package.plugins.iter().collect()
     };
+    plugins_to_show.retain(|p| has_tag(p, tag_filter));
+    plugins_to_show.sort_by(|a, b| a.name.cmp(&b.name));
 
     if plugins_to_show.is_empty() {
         return Err(PluginError::InvalidArgs(format!(
@@ -297,6 +416,18 @@ fn show_plugin_compact(plugin: &Plugin) {
     // Show module info
     println!("  {}: {}", "Module".dimmed(), plugin.module);
 
+    if let Some(ref condition) = plugin.condition {
+        println!("  {}: {}", "Only if".dimmed(), condition);
+    }
+
+    if let Some(ref description) = plugin.description {
+        println!("  {}: {}", "Description".dimmed(), description);
+    }
+
+    if !plugin.tags.is_empty() {
+        println!("  {}: {}", "Tags".dimmed(), plugin.tags.join(", "));
+    }
+
     // Show class or function name
     if let Some(ref class_name) = plugin.class_name {
         println!("  {}: {}", "Class".dimmed(), class_name);
@@ -314,7 +445,7 @@ fn show_plugin_compact(plugin: &Plugin) {
     if !plugin.parameters.is_empty() {
         println!("  {}:", "Arguments".dimmed());
         for param in &plugin.parameters {
-            let req_marker = if param.required { "*" } else { " " };
+            let req_marker = if param.is_required() { "*" } else { " " };
             let default_str = param
                 .default
                 .as_ref()
@@ -341,6 +472,10 @@ fn show_plugin_verbose(plugin: &Plugin) {
     println!("  {}: {:?}", "Type".dimmed(), plugin.plugin_type);
     println!("  {}: {}", "Module".dimmed(), plugin.module);
 
+    if let Some(ref condition) = plugin.condition {
+        println!("  {}: {}", "Only if".dimmed(), condition);
+    }
+
     // Show class or function name
     if let Some(ref class_name) = plugin.class_name {
         println!("  {}: {}", "Class".dimmed(), class_name);
@@ -358,6 +493,14 @@ fn show_plugin_verbose(plugin: &Plugin) {
         println!();
     }
 
+    if let Some(ref description) = plugin.description {
+        println!("  {}: {}", "Description".dimmed(), description);
+    }
+
+    if !plugin.tags.is_empty() {
+        println!("  {}: {}", "Tags".dimmed(), plugin.tags.join(", "));
+    }
+
     // Show hooks
     if !plugin.hooks.is_empty() {
         println!("  {}:", "Hooks".dimmed());
@@ -370,7 +513,7 @@ fn show_plugin_verbose(plugin: &Plugin) {
     if !plugin.parameters.is_empty() {
         println!("  {}:", "Arguments".dimmed());
         for param in &plugin.parameters {
-            let req_marker = if param.required { "*" } else { " " };
+            let req_marker = if param.is_required() { "*" } else { " " };
             let module_str = param
                 .module
                 .as_ref()
@@ -399,7 +542,9 @@ fn show_plugin_verbose(plugin: &Plugin) {
     // Show config schema if available
     if !plugin.config_schema.is_empty() {
         println!("  {}:", "Config Schema".dimmed());
-        for (field_name, field) in plugin.config_schema.iter() {
+        let mut fields: Vec<_> = plugin.config_schema.iter().collect();
+        fields.sort_by_key(|(name, _)| *name);
+        for (field_name, field) in fields {
             let req_marker = if field.required { "*" } else { "" };
             println!("    {}{}: {:?}", field_name, req_marker, field.field_type);
         }
@@ -409,12 +554,13 @@ fn show_plugin_verbose(plugin: &Plugin) {
 #[cfg(test)]
 mod tests {
     use crate::commands::plugins::list::{
-        format_github_origin, format_package_header, format_source, package_source_display,
+        format_github_origin, format_package_header, format_source, has_tag, list_entry,
+        list_groups, list_plugins_json_lines, manifest_provenance, package_source_display,
         package_version, source_kind,
     };
     use colored::control::set_override;
     use r2x_manifest::package_discovery::PackageLocator;
-    use r2x_manifest::types::{Package, PackageSource};
+    use r2x_manifest::types::{Manifest, Package, PackageSource, Plugin};
     use std::fs;
     use std::sync::Arc;
     use tempfile::TempDir;
@@ -608,4 +754,114 @@ mod tests {
             "git@github.com:NREL/r2x-reeds.git@main (417f798)"
         );
     }
+
+    #[test]
+    fn tag_filter_matches_case_insensitively() {
+        let plugin = Plugin {
+            name: Arc::from("break-gens"),
+            tags: smallvec::smallvec![Arc::from("experimental"), Arc::from("ReEDS")],
+            ..Default::default()
+        };
+        assert!(has_tag(&plugin, None));
+        assert!(has_tag(&plugin, Some("reeds")));
+        assert!(!has_tag(&plugin, Some("plexos")));
+    }
+
+    /// Packages and plugins listed out of name order, plus a package without plugins
+    fn unsorted_manifest() -> Manifest {
+        let package = |name: &str, plugins: &[&str]| Package {
+            name: Arc::from(name),
+            plugins: plugins
+                .iter()
+                .map(|plugin| Plugin {
+                    name: Arc::from(*plugin),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        Manifest {
+            packages: vec![
+                package("r2x-reeds", &["upgrader", "break-gens", "parser"]),
+                package("r2x-empty", &[]),
+                package("r2x-plexos", &["exporter", "parser"]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn list_groups_sort_packages_and_plugins_by_name() {
+        let manifest = unsorted_manifest();
+        let groups: Vec<(String, Vec<String>)> = list_groups(&manifest, None).into_iter().collect();
+        assert_eq!(
+            groups,
+            vec![
+                (
+                    "r2x-plexos".to_string(),
+                    vec!["exporter".to_string(), "parser".to_string()]
+                ),
+                (
+                    "r2x-reeds".to_string(),
+                    vec![
+                        "break-gens".to_string(),
+                        "parser".to_string(),
+                        "upgrader".to_string()
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_lines_writes_one_object_per_plugin() {
+        let manifest = unsorted_manifest();
+
+        let mut out = Vec::new();
+        let written = list_plugins_json_lines(&manifest, None, None, None, &mut out);
+        let lines: Vec<serde_json::Value> = String::from_utf8_lossy(&out)
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .filter(serde_json::Value::is_object)
+            .collect();
+        assert_eq!(written.ok(), Some(5));
+        assert_eq!(String::from_utf8_lossy(&out).lines().count(), 5);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0]["package"], "r2x-plexos");
+        assert_eq!(lines[0]["name"], "exporter");
+        assert_eq!(lines[4]["name"], "upgrader");
+
+        let written = list_plugins_json_lines(&manifest, Some("r2x-reeds"), None, None, Vec::new());
+        assert_eq!(written.ok(), Some(3));
+    }
+
+    #[test]
+    fn provenance_line_shows_what_the_manifest_recorded() {
+        let manifest = Manifest {
+            generated_at: Arc::from("2026-01-02T03:04:05+00:00"),
+            r2x_version: Some(Arc::from("0.4.0")),
+            ..Default::default()
+        };
+        assert_eq!(
+            manifest_provenance(&manifest).as_deref(),
+            Some("Manifest written 2026-01-02T03:04:05+00:00 by r2x 0.4.0")
+        );
+
+        let legacy = Manifest {
+            generated_at: Arc::from(""),
+            ..Default::default()
+        };
+        assert_eq!(manifest_provenance(&legacy), None);
+    }
+
+    #[test]
+    fn list_entry_notes_platform_gated_plugins() {
+        let mut plugin = Plugin {
+            name: Arc::from("win-parser"),
+            ..Default::default()
+        };
+        assert_eq!(list_entry(&plugin), "win-parser");
+        plugin.condition = Some(Arc::from(r#"sys.platform == "win32""#));
+        assert_eq!(list_entry(&plugin), "win-parser (platform-gated)");
+    }
 }
@@ -1,7 +1,65 @@
+use clap::Subcommand;
+
 pub mod clean;
 pub mod context;
+pub mod docs;
+pub mod graph;
 pub mod install;
 pub mod list;
 pub mod remove;
+pub mod search;
+pub mod steps;
 pub mod sync;
 mod utils;
+pub mod validate_config;
+pub mod watch;
+
+#[derive(Subcommand, Debug)]
+pub enum PluginAction {
+    /// Render a plugin's configuration reference as a Markdown table
+    Docs {
+        /// Plugin name (e.g., reeds-parser or r2x-reeds.reeds-parser)
+        name: String,
+        /// Write the Markdown to a file instead of stdout
+        #[arg(short = 'o', long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Show which plugins produce data other plugins consume, as a Graphviz DOT graph
+    Graph {
+        /// Emit a Mermaid flowchart instead of DOT
+        #[arg(long)]
+        mermaid: bool,
+        /// Only show plugins of this kind (e.g., parser) and the plugins they connect to
+        #[arg(long, value_name = "KIND")]
+        kind: Option<String>,
+        /// Write the graph to a file instead of stdout
+        #[arg(short = 'o', long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Find installed plugins by name, description or tag
+    Search {
+        /// Text to match against plugin names, descriptions and tags
+        query: String,
+        /// Only show plugins of this kind (e.g., parser)
+        #[arg(long, value_name = "KIND")]
+        kind: Option<String>,
+    },
+    /// Check a JSON or YAML config file against a plugin's config schema
+    ValidateConfig {
+        /// Plugin name (e.g., reeds-parser or r2x-reeds.reeds-parser)
+        name: String,
+        /// Config file to check (.json, .yaml or .yml)
+        file: String,
+    },
+    /// List the steps registered on an upgrader plugin, ordered by priority
+    Steps {
+        /// Upgrader plugin name (e.g., reeds-upgrader or r2x-reeds.reeds-upgrader)
+        name: String,
+        /// Print the steps as JSON
+        #[arg(long)]
+        json: bool,
+        /// Only list steps whose target_version is newer than this data version
+        #[arg(long, value_name = "VERSION")]
+        since: Option<String>,
+    },
+}
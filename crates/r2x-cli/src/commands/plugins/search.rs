@@ -0,0 +1,243 @@
+use crate::commands::plugins::utils::parse_kind;
+use crate::plugins::error::PluginError;
+use r2x_manifest::runtime::{infer_plugin_role, PluginRole};
+use r2x_manifest::types::{Manifest, Plugin};
+
+/// Score for a plugin whose name is the query
+const EXACT_NAME: u32 = 100;
+const NAME_PREFIX: u32 = 80;
+const NAME_SUBSTRING: u32 = 60;
+const EXACT_TAG: u32 = 50;
+const TAG_SUBSTRING: u32 = 40;
+const DESCRIPTION_SUBSTRING: u32 = 30;
+/// Query letters appear in order in the name (e.g. `rdsprs` for `reeds-parser`)
+const NAME_FUZZY: u32 = 10;
+
+/// A plugin matching a search query
+#[derive(Debug, Clone)]
+pub(crate) struct SearchHit<'a> {
+    pub package: &'a str,
+    pub plugin: &'a Plugin,
+    pub role: PluginRole,
+    pub score: u32,
+}
+
+/// Print installed plugins matching `query`, best matches first.
+pub fn search_plugins(query: &str, kind: Option<&str>) -> Result<(), PluginError> {
+    let kind = kind.map(parse_kind).transpose()?;
+    let manifest = Manifest::load()?;
+    let hits = rank_plugins(&manifest, query, kind);
+    print!("{}", render_hits(query, &hits));
+    Ok(())
+}
+
+/// Plugins matching `query`, ordered by descending score, then by name
+pub(crate) fn rank_plugins<'a>(
+    manifest: &'a Manifest,
+    query: &str,
+    kind: Option<PluginRole>,
+) -> Vec<SearchHit<'a>> {
+    let query = query.trim().to_lowercase();
+    let mut hits: Vec<SearchHit<'a>> = manifest
+        .packages
+        .iter()
+        .flat_map(|package| {
+            package
+                .plugins
+                .iter()
+                .map(move |plugin| (package.name.as_ref(), plugin))
+        })
+        .filter_map(|(package, plugin)| {
+            let role = infer_plugin_role(&plugin.name);
+            if kind.is_some_and(|kind| kind != role) {
+                return None;
+            }
+            score_plugin(plugin, &query).map(|score| SearchHit {
+                package,
+                plugin,
+                role,
+                score,
+            })
+        })
+        .collect();
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.plugin.name.cmp(&b.plugin.name))
+            .then_with(|| a.package.cmp(b.package))
+    });
+    hits
+}
+
+/// Best score of `plugin` against a lowercase `query`, or `None` if nothing matches
+fn score_plugin(plugin: &Plugin, query: &str) -> Option<u32> {
+    if query.is_empty() {
+        return None;
+    }
+    let name = plugin.name.to_lowercase();
+    let tags: Vec<String> = plugin.tags.iter().map(|tag| tag.to_lowercase()).collect();
+    let description = plugin
+        .description
+        .as_deref()
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    let scores = [
+        (name == query).then_some(EXACT_NAME),
+        name.starts_with(query).then_some(NAME_PREFIX),
+        name.contains(query).then_some(NAME_SUBSTRING),
+        tags.iter().any(|tag| tag == query).then_some(EXACT_TAG),
+        tags.iter()
+            .any(|tag| tag.contains(query))
+            .then_some(TAG_SUBSTRING),
+        description.contains(query).then_some(DESCRIPTION_SUBSTRING),
+        is_subsequence(query, &name).then_some(NAME_FUZZY),
+    ];
+    scores.into_iter().flatten().max()
+}
+
+/// Whether the characters of `needle` appear in `haystack` in order
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut remaining = haystack.chars();
+    needle.chars().all(|wanted| remaining.any(|c| c == wanted))
+}
+
+fn render_hits(query: &str, hits: &[SearchHit<'_>]) -> String {
+    if hits.is_empty() {
+        return format!("No plugins match '{}'\n", query);
+    }
+
+    let header = ["PLUGIN", "KIND", "DESCRIPTION"];
+    let rows: Vec<[String; 3]> = hits
+        .iter()
+        .map(|hit| {
+            [
+                format!("{}.{}", hit.package, hit.plugin.name),
+                hit.role.as_str().to_string(),
+                hit.plugin
+                    .description
+                    .as_deref()
+                    .and_then(|description| description.lines().next())
+                    .unwrap_or("-")
+                    .to_string(),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let format_row = |cells: [&str; 3]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("{}\n", line.join("  ").trim_end())
+    };
+
+    let mut out = format_row(header);
+    for row in &rows {
+        out.push_str(&format_row([&row[0], &row[1], &row[2]]));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::plugins::search::*;
+    use r2x_manifest::types::Package;
+    use std::sync::Arc;
+
+    fn plugin(name: &str, description: Option<&str>, tags: &[&str]) -> Plugin {
+        Plugin {
+            name: Arc::from(name),
+            module: Arc::from("r2x_demo"),
+            description: description.map(Arc::from),
+            tags: tags.iter().map(|tag| Arc::from(*tag)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn sample_manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        let mut reeds = Package {
+            name: Arc::from("r2x-reeds"),
+            ..Default::default()
+        };
+        reeds.plugins.push(plugin(
+            "reeds-parser",
+            Some("Read ReEDS capacity expansion outputs"),
+            &["capacity"],
+        ));
+        reeds.plugins.push(plugin(
+            "break-gens",
+            Some("Split aggregated generators"),
+            &[],
+        ));
+        let mut plexos = Package {
+            name: Arc::from("r2x-plexos"),
+            ..Default::default()
+        };
+        plexos
+            .plugins
+            .push(plugin("plexos-exporter", None, &["production-cost"]));
+        plexos.plugins.push(plugin("parser", None, &[]));
+        manifest.packages.push(reeds);
+        manifest.packages.push(plexos);
+        manifest.rebuild_indexes();
+        manifest
+    }
+
+    fn names(hits: &[SearchHit<'_>]) -> Vec<String> {
+        hits.iter()
+            .map(|hit| format!("{}.{}", hit.package, hit.plugin.name))
+            .collect()
+    }
+
+    #[test]
+    fn matches_by_description() {
+        let manifest = sample_manifest();
+        let hits = rank_plugins(&manifest, "GENERATORS", None);
+        assert_eq!(names(&hits), vec!["r2x-reeds.break-gens"]);
+        assert_eq!(hits[0].score, DESCRIPTION_SUBSTRING);
+    }
+
+    #[test]
+    fn matches_by_tag() {
+        let manifest = sample_manifest();
+        let hits = rank_plugins(&manifest, "production-cost", None);
+        assert_eq!(names(&hits), vec!["r2x-plexos.plexos-exporter"]);
+        assert_eq!(hits[0].score, EXACT_TAG);
+    }
+
+    #[test]
+    fn exact_name_ranks_first() {
+        let manifest = sample_manifest();
+        let hits = rank_plugins(&manifest, "parser", None);
+        assert_eq!(
+            names(&hits),
+            vec!["r2x-plexos.parser", "r2x-reeds.reeds-parser"]
+        );
+        assert!(hits[0].score > hits[1].score);
+    }
+
+    #[test]
+    fn fuzzy_and_kind_filter() {
+        let manifest = sample_manifest();
+        let hits = rank_plugins(&manifest, "rdsprs", None);
+        assert_eq!(names(&hits), vec!["r2x-reeds.reeds-parser"]);
+
+        let exporters = rank_plugins(&manifest, "r", Some(PluginRole::Exporter));
+        assert_eq!(names(&exporters), vec!["r2x-plexos.plexos-exporter"]);
+        assert!(rank_plugins(&manifest, "", None).is_empty());
+
+        let table = render_hits("rdsprs", &hits);
+        assert!(table.starts_with("PLUGIN"));
+        assert!(table.contains("r2x-reeds.reeds-parser  parser"));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::manifest_lookup::resolve_plugin_ref;
+use crate::plugins::error::PluginError;
+use r2x_manifest::runtime::{infer_plugin_role, PluginRole};
+use r2x_manifest::types::{DecoratorRegistration, Manifest, Plugin};
+
+/// Print the steps registered on an upgrader plugin, ordered by priority.
+///
+/// With `since`, only steps that still apply to data at that version are shown.
+pub fn show_plugin_steps(
+    plugin_name: &str,
+    json: bool,
+    since: Option<&str>,
+) -> Result<(), PluginError> {
+    let manifest = Manifest::load()?;
+    let resolved = resolve_plugin_ref(&manifest, plugin_name)
+        .map_err(|e| PluginError::InvalidArgs(e.to_string()))?;
+    let mut steps = upgrader_steps(resolved.plugin)?;
+    if let Some(since) = since {
+        steps = steps_since(steps, since);
+    }
+
+    if json {
+        let rendered = serde_json::to_string_pretty(&steps)
+            .map_err(|e| PluginError::Config(format!("Failed to serialize steps: {}", e)))?;
+        println!("{}", rendered);
+    } else {
+        print!("{}", render_steps_table(plugin_name, &steps));
+    }
+
+    Ok(())
+}
+
+/// Registrations of an upgrader in execution order (ascending priority)
+pub(crate) fn upgrader_steps(plugin: &Plugin) -> Result<Vec<&DecoratorRegistration>, PluginError> {
+    if infer_plugin_role(&plugin.name) != PluginRole::Upgrader {
+        return Err(PluginError::InvalidArgs(format!(
+            "'{}' is not an upgrader plugin",
+            plugin.name
+        )));
+    }
+
+    let mut steps: Vec<&DecoratorRegistration> = plugin.registrations.iter().collect();
+    steps.sort_by_key(|step| step.effective_priority());
+    Ok(steps)
+}
+
+/// Steps whose `target_version` is newer than `since`, keeping priority order
+pub(crate) fn steps_since<'a>(
+    steps: Vec<&'a DecoratorRegistration>,
+    since: &str,
+) -> Vec<&'a DecoratorRegistration> {
+    steps
+        .into_iter()
+        .filter(|step| step.applies_since(since))
+        .collect()
+}
+
+pub(crate) fn render_steps_table(plugin_name: &str, steps: &[&DecoratorRegistration]) -> String {
+    if steps.is_empty() {
+        return format!("{} has no registered steps\n", plugin_name);
+    }
+
+    let header = ["PRIORITY", "FUNCTION", "DECORATOR", "TARGET VERSION"];
+    let rows: Vec<[String; 4]> = steps
+        .iter()
+        .map(|step| {
+            [
+                step.effective_priority().to_string(),
+                step.function.to_string(),
+                step.decorator.to_string(),
+                step.target_version.as_deref().unwrap_or("-").to_string(),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let format_row = |cells: [&str; 4]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("{}\n", line.join("  ").trim_end())
+    };
+
+    let mut out = format_row(header);
+    for row in &rows {
+        out.push_str(&format_row([&row[0], &row[1], &row[2], &row[3]]));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::plugins::steps::*;
+    use r2x_manifest::types::{Package, PluginType};
+    use std::sync::Arc;
+
+    fn registration(function: &str, target: &str, priority: Option<i64>) -> DecoratorRegistration {
+        DecoratorRegistration {
+            function: Arc::from(function),
+            decorator: Arc::from("register_step"),
+            target_version: Some(Arc::from(target)),
+            min_version: None,
+            max_version: None,
+            priority,
+        }
+    }
+
+    fn sample_manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        let mut package = Package {
+            name: Arc::from("r2x-reeds"),
+            ..Default::default()
+        };
+        package.plugins.push(Plugin {
+            name: Arc::from("reeds-upgrader"),
+            plugin_type: PluginType::Class,
+            module: Arc::from("r2x_reeds.upgrader"),
+            class_name: Some(Arc::from("ReEDSUpgrader")),
+            registrations: vec![
+                registration("drop_cache", "2.1", None),
+                registration("rename_columns", "2.0", Some(20)),
+                registration("move_files", "1.5", Some(10)),
+            ],
+            ..Default::default()
+        });
+        package.plugins.push(Plugin {
+            name: Arc::from("reeds-parser"),
+            plugin_type: PluginType::Class,
+            module: Arc::from("r2x_reeds"),
+            class_name: Some(Arc::from("ReEDSParser")),
+            ..Default::default()
+        });
+        manifest.packages.push(package);
+        manifest.rebuild_indexes();
+        manifest
+    }
+
+    fn steps_for<'a>(
+        manifest: &'a Manifest,
+        name: &str,
+    ) -> Result<Vec<&'a DecoratorRegistration>, PluginError> {
+        let resolved = resolve_plugin_ref(manifest, name)
+            .map_err(|e| PluginError::InvalidArgs(e.to_string()))?;
+        upgrader_steps(resolved.plugin)
+    }
+
+    #[test]
+    fn steps_are_ordered_by_priority() {
+        let manifest = sample_manifest();
+        let steps = steps_for(&manifest, "reeds-upgrader");
+        assert!(steps.is_ok());
+        let Ok(steps) = steps else {
+            return;
+        };
+        let functions: Vec<&str> = steps.iter().map(|step| step.function.as_ref()).collect();
+        assert_eq!(
+            functions,
+            vec!["move_files", "rename_columns", "drop_cache"]
+        );
+
+        let table = render_steps_table("reeds-upgrader", &steps);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("PRIORITY  FUNCTION"));
+        assert!(lines[1].starts_with("10        move_files"));
+        assert!(lines[3].contains("drop_cache") && lines[3].ends_with("2.1"));
+    }
+
+    #[test]
+    fn steps_serialize_to_json_in_order() {
+        let manifest = sample_manifest();
+        let steps = steps_for(&manifest, "r2x-reeds.reeds-upgrader").unwrap_or_default();
+        let json = serde_json::to_value(&steps).unwrap_or_default();
+        assert_eq!(json[0]["function"], "move_files");
+        assert_eq!(json[0]["decorator"], "register_step");
+        assert_eq!(json[0]["target_version"], "1.5");
+        assert_eq!(json[0]["priority"], 10);
+        assert_eq!(json[2]["function"], "drop_cache");
+        assert!(json[2].get("priority").is_none());
+    }
+
+    #[test]
+    fn since_keeps_only_newer_steps() {
+        let manifest = sample_manifest();
+        let steps = steps_for(&manifest, "reeds-upgrader").unwrap_or_default();
+        let functions = |since: &str| -> Vec<String> {
+            steps_since(steps.clone(), since)
+                .iter()
+                .map(|step| step.function.to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            functions("1.0"),
+            vec!["move_files", "rename_columns", "drop_cache"]
+        );
+        assert_eq!(functions("1.5"), vec!["rename_columns", "drop_cache"]);
+        assert_eq!(functions("2.0.0"), vec!["drop_cache"]);
+        assert_eq!(functions("v2.10"), Vec::<String>::new());
+
+        let untargeted = DecoratorRegistration {
+            target_version: None,
+            ..registration("cleanup", "0", None)
+        };
+        assert!(untargeted.applies_since("9.9"));
+    }
+
+    #[test]
+    fn non_upgrader_is_rejected() {
+        let manifest = sample_manifest();
+        assert!(matches!(
+            steps_for(&manifest, "reeds-parser"),
+            Err(PluginError::InvalidArgs(_))
+        ));
+    }
+}
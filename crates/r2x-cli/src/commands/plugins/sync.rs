@@ -5,8 +5,9 @@ use crate::plugins::package_spec::{build_package_spec, is_git_url};
 use colored::Colorize;
 use r2x_ast::AstDiscovery;
 use r2x_logger as logger;
-use r2x_manifest::types::{InstallType, Package, PackageSource};
-use std::collections::HashMap;
+use r2x_manifest::sync::ManifestDiff;
+use r2x_manifest::types::{InstallType, Package, PackageSource, Plugin};
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::Arc;
@@ -36,7 +37,13 @@ struct UpgradeCandidate {
 ///
 /// With `upgrade = true`, explicit packages are upgraded using:
 /// `uv pip install --upgrade --python <venv-python> <target>`.
-pub fn sync_manifest(ctx: &mut PluginContext, upgrade: bool) -> Result<(), PluginError> {
+/// With `include_dev`, every package is rediscovered including its top-level
+/// `tests/` and `examples/`.
+pub fn sync_manifest(
+    ctx: &mut PluginContext,
+    upgrade: bool,
+    include_dev: bool,
+) -> Result<(), PluginError> {
     let total_start = std::time::Instant::now();
 
     if ctx.manifest.is_empty() {
@@ -66,6 +73,77 @@ pub fn sync_manifest(ctx: &mut PluginContext, upgrade: bool) -> Result<(), Plugi
     let num_packages = packages_to_sync.len();
     logger::step(&format!("Syncing {} package(s)...", num_packages));
 
+    let (synced_packages, total_plugins) =
+        discover_packages(ctx, &packages_to_sync, !include_dev, include_dev);
+
+    ctx.manifest.save()?;
+
+    if upgrade && upgraded_count > 0 {
+        let after = capture_package_states(&packages_to_sync, &ctx.locator);
+        if let Some(before) = baseline.as_ref() {
+            print_upgrade_changes(&packages_to_sync, before, &after, &ctx.locator);
+        }
+    }
+
+    let elapsed_ms = total_start.elapsed().as_millis();
+    println!(
+        "{}",
+        format!(
+            "Synced {} package(s), {} plugin(s) in {}ms",
+            synced_packages, total_plugins, elapsed_ms
+        )
+        .dimmed()
+    );
+
+    Ok(())
+}
+
+/// Re-run discovery for every package and report how the manifest would change.
+///
+/// The manifest on disk is left untouched. Returns `true` when it is in sync,
+/// so `r2x sync --check` can fail CI on drift like `cargo fmt --check`.
+pub fn check_manifest(ctx: &mut PluginContext, include_dev: bool) -> Result<bool, PluginError> {
+    let diffs = collect_check_diffs(ctx, include_dev);
+    print!("{}", render_check(&diffs));
+    Ok(diffs.is_empty())
+}
+
+/// Differences between the current manifest and a fresh discovery, leaving
+/// `ctx.manifest` as it was
+fn collect_check_diffs(ctx: &mut PluginContext, include_dev: bool) -> Vec<ManifestDiff> {
+    let packages_to_sync = collect_packages_to_sync(&ctx.manifest.packages);
+    let current = ctx.manifest.clone();
+    discover_packages(ctx, &packages_to_sync, false, include_dev);
+    let discovered = std::mem::replace(&mut ctx.manifest, current);
+    ctx.manifest.diff(&discovered)
+}
+
+/// Output of `r2x sync --check`: one line per difference, or an all-clear
+fn render_check(diffs: &[ManifestDiff]) -> String {
+    if diffs.is_empty() {
+        return "Manifest is in sync with installed plugins\n".to_string();
+    }
+    let mut out = format!(
+        "Manifest is out of date ({} difference(s)); run `r2x sync`:\n",
+        diffs.len()
+    );
+    for diff in diffs {
+        out.push_str(&format!("  {}\n", diff));
+    }
+    out
+}
+
+/// Re-run discovery for `packages`, updating `ctx.manifest` in place.
+///
+/// With `reuse_unchanged`, non-editable packages whose installed version matches
+/// the manifest keep their plugins without an AST parse. Returns the number of
+/// synced packages and plugins.
+fn discover_packages(
+    ctx: &mut PluginContext,
+    packages_to_sync: &[SyncPackage],
+    reuse_unchanged: bool,
+    include_dev: bool,
+) -> (usize, usize) {
     // Partition packages into "unchanged" (skip AST) and "stale" (need rediscovery).
     //
     // For non-editable installs: if the installed version matches the manifest version,
@@ -75,13 +153,13 @@ pub fn sync_manifest(ctx: &mut PluginContext, upgrade: bool) -> Result<(), Plugi
     let mut unchanged_plugins = 0usize;
     let mut needs_discovery: Vec<_> = Vec::new();
 
-    for package in &packages_to_sync {
+    for package in packages_to_sync {
         let installed_version = ctx.locator.read_version(&package.name);
         let version_matches = installed_version
             .as_deref()
             .is_some_and(|v| v == package.manifest_version && !package.manifest_version.is_empty());
 
-        if !package.editable_install && version_matches {
+        if reuse_unchanged && !package.editable_install && version_matches {
             // Version unchanged, skip expensive AST discovery.
             let existing_plugins = ctx
                 .manifest
@@ -135,12 +213,13 @@ pub fn sync_manifest(ctx: &mut PluginContext, upgrade: bool) -> Result<(), Plugi
                 .map(
                     |(package, package_path, version, dist_info, source_kind, source_uri)| {
                         s.spawn(move || {
-                            let ast_plugins = AstDiscovery::discover_plugins(
+                            let ast_plugins = AstDiscovery::discover_plugins_with_dev_dirs(
                                 package_path,
                                 &package.name,
                                 Some(venv_path),
                                 Some(version.as_str()),
                                 dist_info.as_deref(),
+                                include_dev,
                             );
                             (package, version, *source_kind, source_uri, ast_plugins)
                         })
@@ -193,26 +272,146 @@ pub fn sync_manifest(ctx: &mut PluginContext, upgrade: bool) -> Result<(), Plugi
         }
     }
 
+    (synced_packages, total_plugins)
+}
+
+/// Plugins of one package before and after re-discovery
+pub(super) struct PackageResync {
+    pub name: String,
+    pub before: Vec<Plugin>,
+    pub after: Vec<Plugin>,
+}
+
+/// Source directories of editable packages, used to watch for changes
+pub(super) fn editable_source_roots(ctx: &PluginContext) -> Vec<(String, PathBuf)> {
+    collect_packages_to_sync(&ctx.manifest.packages)
+        .iter()
+        .filter(|package| package.editable_install)
+        .filter_map(|package| {
+            resolve_package_path(&ctx.locator, package)
+                .ok()
+                .map(|path| (package.name.clone(), path))
+        })
+        .collect()
+}
+
+/// Re-run discovery for only the named packages and save the manifest.
+pub(super) fn resync_packages(
+    ctx: &mut PluginContext,
+    names: &BTreeSet<String>,
+    include_dev: bool,
+) -> Result<Vec<PackageResync>, PluginError> {
+    let packages: Vec<SyncPackage> = collect_packages_to_sync(&ctx.manifest.packages)
+        .into_iter()
+        .filter(|package| names.contains(&package.name))
+        .collect();
+
+    let mut results = Vec::new();
+    for package in &packages {
+        let package_path = resolve_package_path(&ctx.locator, package)?;
+        let version = ctx
+            .locator
+            .read_version(&package.name)
+            .unwrap_or_else(|| package.manifest_version.clone());
+        let dist_info = ctx.locator.find_dist_info_path(&package.name);
+
+        let plugins = match AstDiscovery::discover_plugins_with_dev_dirs(
+            &package_path,
+            &package.name,
+            Some(ctx.venv_path.as_str()),
+            Some(version.as_str()),
+            dist_info.as_deref(),
+            include_dev,
+        ) {
+            Ok(plugins) => plugins,
+            Err(e) => {
+                logger::warn(&format!(
+                    "Failed to discover plugins for '{}': {}",
+                    package.name, e
+                ));
+                continue;
+            }
+        };
+
+        let pkg = ctx.manifest.get_or_create_package(&package.name);
+        let before = std::mem::replace(&mut pkg.plugins, plugins.clone());
+        pkg.rebuild_plugin_index();
+        pkg.version = Arc::from(version.as_str());
+
+        results.push(PackageResync {
+            name: package.name.clone(),
+            before,
+            after: plugins,
+        });
+    }
+
     ctx.manifest.save()?;
+    Ok(results)
+}
 
-    if upgrade && upgraded_count > 0 {
-        let after = capture_package_states(&packages_to_sync, &ctx.locator);
-        if let Some(before) = baseline.as_ref() {
-            print_upgrade_changes(&packages_to_sync, before, &after, &ctx.locator);
+/// `uv pip install` target and editable flag for every explicitly installed package
+fn reinstall_targets(packages: &[Package]) -> Vec<(String, bool)> {
+    let mut targets: Vec<(String, bool)> = packages
+        .iter()
+        .filter(|pkg| pkg.install_type == InstallType::Explicit)
+        .map(|pkg| match pkg.source_uri.as_deref() {
+            Some(uri) if is_git_url(uri) => (uri.to_string(), false),
+            Some(uri) if Path::new(uri).exists() => (uri.to_string(), pkg.editable_install),
+            _ => (pkg.name.to_string(), false),
+        })
+        .collect();
+    targets.sort();
+    targets.dedup();
+    targets
+}
+
+/// Reinstall the manifest's explicitly installed packages in one `uv pip install` call.
+///
+/// Used after the venv is recreated; dependencies come back with the packages
+/// that pulled them in. Returns the number of install targets.
+pub(crate) fn reinstall_packages(
+    uv_path: &str,
+    python_path: &str,
+    packages: &[Package],
+) -> Result<usize, PluginError> {
+    let targets = reinstall_targets(packages);
+    if targets.is_empty() {
+        return Ok(0);
+    }
+
+    let mut args: Vec<&str> = vec![
+        "pip",
+        "install",
+        "--python",
+        python_path,
+        "--prerelease=allow",
+        "--no-progress",
+    ];
+    for (target, editable) in &targets {
+        if *editable {
+            args.push("-e");
         }
+        args.push(target);
     }
+    logger::step(&format!("Reinstalling {} package(s)...", targets.len()));
+    logger::debug(&format!("Running: {} {}", uv_path, args.join(" ")));
 
-    let elapsed_ms = total_start.elapsed().as_millis();
-    println!(
-        "{}",
-        format!(
-            "Synced {} package(s), {} plugin(s) in {}ms",
-            synced_packages, total_plugins, elapsed_ms
-        )
-        .dimmed()
-    );
+    let status = Command::new(uv_path)
+        .args(&args)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(PluginError::Io)?;
 
-    Ok(())
+    if !status.success() {
+        return Err(PluginError::CommandFailed {
+            command: format!("{uv_path} {}", args.join(" ")),
+            status: status.code(),
+        });
+    }
+
+    Ok(targets.len())
 }
 
 fn collect_packages_to_sync(packages: &[Package]) -> Vec<SyncPackage> {
@@ -649,6 +848,62 @@ mod tests {
         Some((temp, locator))
     }
 
+    #[test]
+    fn check_reports_drift_until_synced() {
+        let Some((tmp, locator)) = empty_locator() else {
+            return;
+        };
+        let root = tmp.path().join("r2x-demo");
+        let package_dir = root.join("src").join("r2x_demo");
+        assert!(std::fs::create_dir_all(&package_dir).is_ok());
+        assert!(std::fs::write(
+            root.join("pyproject.toml"),
+            "[project]\nname = \"r2x-demo\"\n\n[project.entry-points.r2x_plugin]\ndemo = \"r2x_demo.plugins:demo\"\n",
+        )
+        .is_ok());
+        assert!(std::fs::write(package_dir.join("__init__.py"), "").is_ok());
+        assert!(std::fs::write(
+            package_dir.join("plugins.py"),
+            "def demo(system):\n    return system\n"
+        )
+        .is_ok());
+
+        let mut manifest = r2x_manifest::types::Manifest::default();
+        let pkg = manifest.get_or_create_package("r2x-demo");
+        pkg.editable_install = true;
+        pkg.source_uri = Some(Arc::from(root.to_string_lossy().as_ref()));
+        pkg.plugins.push(Plugin {
+            name: Arc::from("old-demo"),
+            ..Default::default()
+        });
+        let mut ctx = PluginContext {
+            config: r2x_config::Config::default(),
+            manifest,
+            uv_path: String::new(),
+            venv_path: String::new(),
+            python_path: String::new(),
+            locator,
+        };
+
+        let drifted = check_manifest(&mut ctx, false);
+        assert!(matches!(drifted, Ok(false)));
+        assert_eq!(
+            ctx.manifest.packages[0].plugins[0].name.as_ref(),
+            "old-demo"
+        );
+        let diffs = collect_check_diffs(&mut ctx, false);
+        assert!(render_check(&diffs).contains("  - plugin old-demo (r2x-demo)\n"));
+        assert!(render_check(&diffs).contains("  + plugin demo (r2x-demo)\n"));
+
+        let packages = collect_packages_to_sync(&ctx.manifest.packages);
+        discover_packages(&mut ctx, &packages, true, false);
+        assert!(matches!(check_manifest(&mut ctx, false), Ok(true)));
+        assert_eq!(
+            render_check(&[]),
+            "Manifest is in sync with installed plugins\n"
+        );
+    }
+
     #[test]
     fn upgrade_target_uses_package_name_for_pypi() {
         let Some((_tmp, locator)) = empty_locator() else {
@@ -1,3 +1,17 @@
+use crate::manifest_lookup::alias_role;
+use crate::plugins::error::PluginError;
+use r2x_manifest::runtime::PluginRole;
+
+/// Parse a `--kind` value such as `parser` or `transformer`.
+pub fn parse_kind(name: &str) -> Result<PluginRole, PluginError> {
+    alias_role(name).ok_or_else(|| {
+        PluginError::InvalidArgs(format!(
+            "Unknown plugin kind '{}'. Expected one of: parser, exporter, modifier, upgrader, translation, validator, utility",
+            name
+        ))
+    })
+}
+
 /// Shorten a git commit hash to 7 characters.
 pub fn short_commit(commit: &str) -> &str {
     const SHORT_COMMIT_LEN: usize = 7;
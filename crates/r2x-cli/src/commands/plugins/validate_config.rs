@@ -0,0 +1,336 @@
+use crate::manifest_lookup::resolve_plugin_ref;
+use crate::plugins::error::PluginError;
+use r2x_logger as logger;
+use r2x_manifest::types::{Constraint, FieldType, Manifest, SchemaField, SchemaFields};
+use regex::Regex;
+use serde_json::Value;
+use std::path::Path;
+
+/// A config value that breaks the plugin's schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ConfigProblem {
+    /// Dotted path of the field, e.g. `solver.threads`
+    pub field: String,
+    pub message: String,
+}
+
+/// Outcome of checking a config against a schema
+#[derive(Debug, Default)]
+pub(crate) struct ConfigCheck {
+    pub problems: Vec<ConfigProblem>,
+    /// Constraints that could not be evaluated, such as a Python regex the
+    /// `regex` crate does not support; these do not fail the check
+    pub unchecked: Vec<ConfigProblem>,
+}
+
+/// Check a JSON or YAML config file against a plugin's config schema.
+///
+/// Every problem is printed; any problem makes the command fail.
+pub fn validate_plugin_config(plugin_name: &str, file: &str) -> Result<(), PluginError> {
+    let manifest = Manifest::load()?;
+    let resolved = resolve_plugin_ref(&manifest, plugin_name)
+        .map_err(|e| PluginError::InvalidArgs(e.to_string()))?;
+    let config = read_config_file(Path::new(file))?;
+
+    if resolved.plugin.config_schema.is_empty() {
+        logger::warn(&format!(
+            "{} has no extracted config schema; nothing to check",
+            plugin_name
+        ));
+    }
+
+    let ConfigCheck {
+        problems,
+        unchecked,
+    } = check_config(&resolved.plugin.config_schema, &config);
+    for note in &unchecked {
+        logger::warn(&format!("{}: {}", note.field, note.message));
+    }
+    if problems.is_empty() {
+        logger::success(&format!("{} is a valid config for {}", file, plugin_name));
+        return Ok(());
+    }
+
+    println!("{}", render_problems(&problems));
+    Err(PluginError::Config(format!(
+        "{} problem(s) in {} for {}",
+        problems.len(),
+        file,
+        plugin_name
+    )))
+}
+
+fn read_config_file(path: &Path) -> Result<Value, PluginError> {
+    let content = std::fs::read_to_string(path)?;
+    let is_yaml = path
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml");
+    let parsed = if is_yaml {
+        serde_yaml::from_str(&content).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str(&content).map_err(|e| e.to_string())
+    };
+    parsed
+        .map_err(|e| PluginError::InvalidArgs(format!("Failed to parse {}: {}", path.display(), e)))
+}
+
+/// Problems found checking `config` against `schema`, ordered by field
+pub(crate) fn check_config(schema: &SchemaFields, config: &Value) -> ConfigCheck {
+    let mut check = ConfigCheck::default();
+    check_object(schema, config, "", &mut check);
+    check
+}
+
+fn check_object(schema: &SchemaFields, value: &Value, prefix: &str, out: &mut ConfigCheck) {
+    let Some(map) = value.as_object() else {
+        out.problems.push(ConfigProblem {
+            field: if prefix.is_empty() {
+                "(config)".to_string()
+            } else {
+                prefix.to_string()
+            },
+            message: format!("expected an object, got {}", kind_of(value)),
+        });
+        return;
+    };
+
+    let mut fields: Vec<_> = schema.iter().collect();
+    fields.sort_by_key(|(name, _)| *name);
+    for (name, field) in fields {
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        match map.get(name.as_ref()) {
+            None if field.required => out.problems.push(ConfigProblem {
+                field: path,
+                message: "missing required field".to_string(),
+            }),
+            None => {}
+            Some(Value::Null) if !field.required => {}
+            Some(value) => check_field(field, value, &path, out),
+        }
+    }
+}
+
+fn check_field(field: &SchemaField, value: &Value, path: &str, out: &mut ConfigCheck) {
+    let mut problem = |message: String| {
+        out.problems.push(ConfigProblem {
+            field: path.to_string(),
+            message,
+        });
+    };
+
+    if !matches_type(field.field_type, value) {
+        problem(format!(
+            "expected {}, got {}",
+            type_name(field.field_type),
+            kind_of(value)
+        ));
+        return;
+    }
+
+    if let (Some(allowed), Some(text)) = (&field.enum_values, value.as_str()) {
+        if !allowed.iter().any(|option| option.as_ref() == text) {
+            let options: Vec<&str> = allowed.iter().map(AsRef::as_ref).collect();
+            problem(format!("'{}' is not one of: {}", text, options.join(", ")));
+        }
+    }
+
+    for constraint in &field.constraints {
+        match violation(constraint, value) {
+            Ok(Some(message)) => problem(message),
+            Ok(None) => {}
+            Err(message) => out.unchecked.push(ConfigProblem {
+                field: path.to_string(),
+                message,
+            }),
+        }
+    }
+
+    if let Some(properties) = &field.properties {
+        check_object(properties, value, path, out);
+    }
+}
+
+fn matches_type(field_type: FieldType, value: &Value) -> bool {
+    match field_type {
+        FieldType::Str | FieldType::Datetime => value.is_string(),
+        FieldType::Int => value.is_i64() || value.is_u64(),
+        FieldType::Float => value.is_number(),
+        FieldType::Bool => value.is_boolean(),
+        FieldType::Array => value.is_array(),
+        FieldType::Object => value.is_object(),
+        FieldType::Any => true,
+    }
+}
+
+/// Why `value` breaks `constraint`, or `None` if it holds or does not apply
+///
+/// `Err` when the constraint cannot be evaluated: a `pattern` written in
+/// Python regex syntax that the `regex` crate rejects.
+fn violation(constraint: &Constraint, value: &Value) -> Result<Option<String>, String> {
+    let number = value.as_f64();
+    let length = match value {
+        Value::String(text) => Some(text.chars().count()),
+        Value::Array(items) => Some(items.len()),
+        _ => None,
+    };
+    let holds = match constraint {
+        Constraint::Ge(bound) => number.map(|n| n >= *bound),
+        Constraint::Le(bound) => number.map(|n| n <= *bound),
+        Constraint::Gt(bound) => number.map(|n| n > *bound),
+        Constraint::Lt(bound) => number.map(|n| n < *bound),
+        Constraint::MultipleOf(step) => number
+            .filter(|_| *step != 0.0)
+            .map(|n| ((n / step).round() * step - n).abs() < f64::EPSILON * n.abs().max(1.0)),
+        Constraint::MinLen(min) => length.map(|len| len >= *min as usize),
+        Constraint::MaxLen(max) => length.map(|len| len <= *max as usize),
+        Constraint::Pattern(pattern) => match value.as_str() {
+            Some(text) => {
+                let regex = Regex::new(pattern).map_err(|_| {
+                    format!(
+                        "pattern {} not checked: it cannot be compiled outside Python",
+                        pattern
+                    )
+                })?;
+                Some(regex.is_match(text))
+            }
+            None => None,
+        },
+    };
+    if holds != Some(false) {
+        return Ok(None);
+    }
+
+    let shown = match value {
+        Value::String(text) => format!("'{}'", text),
+        Value::Array(items) => format!("{} item(s)", items.len()),
+        other => other.to_string(),
+    };
+    Ok(Some(match constraint {
+        Constraint::Ge(bound) => format!("{} must be >= {}", shown, bound),
+        Constraint::Le(bound) => format!("{} must be <= {}", shown, bound),
+        Constraint::Gt(bound) => format!("{} must be > {}", shown, bound),
+        Constraint::Lt(bound) => format!("{} must be < {}", shown, bound),
+        Constraint::MultipleOf(step) => format!("{} must be a multiple of {}", shown, step),
+        Constraint::MinLen(min) => format!("{} is shorter than the minimum length {}", shown, min),
+        Constraint::MaxLen(max) => format!("{} is longer than the maximum length {}", shown, max),
+        Constraint::Pattern(pattern) => format!("{} does not match pattern {}", shown, pattern),
+    }))
+}
+
+fn type_name(field_type: FieldType) -> &'static str {
+    match field_type {
+        FieldType::Str => "a string",
+        FieldType::Int => "an integer",
+        FieldType::Float => "a number",
+        FieldType::Bool => "a boolean",
+        FieldType::Array => "an array",
+        FieldType::Object => "an object",
+        FieldType::Datetime => "a datetime string",
+        FieldType::Any => "any value",
+    }
+}
+
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(n) if n.is_f64() => "a number",
+        Value::Number(_) => "an integer",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn render_problems(problems: &[ConfigProblem]) -> String {
+    problems
+        .iter()
+        .map(|problem| format!("  - {}: {}", problem.field, problem.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::plugins::validate_config::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn field(field_type: FieldType, required: bool) -> SchemaField {
+        SchemaField {
+            field_type,
+            required,
+            ..Default::default()
+        }
+    }
+
+    fn schema() -> SchemaFields {
+        let mut schema = SchemaFields::default();
+        let mut solve_year = field(FieldType::Int, true);
+        solve_year.constraints.push(Constraint::Ge(2020.0));
+        solve_year.constraints.push(Constraint::Le(2050.0));
+        schema.insert(Arc::from("solve_year"), solve_year);
+
+        let mut scenario = field(FieldType::Str, false);
+        scenario.enum_values = Some(Arc::from(vec![Arc::from("base"), Arc::from("high")]));
+        schema.insert(Arc::from("scenario"), scenario);
+
+        schema.insert(Arc::from("weather_year"), field(FieldType::Int, false));
+        schema
+    }
+
+    #[test]
+    fn valid_config_has_no_problems() {
+        let config = json!({"solve_year": 2030, "scenario": "high", "weather_year": null});
+        let check = check_config(&schema(), &config);
+        assert!(check.problems.is_empty());
+        assert!(check.unchecked.is_empty());
+    }
+
+    #[test]
+    fn reports_constraint_enum_type_and_missing_fields() {
+        let config = json!({"solve_year": 2065, "scenario": "low", "weather_year": "2012"});
+        let problems = check_config(&schema(), &config).problems;
+        assert_eq!(
+            render_problems(&problems),
+            "  - scenario: 'low' is not one of: base, high\n  - solve_year: 2065 must be <= 2050\n  - weather_year: expected an integer, got a string"
+        );
+
+        let problems = check_config(&schema(), &json!({})).problems;
+        assert_eq!(
+            problems,
+            vec![ConfigProblem {
+                field: "solve_year".to_string(),
+                message: "missing required field".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn unsupported_patterns_are_reported_as_unchecked() {
+        let mut schema = SchemaFields::default();
+        let mut code = field(FieldType::Str, true);
+        // Lookahead is valid Python regex but unsupported by the regex crate
+        code.constraints
+            .push(Constraint::Pattern(Arc::from("^(?=[A-Z])\\w+$")));
+        schema.insert(Arc::from("code"), code);
+        let mut region = field(FieldType::Str, true);
+        region
+            .constraints
+            .push(Constraint::Pattern(Arc::from("^p[0-9]+$")));
+        schema.insert(Arc::from("region"), region);
+
+        let check = check_config(&schema, &json!({"code": "abc", "region": "x1"}));
+        assert_eq!(
+            render_problems(&check.problems),
+            "  - region: 'x1' does not match pattern ^p[0-9]+$"
+        );
+        assert_eq!(check.unchecked.len(), 1);
+        assert_eq!(check.unchecked[0].field, "code");
+        assert!(check.unchecked[0].message.contains("not checked"));
+    }
+}
@@ -0,0 +1,294 @@
+use crate::commands::plugins::context::PluginContext;
+use crate::commands::plugins::sync::{editable_source_roots, resync_packages, PackageResync};
+use crate::plugins::error::PluginError;
+use colored::Colorize;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use r2x_logger as logger;
+use r2x_manifest::types::Plugin;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// Quiet period after the last change before discovery re-runs
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How often the loop wakes up to check for Ctrl-C and ready batches
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Collects changed packages until saves stop arriving
+struct ChangeBatch {
+    roots: Vec<(String, PathBuf)>,
+    pending: BTreeSet<String>,
+    last_event: Option<Instant>,
+}
+
+impl ChangeBatch {
+    fn new(roots: Vec<(String, PathBuf)>) -> Self {
+        Self {
+            roots,
+            pending: BTreeSet::new(),
+            last_event: None,
+        }
+    }
+
+    /// Record a watcher event. Returns true if it touched a watched `.py` file.
+    fn record(&mut self, event: &Event, now: Instant) -> bool {
+        if !matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) {
+            return false;
+        }
+
+        let mut touched = false;
+        for path in &event.paths {
+            if path.extension().and_then(|ext| ext.to_str()) != Some("py") {
+                continue;
+            }
+            if let Some(package) = self.package_for(path) {
+                self.pending.insert(package);
+                touched = true;
+            }
+        }
+        if touched {
+            self.last_event = Some(now);
+        }
+        touched
+    }
+
+    /// Packages to re-discover once the debounce window has passed
+    fn take_ready(&mut self, now: Instant) -> Option<BTreeSet<String>> {
+        let last = self.last_event?;
+        if now.duration_since(last) < DEBOUNCE {
+            return None;
+        }
+        self.last_event = None;
+        Some(std::mem::take(&mut self.pending))
+    }
+
+    /// Longest matching root wins so nested packages resolve correctly.
+    fn package_for(&self, path: &Path) -> Option<String> {
+        self.roots
+            .iter()
+            .filter(|(_, root)| path.starts_with(root))
+            .max_by_key(|(_, root)| root.components().count())
+            .map(|(name, _)| name.clone())
+    }
+}
+
+/// Plugin-level changes in one package after re-discovery
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PluginDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+    changed: Vec<String>,
+}
+
+impl PluginDiff {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn diff_plugins(before: &[Plugin], after: &[Plugin]) -> PluginDiff {
+    let find = |plugins: &[Plugin], name: &str| -> Option<serde_json::Value> {
+        plugins
+            .iter()
+            .find(|p| p.name.as_ref() == name)
+            .and_then(|p| serde_json::to_value(p).ok())
+    };
+
+    let mut diff = PluginDiff::default();
+    for plugin in after {
+        match find(before, &plugin.name) {
+            None => diff.added.push(plugin.name.to_string()),
+            Some(old) if serde_json::to_value(plugin).ok().as_ref() != Some(&old) => {
+                diff.changed.push(plugin.name.to_string());
+            }
+            Some(_) => {}
+        }
+    }
+    for plugin in before {
+        if find(after, &plugin.name).is_none() {
+            diff.removed.push(plugin.name.to_string());
+        }
+    }
+    diff
+}
+
+fn print_resync(result: &PackageResync) {
+    let diff = diff_plugins(&result.before, &result.after);
+    if diff.is_empty() {
+        logger::info(&format!("{}: no manifest changes", result.name));
+        return;
+    }
+
+    println!("{}", result.name.bold());
+    for name in &diff.added {
+        println!("  {} {}", "+".green(), name);
+    }
+    for name in &diff.removed {
+        println!("  {} {}", "-".red(), name);
+    }
+    for name in &diff.changed {
+        println!("  {} {}", "~".yellow(), name);
+    }
+}
+
+/// Watch editable package sources and re-sync the manifest on `.py` changes.
+///
+/// Runs until Ctrl-C. Only the packages whose files changed are re-discovered.
+pub fn watch_manifest(ctx: &mut PluginContext, include_dev: bool) -> Result<(), PluginError> {
+    let roots = editable_source_roots(ctx);
+    if roots.is_empty() {
+        logger::warn("No editable packages to watch. Install one with: r2x install -e <path>");
+        return Ok(());
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| PluginError::Discovery(format!("Failed to start file watcher: {}", e)))?;
+    for (name, root) in &roots {
+        watcher.watch(root, RecursiveMode::Recursive).map_err(|e| {
+            PluginError::Discovery(format!(
+                "Failed to watch '{}' at {}: {}",
+                name,
+                root.display(),
+                e
+            ))
+        })?;
+        logger::info(&format!("Watching {} ({})", name, root.display()));
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = Arc::clone(&stop);
+        ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst))
+            .map_err(|e| PluginError::Config(format!("Failed to set Ctrl-C handler: {}", e)))?;
+    }
+
+    eprintln!("{}", "Watching for changes. Press Ctrl-C to stop.".dimmed());
+
+    let mut batch = ChangeBatch::new(roots);
+    while !stop.load(Ordering::SeqCst) {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(event)) => {
+                batch.record(&event, Instant::now());
+            }
+            Ok(Err(e)) => logger::warn(&format!("File watcher error: {}", e)),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        if let Some(packages) = batch.take_ready(Instant::now()) {
+            logger::step(&format!(
+                "Re-discovering: {}",
+                packages.iter().cloned().collect::<Vec<_>>().join(", ")
+            ));
+            match resync_packages(ctx, &packages, include_dev) {
+                Ok(results) => results.iter().for_each(print_resync),
+                Err(e) => logger::warn(&format!("Re-sync failed: {}", e)),
+            }
+        }
+    }
+
+    logger::info("Stopped watching");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::plugins::watch::*;
+    use notify::event::{CreateKind, DataChange, ModifyKind};
+    use std::sync::Arc;
+
+    fn roots() -> Vec<(String, PathBuf)> {
+        vec![
+            ("r2x-reeds".to_string(), PathBuf::from("/src/r2x-reeds")),
+            (
+                "r2x-reeds-ext".to_string(),
+                PathBuf::from("/src/r2x-reeds/ext"),
+            ),
+        ]
+    }
+
+    fn modify(path: &str) -> Event {
+        Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content)))
+            .add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn py_change_triggers_rediscovery_after_debounce() {
+        let mut batch = ChangeBatch::new(roots());
+        let start = Instant::now();
+        assert!(batch.record(&modify("/src/r2x-reeds/parser.py"), start));
+
+        assert!(batch.take_ready(start).is_none());
+        let ready = batch.take_ready(start + DEBOUNCE);
+        assert!(ready.is_some_and(|p| p.into_iter().collect::<Vec<_>>() == vec!["r2x-reeds"]));
+        assert!(batch.take_ready(start + DEBOUNCE * 2).is_none());
+    }
+
+    #[test]
+    fn rapid_saves_are_batched() {
+        let mut batch = ChangeBatch::new(roots());
+        let start = Instant::now();
+        batch.record(&modify("/src/r2x-reeds/a.py"), start);
+        batch.record(
+            &Event::new(EventKind::Create(CreateKind::File))
+                .add_path("/src/r2x-reeds/ext/b.py".into()),
+            start + DEBOUNCE / 2,
+        );
+
+        // The second save restarts the window.
+        assert!(batch.take_ready(start + DEBOUNCE).is_none());
+        let ready = batch.take_ready(start + DEBOUNCE / 2 + DEBOUNCE);
+        assert!(ready.is_some_and(|p| p.len() == 2 && p.contains("r2x-reeds-ext")));
+    }
+
+    #[test]
+    fn ignores_non_python_and_unwatched_files() {
+        let mut batch = ChangeBatch::new(roots());
+        let now = Instant::now();
+        assert!(!batch.record(&modify("/src/r2x-reeds/README.md"), now));
+        assert!(!batch.record(&modify("/elsewhere/plugin.py"), now));
+        assert!(!batch.record(
+            &Event::new(EventKind::Access(notify::event::AccessKind::Any))
+                .add_path("/src/r2x-reeds/a.py".into()),
+            now
+        ));
+        assert!(batch.take_ready(now + DEBOUNCE).is_none());
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_plugins() {
+        let plugin = |name: &str, function: &str| Plugin {
+            name: Arc::from(name),
+            function_name: Some(Arc::from(function)),
+            ..Plugin::default()
+        };
+        let before = vec![
+            plugin("keep", "keep"),
+            plugin("edit", "old"),
+            plugin("gone", "gone"),
+        ];
+        let after = vec![
+            plugin("keep", "keep"),
+            plugin("edit", "new"),
+            plugin("fresh", "fresh"),
+        ];
+
+        let diff = diff_plugins(&before, &after);
+        assert_eq!(
+            diff,
+            PluginDiff {
+                added: vec!["fresh".to_string()],
+                removed: vec!["gone".to_string()],
+                changed: vec!["edit".to_string()],
+            }
+        );
+        assert!(diff_plugins(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::commands::run::RunError;
+use r2x_python::errors::PyErrorKind;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Error kinds accepted by `--exit-code-map`, with their default exit codes
+///
+/// Usage and configuration mistakes the user can fix come first; problems
+/// with the installed plugins or the Python environment follow.
+pub const DEFAULT_EXIT_CODES: &[(&str, i32)] = &[
+    ("steps-failed", 1),
+    ("matrix-failed", 1),
+    ("invalid-args", 2),
+    ("config", 3),
+    ("pipeline", 3),
+    ("plugin-not-found", 4),
+    ("manifest", 4),
+    ("verification", 5),
+    ("bridge", 6),
+    ("validation-failed", 7),
+    ("memory-limit", 8),
+    ("timeout", 9),
+];
+
+impl RunError {
+    /// Key naming this error in `--exit-code-map`
+    ///
+    /// A plugin raising `ValueError` (or a subclass such as pydantic's
+    /// `ValidationError`) is rejecting its config, so it counts as `config`
+    /// rather than `bridge`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RunError::Bridge(e) if e.py_error_kind() == Some(PyErrorKind::Value) => "config",
+            RunError::Manifest(_) => "manifest",
+            RunError::Bridge(_) => "bridge",
+            RunError::Pipeline(_) => "pipeline",
+            RunError::Config(_) => "config",
+            RunError::PluginNotFound(_) => "plugin-not-found",
+            RunError::InvalidArgs(_) => "invalid-args",
+            RunError::Verification(_) => "verification",
+            RunError::ValidationFailed(_) => "validation-failed",
+            RunError::StepsFailed(_) => "steps-failed",
+            RunError::MatrixRunsFailed(_) => "matrix-failed",
+            RunError::MemoryLimitExceeded { .. } => "memory-limit",
+            RunError::StepTimedOut { .. } => "timeout",
+        }
+    }
+}
+
+/// Exit codes chosen with `--exit-code-map kind=code,...`
+///
+/// Kinds left out keep their code from [`DEFAULT_EXIT_CODES`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExitCodeMap {
+    overrides: HashMap<String, i32>,
+}
+
+impl ExitCodeMap {
+    /// Process exit code for a failed `r2x run`
+    pub fn code_for(&self, error: &RunError) -> i32 {
+        let kind = error.kind();
+        self.overrides
+            .get(kind)
+            .copied()
+            .or_else(|| {
+                DEFAULT_EXIT_CODES
+                    .iter()
+                    .find(|(name, _)| *name == kind)
+                    .map(|(_, code)| *code)
+            })
+            .unwrap_or(1)
+    }
+}
+
+impl FromStr for ExitCodeMap {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut overrides = HashMap::new();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let Some((kind, code)) = entry.split_once('=') else {
+                return Err(format!("invalid entry '{}' (expected kind=code)", entry));
+            };
+            let kind = kind.trim();
+            if !DEFAULT_EXIT_CODES.iter().any(|(name, _)| *name == kind) {
+                let kinds: Vec<&str> = DEFAULT_EXIT_CODES.iter().map(|(name, _)| *name).collect();
+                return Err(format!(
+                    "unknown error kind '{}' (expected one of {})",
+                    kind,
+                    kinds.join(", ")
+                ));
+            }
+            let code = code
+                .trim()
+                .parse::<i32>()
+                .ok()
+                .filter(|code| (1..=255).contains(code))
+                .ok_or_else(|| format!("exit code for '{}' must be 1-255", kind))?;
+            overrides.insert(kind.to_string(), code);
+        }
+        Ok(Self { overrides })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::exit_codes::*;
+    use crate::errors::PipelineError;
+    use r2x_manifest::errors::ManifestError;
+    use r2x_python::errors::BridgeError;
+
+    fn every_variant() -> Vec<RunError> {
+        vec![
+            RunError::Manifest(ManifestError::InvalidPlugin("bad".into())),
+            RunError::Bridge(BridgeError::R2XCoreNotInstalled),
+            RunError::Pipeline(PipelineError::InvalidConfig("bad".into())),
+            RunError::Config("bad".into()),
+            RunError::PluginNotFound("parser".into()),
+            RunError::InvalidArgs("bad".into()),
+            RunError::Verification("bad".into()),
+            RunError::ValidationFailed("bad".into()),
+            RunError::StepsFailed(vec!["parser".into()]),
+            RunError::MatrixRunsFailed(vec!["scenario=high".into()]),
+            RunError::MemoryLimitExceeded {
+                step: "parser".into(),
+                limit_mb: 1,
+                rss_mb: 2,
+            },
+            RunError::StepTimedOut {
+                step: "parser".into(),
+                timeout_secs: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn every_error_has_its_default_exit_code() {
+        let map = ExitCodeMap::default();
+        let mut codes: Vec<(&str, i32)> = every_variant()
+            .iter()
+            .map(|error| (error.kind(), map.code_for(error)))
+            .collect();
+        let mut expected = DEFAULT_EXIT_CODES.to_vec();
+        codes.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(codes, expected);
+    }
+
+    #[test]
+    fn configured_codes_replace_the_defaults() {
+        let entries: Vec<String> = DEFAULT_EXIT_CODES
+            .iter()
+            .enumerate()
+            .map(|(idx, (kind, _))| format!("{}={}", kind, 100 + idx))
+            .collect();
+        let map = entries.join(",").parse::<ExitCodeMap>();
+        assert!(map.is_ok());
+        let Ok(map) = map else {
+            return;
+        };
+        for error in every_variant() {
+            let idx = DEFAULT_EXIT_CODES
+                .iter()
+                .position(|(kind, _)| *kind == error.kind());
+            assert_eq!(
+                Some(map.code_for(&error)),
+                idx.and_then(|idx| i32::try_from(100 + idx).ok())
+            );
+        }
+
+        let partial = "bridge=70".parse::<ExitCodeMap>();
+        assert!(partial.is_ok_and(|map| map.code_for(&RunError::Config("bad".into())) == 3));
+    }
+
+    #[test]
+    fn plugin_value_errors_count_as_config_errors() {
+        let raised = |py_type: &str, kind: PyErrorKind| {
+            RunError::Bridge(BridgeError::PythonTyped {
+                py_type: py_type.to_string(),
+                kind,
+                message: "bad".into(),
+            })
+        };
+        assert_eq!(raised("ValueError", PyErrorKind::Value).kind(), "config");
+        assert_eq!(
+            raised("ValidationError", PyErrorKind::Value).kind(),
+            "config"
+        );
+        assert_eq!(raised("ImportError", PyErrorKind::Import).kind(), "bridge");
+    }
+
+    #[test]
+    fn malformed_maps_are_rejected() {
+        assert!("bridge".parse::<ExitCodeMap>().is_err());
+        assert!(matches!(
+            "network=5".parse::<ExitCodeMap>(),
+            Err(msg) if msg.contains("unknown error kind 'network'")
+        ));
+        assert!("config=0".parse::<ExitCodeMap>().is_err());
+        assert!("config=300".parse::<ExitCodeMap>().is_err());
+    }
+}
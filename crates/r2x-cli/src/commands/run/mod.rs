@@ -1,17 +1,28 @@
 use crate::common::GlobalOpts;
 use crate::errors::PipelineError;
+use crate::package_verification::VerificationError;
+use crate::pipeline_config::PipelineConfig;
 use clap::Parser;
+use exit_codes::ExitCodeMap;
 use pipeline::handle_pipeline_mode;
+use pipeline::input::InputFormat;
+use pipeline::output::OutputFormat;
+use pipeline::report::{PipelineRunReport, RunOptions};
 use plugin::handle_plugin_command;
+use r2x_config::Config;
 use r2x_logger as logger;
 use r2x_manifest::errors::ManifestError;
 use r2x_manifest::runtime::{PluginRole, RuntimeBindings};
 use r2x_manifest::types::PluginType;
 use r2x_python::errors::BridgeError;
-use r2x_python::plugin_invoker::PluginInvocationTimings;
+use r2x_python::plugin_invoker::{PluginInvocationTimings, ValidationReport};
+use r2x_python::python_bridge::Bridge;
+use std::path::PathBuf;
 use std::time::Duration;
 
-mod pipeline;
+pub mod exit_codes;
+mod picker;
+pub mod pipeline;
 mod plugin;
 
 #[derive(Debug)]
@@ -23,6 +34,22 @@ pub enum RunError {
     PluginNotFound(String),
     InvalidArgs(String),
     Verification(String),
+    ValidationFailed(String),
+    /// Steps that failed in a pipeline run with `--no-fail-fast`
+    StepsFailed(Vec<String>),
+    /// Combinations that failed in an `r2x run --matrix` run
+    MatrixRunsFailed(Vec<String>),
+    /// Process RSS went over `--max-memory` while `step` was running
+    MemoryLimitExceeded {
+        step: String,
+        limit_mb: u64,
+        rss_mb: u64,
+    },
+    /// `step` did not return within `--plugin-timeout`
+    StepTimedOut {
+        step: String,
+        timeout_secs: u64,
+    },
 }
 
 impl std::fmt::Display for RunError {
@@ -39,6 +66,33 @@ impl std::fmt::Display for RunError {
             RunError::Verification(msg) => {
                 write!(f, "Package verification error: {}", msg)
             }
+            RunError::ValidationFailed(msg) => write!(f, "Validation failed: {}", msg),
+            RunError::StepsFailed(steps) => write!(
+                f,
+                "{} pipeline step(s) failed: {}",
+                steps.len(),
+                steps.join(", ")
+            ),
+            RunError::MatrixRunsFailed(runs) => write!(
+                f,
+                "{} matrix run(s) failed: {}",
+                runs.len(),
+                runs.join("; ")
+            ),
+            RunError::MemoryLimitExceeded {
+                step,
+                limit_mb,
+                rss_mb,
+            } => write!(
+                f,
+                "Step '{}' exceeded the memory limit: {} MB used, limit {} MB (--max-memory)",
+                step, rss_mb, limit_mb
+            ),
+            RunError::StepTimedOut { step, timeout_secs } => write!(
+                f,
+                "Step '{}' did not finish within {}s (--plugin-timeout); its Python code may still be running",
+                step, timeout_secs
+            ),
         }
     }
 }
@@ -75,10 +129,87 @@ pub struct RunCommand {
     pub list: bool,
     #[arg(long)]
     pub print: bool,
+    /// With --print, show each step's final config exactly as sent to its plugin
+    #[arg(long, requires = "print")]
+    pub resolved: bool,
     #[arg(long)]
     pub dry_run: bool,
+    /// Print the resolved call target and kwargs plan for each step without running it
+    #[arg(long)]
+    pub explain: bool,
     #[arg(short = 'o', long, value_name = "FILE")]
     pub output: Option<String>,
+    /// Reserialize JSON output as `pretty` (indented) or `compact` (default: as the plugin wrote it)
+    #[arg(long, value_name = "FORMAT")]
+    pub format: Option<OutputFormat>,
+    /// Write outputs under this folder instead of the YAML `output_folder`
+    #[arg(long, value_name = "PATH")]
+    pub set_output_folder: Option<String>,
+    /// Run only the Nth step (1-based) of the pipeline
+    #[arg(long, value_name = "N")]
+    pub step: Option<usize>,
+    /// JSON or YAML file to use as stdin for `--step` in place of upstream output
+    #[arg(long, value_name = "FILE", requires = "step")]
+    pub input: Option<String>,
+    /// Parse `--input` as `json` or `yaml` (default: from the file extension)
+    #[arg(long, value_name = "FORMAT", requires = "input")]
+    pub input_format: Option<InputFormat>,
+    /// Run only the steps tagged with one of these (comma-separated)
+    #[arg(
+        long,
+        value_name = "TAGS",
+        value_delimiter = ',',
+        conflicts_with = "step"
+    )]
+    pub tags: Vec<String>,
+    /// Stop the pipeline at the first failing step (default)
+    #[arg(long, overrides_with = "no_fail_fast")]
+    pub fail_fast: bool,
+    /// Keep running after a step fails; the failed step's input flows on to later steps
+    #[arg(long, alias = "continue-on-error", overrides_with = "fail_fast")]
+    pub no_fail_fast: bool,
+    /// With --no-fail-fast, write each step's status, error and duration to FILE as JSON
+    ///
+    /// Not available with --matrix, where every combination would overwrite the same FILE.
+    #[arg(long, value_name = "FILE", requires = "no_fail_fast")]
+    pub keep_going_report: Option<String>,
+    /// Abort the run if the process uses more than this much resident memory
+    #[arg(long, value_name = "MB")]
+    pub max_memory: Option<u64>,
+    /// Give up on a step that runs longer than this many seconds
+    #[arg(long, value_name = "SECS")]
+    pub plugin_timeout: Option<u64>,
+    /// Print a table of per-step status and timings when the run ends
+    #[arg(long)]
+    pub summary: bool,
+    /// JSON or TOML file resolving `${SECRET:key}` references (default: $R2X_SECRETS_FILE)
+    #[arg(long, value_name = "FILE")]
+    pub secrets: Option<String>,
+    /// Run the pipeline N times and print min/median/mean/max/std-dev of the run time
+    #[arg(long, value_name = "N")]
+    pub repeat: Option<usize>,
+    /// Run against a venv for this Python version (kept under the cache dir) without changing config;
+    /// must be the Python r2x embeds
+    #[arg(long, value_name = "VERSION")]
+    pub python: Option<String>,
+    /// Power-user escape hatch: skip checking (and reinstalling) plugin packages before running
+    #[arg(long)]
+    pub no_verify: bool,
+    /// Treat upgrader input as this data version, running only steps with a newer target_version
+    #[arg(long, value_name = "VERSION")]
+    pub since: Option<String>,
+    /// Exit with these codes per error kind, e.g. `config=78,bridge=70` (see README for defaults)
+    #[arg(long, value_name = "KIND=CODE,...")]
+    pub exit_code_map: Option<ExitCodeMap>,
+    /// Run the pipeline once per combination of the file's `matrix:` values
+    #[arg(
+        long,
+        conflicts_with_all = ["output", "repeat", "keep_going_report", "set_output_folder"]
+    )]
+    pub matrix: bool,
+    /// Ignore the machine-wide pipeline-defaults.yaml next to the r2x config file
+    #[arg(long)]
+    pub no_global_defaults: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -91,28 +222,93 @@ pub struct PluginCommand {
     pub plugin_name: Option<String>,
     #[arg(long)]
     pub show_help: bool,
+    /// JSON file (e.g. a serialized system) to pass to the plugin as stdin; may be gzip-compressed
+    #[arg(long, value_name = "PATH")]
+    pub stdin_file: Option<String>,
+    /// Skip checking the plugin's package before running, like `r2x run --no-verify`
+    ///
+    /// Must come before the plugin arguments.
+    #[arg(long)]
+    pub no_verify: bool,
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
     pub args: Vec<String>,
 }
 
 pub fn handle_run(cmd: RunCommand, opts: GlobalOpts) -> Result<(), RunError> {
+    let venv_config = match cmd.python.as_deref() {
+        Some(version) => python_override(version)?,
+        None => None,
+    };
+    if let Some(config) = &venv_config {
+        Bridge::use_config(config.clone());
+    }
+    r2x_python::plugin_invoker::set_upgrade_since(cmd.since.clone());
     match cmd.command {
-        Some(RunSubcommand::Plugin(plugin_cmd)) => handle_plugin_command(plugin_cmd, &opts),
+        Some(RunSubcommand::Plugin(plugin_cmd)) => {
+            handle_plugin_command(plugin_cmd, cmd.no_verify, venv_config.as_ref(), &opts)
+        }
         None => {
-            let yaml_path = cmd.yaml_path.unwrap_or_else(|| "pipeline.yaml".to_string());
-            handle_pipeline_mode(
-                yaml_path,
-                cmd.pipeline_name,
-                cmd.list,
-                cmd.print,
-                cmd.dry_run,
-                cmd.output,
-                &opts,
-            )
+            let yaml_path = cmd
+                .yaml_path
+                .clone()
+                .unwrap_or_else(|| "pipeline.yaml".to_string());
+            handle_pipeline_mode(&yaml_path, &cmd, venv_config.as_ref(), &opts)
         }
     }
 }
 
+/// Config pointing at a venv for `version`, leaving the saved config untouched.
+///
+/// The plugins run inside the embedded interpreter, so `version` must be the
+/// Python r2x was built against. Returns `None` when it is already the
+/// configured version; otherwise the venv is created if missing and the
+/// config is handed to the bridge and package checks for this run only.
+fn python_override(version: &str) -> Result<Option<Config>, RunError> {
+    check_python_override(version, &Bridge::compiled_python_version())?;
+    let config =
+        Config::load().map_err(|e| RunError::Config(format!("Failed to load config: {}", e)))?;
+    let Some(scoped) = config.with_python_override(version) else {
+        logger::debug(&format!("Python {} is the configured version", version));
+        return Ok(None);
+    };
+
+    let venv_path = PathBuf::from(scoped.get_venv_path());
+    if !venv_path.exists() {
+        Bridge::create_venv_with_version(&scoped, &venv_path, version)?;
+    }
+    logger::info(&format!(
+        "Using Python {} venv at {}",
+        version,
+        venv_path.display()
+    ));
+    Ok(Some(scoped))
+}
+
+/// Reject a `--python` version other than the one r2x embeds
+fn check_python_override(version: &str, compiled: &str) -> Result<(), RunError> {
+    if version.trim() == compiled {
+        return Ok(());
+    }
+    Err(RunError::InvalidArgs(format!(
+        "--python {}: r2x embeds Python {} and can't load plugins from a Python {} venv",
+        version.trim(),
+        compiled,
+        version.trim()
+    )))
+}
+
+/// Run a pipeline from `config` and return a structured report.
+///
+/// Nothing is printed to stdout; the caller decides how to present the
+/// report and the system JSON in [`PipelineRunReport::final_output`].
+pub fn run_pipeline_api(
+    config: &PipelineConfig,
+    name: &str,
+    opts: &RunOptions,
+) -> Result<PipelineRunReport, RunError> {
+    pipeline::run_pipeline_report(config, name, opts)
+}
+
 pub(super) fn build_call_target(bindings: &RuntimeBindings) -> Result<String, RunError> {
     let target = match bindings.plugin_type {
         PluginType::Class => {
@@ -137,6 +333,52 @@ pub(super) fn build_call_target(bindings: &RuntimeBindings) -> Result<String, Ru
     Ok(target)
 }
 
+/// Run `verify` for each plugin, unless `skip` is set by `--no-verify`.
+pub(super) fn verify_plugins<'a, F>(
+    plugin_names: impl IntoIterator<Item = &'a str>,
+    skip: bool,
+    mut verify: F,
+) -> Result<(), RunError>
+where
+    F: FnMut(&str) -> Result<(), VerificationError>,
+{
+    if skip {
+        logger::warn(
+            "Skipping package verification (--no-verify); missing packages will not be reinstalled",
+        );
+        return Ok(());
+    }
+    for plugin_name in plugin_names {
+        verify(plugin_name).map_err(|e| RunError::Verification(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Report a validator's diagnostics and fail when validation did not pass.
+pub(super) fn check_validation_output(plugin_name: &str, output: &str) -> Result<(), RunError> {
+    let report = ValidationReport::from_json(output).ok_or_else(|| {
+        RunError::Config(format!(
+            "Validator '{}' returned an unreadable report: {}",
+            plugin_name, output
+        ))
+    })?;
+
+    for diagnostic in &report.diagnostics {
+        logger::warn(&format!("{}: {}", plugin_name, diagnostic));
+    }
+
+    if report.passed {
+        logger::success(&format!("{} validation passed", plugin_name));
+        Ok(())
+    } else {
+        Err(RunError::ValidationFailed(format!(
+            "{} reported {} issue(s)",
+            plugin_name,
+            report.diagnostics.len()
+        )))
+    }
+}
+
 pub(super) fn format_duration(duration: Duration) -> String {
     let total_ms = duration.as_millis();
     if total_ms < 1000 {
@@ -156,3 +398,69 @@ pub(super) fn print_plugin_timing_breakdown(timings: &PluginInvocationTimings) {
         format_duration(timings.serialization)
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::*;
+
+    #[test]
+    fn failing_validation_returns_error() {
+        let report = ValidationReport {
+            passed: false,
+            diagnostics: vec!["generator 7 has negative capacity".to_string()],
+        };
+        let result = check_validation_output("system-validator", &report.to_json());
+        assert!(matches!(result, Err(RunError::ValidationFailed(_))));
+    }
+
+    #[test]
+    fn passing_validation_returns_ok() {
+        let report = ValidationReport {
+            passed: true,
+            diagnostics: Vec::new(),
+        };
+        assert!(check_validation_output("system-validator", &report.to_json()).is_ok());
+    }
+
+    #[test]
+    fn verification_runs_for_every_plugin_by_default() {
+        let mut verified = Vec::new();
+        let result = verify_plugins(["parser", "exporter"], false, |name| {
+            verified.push(name.to_string());
+            Ok(())
+        });
+        assert!(result.is_ok());
+        assert_eq!(verified, vec!["parser", "exporter"]);
+
+        let result = verify_plugins(["parser"], false, |name| {
+            Err(VerificationError::VerificationFailed(name.to_string()))
+        });
+        assert!(matches!(result, Err(RunError::Verification(_))));
+    }
+
+    #[test]
+    fn no_verify_skips_verification() {
+        let mut calls = 0;
+        let result = verify_plugins(["parser", "exporter"], true, |_| {
+            calls += 1;
+            Ok(())
+        });
+        assert!(result.is_ok());
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn python_override_must_match_the_embedded_python() {
+        assert!(check_python_override("3.12", "3.12").is_ok());
+        assert!(matches!(
+            check_python_override("3.13", "3.12"),
+            Err(RunError::InvalidArgs(ref msg)) if msg.contains("3.13") && msg.contains("3.12")
+        ));
+    }
+
+    #[test]
+    fn unreadable_validation_output_is_a_config_error() {
+        let result = check_validation_output("system-validator", "null");
+        assert!(matches!(result, Err(RunError::Config(_))));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::commands::plugins::docs::format_field_type;
+use colored::Colorize;
+use r2x_manifest::types::{Manifest, Plugin};
+use std::io::{self, BufRead, Write};
+
+/// What `r2x run plugin` does when no plugin name is given
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum PickerOutcome {
+    /// Print the plugin list (not a terminal, or nothing installed)
+    List,
+    /// Run the picked plugin with `key=value` args from the field prompts
+    Run { plugin: String, args: Vec<String> },
+    /// Input ended before a plugin and its fields were chosen
+    Cancelled,
+}
+
+/// A required config field the picker asks for
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct FieldPrompt {
+    pub name: String,
+    /// Text shown before the answer, e.g. `solve_year (int)`
+    pub label: String,
+}
+
+/// Prompts for the required fields of `plugin`'s config schema, sorted by name
+pub(super) fn required_field_prompts(plugin: &Plugin) -> Vec<FieldPrompt> {
+    let mut prompts: Vec<FieldPrompt> = plugin
+        .config_schema
+        .iter()
+        .filter(|(_, field)| field.required)
+        .map(|(name, field)| FieldPrompt {
+            name: name.to_string(),
+            label: format!("{} ({})", name, format_field_type(field)),
+        })
+        .collect();
+    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+    prompts
+}
+
+/// Let the user pick a plugin by number, then answer its required fields.
+///
+/// Only prompts when `interactive`; otherwise, or with no plugins
+/// installed, the caller prints the list as before.
+pub(super) fn pick_plugin<R: BufRead, W: Write>(
+    manifest: &Manifest,
+    interactive: bool,
+    mut input: R,
+    mut out: W,
+) -> io::Result<PickerOutcome> {
+    let mut choices: Vec<(String, &Plugin)> = manifest
+        .packages
+        .iter()
+        .flat_map(|pkg| {
+            pkg.plugins
+                .iter()
+                .map(move |plugin| (format!("{}.{}", pkg.name, plugin.name), plugin))
+        })
+        .collect();
+    if !interactive || choices.is_empty() {
+        return Ok(PickerOutcome::List);
+    }
+    choices.sort_by(|a, b| a.0.cmp(&b.0));
+
+    writeln!(out, "Available plugins:\n")?;
+    for (idx, (name, _)) in choices.iter().enumerate() {
+        writeln!(out, "  {:>2}) {}", idx + 1, name)?;
+    }
+    writeln!(out)?;
+
+    let (name, plugin) = loop {
+        let prompt = format!("Select a plugin [1-{}]", choices.len());
+        let Some(answer) = ask(&mut input, &mut out, &prompt)? else {
+            return Ok(PickerOutcome::Cancelled);
+        };
+        match answer.parse::<usize>() {
+            Ok(number) if (1..=choices.len()).contains(&number) => break &choices[number - 1],
+            _ => writeln!(out, "Enter a number between 1 and {}", choices.len())?,
+        }
+    };
+
+    let mut args = Vec::new();
+    for prompt in required_field_prompts(plugin) {
+        let value = loop {
+            let Some(answer) = ask(&mut input, &mut out, &prompt.label)? else {
+                return Ok(PickerOutcome::Cancelled);
+            };
+            if !answer.is_empty() {
+                break answer;
+            }
+            writeln!(out, "{} is required", prompt.name)?;
+        };
+        args.push(format!("{}={}", prompt.name, value));
+    }
+
+    Ok(PickerOutcome::Run {
+        plugin: name.clone(),
+        args,
+    })
+}
+
+/// Print `prompt` and read a trimmed answer; `None` once input ends
+fn ask<R: BufRead, W: Write>(
+    input: &mut R,
+    out: &mut W,
+    prompt: &str,
+) -> io::Result<Option<String>> {
+    write!(out, "{} {} {} ", "?".bold().cyan(), prompt, "›".dimmed())?;
+    out.flush()?;
+    let mut answer = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(answer.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::picker::*;
+    use r2x_manifest::types::{FieldType, Package, SchemaField, SchemaFields};
+    use std::sync::Arc;
+
+    fn manifest() -> Manifest {
+        let mut schema = SchemaFields::default();
+        let field = |field_type, required| SchemaField {
+            field_type,
+            required,
+            ..SchemaField::default()
+        };
+        schema.insert(Arc::from("weather_year"), field(FieldType::Int, false));
+        schema.insert(Arc::from("solve_year"), field(FieldType::Int, true));
+        schema.insert(Arc::from("folder"), field(FieldType::Str, true));
+
+        let mut manifest = Manifest::default();
+        manifest.packages.push(Package {
+            name: Arc::from("r2x-reeds"),
+            plugins: vec![
+                Plugin {
+                    name: Arc::from("parser"),
+                    config_schema: schema,
+                    ..Plugin::default()
+                },
+                Plugin {
+                    name: Arc::from("break-gens"),
+                    ..Plugin::default()
+                },
+            ],
+            ..Package::default()
+        });
+        manifest
+    }
+
+    #[test]
+    fn required_fields_become_sorted_prompts() {
+        let manifest = manifest();
+        let prompts = required_field_prompts(&manifest.packages[0].plugins[0]);
+        assert_eq!(
+            prompts,
+            vec![
+                FieldPrompt {
+                    name: "folder".to_string(),
+                    label: "folder (str)".to_string(),
+                },
+                FieldPrompt {
+                    name: "solve_year".to_string(),
+                    label: "solve_year (int)".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn without_a_terminal_the_list_is_printed() {
+        let mut out = Vec::new();
+        let outcome = pick_plugin(&manifest(), false, "1\n".as_bytes(), &mut out);
+        assert!(outcome.is_ok_and(|outcome| outcome == PickerOutcome::List));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn picked_plugin_runs_with_answered_fields() {
+        let input = "9\n2\n\ninputs\n2030\n";
+        let mut out = Vec::new();
+        let outcome = pick_plugin(&manifest(), true, input.as_bytes(), &mut out);
+        assert_eq!(
+            outcome.ok(),
+            Some(PickerOutcome::Run {
+                plugin: "r2x-reeds.parser".to_string(),
+                args: vec!["folder=inputs".to_string(), "solve_year=2030".to_string()],
+            })
+        );
+        let shown = String::from_utf8_lossy(&out);
+        assert!(shown.contains(" 1) r2x-reeds.break-gens"));
+        assert!(shown.contains("Enter a number between 1 and 2"));
+        assert!(shown.contains("folder is required"));
+
+        let outcome = pick_plugin(&manifest(), true, "2\n".as_bytes(), Vec::new());
+        assert!(outcome.is_ok_and(|outcome| outcome == PickerOutcome::Cancelled));
+    }
+}
@@ -0,0 +1,49 @@
+use crate::commands::run::format_duration;
+use colored::Colorize;
+use std::path::Path;
+use std::time::Duration;
+
+/// Header printed before a pipeline runs; empty under `--quiet`
+pub(super) fn running_banner(pipeline_name: &str, log_path: Option<&Path>, quiet: bool) -> String {
+    if quiet {
+        return String::new();
+    }
+    let mut banner = format!("{}\n", format!("Running: {}", pipeline_name).cyan().bold());
+    if let Some(log_path) = log_path {
+        banner.push_str(&format!(
+            "{}\n",
+            format!("  Log file: {}", log_path.display()).dimmed()
+        ));
+    }
+    banner
+}
+
+/// Total wall time line printed after a run; empty under `--quiet`
+pub(super) fn finished_banner(elapsed: Duration, quiet: bool) -> String {
+    if quiet {
+        return String::new();
+    }
+    format!(
+        "{}\n",
+        format!("Finished in: {}", format_duration(elapsed))
+            .green()
+            .bold()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::banner::*;
+
+    #[test]
+    fn banners_are_printed_unless_quiet() {
+        let log = Path::new("/tmp/r2x.log");
+        let running = running_banner("my-pipeline", Some(log), false);
+        assert!(running.contains("Running: my-pipeline"));
+        assert!(running.contains("Log file: /tmp/r2x.log"));
+        assert!(finished_banner(Duration::from_millis(250), false).contains("Finished in: 250ms"));
+
+        assert_eq!(running_banner("my-pipeline", Some(log), true), "");
+        assert_eq!(finished_banner(Duration::from_millis(250), true), "");
+    }
+}
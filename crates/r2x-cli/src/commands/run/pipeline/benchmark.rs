@@ -0,0 +1,147 @@
+use crate::commands::run::format_duration;
+use crate::commands::run::pipeline::report::PipelineRunReport;
+use crate::commands::run::RunError;
+use r2x_logger as logger;
+use std::time::Duration;
+
+/// Wall-time statistics over the runs of `r2x run --repeat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct TimingStats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+    /// Population standard deviation
+    pub std_dev: Duration,
+}
+
+impl TimingStats {
+    pub(super) fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+        let runs = sorted.len();
+        let middle = runs / 2;
+        let median = if runs % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Some(Self {
+            runs,
+            min,
+            median,
+            mean: Duration::from_secs_f64(mean),
+            max,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Reports of a repeated run
+#[derive(Debug)]
+pub(super) struct RepeatOutcome {
+    /// Total duration of each run, in run order
+    pub samples: Vec<Duration>,
+    /// Report of the last run; the only one whose output is kept
+    pub last: PipelineRunReport,
+}
+
+/// Call `run` up to `times` times, stopping early at the first run with failed steps.
+pub(super) fn repeat_runs<F, E>(times: usize, mut run: F) -> Result<RepeatOutcome, E>
+where
+    F: FnMut(usize) -> Result<PipelineRunReport, E>,
+    E: From<RunError>,
+{
+    let mut samples = Vec::with_capacity(times);
+    let mut last = None;
+    for iteration in 0..times {
+        logger::info(&format!("Run {}/{}", iteration + 1, times));
+        let report = run(iteration)?;
+        samples.push(report.elapsed);
+        let succeeded = report.succeeded();
+        last = Some(report);
+        if !succeeded {
+            logger::warn("Stopping repeated runs after a failed run");
+            break;
+        }
+    }
+    let last = last.ok_or_else(|| {
+        E::from(RunError::InvalidArgs(
+            "--repeat needs at least 1 run".into(),
+        ))
+    })?;
+    Ok(RepeatOutcome { samples, last })
+}
+
+/// Table printed after the runs of `r2x run --repeat`
+pub(super) fn render_stats(pipeline: &str, stats: &TimingStats) -> String {
+    let rows = [
+        ("min", stats.min),
+        ("median", stats.median),
+        ("mean", stats.mean),
+        ("max", stats.max),
+        ("std dev", stats.std_dev),
+    ];
+    let mut out = format!("{}: {} runs\n", pipeline, stats.runs);
+    for (label, duration) in rows {
+        out.push_str(&format!("  {:<8} {}\n", label, format_duration(duration)));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::benchmark::*;
+
+    fn report(elapsed: Duration) -> PipelineRunReport {
+        PipelineRunReport {
+            pipeline: "nightly".to_string(),
+            steps: Vec::new(),
+            final_output: Some(format!("{}", elapsed.as_secs())),
+            elapsed,
+        }
+    }
+
+    #[test]
+    fn repeated_run_collects_a_sample_per_run() {
+        let elapsed = [Duration::from_secs(1), Duration::from_secs(3)];
+        let outcome = repeat_runs(2, |iteration| Ok::<_, RunError>(report(elapsed[iteration])));
+        assert!(outcome.is_ok());
+        let Ok(outcome) = outcome else {
+            return;
+        };
+        assert_eq!(outcome.samples, elapsed);
+        assert_eq!(outcome.last.final_output.as_deref(), Some("3"));
+
+        let stats = TimingStats::from_samples(&outcome.samples);
+        assert_eq!(
+            stats,
+            Some(TimingStats {
+                runs: 2,
+                min: Duration::from_secs(1),
+                median: Duration::from_secs(2),
+                mean: Duration::from_secs(2),
+                max: Duration::from_secs(3),
+                std_dev: Duration::from_secs(1),
+            })
+        );
+        let Some(stats) = stats else {
+            return;
+        };
+        let table = render_stats("nightly", &stats);
+        assert!(table.starts_with("nightly: 2 runs\n"));
+        assert!(table.contains("  median   2.00s\n"));
+    }
+
+    #[test]
+    fn no_samples_have_no_stats() {
+        assert_eq!(TimingStats::from_samples(&[]), None);
+    }
+}
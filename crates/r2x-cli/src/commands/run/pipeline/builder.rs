@@ -2,16 +2,15 @@ use r2x_manifest::runtime::RuntimeBindings;
 use r2x_manifest::types::PluginType;
 
 use crate::commands::run::pipeline::constants::{
-    DEFAULT_OUTPUT_ROOT, FOLDER_FIELD_KEYS, PATH_FALLBACK_KEYS, STORE_FIELD_KEYS,
+    FOLDER_FIELD_KEYS, PATH_FALLBACK_KEYS, STORE_FIELD_KEYS,
 };
+use crate::commands::run::pipeline::store::{StoreLocation, StoreSpec};
 use crate::commands::run::RunError;
 
 pub(super) fn build_plugin_config(
     bindings: &RuntimeBindings,
-    package_name: &str,
     yaml_config_json: &str,
-    output_folder: Option<&str>,
-    inherited_store_path: Option<&str>,
+    store: &StoreLocation,
     stdin_overrides: Option<&str>,
 ) -> Result<String, RunError> {
     let mut yaml_config: serde_json::Value = serde_json::from_str(yaml_config_json)
@@ -71,19 +70,33 @@ pub(super) fn build_plugin_config(
 
         if needs_store {
             // Use `path` as primary source for store, with fallbacks
-            let store_value = if let serde_json::Value::Object(ref yaml_map) = yaml_config {
+            let declared = if let serde_json::Value::Object(ref yaml_map) = yaml_config {
                 pick_value(yaml_map, STORE_FIELD_KEYS)
-                    .or_else(|| {
-                        inherited_store_path.map(|p| serde_json::Value::String(p.to_string()))
-                    })
-                    .map_or_else(|| fallback_store_value(package_name, output_folder), Ok)?
-            } else if let Some(inherited) = inherited_store_path {
-                serde_json::Value::String(inherited.to_string())
             } else {
-                fallback_store_value(package_name, output_folder)?
+                None
+            };
+            let spec = declared.as_ref().and_then(StoreSpec::from_value);
+
+            let store_value = match (declared, spec) {
+                // Not a store declaration we understand; hand it to the plugin as-is
+                (Some(value), None) => {
+                    store_value_for_folder = Some(value.clone());
+                    value
+                }
+                (_, Some(spec @ StoreSpec { path: Some(_), .. })) => {
+                    store_value_for_folder = spec.folder().map(serde_json::Value::String);
+                    serde_json::Value::String(spec.path.unwrap_or_default())
+                }
+                // `store: true` or nothing declared
+                _ => {
+                    let store_path = store.prepare()?;
+                    store_value_for_folder = StoreSpec::from_path(store_path)
+                        .folder()
+                        .map(serde_json::Value::String);
+                    serde_json::Value::String(store_path.to_string())
+                }
             };
 
-            store_value_for_folder = Some(store_value.clone());
             final_config.insert("store".to_string(), store_value);
         }
 
@@ -102,7 +115,9 @@ pub(super) fn build_plugin_config(
             let folder_value = explicit_folder
                 .or_else(|| store_value_for_folder.as_ref().and_then(value_string_clone))
                 .or_else(|| {
-                    inherited_store_path.map(|path| serde_json::Value::String(path.to_string()))
+                    store
+                        .declared()
+                        .map(|path| serde_json::Value::String(path.to_string()))
                 });
 
             if let Some(value) = folder_value {
@@ -149,22 +164,48 @@ fn merge_config_values(target: &mut serde_json::Value, overrides: serde_json::Va
     }
 }
 
-fn fallback_store_value(
-    _package_name: &str,
-    output_folder: Option<&str>,
-) -> Result<serde_json::Value, RunError> {
-    let output_folder = output_folder.unwrap_or(DEFAULT_OUTPUT_ROOT);
-    let store_path = format!("{}/store", output_folder);
-    std::fs::create_dir_all(&store_path)
-        .map_err(|e| RunError::Config(format!("Failed to create store directory: {}", e)))?;
-
-    Ok(serde_json::Value::String(store_path))
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::commands::run::pipeline::builder::merge_config_values;
+    use crate::commands::run::pipeline::builder::{build_plugin_config, merge_config_values};
+    use crate::commands::run::pipeline::store::StoreLocation;
+    use r2x_manifest::runtime::{PluginRole, RuntimeBindings};
+    use r2x_manifest::types::{Parameter, PluginType};
     use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn list_valued_config_stays_a_list() {
+        let bindings = RuntimeBindings {
+            entry_module: "r2x_reeds.parser".to_string(),
+            entry_name: "ReEDSParser".to_string(),
+            plugin_type: PluginType::Class,
+            role: PluginRole::Parser,
+            call_method: Some("build_system".to_string()),
+            config: None,
+            parameters: vec![Parameter {
+                name: Arc::from("folders"),
+                types: smallvec::smallvec![Arc::from("list[str]")],
+                module: None,
+                required: true,
+                default: None,
+                description: None,
+            }],
+            requires_store: false,
+            upgrade: None,
+        };
+        let yaml = json!({"folders": ["reeds/s1", "reeds/s2"], "weather_year": 2012}).to_string();
+        let overrides = json!({"folders": ["reeds/s1", "reeds/s2", "reeds/s3"]}).to_string();
+
+        let store = StoreLocation::Inherited("reeds".to_string());
+        let built = build_plugin_config(&bindings, &yaml, &store, None);
+        assert!(built.is_ok_and(|config| config.contains(r#""folders":["reeds/s1","reeds/s2"]"#)));
+        let built = build_plugin_config(&bindings, &yaml, &store, Some(&overrides));
+        let folders = built
+            .ok()
+            .and_then(|config| serde_json::from_str::<serde_json::Value>(&config).ok())
+            .map(|config| config["folders"].clone());
+        assert_eq!(folders, Some(json!(["reeds/s1", "reeds/s2", "reeds/s3"])));
+    }
 
     #[test]
     fn merge_config_values_replaces_existing() {
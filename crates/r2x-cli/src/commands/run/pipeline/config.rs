@@ -1,29 +1,78 @@
 use crate::manifest_lookup::ResolvedPlugin;
-use crate::pipeline_config::PipelineConfig;
+use crate::pipeline_config::{PipelineConfig, PIPELINE_DEFAULTS_FILE};
+use r2x_config::Config;
+use r2x_logger as logger;
 use r2x_manifest::runtime::{build_runtime_bindings, PluginRole};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
+use crate::commands::run::pipeline::store::StoreLocation;
 use crate::commands::run::RunError;
 
+/// Load the machine-wide `pipeline-defaults.yaml` to merge under the pipeline's step configs
+///
+/// The file sits next to the r2x config file; without it nothing changes.
+pub(super) fn load_global_defaults(config: &mut PipelineConfig) -> Result<(), RunError> {
+    let config_path = Config::path();
+    let defaults_path = config_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(PIPELINE_DEFAULTS_FILE);
+    if config.load_defaults_file(&defaults_path)? {
+        logger::debug(&format!(
+            "Loaded pipeline defaults from {}",
+            defaults_path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// A step's config from the pipeline YAML and the store it resolves to
+#[derive(Debug)]
+pub(super) struct StepConfig {
+    /// The config as JSON; `{}` when the YAML has none for the step
+    pub json: String,
+    pub store: StoreLocation,
+}
+
+/// Look up a step's config and resolve its store location
+///
+/// The store comes from the step's own `store_path` or `store`, then
+/// `inherited_store` (declared by an earlier step), then `output_folder`,
+/// then the default output root.
 pub(super) fn resolve_plugin_config_json(
     config: &PipelineConfig,
     plugin_ref: &str,
     resolved: &ResolvedPlugin<'_>,
+    inherited_store: Option<&str>,
+    output_folder: Option<&str>,
+) -> Result<StepConfig, RunError> {
+    let json = lookup_plugin_config_json(config, plugin_ref, resolved)?;
+    let value = serde_json::from_str(&json).unwrap_or(serde_json::Value::Null);
+    let store = StoreLocation::resolve(&value, inherited_store, output_folder);
+    Ok(StepConfig { json, store })
+}
+
+fn lookup_plugin_config_json(
+    config: &PipelineConfig,
+    plugin_ref: &str,
+    resolved: &ResolvedPlugin<'_>,
 ) -> Result<String, RunError> {
     let plugin_name = resolved.plugin.name.as_ref();
     let package_name = resolved.package.name.as_ref();
     let role = build_runtime_bindings(resolved.plugin).role;
     let kind_alias = plugin_role_alias(role);
 
-    for key in config_key_candidates(plugin_ref, package_name, plugin_name, kind_alias) {
-        if config.config.contains_key(&key) {
-            return config
-                .get_plugin_config_json(&key)
-                .map_err(RunError::Pipeline);
-        }
-    }
-
-    Ok("{}".to_string())
+    let candidates = config_key_candidates(plugin_ref, package_name, plugin_name, kind_alias);
+    let find = |entries: &HashMap<String, serde_yaml::Value>| {
+        candidates
+            .iter()
+            .find(|key| entries.contains_key(key.as_str()))
+            .map(String::as_str)
+    };
+    config
+        .plugin_config_json_with_defaults(find(&config.config), find(&config.config_defaults))
+        .map_err(RunError::Pipeline)
 }
 
 fn config_key_candidates(
@@ -84,6 +133,48 @@ fn plugin_role_alias(role: PluginRole) -> Option<&'static str> {
         PluginRole::Upgrader => Some("upgrader"),
         PluginRole::Modifier => Some("modifier"),
         PluginRole::Translation => Some("translation"),
+        PluginRole::Validator => Some("validator"),
         PluginRole::Utility => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::config::*;
+    use r2x_manifest::types::{Package, Plugin};
+    use std::sync::Arc;
+
+    #[test]
+    fn qualified_defaults_sit_under_a_short_pipeline_entry() {
+        let config = PipelineConfig::parse(
+            "pipelines:\n  demo: [r2x-reeds.parser]\nconfig:\n  parser:\n    solve_year: 2035\n",
+        );
+        assert!(config.is_ok());
+        let Ok(mut config) = config else {
+            return;
+        };
+        config.config_defaults.insert(
+            "r2x-reeds.parser".to_string(),
+            serde_yaml::from_str("{solve_year: 2030, data_root: /shared/reeds}")
+                .unwrap_or_default(),
+        );
+        let package = Package {
+            name: Arc::from("r2x-reeds"),
+            ..Package::default()
+        };
+        let plugin = Plugin {
+            name: Arc::from("parser"),
+            ..Plugin::default()
+        };
+        let resolved = ResolvedPlugin {
+            package: &package,
+            plugin: &plugin,
+        };
+
+        let json = lookup_plugin_config_json(&config, "r2x-reeds.parser", &resolved);
+        let value: serde_json::Value =
+            serde_json::from_str(&json.unwrap_or_default()).unwrap_or_default();
+        assert_eq!(value["solve_year"], 2035);
+        assert_eq!(value["data_root"], "/shared/reeds");
+    }
+}
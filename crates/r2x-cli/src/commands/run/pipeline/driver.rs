@@ -0,0 +1,270 @@
+use crate::commands::run::pipeline::report::{FailureMode, StepReport, StepStatus};
+use crate::commands::run::RunError;
+use crate::pipeline_config::ResolvedStep;
+use r2x_logger as logger;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Instant;
+
+/// Result of driving every step of a pipeline
+#[derive(Debug, Default)]
+pub(super) struct DriveOutcome {
+    /// Output of the last step that ran
+    pub final_output: Option<Rc<str>>,
+    /// One entry per step, in run order
+    pub steps: Vec<StepReport>,
+    /// Error that stopped the run early; its step is the last entry of `steps`
+    pub aborted: Option<RunError>,
+}
+
+/// Run `steps` in order, wiring each step's stdin from the output of its input step.
+///
+/// `run_step` receives the step index, the step and its stdin, and returns
+/// the output to hand downstream. `seed_input` is the stdin of the first step
+/// without an upstream reference. A step error that ends the run is kept in
+/// [`DriveOutcome::aborted`] alongside the steps that ran.
+pub(super) fn drive_steps<F>(
+    steps: &[ResolvedStep],
+    mode: FailureMode,
+    mut seed_input: Option<Rc<str>>,
+    mut run_step: F,
+) -> DriveOutcome
+where
+    F: FnMut(usize, &ResolvedStep, Option<Rc<str>>) -> Result<Option<Rc<str>>, RunError>,
+{
+    // Output each step hands downstream, keyed by step name. Shared so that
+    // pass-through steps and fan-out branches do not copy the system JSON.
+    let mut step_outputs: HashMap<&str, Option<Rc<str>>> = HashMap::new();
+    let mut outcome = DriveOutcome::default();
+
+    for (idx, step) in steps.iter().enumerate() {
+        let stdin: Option<Rc<str>> = match step.input.as_deref() {
+            Some(input) => step_outputs.get(input).cloned().flatten(),
+            None => seed_input.take(),
+        };
+
+        let start = Instant::now();
+        let (step_output, status) = match run_step(idx, step, stdin.clone()) {
+            Ok(output) => (output, StepStatus::Succeeded),
+            // Running out of memory or time ends the run even with --no-fail-fast;
+            // a timed-out step may still hold the GIL.
+            Err(error)
+                if mode == FailureMode::ContinueOnError
+                    && !matches!(
+                        error,
+                        RunError::MemoryLimitExceeded { .. } | RunError::StepTimedOut { .. }
+                    ) =>
+            {
+                logger::warn(&format!(
+                    "{} failed; continuing (--no-fail-fast)",
+                    step.name
+                ));
+                let status = StepStatus::Failed {
+                    error: error.to_string(),
+                };
+                (stdin, status)
+            }
+            Err(error) => {
+                outcome.steps.push(StepReport {
+                    name: step.name.clone(),
+                    position: step.position,
+                    status: StepStatus::Failed {
+                        error: error.to_string(),
+                    },
+                    elapsed: start.elapsed(),
+                    timings: None,
+                });
+                outcome.aborted = Some(error);
+                break;
+            }
+        };
+        outcome.steps.push(StepReport {
+            name: step.name.clone(),
+            position: step.position,
+            status,
+            elapsed: start.elapsed(),
+            timings: None,
+        });
+
+        outcome.final_output.clone_from(&step_output);
+        step_outputs.insert(step.name.as_str(), step_output);
+    }
+
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::driver::*;
+    use crate::commands::run::RunCommand;
+    use clap::Parser;
+
+    fn linear(names: &[&str]) -> Vec<ResolvedStep> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| ResolvedStep {
+                name: (*name).to_string(),
+                position: idx,
+                input: idx.checked_sub(1).map(|prev| names[prev].to_string()),
+                input_position: idx.checked_sub(1),
+                tags: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Appends the step name to its input; `fails` errors instead.
+    fn mock_step(
+        calls: &mut Vec<String>,
+        step: &ResolvedStep,
+        stdin: Option<Rc<str>>,
+    ) -> Result<Option<Rc<str>>, RunError> {
+        calls.push(step.name.clone());
+        if step.name == "fails" {
+            return Err(RunError::Config("boom".to_string()));
+        }
+        let input = stdin.as_deref().unwrap_or("");
+        Ok(Some(Rc::from(format!("{}>{}", input, step.name))))
+    }
+
+    fn statuses(outcome: &DriveOutcome) -> Vec<(&str, &StepStatus)> {
+        outcome
+            .steps
+            .iter()
+            .map(|step| (step.name.as_str(), &step.status))
+            .collect()
+    }
+
+    #[test]
+    fn fail_fast_aborts_at_failing_step() {
+        let mut calls = Vec::new();
+        let outcome = drive_steps(
+            &linear(&["parser", "fails", "exporter"]),
+            FailureMode::FailFast,
+            None,
+            |_, step, stdin| mock_step(&mut calls, step, stdin),
+        );
+        assert!(matches!(outcome.aborted, Some(RunError::Config(ref msg)) if msg == "boom"));
+        assert_eq!(calls, vec!["parser", "fails"]);
+        assert_eq!(
+            statuses(&outcome),
+            vec![
+                ("parser", &StepStatus::Succeeded),
+                (
+                    "fails",
+                    &StepStatus::Failed {
+                        error: "Configuration error: boom".to_string()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_one_entry_per_step() {
+        let mut calls = Vec::new();
+        let outcome = drive_steps(
+            &linear(&["parser", "modifier", "exporter"]),
+            FailureMode::FailFast,
+            None,
+            |_, step, stdin| mock_step(&mut calls, step, stdin),
+        );
+        assert!(outcome.aborted.is_none());
+        assert_eq!(
+            statuses(&outcome),
+            vec![
+                ("parser", &StepStatus::Succeeded),
+                ("modifier", &StepStatus::Succeeded),
+                ("exporter", &StepStatus::Succeeded),
+            ]
+        );
+        assert_eq!(
+            outcome.final_output.as_deref(),
+            Some(">parser>modifier>exporter")
+        );
+    }
+
+    #[test]
+    fn no_fail_fast_continues_with_failed_step_input() {
+        let mut calls = Vec::new();
+        let outcome = drive_steps(
+            &linear(&["parser", "fails", "exporter"]),
+            FailureMode::ContinueOnError,
+            None,
+            |_, step, stdin| mock_step(&mut calls, step, stdin),
+        );
+        assert_eq!(calls, vec!["parser", "fails", "exporter"]);
+        assert!(outcome.aborted.is_none());
+        assert_eq!(outcome.final_output.as_deref(), Some(">parser>exporter"));
+        assert_eq!(
+            statuses(&outcome),
+            vec![
+                ("parser", &StepStatus::Succeeded),
+                (
+                    "fails",
+                    &StepStatus::Failed {
+                        error: "Configuration error: boom".to_string()
+                    }
+                ),
+                ("exporter", &StepStatus::Succeeded),
+            ]
+        );
+    }
+
+    #[test]
+    fn timed_out_step_ends_run_despite_no_fail_fast() {
+        use crate::commands::run::pipeline::timeout::call_with_timeout;
+        use std::time::Duration;
+
+        let mut calls = Vec::new();
+        let outcome = drive_steps(
+            &linear(&["parser", "hangs", "exporter"]),
+            FailureMode::ContinueOnError,
+            None,
+            |_, step, stdin| {
+                if step.name != "hangs" {
+                    return mock_step(&mut calls, step, stdin);
+                }
+                calls.push(step.name.clone());
+                // Stand-in for a bridge call that never returns in time
+                call_with_timeout(Duration::from_millis(20), || {
+                    std::thread::sleep(Duration::from_secs(5));
+                })
+                .map(|()| None)
+                .map_err(|_| RunError::StepTimedOut {
+                    step: step.name.clone(),
+                    timeout_secs: 0,
+                })
+            },
+        );
+        assert!(
+            matches!(outcome.aborted, Some(RunError::StepTimedOut { ref step, .. }) if step == "hangs")
+        );
+        assert_eq!(calls, vec!["parser", "hangs"]);
+        assert_eq!(outcome.steps.len(), 2);
+    }
+
+    #[test]
+    fn failure_mode_flags() {
+        let mode = |args: &[&str]| {
+            let argv = ["run", "pipeline.yaml", "p"].iter().chain(args);
+            RunCommand::try_parse_from(argv)
+                .ok()
+                .map(|cmd| FailureMode::from_command(&cmd))
+        };
+        assert_eq!(mode(&[]), Some(FailureMode::FailFast));
+        assert_eq!(mode(&["--fail-fast"]), Some(FailureMode::FailFast));
+        assert_eq!(
+            mode(&["--no-fail-fast"]),
+            Some(FailureMode::ContinueOnError)
+        );
+        assert_eq!(
+            mode(&["--continue-on-error"]),
+            Some(FailureMode::ContinueOnError)
+        );
+        assert_eq!(
+            mode(&["--no-fail-fast", "--fail-fast"]),
+            Some(FailureMode::FailFast)
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use crate::commands::run::RunError;
+use crate::pipeline_config::PipelineConfig;
+use colored::Colorize;
+use r2x_logger as logger;
+use r2x_manifest::runtime::{find_config_param, PluginRole, RuntimeBindings};
+use r2x_manifest::types::{DefaultValue, Manifest, SchemaFields};
+
+use super::resolved::plan_steps;
+
+/// Keyword arguments the Python bridge would build for a plugin call.
+///
+/// Mirrors the decisions made by `Bridge::build_kwargs`, using the manifest
+/// config schema instead of inspecting an instantiated config object.
+#[derive(Debug, Default)]
+pub(super) struct KwargsPlan {
+    pub created: Vec<String>,
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Print the call target and kwargs plan for every step without invoking Python.
+pub(super) fn explain_pipeline(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    manifest: &Manifest,
+) -> Result<(), RunError> {
+    let planned = plan_steps(config, pipeline_name, manifest)?;
+
+    logger::success(&format!("Pipeline: {}", pipeline_name));
+    let total_steps = planned.len();
+
+    for (idx, planned) in planned.iter().enumerate() {
+        let plugin_name = &planned.step.name;
+        let bindings = &planned.bindings;
+        let target = crate::commands::run::build_call_target(bindings)?;
+        // Steps with a declared input receive that step's output on stdin.
+        let plan = plan_kwargs(
+            bindings,
+            &planned.plugin.plugin.config_schema,
+            &planned.config,
+            planned.step.input.is_some(),
+        );
+
+        println!(
+            "{}",
+            format_step_explanation(plugin_name, idx + 1, total_steps, &target, &plan)
+        );
+    }
+
+    println!(
+        "{}  No actual execution. Use without --explain to run the pipeline.",
+        "✔".green()
+    );
+
+    Ok(())
+}
+
+pub(super) fn plan_kwargs(
+    bindings: &RuntimeBindings,
+    config_fields: &SchemaFields,
+    config: &serde_json::Value,
+    has_stdin: bool,
+) -> KwargsPlan {
+    let mut plan = KwargsPlan::default();
+    let config_map = config.as_object().cloned().unwrap_or_default();
+
+    if bindings.role == PluginRole::Upgrader
+        && bindings.config.is_none()
+        && bindings.parameters.is_empty()
+    {
+        plan.created.extend(config_map.keys().cloned());
+        return plan;
+    }
+
+    let config_param_name = bindings.config.as_ref().map(|config_spec| {
+        find_config_param(&bindings.parameters, &config_spec.name)
+            .map_or_else(|| "config".to_string(), |param| param.name.to_string())
+    });
+
+    if let Some(name) = &config_param_name {
+        plan.created.push(format!("{} (config class)", name));
+    }
+
+    for param in &bindings.parameters {
+        let name = param.name.as_ref();
+        if config_param_name.as_deref() == Some(name) {
+            plan.skipped.push((
+                name.to_string(),
+                "already handled as config class".to_string(),
+            ));
+            continue;
+        }
+
+        let has_data_store_type = param.types.iter().any(|t| t.contains("DataStore"));
+        if name == "store" || name == "data_store" || has_data_store_type {
+            let has_store_value = ["store", name, "store_path", "path"]
+                .iter()
+                .any(|key| config_map.contains_key(*key));
+            if has_store_value {
+                plan.created.push(format!("{} (DataStore)", name));
+            } else {
+                plan.skipped.push((
+                    name.to_string(),
+                    "no store path found in config".to_string(),
+                ));
+            }
+            continue;
+        }
+
+        if config_param_name.is_some() && config_fields.fields.contains_key(name) {
+            plan.skipped
+                .push((name.to_string(), "already in config object".to_string()));
+            continue;
+        }
+
+        if let Some(value) = config_map.get(name) {
+            if param
+                .default
+                .as_ref()
+                .is_some_and(|default| json_matches_default(value, default))
+            {
+                plan.skipped
+                    .push((name.to_string(), "matches declared default".to_string()));
+                continue;
+            }
+            plan.created.push(name.to_string());
+            if name == "folder_path" && !config_map.contains_key("path") {
+                plan.created.push("path (alias of folder_path)".to_string());
+            }
+        } else if param.is_required() {
+            let stdin_param = name == "stdin" || name == "system";
+            let reason = if stdin_param && has_stdin {
+                "will be provided via stdin"
+            } else {
+                "missing in config (required)"
+            };
+            plan.skipped.push((name.to_string(), reason.to_string()));
+        } else {
+            plan.skipped.push((
+                name.to_string(),
+                "not found in config (optional)".to_string(),
+            ));
+        }
+    }
+
+    if has_stdin {
+        if bindings
+            .parameters
+            .iter()
+            .any(|p| p.name.as_ref() == "stdin")
+        {
+            plan.created.push("stdin (from pipeline)".to_string());
+        } else {
+            plan.skipped.push((
+                "stdin".to_string(),
+                "plugin has no stdin parameter".to_string(),
+            ));
+        }
+    }
+
+    plan
+}
+
+fn json_matches_default(value: &serde_json::Value, default: &DefaultValue) -> bool {
+    match default {
+        DefaultValue::Bool(expected) => value.as_bool() == Some(*expected),
+        DefaultValue::Int(expected) => value.as_i64() == Some(*expected),
+        DefaultValue::Float(expected) => value
+            .as_f64()
+            .is_some_and(|actual| (actual - *expected).abs() < f64::EPSILON),
+        DefaultValue::Str(expected) => value.as_str() == Some(expected.as_ref()),
+        DefaultValue::Array(_) => false,
+        DefaultValue::Expr { .. } => default.is_none() && value.is_null(),
+    }
+}
+
+pub(super) fn format_step_explanation(
+    plugin_name: &str,
+    step_num: usize,
+    total_steps: usize,
+    target: &str,
+    plan: &KwargsPlan,
+) -> String {
+    let mut lines = vec![
+        format!("[{}/{}] {}", step_num, total_steps, plugin_name),
+        format!("  target: {}", target),
+        "  kwargs:".to_string(),
+    ];
+    if plan.created.is_empty() {
+        lines.push("    (none)".to_string());
+    }
+    for name in &plan.created {
+        lines.push(format!("    + {}", name));
+    }
+    if !plan.skipped.is_empty() {
+        lines.push("  skipped:".to_string());
+        for (name, reason) in &plan.skipped {
+            lines.push(format!("    - {}: {}", name, reason));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::explain::*;
+    use r2x_manifest::runtime::RuntimeConfig;
+    use r2x_manifest::types::{Parameter, PluginType, SchemaField};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn param(name: &str, ty: &str, required: bool) -> Parameter {
+        Parameter {
+            name: Arc::from(name),
+            types: smallvec::smallvec![Arc::from(ty)],
+            module: None,
+            required,
+            default: None,
+            description: None,
+        }
+    }
+
+    fn exporter_bindings() -> RuntimeBindings {
+        RuntimeBindings {
+            entry_module: "r2x_plexos.exporter".to_string(),
+            entry_name: "PlexosExporter".to_string(),
+            plugin_type: PluginType::Class,
+            role: PluginRole::Exporter,
+            call_method: Some("export".to_string()),
+            config: Some(RuntimeConfig {
+                module: "r2x_plexos.config".to_string(),
+                name: "PlexosConfig".to_string(),
+                fields: Vec::new(),
+            }),
+            parameters: vec![
+                param("config", "PlexosConfig", true),
+                param("system", "System", true),
+                param("stdin", "str", false),
+                param("model_name", "str", false),
+            ],
+            requires_store: false,
+            upgrade: None,
+        }
+    }
+
+    #[test]
+    fn explain_lists_config_class_and_stdin_params() {
+        let bindings = exporter_bindings();
+        let mut fields = SchemaFields::default();
+        fields
+            .fields
+            .insert(Arc::from("model_name"), SchemaField::default());
+        let config = json!({"model_name": "base", "output_folder": "out"});
+
+        let plan = plan_kwargs(&bindings, &fields, &config, true);
+        let output = format_step_explanation(
+            "plexos-exporter",
+            2,
+            2,
+            "r2x_plexos.exporter:PlexosExporter.export",
+            &plan,
+        );
+
+        assert!(output.contains("target: r2x_plexos.exporter:PlexosExporter.export"));
+        assert!(output.contains("+ config (config class)"));
+        assert!(output.contains("+ stdin (from pipeline)"));
+        assert!(output.contains("- system: will be provided via stdin"));
+        assert!(output.contains("- model_name: already in config object"));
+    }
+
+    #[test]
+    fn explain_first_step_reports_missing_stdin_params() {
+        let bindings = exporter_bindings();
+        let plan = plan_kwargs(&bindings, &SchemaFields::default(), &json!({}), false);
+
+        assert!(plan
+            .skipped
+            .iter()
+            .any(|(name, reason)| name == "system" && reason == "missing in config (required)"));
+        assert!(!plan.created.iter().any(|name| name.starts_with("stdin")));
+    }
+}
@@ -0,0 +1,261 @@
+use crate::errors::PipelineError;
+use crate::pipeline_config::{PipelineConfig, PipelineHooks};
+use crate::secrets::{MIN_REDACTED_LEN, SECRET_PREFIX};
+use r2x_logger as logger;
+use std::process::{Command, ExitStatus};
+
+/// Run the pipeline's `on_success` or `on_failure` hook.
+pub(super) fn run_pipeline_hook(config: &PipelineConfig, pipeline: &str, succeeded: bool) {
+    run_hook(config, &config.hooks, &[("pipeline", pipeline)], succeeded);
+}
+
+/// Run the hook configured for `step` under `step_hooks`, if any.
+pub(super) fn run_step_hook(config: &PipelineConfig, pipeline: &str, step: &str, succeeded: bool) {
+    if let Some(hooks) = config.step_hooks.get(step) {
+        run_hook(
+            config,
+            hooks,
+            &[("pipeline", pipeline), ("step", step)],
+            succeeded,
+        );
+    }
+}
+
+/// Run the hook matching the outcome and log its output.
+///
+/// A hook that fails only logs a warning; it never changes the result of the
+/// run. Output goes to the log rather than stdout, which carries the system JSON.
+fn run_hook(
+    config: &PipelineConfig,
+    hooks: &PipelineHooks,
+    tokens: &[(&str, &str)],
+    succeeded: bool,
+) -> Option<ExitStatus> {
+    let template = hooks.for_outcome(succeeded)?;
+    let status = if succeeded { "success" } else { "failure" };
+    let command = match expand_hook(config, template, tokens, status) {
+        Ok(command) => command,
+        Err(e) => {
+            logger::warn(&format!("Skipping hook '{}': {}", template, e));
+            return None;
+        }
+    };
+
+    logger::debug(&format!("Running {} hook: {}", status, command));
+    match shell_command(&command).output() {
+        Ok(output) => {
+            logger::capture_output(&format!("hook: {}", command), &output);
+            if !output.status.success() {
+                logger::warn(&format!(
+                    "Hook exited with {:?}: {}",
+                    output.status.code(),
+                    command
+                ));
+            }
+            Some(output.status)
+        }
+        Err(e) => {
+            logger::warn(&format!("Failed to run hook '{}': {}", command, e));
+            None
+        }
+    }
+}
+
+/// Replace each `${name}` with a hook token or pipeline variable, shell-quoted.
+///
+/// Only the braced form is expanded, so `$VAR` and `$(cmd)` reach the shell
+/// untouched. Each value becomes a single shell word whatever it contains.
+fn expand_hook(
+    config: &PipelineConfig,
+    template: &str,
+    tokens: &[(&str, &str)],
+    status: &str,
+) -> Result<String, PipelineError> {
+    let mut command = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        let end = rest[start..].find('}').ok_or_else(|| {
+            PipelineError::InvalidConfig("Unclosed variable substitution ${".to_string())
+        })?;
+        let name = &rest[start + 2..start + end];
+        let value = match tokens.iter().find(|(token, _)| *token == name) {
+            Some((_, value)) => (*value).to_string(),
+            None if name == "status" => status.to_string(),
+            None => config.get_variable_string(name)?,
+        };
+        let quoted = shell_quote(&value);
+        // Quoting rewrites a secret holding a quote, so mask the quoted form too
+        if name.starts_with(SECRET_PREFIX) && value.chars().count() >= MIN_REDACTED_LEN {
+            logger::redact_value(&quoted);
+        }
+        command.push_str(&rest[..start]);
+        command.push_str(&quoted);
+        rest = &rest[start + end + 1..];
+    }
+    command.push_str(rest);
+    Ok(command)
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+#[cfg(not(windows))]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::hooks::*;
+    use tempfile::TempDir;
+
+    fn config_with_hooks(dir: &TempDir) -> Option<PipelineConfig> {
+        let marker = dir.path().join("hook.txt");
+        let yaml = format!(
+            r#"
+variables:
+  marker: "{}"
+pipelines:
+  nightly: [parser]
+on_success: "echo ${{pipeline}} ${{status}} > ${{marker}}"
+on_failure: "echo ${{pipeline}} ${{status}} > ${{marker}}"
+step_hooks:
+  parser:
+    on_failure: "echo ${{step}} ${{status}} > ${{marker}}"
+"#,
+            marker.display()
+        );
+        serde_yaml::from_str(&yaml).ok()
+    }
+
+    fn marker(dir: &TempDir) -> String {
+        std::fs::read_to_string(dir.path().join("hook.txt"))
+            .map(|content| content.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn expands_tokens_before_variables() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let config = config_with_hooks(&dir);
+        assert!(config.is_some());
+        let Some(config) = config else {
+            return;
+        };
+        let command = expand_hook(
+            &config,
+            "notify ${pipeline} ${status} ${marker}",
+            &[("pipeline", "nightly")],
+            "success",
+        );
+        assert_eq!(
+            command.ok(),
+            Some(format!(
+                "notify {} {} {}",
+                shell_quote("nightly"),
+                shell_quote("success"),
+                shell_quote(&dir.path().join("hook.txt").display().to_string())
+            ))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn values_are_quoted_and_shell_syntax_is_left_alone() {
+        let config: Option<PipelineConfig> = serde_yaml::from_str(
+            "variables:\n  label: \"it's $(rm -rf x)\"\npipelines:\n  nightly: [parser]\n",
+        )
+        .ok();
+        let Some(config) = config else {
+            return;
+        };
+        let command = expand_hook(
+            &config,
+            "echo ${label} $HOME $(date) ${pipeline}",
+            &[("pipeline", "a b")],
+            "success",
+        );
+        assert_eq!(
+            command.ok().as_deref(),
+            Some("echo 'it'\\''s $(rm -rf x)' $HOME $(date) 'a b'")
+        );
+        assert!(expand_hook(&config, "echo ${HOME}", &[], "success").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn quoted_secrets_are_redacted() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let path = dir.path().join("secrets.json");
+        assert!(std::fs::write(&path, r#"{"password": "it's-hunter2"}"#).is_ok());
+        let Ok(mut config) = PipelineConfig::parse("pipelines:\n  nightly: [parser]\n") else {
+            return;
+        };
+        config.secrets = crate::secrets::Secrets::load(&path).ok();
+        let command = expand_hook(&config, "login ${SECRET:password}", &[], "success");
+        assert!(command.is_ok_and(|command| logger::redact(&command) == "login ***"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn success_hook_runs_on_success() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let Some(config) = config_with_hooks(&dir) else {
+            return;
+        };
+        let status = run_hook(&config, &config.hooks, &[("pipeline", "nightly")], true);
+        assert!(status.is_some_and(|status| status.success()));
+        assert_eq!(marker(&dir), "nightly success");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failure_hook_runs_on_failure() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let Some(config) = config_with_hooks(&dir) else {
+            return;
+        };
+        run_pipeline_hook(&config, "nightly", false);
+        assert_eq!(marker(&dir), "nightly failure");
+
+        run_step_hook(&config, "nightly", "parser", false);
+        assert_eq!(marker(&dir), "parser failure");
+    }
+
+    #[test]
+    fn missing_hook_does_nothing() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let Some(config) = config_with_hooks(&dir) else {
+            return;
+        };
+        let hooks = config.step_hooks.get("parser").cloned().unwrap_or_default();
+        assert!(run_hook(&config, &hooks, &[("step", "parser")], true).is_none());
+        assert!(!dir.path().join("hook.txt").exists());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::commands::run::RunError;
+use serde_json::Value;
+use std::path::Path;
+use std::str::FromStr;
+
+/// How `r2x run` parses the `--input` seed file (`--input-format`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InputFormat {
+    /// Passed to the step as written
+    Json,
+    /// Converted to compact JSON before it reaches the step
+    Yaml,
+}
+
+impl InputFormat {
+    /// `yaml` for `.yaml`/`.yml` files, otherwise `json`
+    fn from_path(path: &str) -> Self {
+        match Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("yaml" | "yml") => Self::Yaml,
+            _ => Self::Json,
+        }
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            other => Err(format!(
+                "invalid input format '{}' (expected json or yaml)",
+                other
+            )),
+        }
+    }
+}
+
+/// Read `--input` as the JSON stdin of the seeded step
+///
+/// Without a format it is inferred from the extension. JSON must parse but
+/// is passed on unchanged; YAML is reserialized as compact JSON.
+pub(super) fn read_input_file(path: &str, format: Option<InputFormat>) -> Result<String, RunError> {
+    let contents = std::fs::read_to_string(Path::new(path))
+        .map_err(|e| RunError::InvalidArgs(format!("Failed to read --input '{}': {}", path, e)))?;
+    let invalid = |e: &dyn std::fmt::Display| {
+        RunError::InvalidArgs(format!("Failed to parse --input '{}': {}", path, e))
+    };
+
+    match format.unwrap_or_else(|| InputFormat::from_path(path)) {
+        InputFormat::Json => {
+            serde_json::from_str::<Value>(&contents).map_err(|e| invalid(&e))?;
+            Ok(contents)
+        }
+        InputFormat::Yaml => {
+            let value: Value = serde_yaml::from_str(&contents).map_err(|e| invalid(&e))?;
+            serde_json::to_string(&value).map_err(|e| invalid(&e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::input::*;
+    use std::io::Write;
+
+    fn seed_file(suffix: &str, contents: &str) -> Option<tempfile::NamedTempFile> {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().ok()?;
+        file.write_all(contents.as_bytes()).ok()?;
+        Some(file)
+    }
+
+    #[test]
+    fn json_and_yaml_seeds_produce_the_same_stdin() {
+        let Some(json) = seed_file(
+            ".json",
+            r#"{"system":{"buses":[{"name":"b1","voltage":230.0}]}}"#,
+        ) else {
+            return;
+        };
+        let Some(yaml) = seed_file(
+            ".yml",
+            "system:\n  buses:\n    - name: b1\n      voltage: 230.0\n",
+        ) else {
+            return;
+        };
+
+        let from_json = read_input_file(&json.path().to_string_lossy(), None);
+        let from_yaml = read_input_file(&yaml.path().to_string_lossy(), None);
+        assert!(from_json.is_ok());
+        assert_eq!(from_json.ok(), from_yaml.ok());
+    }
+
+    #[test]
+    fn explicit_format_overrides_the_extension() {
+        let Some(file) = seed_file(".txt", "system: {}\n") else {
+            return;
+        };
+        let path = file.path().to_string_lossy().to_string();
+
+        assert!(matches!(
+            read_input_file(&path, None),
+            Err(RunError::InvalidArgs(ref msg)) if msg.contains("Failed to parse --input")
+        ));
+        assert_eq!(
+            read_input_file(&path, Some(InputFormat::Yaml))
+                .ok()
+                .as_deref(),
+            Some(r#"{"system":{}}"#)
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::commands::run::pipeline::report::{PipelineRunReport, StepStatus};
+use crate::commands::run::pipeline::summary::{executed_step, summary_rows};
+use crate::pipeline_config::ResolvedStep;
+use serde_json::{json, Value};
+
+/// JSON written by `r2x run --keep-going-report`, for CI jobs to annotate failures from
+///
+/// Lists every pipeline step in order with its status (`ok`, `failed` or
+/// `skipped`), its error message when it failed, and its wall time in seconds
+/// (`null` for skipped steps).
+pub(super) fn keep_going_report(steps: &[ResolvedStep], report: &PipelineRunReport) -> Value {
+    let rows = summary_rows(steps, report);
+    let steps: Vec<Value> = steps
+        .iter()
+        .zip(rows)
+        .map(|(step, row)| {
+            let error = executed_step(report, step.position).and_then(|step| match &step.status {
+                StepStatus::Failed { error } => Some(error.clone()),
+                StepStatus::Succeeded => None,
+            });
+            json!({
+                "name": row.name,
+                "status": row.status.label(),
+                "error": error,
+                "duration_secs": row.wall.map(|wall| wall.as_secs_f64()),
+            })
+        })
+        .collect();
+
+    json!({
+        "pipeline": report.pipeline,
+        "succeeded": report.succeeded(),
+        "duration_secs": report.elapsed.as_secs_f64(),
+        "steps": steps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::keep_going::*;
+    use crate::commands::run::pipeline::report::StepReport;
+    use std::time::Duration;
+
+    fn step(name: &str, position: usize, status: StepStatus) -> StepReport {
+        StepReport {
+            name: name.to_string(),
+            position,
+            status,
+            elapsed: Duration::from_millis(500),
+            timings: None,
+        }
+    }
+
+    fn pipeline(names: &[&str]) -> Vec<ResolvedStep> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(position, name)| ResolvedStep {
+                name: (*name).to_string(),
+                position,
+                input: None,
+                input_position: None,
+                tags: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn report_records_a_failing_middle_step() {
+        let report = PipelineRunReport {
+            pipeline: "nightly".to_string(),
+            steps: vec![
+                step("parser", 0, StepStatus::Succeeded),
+                step(
+                    "break-gens",
+                    1,
+                    StepStatus::Failed {
+                        error: "no generators to break".to_string(),
+                    },
+                ),
+                step("exporter", 2, StepStatus::Succeeded),
+            ],
+            final_output: None,
+            elapsed: Duration::from_secs(2),
+        };
+        let json = keep_going_report(
+            &pipeline(&["parser", "break-gens", "exporter", "plots"]),
+            &report,
+        );
+        assert_eq!(json["pipeline"], "nightly");
+        assert_eq!(json["succeeded"], false);
+        assert_eq!(json["duration_secs"], 2.0);
+
+        let statuses: Vec<(&str, &str)> = json["steps"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|step| Some((step["name"].as_str()?, step["status"].as_str()?)))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("parser", "ok"),
+                ("break-gens", "failed"),
+                ("exporter", "ok"),
+                ("plots", "skipped"),
+            ]
+        );
+        assert_eq!(json["steps"][0]["error"], Value::Null);
+        assert_eq!(json["steps"][1]["error"], "no generators to break");
+        assert_eq!(json["steps"][1]["duration_secs"], 0.5);
+        assert_eq!(json["steps"][3]["duration_secs"], Value::Null);
+    }
+
+    #[test]
+    fn repeated_steps_report_their_own_status() {
+        let report = PipelineRunReport {
+            pipeline: "nightly".to_string(),
+            steps: vec![
+                step("check", 0, StepStatus::Succeeded),
+                step("parser", 1, StepStatus::Succeeded),
+                step(
+                    "check",
+                    2,
+                    StepStatus::Failed {
+                        error: "still broken".to_string(),
+                    },
+                ),
+            ],
+            final_output: None,
+            elapsed: Duration::from_secs(2),
+        };
+        let json = keep_going_report(&pipeline(&["check", "parser", "check"]), &report);
+        assert_eq!(json["steps"][0]["status"], "ok");
+        assert_eq!(json["steps"][0]["error"], Value::Null);
+        assert_eq!(json["steps"][2]["status"], "failed");
+        assert_eq!(json["steps"][2]["error"], "still broken");
+    }
+}
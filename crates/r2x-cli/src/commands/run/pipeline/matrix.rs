@@ -0,0 +1,150 @@
+use crate::commands::run::RunError;
+use crate::pipeline_config::PipelineConfig;
+use colored::Colorize;
+
+/// Result of one combination of `r2x run --matrix`
+#[derive(Debug)]
+pub(super) struct MatrixOutcome {
+    /// The combination's variables, e.g. `scenario=high, year=2030`
+    pub label: String,
+    pub error: Option<String>,
+}
+
+/// Run `run` once per `matrix` combination, one after another.
+///
+/// Each run sees a config with the combination set as variables, so they
+/// reach the step configs and `output_folder`. A failed combination does
+/// not stop the rest, except for a timeout or memory limit: the abandoned
+/// step may still be running in this process, so those end the whole run.
+pub(super) fn run_matrix<F>(
+    config: &PipelineConfig,
+    mut run: F,
+) -> Result<Vec<MatrixOutcome>, RunError>
+where
+    F: FnMut(&PipelineConfig) -> Result<(), RunError>,
+{
+    let combinations = config.matrix_combinations();
+    if combinations.is_empty() {
+        return Err(RunError::InvalidArgs(
+            "--matrix needs a `matrix:` section with at least one value per variable".to_string(),
+        ));
+    }
+
+    let mut outcomes = Vec::with_capacity(combinations.len());
+    for combination in &combinations {
+        let label = combination
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value_label(value)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let error = match run(&config.with_variables(combination)) {
+            Ok(()) => None,
+            Err(e @ (RunError::StepTimedOut { .. } | RunError::MemoryLimitExceeded { .. })) => {
+                return Err(e)
+            }
+            Err(e) => Some(e.to_string()),
+        };
+        outcomes.push(MatrixOutcome { label, error });
+    }
+    Ok(outcomes)
+}
+
+fn value_label(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// One `ok`/`failed` line per combination
+pub(super) fn render_matrix_report(outcomes: &[MatrixOutcome]) -> String {
+    let mut report = format!("\n{}\n", "Matrix runs:".bold());
+    for outcome in outcomes {
+        match &outcome.error {
+            None => report.push_str(&format!("  {}  {}\n", "ok    ".green(), outcome.label)),
+            Some(error) => report.push_str(&format!(
+                "  {}  {}: {}\n",
+                "failed".red().bold(),
+                outcome.label,
+                error
+            )),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::matrix::*;
+    use crate::commands::run::pipeline::resolved::resolve_output_folder;
+
+    #[test]
+    fn each_combination_runs_with_its_own_output_folder() {
+        let yaml = "matrix:\n  scenario: [base, high, low]\noutput_folder: out/${scenario}\npipelines:\n  demo: [parser]\n";
+        let config = PipelineConfig::parse(yaml);
+        assert!(config.is_ok());
+        let Ok(config) = config else {
+            return;
+        };
+
+        let mut folders = Vec::new();
+        let outcomes = run_matrix(&config, |config| {
+            let folder = resolve_output_folder(config, "demo")?;
+            folders.push(folder.clone());
+            if folder.as_deref() == Some("out/high") {
+                return Err(RunError::StepsFailed(vec!["parser".to_string()]));
+            }
+            Ok(())
+        });
+        assert!(outcomes.is_ok());
+        let Ok(outcomes) = outcomes else {
+            return;
+        };
+
+        assert_eq!(
+            folders,
+            [
+                Some("out/base".to_string()),
+                Some("out/high".to_string()),
+                Some("out/low".to_string())
+            ]
+        );
+        let report = render_matrix_report(&outcomes);
+        assert!(report.contains("scenario=base"));
+        assert!(report.contains("scenario=high: 1 pipeline step(s) failed: parser"));
+        assert!(outcomes[2].error.is_none());
+    }
+
+    #[test]
+    fn timeouts_end_the_matrix() {
+        let config = PipelineConfig::parse(
+            "matrix:\n  scenario: [base, high, low]\npipelines:\n  demo: [parser]\n",
+        );
+        assert!(config.is_ok());
+        let Ok(config) = config else {
+            return;
+        };
+
+        let mut runs = 0;
+        let result = run_matrix(&config, |_| {
+            runs += 1;
+            Err(RunError::StepTimedOut {
+                step: "parser".to_string(),
+                timeout_secs: 1,
+            })
+        });
+        assert!(matches!(result, Err(RunError::StepTimedOut { .. })));
+        assert_eq!(runs, 1);
+    }
+
+    #[test]
+    fn matrix_is_required() {
+        let config = PipelineConfig::parse("pipelines:\n  demo: [parser]\n");
+        assert!(config.is_ok_and(|config| matches!(
+            run_matrix(&config, |_| Ok(())),
+            Err(RunError::InvalidArgs(_))
+        )));
+    }
+}
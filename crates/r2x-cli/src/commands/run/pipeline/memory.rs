@@ -0,0 +1,137 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// How often the monitor samples the process RSS
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+pub(super) const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Background thread that watches resident memory during a run (`--max-memory`)
+///
+/// Fires `on_exceeded` once, with the offending RSS in bytes, the first time a
+/// sample goes over the limit. The monitor stops when dropped.
+pub(super) struct MemoryMonitor {
+    stop: Arc<AtomicBool>,
+    /// RSS in bytes at the sample that crossed the limit; 0 while under it
+    exceeded_at: Arc<AtomicU64>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MemoryMonitor {
+    /// Watch this process's RSS against `limit_mb`.
+    pub(super) fn start<F>(limit_mb: u64, on_exceeded: F) -> Self
+    where
+        F: FnOnce(u64) + Send + 'static,
+    {
+        let pid = sysinfo::get_current_pid().ok();
+        let mut system = System::new();
+        let sample = move || {
+            let pid = pid?;
+            system.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[pid]),
+                false,
+                ProcessRefreshKind::nothing().with_memory(),
+            );
+            system.process(pid).map(sysinfo::Process::memory)
+        };
+        Self::start_with(
+            limit_mb.saturating_mul(BYTES_PER_MB),
+            POLL_INTERVAL,
+            sample,
+            on_exceeded,
+        )
+    }
+
+    /// Poll `sample` for an RSS in bytes every `interval` until it exceeds `limit_bytes`.
+    fn start_with<S, F>(limit_bytes: u64, interval: Duration, mut sample: S, on_exceeded: F) -> Self
+    where
+        S: FnMut() -> Option<u64> + Send + 'static,
+        F: FnOnce(u64) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let exceeded_at = Arc::new(AtomicU64::new(0));
+
+        let handle = {
+            let stop = Arc::clone(&stop);
+            let exceeded_at = Arc::clone(&exceeded_at);
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::SeqCst) {
+                    if let Some(rss) = sample().filter(|rss| *rss > limit_bytes) {
+                        exceeded_at.store(rss, Ordering::SeqCst);
+                        on_exceeded(rss);
+                        return;
+                    }
+                    std::thread::park_timeout(interval);
+                }
+            })
+        };
+
+        Self {
+            stop,
+            exceeded_at,
+            handle: Some(handle),
+        }
+    }
+
+    /// RSS in bytes at the moment the limit was crossed, if it has been
+    pub(super) fn exceeded(&self) -> Option<u64> {
+        match self.exceeded_at.load(Ordering::SeqCst) {
+            0 => None,
+            rss => Some(rss),
+        }
+    }
+}
+
+impl Drop for MemoryMonitor {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::memory::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn callback_fires_once_when_rss_exceeds_limit() {
+        let mut samples = vec![10, 50, 120, 200].into_iter();
+        let (tx, rx) = mpsc::channel();
+        let monitor = MemoryMonitor::start_with(
+            100,
+            Duration::from_millis(1),
+            move || samples.next(),
+            move |rss| {
+                let _ = tx.send(rss);
+            },
+        );
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).ok(), Some(120));
+        assert_eq!(monitor.exceeded(), Some(120));
+        drop(monitor);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn no_callback_while_under_limit() {
+        let (tx, rx) = mpsc::channel();
+        let monitor = MemoryMonitor::start_with(
+            100,
+            Duration::from_millis(1),
+            || Some(99),
+            move |rss| {
+                let _ = tx.send(rss);
+            },
+        );
+
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+        assert_eq!(monitor.exceeded(), None);
+    }
+}
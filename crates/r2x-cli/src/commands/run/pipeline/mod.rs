@@ -1,53 +1,108 @@
-use crate::commands::run::RunError;
+use crate::commands::plugins::steps::{steps_since, upgrader_steps};
+use crate::commands::run::{verify_plugins, RunCommand, RunError};
 use crate::common::GlobalOpts;
 use crate::errors::PipelineError;
 use crate::manifest_lookup::{resolve_plugin_ref, PluginRefError};
 use crate::package_verification;
-use crate::pipeline_config::PipelineConfig;
+use crate::pipeline_config::{PipelineConfig, PipelineStep, ResolvedStep};
+use crate::secrets::Secrets;
 use colored::Colorize;
+use r2x_config::Config;
 use r2x_logger as logger;
-use r2x_manifest::runtime::build_runtime_bindings;
+use r2x_manifest::runtime::{ambiguous_step_order, build_runtime_bindings, PluginRole};
 use r2x_manifest::types::Manifest;
+use r2x_python::plugin_invoker::{PluginInvocationTimings, PluginOutcome};
 use r2x_python::python_bridge::Bridge;
-use std::time::Instant;
+use std::io::Write;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
+mod banner;
+mod benchmark;
 mod builder;
 mod config;
 mod constants;
+mod driver;
+mod explain;
+mod hooks;
+pub mod input;
+mod keep_going;
+mod matrix;
+mod memory;
+pub mod output;
 mod overrides;
+pub mod report;
+mod resolved;
+mod selection;
+mod store;
+mod summary;
+mod timeout;
 mod validation;
+mod working_dir;
 
+use banner::{finished_banner, running_banner};
+use benchmark::{render_stats, repeat_runs, TimingStats};
 use builder::build_plugin_config;
-use config::resolve_plugin_config_json;
+use config::{load_global_defaults, resolve_plugin_config_json};
+use driver::drive_steps;
+use explain::explain_pipeline;
+use hooks::{run_pipeline_hook, run_step_hook};
+use keep_going::keep_going_report;
+use matrix::{render_matrix_report, run_matrix};
+use memory::{MemoryMonitor, BYTES_PER_MB};
+use output::{format_output, write_output_atomically};
 use overrides::prepare_pipeline_overrides;
-use validation::validate_pipeline_configs;
+use report::{PipelineRunReport, RunFailure, RunOptions, StepStatus};
+use resolved::{override_output_folder, render_resolved_config, resolve_output_folder};
+use selection::{select_single_step, select_tagged_steps};
+use summary::render_summary;
+use timeout::call_with_timeout;
+use validation::{validate_pipeline_configs, validate_pipeline_plugins};
+use working_dir::WorkingDirGuard;
 
 pub(super) fn handle_pipeline_mode(
-    yaml_path: String,
-    pipeline_name: Option<String>,
-    list: bool,
-    print: bool,
-    dry_run: bool,
-    output: Option<String>,
+    yaml_path: &str,
+    cmd: &RunCommand,
+    venv_config: Option<&Config>,
     opts: &GlobalOpts,
 ) -> Result<(), RunError> {
-    let config = PipelineConfig::load(&yaml_path)?;
+    let mut config = PipelineConfig::load(yaml_path)?;
+    if !cmd.no_global_defaults {
+        load_global_defaults(&mut config)?;
+    }
+    config.secrets = Secrets::from_arg_or_env(cmd.secrets.as_deref().map(Path::new))?;
+    if let Some(folder) = cmd.set_output_folder.as_deref() {
+        override_output_folder(&mut config, folder)?;
+    }
 
-    if list {
+    if cmd.list {
         list_pipelines(&config);
-    } else if print {
-        if let Some(name) = pipeline_name {
-            print_pipeline_config(&config, &name)?;
+    } else if cmd.print {
+        if let Some(name) = &cmd.pipeline_name {
+            if cmd.resolved {
+                print_resolved_config(&config, name)?;
+            } else {
+                print_pipeline_config(&config, name)?;
+            }
         } else {
             return Err(RunError::InvalidArgs(
                 "Pipeline name required with --print".to_string(),
             ));
         }
-    } else if let Some(name) = pipeline_name {
-        if dry_run {
-            show_pipeline_flow(&config, &name)?;
+    } else if let Some(name) = &cmd.pipeline_name {
+        let pipeline = config.pipeline_steps(name)?;
+        let manifest = Manifest::load()?;
+        validate_pipeline_plugins(&pipeline, &manifest)?;
+
+        if cmd.dry_run {
+            show_pipeline_flow(&config, name, &manifest, cmd.since.as_deref())?;
+        } else if cmd.explain {
+            explain_pipeline(&config, name, &manifest)?;
+        } else if cmd.matrix {
+            run_matrix_pipeline(&config, name, &manifest, cmd, venv_config, opts)?;
         } else {
-            run_pipeline(&config, &name, output.as_deref(), opts)?;
+            run_pipeline(&config, name, &manifest, cmd, venv_config, opts)?;
         }
     } else {
         return Err(RunError::InvalidArgs(
@@ -59,6 +114,12 @@ pub(super) fn handle_pipeline_mode(
 }
 
 fn list_pipelines(config: &PipelineConfig) {
+    if let Err(errors) = config.validate() {
+        for error in errors {
+            logger::warn(&error.to_string());
+        }
+    }
+
     let pipelines = config.list_pipelines();
 
     if pipelines.is_empty() {
@@ -71,7 +132,12 @@ fn list_pipelines(config: &PipelineConfig) {
         if let Some(steps) = config.get_pipeline(&name) {
             println!("  {} ({} steps)", name, steps.len());
             for step in steps {
-                println!("    - {}", step);
+                match step {
+                    PipelineStep::Node { name, requires, .. } if !requires.is_empty() => {
+                        println!("    - {} (requires: {})", name, requires.join(", "));
+                    }
+                    _ => println!("    - {}", step.name()),
+                }
             }
         }
     }
@@ -79,30 +145,46 @@ fn list_pipelines(config: &PipelineConfig) {
 
 fn print_pipeline_config(config: &PipelineConfig, pipeline_name: &str) -> Result<(), RunError> {
     let output = config.print_pipeline_config(pipeline_name)?;
-    println!("{}", output);
+    println!("{}", logger::redact(&output));
     Ok(())
 }
 
-fn show_pipeline_flow(config: &PipelineConfig, pipeline_name: &str) -> Result<(), RunError> {
-    let pipeline = config
-        .get_pipeline(pipeline_name)
-        .ok_or_else(|| PipelineError::PipelineNotFound(pipeline_name.to_string()))?;
-
+fn print_resolved_config(config: &PipelineConfig, pipeline_name: &str) -> Result<(), RunError> {
+    let pipeline = config.pipeline_steps(pipeline_name)?;
     let manifest = Manifest::load()?;
+    validate_pipeline_plugins(&pipeline, &manifest)?;
+    let output = render_resolved_config(config, pipeline_name, &manifest)?;
+    println!("{}", logger::redact(&output));
+    Ok(())
+}
+
+fn show_pipeline_flow(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    manifest: &Manifest,
+    since: Option<&str>,
+) -> Result<(), RunError> {
+    let pipeline = config.resolve_pipeline(pipeline_name)?;
 
     logger::success(&format!("Pipeline: {}", pipeline_name));
     println!("\nPipeline flow (--dry-run):");
 
-    for (index, plugin_name) in pipeline.iter().enumerate() {
-        let resolved = resolve_plugin_ref(&manifest, plugin_name).map_err(|err| match err {
+    for step in &pipeline {
+        let plugin_name = &step.name;
+        let resolved = resolve_plugin_ref(manifest, plugin_name).map_err(|err| match err {
             PluginRefError::NotFound(_) => RunError::PluginNotFound(plugin_name.clone()),
-            PluginRefError::Ambiguous { .. } => RunError::Config(err.to_string()),
+            PluginRefError::Ambiguous { .. } | PluginRefError::Duplicate { .. } => {
+                RunError::Config(err.to_string())
+            }
         })?;
         let plugin = resolved.plugin;
 
         // Check if it's a class-based plugin
         let is_class = plugin.class_name.is_some();
-        let input_marker = if index > 0 { "← stdin" } else { "" };
+        let input_marker = match &step.input {
+            Some(input) => format!("← {}", input),
+            None => String::new(),
+        };
         let output_marker = if is_class { "→ stdout" } else { "" };
 
         print!("  {}", plugin_name);
@@ -113,6 +195,26 @@ fn show_pipeline_flow(config: &PipelineConfig, pipeline_name: &str) -> Result<()
             print!("  {}", output_marker.dimmed());
         }
         println!();
+
+        if let Ok(steps) = upgrader_steps(plugin) {
+            for warning in ambiguous_step_order(&plugin.registrations) {
+                logger::warn(&format!("{}: {}", plugin_name, warning));
+            }
+            for step in since
+                .map(|since| steps_since(steps, since))
+                .unwrap_or_default()
+            {
+                println!(
+                    "      {} {}",
+                    step.function,
+                    format!(
+                        "(target {})",
+                        step.target_version.as_deref().unwrap_or("any")
+                    )
+                    .dimmed()
+                );
+            }
+        }
     }
 
     println!(
@@ -123,185 +225,561 @@ fn show_pipeline_flow(config: &PipelineConfig, pipeline_name: &str) -> Result<()
     Ok(())
 }
 
+/// Run `pipeline_name` once per `matrix` combination, then report each one
+fn run_matrix_pipeline(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    manifest: &Manifest,
+    cmd: &RunCommand,
+    venv_config: Option<&Config>,
+    opts: &GlobalOpts,
+) -> Result<(), RunError> {
+    let outcomes = run_matrix(config, |config| {
+        run_pipeline(config, pipeline_name, manifest, cmd, venv_config, opts)
+    })?;
+    eprint!("{}", render_matrix_report(&outcomes));
+
+    let failed: Vec<String> = outcomes
+        .into_iter()
+        .filter(|outcome| outcome.error.is_some())
+        .map(|outcome| outcome.label)
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(RunError::MatrixRunsFailed(failed))
+    }
+}
+
 fn run_pipeline(
     config: &PipelineConfig,
     pipeline_name: &str,
-    output_file: Option<&str>,
+    manifest: &Manifest,
+    cmd: &RunCommand,
+    venv_config: Option<&Config>,
     opts: &GlobalOpts,
 ) -> Result<(), RunError> {
-    let pipeline = config
-        .get_pipeline(pipeline_name)
-        .ok_or_else(|| PipelineError::PipelineNotFound(pipeline_name.to_string()))?;
+    let quiet = opts.quiet > 0;
+    eprint!(
+        "{}",
+        running_banner(pipeline_name, logger::get_log_path().as_deref(), quiet)
+    );
+
+    let run_options = RunOptions::from_command(cmd, venv_config.cloned());
+    let result = match cmd.repeat {
+        Some(0) => {
+            return Err(RunError::InvalidArgs(
+                "--repeat needs at least 1 run".to_string(),
+            ))
+        }
+        // Only the last run's system is written out; earlier outputs are dropped.
+        Some(times) => repeat_runs(times, |_| {
+            execute_pipeline(config, pipeline_name, manifest, &run_options, opts)
+        })
+        .map(|outcome| {
+            if let Some(stats) = TimingStats::from_samples(&outcome.samples) {
+                eprint!("{}", render_stats(pipeline_name, &stats));
+            }
+            outcome.last
+        }),
+        None => execute_pipeline(config, pipeline_name, manifest, &run_options, opts),
+    };
+    let report = match result {
+        Ok(report) => report,
+        Err(failure) => {
+            if let Some(partial) = &failure.partial {
+                write_step_reports(config, pipeline_name, cmd, &run_options, partial)?;
+            }
+            return Err(failure.error);
+        }
+    };
+    write_step_reports(config, pipeline_name, cmd, &run_options, &report)?;
+
+    eprint!("{}", finished_banner(report.elapsed, quiet));
+
+    if let Some(final_output) = &report.final_output {
+        let final_output = format_output(final_output, cmd.format);
+        let no_stdout = opts.no_stdout || logger::get_no_stdout();
+        if let Some(output_path) = cmd.output.as_deref() {
+            logger::step(&format!("Writing output to: {}", output_path));
+            write_output_atomically(Path::new(output_path), |file| {
+                file.write_all(final_output.as_bytes())
+            })
+            .map_err(|e| RunError::Pipeline(PipelineError::Io(e)))?;
+            logger::success(&format!("Output saved to: {}", output_path));
+        } else if opts.suppress_stdout() || no_stdout {
+            logger::debug("Pipeline output suppressed");
+        } else {
+            println!("{}", final_output);
+        }
+    }
+
+    if !report.succeeded() {
+        let mut failed = Vec::new();
+        for step in report.failed_steps() {
+            if let StepStatus::Failed { error } = &step.status {
+                logger::error(&format!("{}: {}", step.name, error));
+            }
+            failed.push(step.name.clone());
+        }
+        return Err(RunError::StepsFailed(failed));
+    }
+
+    Ok(())
+}
+
+/// Print the `--summary` table and write the `--keep-going-report` file for `report`.
+fn write_step_reports(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    cmd: &RunCommand,
+    run_options: &RunOptions,
+    report: &PipelineRunReport,
+) -> Result<(), RunError> {
+    if !run_options.summary && cmd.keep_going_report.is_none() {
+        return Ok(());
+    }
+    let steps = config.resolve_pipeline(pipeline_name)?;
+    if run_options.summary {
+        eprint!("{}", render_summary(&steps, report));
+    }
+    if let Some(report_path) = cmd.keep_going_report.as_deref() {
+        let rendered = serde_json::to_string_pretty(&keep_going_report(&steps, report))
+            .map_err(|e| RunError::Config(format!("Failed to serialize step report: {}", e)))?;
+        write_output_atomically(Path::new(report_path), |file| {
+            file.write_all(rendered.as_bytes())
+        })
+        .map_err(|e| RunError::Pipeline(PipelineError::Io(e)))?;
+        logger::info(&format!("Step report written to: {}", report_path));
+    }
+    Ok(())
+}
 
+/// Load the manifest and run `pipeline_name` for [`crate::commands::run::run_pipeline_api`].
+pub(super) fn run_pipeline_report(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    run_options: &RunOptions,
+) -> Result<PipelineRunReport, RunError> {
+    let pipeline = config.pipeline_steps(pipeline_name)?;
     let manifest = Manifest::load()?;
+    validate_pipeline_plugins(&pipeline, &manifest)?;
+    execute_pipeline(
+        config,
+        pipeline_name,
+        &manifest,
+        run_options,
+        &GlobalOpts::default(),
+    )
+    .map_err(|failure| failure.error)
+}
+
+/// Run a pipeline, then its `on_success` or `on_failure` hook.
+fn execute_pipeline(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    manifest: &Manifest,
+    run_options: &RunOptions,
+    opts: &GlobalOpts,
+) -> Result<PipelineRunReport, RunFailure> {
+    let result = execute_pipeline_steps(config, pipeline_name, manifest, run_options, opts);
+    let succeeded = result.as_ref().is_ok_and(PipelineRunReport::succeeded);
+    run_pipeline_hook(config, pipeline_name, succeeded);
+    result
+}
+
+/// Import `r2x_core` and the plugin module of each step about to run
+///
+/// Each module is imported from inside its step's `working_dir` and with its
+/// `python_path` entries on `sys.path`, as the step itself would, and only
+/// once however many steps share it. A step whose paths can't be set up is
+/// skipped; it reports the error when it runs.
+fn warm_up_steps(
+    bridge: &Bridge,
+    config: &PipelineConfig,
+    manifest: &Manifest,
+    steps: &[ResolvedStep],
+) {
+    bridge.warm_up(&["r2x_core"]);
+    let mut warmed: Vec<String> = Vec::new();
+    for step in steps {
+        let Ok(resolved) = resolve_plugin_ref(manifest, &step.name) else {
+            continue;
+        };
+        let module = resolved.plugin.module.to_string();
+        if module.is_empty() || warmed.contains(&module) {
+            continue;
+        }
+        let Ok(working_dir) = config.get_working_dir(&step.name) else {
+            continue;
+        };
+        let Ok(python_path) = config.get_python_path(&step.name) else {
+            continue;
+        };
+        let _working_dir_guard = match working_dir {
+            Some(dir) => match WorkingDirGuard::enter(&dir) {
+                Ok(guard) => Some(guard),
+                Err(e) => {
+                    logger::debug(&format!("Not preimporting {}: {}", module, e));
+                    continue;
+                }
+            },
+            None => None,
+        };
+        let _python_path_guard = if python_path.is_empty() {
+            None
+        } else {
+            match bridge.extend_sys_path(&python_path) {
+                Ok(guard) => Some(guard),
+                Err(e) => {
+                    logger::debug(&format!("Not preimporting {}: {}", module, e));
+                    continue;
+                }
+            }
+        };
+        bridge.warm_up(&[module.as_str()]);
+        warmed.push(module);
+    }
+}
+
+/// Resolve, verify and run a pipeline, returning per-step results without printing them.
+///
+/// A step error that stops the run comes back with the steps that ran before it.
+fn execute_pipeline_steps(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    manifest: &Manifest,
+    run_options: &RunOptions,
+    opts: &GlobalOpts,
+) -> Result<PipelineRunReport, RunFailure> {
+    // With a timeout the step runs on a worker thread, which the memory
+    // guard's interrupt of the main thread would never reach.
+    if run_options.max_memory_mb.is_some() && run_options.plugin_timeout.is_some() {
+        return Err(RunError::InvalidArgs(
+            "--max-memory can't be combined with --plugin-timeout".to_string(),
+        )
+        .into());
+    }
+
+    let mut pipeline = config.resolve_pipeline(pipeline_name)?;
+
+    // Stdin for the first step executed; only set by `--step` with `--input`.
+    let mut seed_input: Option<Rc<str>> = None;
+    if let Some(step) = run_options.step {
+        let (selected, stdin) = select_single_step(
+            pipeline,
+            step,
+            run_options.input.as_deref(),
+            run_options.input_format,
+        )?;
+        logger::info(&format!("Running only step {}: {}", step, selected.name));
+        seed_input = stdin.map(Rc::from);
+        pipeline = vec![selected];
+    }
+    if !run_options.tags.is_empty() {
+        pipeline = select_tagged_steps(pipeline, &run_options.tags);
+        if pipeline.is_empty() {
+            return Ok(PipelineRunReport {
+                pipeline: pipeline_name.to_string(),
+                steps: Vec::new(),
+                final_output: None,
+                elapsed: Duration::ZERO,
+            });
+        }
+    }
+    let step_names: Vec<String> = pipeline.iter().map(|step| step.name.clone()).collect();
+
     let total_steps = pipeline.len();
 
     logger::debug("Verifying packages for pipeline...");
-    for plugin_name in pipeline {
-        package_verification::verify_and_ensure_plugin(&manifest, plugin_name)
-            .map_err(|e| RunError::Verification(e.to_string()))?;
-    }
-    logger::debug("All pipeline packages verified");
+    verify_plugins(
+        step_names.iter().map(String::as_str),
+        run_options.skip_verification,
+        |plugin_name| {
+            package_verification::verify_and_ensure_plugin(
+                manifest,
+                plugin_name,
+                run_options.venv_config.as_ref(),
+            )
+        },
+    )?;
 
     // Validate all plugin configs upfront before running anything
     logger::debug("Validating pipeline configs...");
-    validate_pipeline_configs(config, pipeline, &manifest)?;
+    validate_pipeline_configs(config, &step_names, manifest)?;
     logger::debug("All pipeline configs validated");
 
+    // Import r2x-core and the plugin modules up front so the first step doesn't pay for them
+    match Bridge::get() {
+        Ok(bridge) => warm_up_steps(bridge, config, manifest, &pipeline),
+        Err(e) => logger::debug(&format!("Skipping Python warm-up: {}", e)),
+    }
+
     let pipeline_start = Instant::now();
-    eprintln!("{}", format!("Running: {}", pipeline_name).cyan().bold());
 
-    // Show log file location to user
-    if let Some(log_path) = logger::get_log_path() {
-        eprintln!("{}", format!("  Log file: {}", log_path.display()).dimmed());
-    }
+    let resolved_output_folder = resolve_output_folder(config, pipeline_name)?;
 
-    let mut current_stdin: Option<String> = None;
+    let failure_mode = run_options.failure_mode;
+    logger::info(&format!("Failure mode: {}", failure_mode.describe()));
 
-    let resolved_output_folder = if let Some(folder) = &config.output_folder {
-        Some(
-            config
-                .substitute_string(folder)
-                .map_err(RunError::Pipeline)?,
-        )
-    } else {
-        None
+    let ctx = StepContext {
+        config,
+        manifest,
+        opts,
+        output_folder: resolved_output_folder.as_deref(),
+        total_steps,
+        plugin_timeout: run_options.plugin_timeout,
     };
+    let memory_guard = run_options.max_memory_mb.map(|limit_mb| {
+        logger::debug(&format!("Memory limit: {} MB", limit_mb));
+        let monitor = MemoryMonitor::start(limit_mb, move |rss| {
+            logger::error(&format!(
+                "Memory limit of {} MB exceeded ({} MB in use); aborting the active step",
+                limit_mb,
+                rss / BYTES_PER_MB
+            ));
+            if let Err(e) = Bridge::interrupt_main() {
+                logger::warn(&format!("Failed to interrupt plugin: {}", e));
+            }
+        });
+        (limit_mb, monitor)
+    });
 
     let mut current_store_path: Option<String> = None;
-
-    for (idx, plugin_name) in pipeline.iter().enumerate() {
-        let step_num = idx + 1;
-        logger::spinner_start(&format!("  {} [{}/{}]", plugin_name, step_num, total_steps));
-        let step_start = Instant::now();
-
-        let resolved = resolve_plugin_ref(&manifest, plugin_name).map_err(|err| match err {
-            PluginRefError::NotFound(_) => RunError::PluginNotFound(plugin_name.clone()),
-            PluginRefError::Ambiguous { .. } => RunError::Config(err.to_string()),
-        })?;
-        let pkg = resolved.package;
-        let plugin = resolved.plugin;
-
-        let bindings = build_runtime_bindings(plugin);
-
-        let yaml_config = resolve_plugin_config_json(config, plugin_name, &resolved)?;
-
-        if let Ok(serde_json::Value::Object(map)) =
-            serde_json::from_str::<serde_json::Value>(&yaml_config)
-        {
-            if let Some(store_path) = map.get("store_path").and_then(|value| value.as_str()) {
-                current_store_path = Some(store_path.to_string());
+    let mut step_timings: Vec<Option<PluginInvocationTimings>> = vec![None; total_steps];
+    let mut outcome = drive_steps(&pipeline, failure_mode, seed_input, |idx, step, stdin| {
+        let mut result = execute_step(&ctx, &mut current_store_path, idx, step, stdin).map(
+            |(output, timings)| {
+                step_timings[idx] = timings;
+                output
+            },
+        );
+        // The interrupt surfaces as a plugin error; report it as the memory limit instead.
+        if let Some((limit_mb, monitor)) = &memory_guard {
+            if let Some(rss) = monitor.exceeded() {
+                result = Err(RunError::MemoryLimitExceeded {
+                    step: step.name.clone(),
+                    limit_mb: *limit_mb,
+                    rss_mb: rss / BYTES_PER_MB,
+                });
             }
         }
+        run_step_hook(config, pipeline_name, &step.name, result.is_ok());
+        result
+    });
+    for (report, timings) in outcome.steps.iter_mut().zip(step_timings) {
+        report.timings = timings;
+    }
 
-        let pipeline_input = current_stdin.as_deref();
-        let stdin_json = pipeline_input;
+    let report = PipelineRunReport {
+        pipeline: pipeline_name.to_string(),
+        steps: outcome.steps,
+        final_output: outcome.final_output.map(|output| output.to_string()),
+        elapsed: pipeline_start.elapsed(),
+    };
+    match outcome.aborted {
+        Some(error) => Err(RunFailure {
+            error,
+            partial: Some(report),
+        }),
+        None => Ok(report),
+    }
+}
 
-        let pipeline_overrides =
-            prepare_pipeline_overrides(pipeline_input, &bindings, plugin_name)?;
+/// Per-run state shared by every step
+struct StepContext<'a> {
+    config: &'a PipelineConfig,
+    manifest: &'a Manifest,
+    opts: &'a GlobalOpts,
+    output_folder: Option<&'a str>,
+    total_steps: usize,
+    plugin_timeout: Option<Duration>,
+}
 
-        let final_config_json = build_plugin_config(
-            &bindings,
-            &pkg.name,
-            &yaml_config,
-            resolved_output_folder.as_deref(),
-            current_store_path.as_deref(),
-            pipeline_overrides.as_deref(),
-        )?;
+/// Run one pipeline step and return the output it hands downstream, with the plugin's timings.
+fn execute_step(
+    ctx: &StepContext<'_>,
+    current_store_path: &mut Option<String>,
+    idx: usize,
+    step: &ResolvedStep,
+    current_stdin: Option<Rc<str>>,
+) -> Result<(Option<Rc<str>>, Option<PluginInvocationTimings>), RunError> {
+    let StepContext {
+        config,
+        manifest,
+        opts,
+        output_folder,
+        total_steps,
+        plugin_timeout,
+    } = *ctx;
+
+    let plugin_name = &step.name;
+    let step_num = idx + 1;
+    logger::spinner_start(&format!("  {} [{}/{}]", plugin_name, step_num, total_steps));
+    let step_start = Instant::now();
+
+    // Held until the step returns so the previous CWD is restored on every exit path.
+    let working_dir_guard = match config.get_working_dir(plugin_name)? {
+        Some(dir) => {
+            logger::debug(&format!("Working directory: {}", dir.display()));
+            Some(WorkingDirGuard::enter(&dir)?)
+        }
+        None => None,
+    };
 
-        let target = crate::commands::run::build_call_target(&bindings)?;
-        let bridge = Bridge::get()?;
-        logger::debug(&format!("Invoking: {}", target));
+    let resolved = resolve_plugin_ref(manifest, plugin_name).map_err(|err| match err {
+        PluginRefError::NotFound(_) => RunError::PluginNotFound(plugin_name.clone()),
+        PluginRefError::Ambiguous { .. } | PluginRefError::Duplicate { .. } => {
+            RunError::Config(err.to_string())
+        }
+    })?;
+    let plugin = resolved.plugin;
+
+    let bindings = build_runtime_bindings(plugin);
+
+    let step_config = resolve_plugin_config_json(
+        config,
+        plugin_name,
+        &resolved,
+        current_store_path.as_deref(),
+        output_folder,
+    )?;
+    *current_store_path = step_config.store.declared().map(str::to_string);
+
+    let pipeline_input = current_stdin.as_deref();
+    let stdin_json = pipeline_input;
+
+    let pipeline_overrides = prepare_pipeline_overrides(pipeline_input, &bindings, plugin_name)?;
+
+    let final_config_json = build_plugin_config(
+        &bindings,
+        &step_config.json,
+        &step_config.store,
+        pipeline_overrides.as_deref(),
+    )?;
+
+    let target = crate::commands::run::build_call_target(&bindings)?;
+    let bridge = Bridge::get()?;
+    let python_path = config.get_python_path(plugin_name)?;
+    // Like the working directory, held until the step returns.
+    let python_path_guard = if python_path.is_empty() {
+        None
+    } else {
+        logger::debug(&format!("Extra sys.path entries: {:?}", python_path));
+        Some(bridge.extend_sys_path(&python_path)?)
+    };
+    logger::debug(&format!("Invoking: {}", target));
 
-        // Set current plugin context for logging
-        logger::set_current_plugin(Some(plugin_name.clone()));
+    // Set current plugin context for logging
+    logger::set_current_plugin(Some(plugin_name.clone()));
 
-        // Reconfigure Python logging with plugin name
-        if let Err(e) = Bridge::reconfigure_logging_for_plugin(plugin_name) {
-            logger::warn(&format!(
-                "Failed to reconfigure Python logging for plugin {}: {}",
-                plugin_name, e
-            ));
-        }
+    // Reconfigure Python logging with plugin name
+    if let Err(e) = Bridge::reconfigure_logging_for_plugin(plugin_name) {
+        logger::warn(&format!(
+            "Failed to reconfigure Python logging for plugin {}: {}",
+            plugin_name, e
+        ));
+    }
 
-        let invocation_result = match bridge.invoke_plugin_with_bindings(
+    let invocation = match plugin_timeout {
+        Some(timeout) => {
+            let stdin_json = stdin_json.map(str::to_string);
+            let bindings = bindings.clone();
+            call_with_timeout(timeout, move || {
+                bridge.invoke_plugin_with_bindings(
+                    &target,
+                    &final_config_json,
+                    stdin_json.as_deref(),
+                    Some(&bindings),
+                )
+            })
+        }
+        None => Ok(bridge.invoke_plugin_with_bindings(
             &target,
             &final_config_json,
             stdin_json,
             Some(&bindings),
-        ) {
-            Ok(inv_result) => {
-                let elapsed = step_start.elapsed();
-                logger::spinner_success(&format!(
-                    "{} [{}/{}] ({})",
-                    plugin_name,
-                    step_num,
-                    total_steps,
-                    crate::commands::run::format_duration(elapsed)
-                ));
-                if logger::get_verbosity() > 0 {
-                    if let Some(timings) = &inv_result.timings {
-                        crate::commands::run::print_plugin_timing_breakdown(timings);
-                    }
-                }
-                inv_result
-            }
-            Err(e) => {
-                let elapsed = step_start.elapsed();
-                logger::spinner_error(&format!(
-                    "{} [{}/{}] ({})",
-                    plugin_name,
-                    step_num,
-                    total_steps,
-                    crate::commands::run::format_duration(elapsed)
-                ));
-                // Clear plugin context before returning error
-                logger::set_current_plugin(None);
-                return Err(RunError::Bridge(e));
-            }
-        };
-
-        // Clear plugin context after execution
+        )),
+    };
+    let Ok(invocation) = invocation else {
+        // The abandoned worker may still import from these entries or read
+        // relative paths; restoring sys.path or the CWD now would race with
+        // it, so leave both in place.
+        std::mem::forget(python_path_guard);
+        std::mem::forget(working_dir_guard);
+        logger::spinner_error(&format!(
+            "{} [{}/{}] (timed out)",
+            plugin_name, step_num, total_steps
+        ));
         logger::set_current_plugin(None);
+        return Err(RunError::StepTimedOut {
+            step: plugin_name.clone(),
+            timeout_secs: plugin_timeout.map_or(0, |timeout| timeout.as_secs()),
+        });
+    };
 
-        let result = invocation_result.output;
-        let no_stdout = opts.no_stdout || logger::get_no_stdout();
-
-        if !result.is_empty() && result != "null" {
-            if no_stdout {
-                logger::debug("Plugin produced output (suppressed by --no-stdout)");
-            } else {
-                logger::debug(&format!("Plugin produced output ({} bytes)", result.len()));
+    let invocation_result = match invocation {
+        Ok(inv_result) => {
+            let elapsed = step_start.elapsed();
+            logger::spinner_success(&format!(
+                "{} [{}/{}] ({})",
+                plugin_name,
+                step_num,
+                total_steps,
+                crate::commands::run::format_duration(elapsed)
+            ));
+            if logger::get_verbosity() > 0 {
+                if let Some(timings) = &inv_result.timings {
+                    crate::commands::run::print_plugin_timing_breakdown(timings);
+                }
             }
-            current_stdin = Some(result);
-        } else {
-            logger::debug("Plugin produced no output or output not used");
+            inv_result
         }
-    }
+        Err(e) => {
+            let elapsed = step_start.elapsed();
+            logger::spinner_error(&format!(
+                "{} [{}/{}] ({})",
+                plugin_name,
+                step_num,
+                total_steps,
+                crate::commands::run::format_duration(elapsed)
+            ));
+            // Clear plugin context before returning error
+            logger::set_current_plugin(None);
+            return Err(RunError::Bridge(e));
+        }
+    };
 
-    eprintln!(
-        "{}",
-        format!(
-            "Finished in: {}",
-            crate::commands::run::format_duration(pipeline_start.elapsed())
-        )
-        .green()
-        .bold()
-    );
+    // Clear plugin context after execution
+    logger::set_current_plugin(None);
 
-    if let Some(final_output) = current_stdin {
-        let no_stdout = opts.no_stdout || logger::get_no_stdout();
-        if let Some(output_path) = output_file {
-            logger::step(&format!("Writing output to: {}", output_path));
-            std::fs::write(output_path, final_output.as_bytes())
-                .map_err(|e| RunError::Pipeline(PipelineError::Io(e)))?;
-            logger::success(&format!("Output saved to: {}", output_path));
-        } else if opts.suppress_stdout() || no_stdout {
-            logger::debug("Pipeline output suppressed");
-        } else {
-            println!("{}", final_output);
+    let no_stdout = opts.no_stdout || logger::get_no_stdout();
+
+    let step_output = if bindings.role == PluginRole::Validator {
+        // Validators only report; the system flows through to the next step unchanged.
+        crate::commands::run::check_validation_output(plugin_name, &invocation_result.output)?;
+        current_stdin
+    } else {
+        match invocation_result.outcome {
+            PluginOutcome::Produced(result) => {
+                if no_stdout {
+                    logger::debug("Plugin produced output (suppressed by --no-stdout)");
+                } else {
+                    logger::debug(&format!("Plugin produced output ({} bytes)", result.len()));
+                }
+                Some(Rc::from(result))
+            }
+            PluginOutcome::NullReturn => {
+                logger::debug("Plugin returned None; keeping previous step output");
+                current_stdin
+            }
+            PluginOutcome::SideEffectOnly => {
+                // The step consumed the system (e.g. wrote files); nothing flows downstream.
+                logger::debug("Plugin completed without forwarding output");
+                None
+            }
         }
-    }
+    };
 
-    Ok(())
+    Ok((step_output, invocation_result.timings))
 }
@@ -0,0 +1,146 @@
+use serde_json::Value;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// How `r2x run` serializes a JSON final output (`--format`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// Indented, for inspection
+    Pretty,
+    /// No whitespace between tokens, for storage
+    Compact,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "compact" => Ok(Self::Compact),
+            other => Err(format!(
+                "invalid output format '{}' (expected pretty or compact)",
+                other
+            )),
+        }
+    }
+}
+
+/// Reserialize `output` in `format`
+///
+/// Keys keep the plugin's order. Without a format, or when the output is
+/// not JSON, it is returned as the plugin produced it.
+pub(super) fn format_output(output: &str, format: Option<OutputFormat>) -> String {
+    let Some(format) = format else {
+        return output.to_string();
+    };
+    let Ok(value) = serde_json::from_str::<Value>(output) else {
+        return output.to_string();
+    };
+    let rendered = match format {
+        OutputFormat::Pretty => serde_json::to_string_pretty(&value),
+        OutputFormat::Compact => serde_json::to_string(&value),
+    };
+    rendered.unwrap_or_else(|_| output.to_string())
+}
+
+/// Write `-o` output to a temp file beside `path`, renaming it into place once `write` succeeds
+///
+/// Readers of `path` never see a half-written file. If `write` fails or
+/// panics, the temp file is removed and `path` is left as it was. A signal
+/// or `process::exit` skips that cleanup, so a stray `.r2x-output-*` file
+/// can be left beside `path`; `path` itself is still untouched.
+pub(super) fn write_output_atomically<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut File) -> io::Result<()>,
+{
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut temp = tempfile::Builder::new()
+        .prefix(".r2x-output-")
+        .tempfile_in(dir)?;
+    write(temp.as_file_mut())?;
+    temp.as_file_mut().flush()?;
+    temp.as_file().sync_all()?;
+    // Temp files are private to the user; give the output the usual permissions
+    if let Ok(existing) = std::fs::metadata(path) {
+        temp.as_file().set_permissions(existing.permissions())?;
+    } else {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            temp.as_file()
+                .set_permissions(std::fs::Permissions::from_mode(0o644))?;
+        }
+    }
+    temp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::output::*;
+
+    const SYSTEM: &str = "{ \"name\": \"reeds\",\n  \"buses\": [ 1, 2 ] }";
+
+    #[test]
+    fn compact_output_has_no_extra_whitespace() {
+        assert_eq!(
+            format_output(SYSTEM, Some(OutputFormat::Compact)),
+            r#"{"name":"reeds","buses":[1,2]}"#
+        );
+    }
+
+    #[test]
+    fn pretty_output_is_indented() {
+        let pretty = format_output(SYSTEM, Some(OutputFormat::Pretty));
+        assert!(pretty.starts_with("{\n  \"name\": \"reeds\",\n  \"buses\": [\n"));
+        assert!(pretty.ends_with("\n  ]\n}"));
+        assert!(pretty.contains("\n    1,\n"));
+    }
+
+    #[test]
+    fn output_is_unchanged_without_a_format_or_json() {
+        assert_eq!(format_output(SYSTEM, None), SYSTEM);
+        assert_eq!(
+            format_output("not json", Some(OutputFormat::Compact)),
+            "not json"
+        );
+        assert_eq!("Compact".parse(), Ok(OutputFormat::Compact));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn atomic_write_replaces_the_target_on_success() {
+        let Ok(dir) = tempfile::tempdir() else {
+            return;
+        };
+        let target = dir.path().join("output.json");
+        assert!(std::fs::write(&target, "old").is_ok());
+
+        let written = write_output_atomically(&target, |file| file.write_all(b"{}"));
+        assert!(written.is_ok());
+        assert_eq!(std::fs::read_to_string(&target).ok().as_deref(), Some("{}"));
+    }
+
+    #[test]
+    fn failed_write_leaves_no_partial_output() {
+        let Ok(dir) = tempfile::tempdir() else {
+            return;
+        };
+        let target = dir.path().join("output.json");
+
+        let written = write_output_atomically(&target, |file| {
+            file.write_all(b"{\"buses\": [")?;
+            Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"))
+        });
+        assert!(written.is_err());
+        assert!(!target.exists());
+        let leftovers = std::fs::read_dir(dir.path()).map(Iterator::count).ok();
+        assert_eq!(leftovers, Some(0));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::commands::run::pipeline::input::InputFormat;
+use crate::commands::run::{RunCommand, RunError};
+use r2x_config::Config;
+use r2x_python::plugin_invoker::PluginInvocationTimings;
+use std::time::Duration;
+
+/// What the pipeline does when a step fails
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailureMode {
+    /// Abort at the first failing step (the default)
+    #[default]
+    FailFast,
+    /// Log the failure, pass the failed step's input on, and keep going
+    ContinueOnError,
+}
+
+impl FailureMode {
+    pub(super) fn from_command(cmd: &RunCommand) -> Self {
+        if cmd.no_fail_fast {
+            FailureMode::ContinueOnError
+        } else {
+            FailureMode::FailFast
+        }
+    }
+
+    pub(super) fn describe(self) -> &'static str {
+        match self {
+            FailureMode::FailFast => "fail-fast (stop at the first failing step)",
+            FailureMode::ContinueOnError => {
+                "no-fail-fast (continue past failing steps with their input)"
+            }
+        }
+    }
+}
+
+/// Options for running a pipeline without the CLI
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Run only this step (1-based, in execution order)
+    pub step: Option<usize>,
+    /// JSON file used as stdin for `step` in place of upstream output
+    pub input: Option<String>,
+    /// Format of `input`; inferred from its extension when unset
+    pub input_format: Option<InputFormat>,
+    /// Run only the steps carrying one of these tags; empty runs every step
+    pub tags: Vec<String>,
+    pub failure_mode: FailureMode,
+    /// Abort when resident memory goes over this many MB
+    pub max_memory_mb: Option<u64>,
+    /// Abandon a step whose plugin call runs longer than this
+    pub plugin_timeout: Option<Duration>,
+    /// Print a per-step timing table when the run ends
+    pub summary: bool,
+    /// Run without verifying plugin packages first
+    pub skip_verification: bool,
+    /// Venv to verify packages in (see `r2x run --python`); the saved config when unset
+    pub venv_config: Option<Config>,
+}
+
+impl RunOptions {
+    pub(super) fn from_command(cmd: &RunCommand, venv_config: Option<Config>) -> Self {
+        Self {
+            step: cmd.step,
+            input: cmd.input.clone(),
+            input_format: cmd.input_format,
+            tags: cmd.tags.clone(),
+            failure_mode: FailureMode::from_command(cmd),
+            max_memory_mb: cmd.max_memory,
+            plugin_timeout: cmd.plugin_timeout.map(Duration::from_secs),
+            summary: cmd.summary,
+            skip_verification: cmd.no_verify,
+            venv_config,
+        }
+    }
+}
+
+/// How a pipeline step ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepStatus {
+    Succeeded,
+    /// The step errored and the run continued past it
+    Failed {
+        error: String,
+    },
+}
+
+/// One executed pipeline step
+#[derive(Debug, Clone)]
+pub struct StepReport {
+    pub name: String,
+    /// [`crate::pipeline_config::ResolvedStep::position`] of the step
+    pub position: usize,
+    pub status: StepStatus,
+    pub elapsed: Duration,
+    /// Python call and serialization time, when the plugin reported them
+    pub timings: Option<PluginInvocationTimings>,
+}
+
+/// Structured result of a pipeline run, left to the caller to present
+#[derive(Debug, Clone)]
+pub struct PipelineRunReport {
+    pub pipeline: String,
+    /// Executed steps in order
+    pub steps: Vec<StepReport>,
+    /// Output of the last step, usually the serialized system
+    pub final_output: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl PipelineRunReport {
+    /// Steps that failed while running with [`FailureMode::ContinueOnError`]
+    pub fn failed_steps(&self) -> impl Iterator<Item = &StepReport> {
+        self.steps
+            .iter()
+            .filter(|step| matches!(step.status, StepStatus::Failed { .. }))
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.failed_steps().next().is_none()
+    }
+}
+
+/// Error that ended a run, with the steps that ran before it when there were any
+#[derive(Debug)]
+pub(super) struct RunFailure {
+    pub error: RunError,
+    pub partial: Option<PipelineRunReport>,
+}
+
+impl<E: Into<RunError>> From<E> for RunFailure {
+    fn from(error: E) -> Self {
+        Self {
+            error: error.into(),
+            partial: None,
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use crate::commands::run::RunError;
+use crate::manifest_lookup::{resolve_plugin_ref, PluginRefError, ResolvedPlugin};
+use crate::pipeline_config::{PipelineConfig, ResolvedStep};
+use r2x_manifest::runtime::{build_runtime_bindings, RuntimeBindings};
+use r2x_manifest::types::Manifest;
+use serde_json::json;
+use std::path::Path;
+
+use super::builder::build_plugin_config;
+use super::config::resolve_plugin_config_json;
+
+/// A pipeline step with the config `r2x run` would send its plugin
+///
+/// Built without invoking Python, so the upstream output a running step
+/// merges into its config (`json_path` and friends) is not included.
+pub(super) struct PlannedStep<'m> {
+    pub step: ResolvedStep,
+    pub plugin: ResolvedPlugin<'m>,
+    pub bindings: RuntimeBindings,
+    pub config: serde_json::Value,
+}
+
+/// Output folder of `pipeline_name` with variables substituted
+pub(super) fn resolve_output_folder(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+) -> Result<Option<String>, RunError> {
+    config
+        .output_folder_for(pipeline_name)
+        .map(|folder| config.substitute_string(folder))
+        .transpose()
+        .map_err(RunError::Pipeline)
+}
+
+/// Replace `output_folder` for this run (`r2x run --set-output-folder`)
+///
+/// This also wins over any `output_folder` set under a pipeline.
+///
+/// The folder is not created here; this only checks that its nearest
+/// existing ancestor is a writable directory, so creating it later can work.
+pub(super) fn override_output_folder(
+    config: &mut PipelineConfig,
+    folder: &str,
+) -> Result<(), RunError> {
+    let path = Path::new(folder);
+    let ancestor = path
+        .ancestors()
+        .find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())
+        .unwrap_or(Path::new("."));
+    let writable_dir = std::fs::metadata(ancestor)
+        .is_ok_and(|metadata| metadata.is_dir() && !metadata.permissions().readonly());
+    if !writable_dir {
+        return Err(RunError::InvalidArgs(format!(
+            "--set-output-folder '{}' cannot be created: '{}' is not a writable directory",
+            folder,
+            ancestor.display()
+        )));
+    }
+    config.output_folder = Some(folder.to_string());
+    for pipeline in config.pipelines.values_mut() {
+        pipeline.output_folder = None;
+    }
+    Ok(())
+}
+
+/// Resolve every step of a pipeline and build its final plugin config
+pub(super) fn plan_steps<'m>(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    manifest: &'m Manifest,
+) -> Result<Vec<PlannedStep<'m>>, RunError> {
+    let pipeline = config.resolve_pipeline(pipeline_name)?;
+    let output_folder = resolve_output_folder(config, pipeline_name)?;
+    let mut current_store_path: Option<String> = None;
+    let mut planned = Vec::with_capacity(pipeline.len());
+
+    for step in pipeline {
+        let plugin_name = &step.name;
+        let resolved = resolve_plugin_ref(manifest, plugin_name).map_err(|err| match err {
+            PluginRefError::NotFound(_) => RunError::PluginNotFound(plugin_name.clone()),
+            PluginRefError::Ambiguous { .. } | PluginRefError::Duplicate { .. } => {
+                RunError::Config(err.to_string())
+            }
+        })?;
+        let bindings = build_runtime_bindings(resolved.plugin);
+
+        let step_config = resolve_plugin_config_json(
+            config,
+            plugin_name,
+            &resolved,
+            current_store_path.as_deref(),
+            output_folder.as_deref(),
+        )?;
+        current_store_path = step_config.store.declared().map(str::to_string);
+
+        let final_config_json =
+            build_plugin_config(&bindings, &step_config.json, &step_config.store, None)?;
+        let final_config = serde_json::from_str(&final_config_json)
+            .map_err(|e| RunError::Config(format!("Failed to parse plugin config: {}", e)))?;
+
+        planned.push(PlannedStep {
+            step,
+            plugin: resolved,
+            bindings,
+            config: final_config,
+        });
+    }
+
+    Ok(planned)
+}
+
+/// The `--print --resolved` document: each step's final config as JSON
+pub(super) fn render_resolved_config(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    manifest: &Manifest,
+) -> Result<String, RunError> {
+    let steps: Vec<serde_json::Value> = plan_steps(config, pipeline_name, manifest)?
+        .into_iter()
+        .map(|planned| {
+            json!({
+                "name": planned.step.name,
+                "plugin": format!("{}.{}", planned.plugin.package.name, planned.plugin.plugin.name),
+                "input": planned.step.input,
+                "config": planned.config,
+            })
+        })
+        .collect();
+    let document = json!({
+        "pipeline": pipeline_name,
+        "output_folder": resolve_output_folder(config, pipeline_name)?,
+        "steps": steps,
+    });
+    serde_json::to_string_pretty(&document)
+        .map_err(|e| RunError::Config(format!("Failed to serialize resolved config: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::resolved::*;
+    use r2x_manifest::types::{Package, Plugin, PluginType};
+    use std::sync::Arc;
+
+    fn manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        let mut package = Package {
+            name: Arc::from("r2x-demo"),
+            ..Default::default()
+        };
+        package.plugins.push(Plugin {
+            name: Arc::from("add-tags"),
+            plugin_type: PluginType::Function,
+            module: Arc::from("r2x_demo.tags"),
+            function_name: Some(Arc::from("add_tags")),
+            ..Default::default()
+        });
+        manifest.packages.push(package);
+        manifest.rebuild_indexes();
+        manifest
+    }
+
+    #[test]
+    fn resolved_print_substitutes_variables() {
+        let yaml = "variables:\n  year: 2030\n  scenario: base\noutput_folder: out/${scenario}\npipelines:\n  demo: [add-tags]\nconfig:\n  add-tags:\n    label: ${scenario}-$(year)\n    years: ['${year}']\n";
+        let config = PipelineConfig::parse(yaml);
+        assert!(config.is_ok());
+        let Ok(config) = config else {
+            return;
+        };
+
+        let output = render_resolved_config(&config, "demo", &manifest());
+        assert!(output.is_ok());
+        let Ok(output) = output else {
+            return;
+        };
+        assert!(!output.contains("${"));
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap_or_default();
+        assert_eq!(document["output_folder"], "out/base");
+        assert_eq!(document["steps"][0]["plugin"], "r2x-demo.add-tags");
+        assert_eq!(document["steps"][0]["config"]["label"], "base-2030");
+        assert_eq!(document["steps"][0]["config"]["years"][0], "2030");
+    }
+
+    #[test]
+    fn output_folder_override_takes_precedence_over_yaml() {
+        let yaml = "variables:\n  scenario: base\noutput_folder: out/${scenario}\npipelines:\n  demo: [add-tags]\n";
+        let config = PipelineConfig::parse(yaml);
+        assert!(config.is_ok());
+        let Ok(mut config) = config else {
+            return;
+        };
+        let Ok(temp) = tempfile::tempdir() else {
+            return;
+        };
+        let folder = temp
+            .path()
+            .join("runs/today")
+            .to_string_lossy()
+            .into_owned();
+
+        assert!(override_output_folder(&mut config, &folder).is_ok());
+        assert_eq!(
+            resolve_output_folder(&config, "demo")
+                .ok()
+                .flatten()
+                .as_deref(),
+            Some(folder.as_str())
+        );
+        let output = render_resolved_config(&config, "demo", &manifest()).unwrap_or_default();
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap_or_default();
+        assert_eq!(document["output_folder"], folder.as_str());
+    }
+
+    #[test]
+    fn each_pipeline_uses_its_own_output_folder() {
+        let yaml = "variables:\n  scenario: base\noutput_folder: out/shared\npipelines:\n  reeds:\n    output_folder: out/${scenario}/reeds\n    steps: [add-tags]\n  plexos:\n    output_folder: out/plexos\n    steps: [add-tags]\n  plain: [add-tags]\n";
+        let config = PipelineConfig::parse(yaml);
+        assert!(config.is_ok());
+        let Ok(config) = config else {
+            return;
+        };
+        let folder = |pipeline: &str| resolve_output_folder(&config, pipeline).ok().flatten();
+
+        assert_eq!(folder("reeds").as_deref(), Some("out/base/reeds"));
+        assert_eq!(folder("plexos").as_deref(), Some("out/plexos"));
+        assert_eq!(folder("plain").as_deref(), Some("out/shared"));
+        assert_eq!(
+            config.pipeline_steps("reeds").ok(),
+            Some(vec!["add-tags".to_string()])
+        );
+
+        let output = render_resolved_config(&config, "plexos", &manifest()).unwrap_or_default();
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap_or_default();
+        assert_eq!(document["output_folder"], "out/plexos");
+    }
+
+    #[test]
+    fn output_folder_override_under_a_file_is_rejected() {
+        let Ok(mut config) = PipelineConfig::parse("pipelines:\n  demo: [add-tags]\n") else {
+            return;
+        };
+        let Ok(temp) = tempfile::tempdir() else {
+            return;
+        };
+        let file = temp.path().join("notes.txt");
+        assert!(std::fs::write(&file, "x").is_ok());
+
+        let folder = file.join("out").to_string_lossy().into_owned();
+        assert!(override_output_folder(&mut config, &folder).is_err());
+        assert_eq!(config.output_folder, None);
+    }
+}
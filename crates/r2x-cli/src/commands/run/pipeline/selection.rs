@@ -0,0 +1,226 @@
+use crate::commands::run::pipeline::input::{read_input_file, InputFormat};
+use crate::commands::run::RunError;
+use crate::pipeline_config::ResolvedStep;
+use r2x_logger as logger;
+use std::collections::{HashMap, HashSet};
+
+/// Pick the 1-based `step` from a resolved pipeline for `--step`.
+///
+/// Steps that normally receive upstream output need `--input` to stand in
+/// for it. The returned step has no upstream reference; its stdin is the
+/// input file as JSON, if any.
+pub(super) fn select_single_step(
+    pipeline: Vec<ResolvedStep>,
+    step: usize,
+    input_file: Option<&str>,
+    input_format: Option<InputFormat>,
+) -> Result<(ResolvedStep, Option<String>), RunError> {
+    let total = pipeline.len();
+    if step == 0 || step > total {
+        return Err(RunError::InvalidArgs(format!(
+            "--step {} is out of range (pipeline has {} step{})",
+            step,
+            total,
+            if total == 1 { "" } else { "s" }
+        )));
+    }
+
+    let Some(mut selected) = pipeline.into_iter().nth(step - 1) else {
+        return Err(RunError::InvalidArgs(format!(
+            "--step {} is out of range",
+            step
+        )));
+    };
+
+    selected.input_position = None;
+    let stdin = match (selected.input.take(), input_file) {
+        (_, Some(path)) => Some(read_input_file(path, input_format)?),
+        (Some(upstream), None) => {
+            return Err(RunError::InvalidArgs(format!(
+                "--step {} ({}) reads the output of '{}'; pass it with --input <FILE>",
+                step, selected.name, upstream
+            )));
+        }
+        (None, None) => None,
+    };
+
+    Ok((selected, stdin))
+}
+
+/// Keep the steps carrying any of `tags`, in run order, for `--tags`.
+///
+/// A kept step whose input step was dropped reads that step's input instead,
+/// so stdin carries forward the output of the last upstream step that runs.
+/// Upstream steps are followed by position, so a name listed more than once
+/// is not confused with its other occurrences. Warns when no step matches.
+pub(super) fn select_tagged_steps(
+    pipeline: Vec<ResolvedStep>,
+    tags: &[String],
+) -> Vec<ResolvedStep> {
+    let inputs: HashMap<usize, (Option<String>, Option<usize>)> = pipeline
+        .iter()
+        .map(|step| (step.position, (step.input.clone(), step.input_position)))
+        .collect();
+    let (kept, dropped): (Vec<ResolvedStep>, Vec<ResolvedStep>) = pipeline
+        .into_iter()
+        .partition(|step| step.tags.iter().any(|tag| tags.contains(tag)));
+    let dropped: HashSet<usize> = dropped.iter().map(|step| step.position).collect();
+
+    if kept.is_empty() {
+        logger::warn(&format!(
+            "No pipeline step is tagged {}; nothing to run",
+            tags.join(", ")
+        ));
+    }
+
+    kept.into_iter()
+        .map(|mut step| {
+            let mut visited = HashSet::new();
+            while let Some(upstream) = step.input_position {
+                if !dropped.contains(&upstream) || !visited.insert(upstream) {
+                    break;
+                }
+                (step.input, step.input_position) =
+                    inputs.get(&upstream).cloned().unwrap_or_default();
+            }
+            step
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::selection::*;
+    use std::io::Write;
+
+    fn linear(names: &[&str]) -> Vec<ResolvedStep> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| ResolvedStep {
+                name: (*name).to_string(),
+                position: idx,
+                input: idx.checked_sub(1).map(|prev| names[prev].to_string()),
+                input_position: idx.checked_sub(1),
+                tags: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn tagged(names: &[(&str, &[&str])]) -> Vec<ResolvedStep> {
+        let pipeline = linear(&names.iter().map(|(name, _)| *name).collect::<Vec<_>>());
+        pipeline
+            .into_iter()
+            .zip(names)
+            .map(|(mut step, (_, tags))| {
+                step.tags = tags.iter().map(|tag| (*tag).to_string()).collect();
+                step
+            })
+            .collect()
+    }
+
+    #[test]
+    fn first_step_runs_without_input() {
+        let result = select_single_step(linear(&["parser", "break-gens"]), 1, None, None);
+        assert!(result.is_ok_and(|(step, stdin)| step.name == "parser" && stdin.is_none()));
+    }
+
+    #[test]
+    fn later_step_requires_input() {
+        let result = select_single_step(linear(&["parser", "break-gens"]), 2, None, None);
+        assert!(matches!(
+            result,
+            Err(RunError::InvalidArgs(ref msg)) if msg.contains("--input") && msg.contains("parser")
+        ));
+    }
+
+    #[test]
+    fn later_step_reads_input_file() {
+        let Ok(mut file) = tempfile::NamedTempFile::new() else {
+            return;
+        };
+        if file.write_all(br#"{"system": {}}"#).is_err() {
+            return;
+        }
+        let path = file.path().to_string_lossy().to_string();
+
+        let result = select_single_step(linear(&["parser", "break-gens"]), 2, Some(&path), None);
+        assert!(result.is_ok_and(|(step, stdin)| step.name == "break-gens"
+            && step.input.is_none()
+            && stdin.as_deref() == Some(r#"{"system": {}}"#)));
+    }
+
+    #[test]
+    fn step_out_of_range_errors() {
+        assert!(matches!(
+            select_single_step(linear(&["parser"]), 0, None, None),
+            Err(RunError::InvalidArgs(_))
+        ));
+        assert!(matches!(
+            select_single_step(linear(&["parser"]), 2, None, None),
+            Err(RunError::InvalidArgs(ref msg)) if msg.contains("has 1 step)")
+        ));
+    }
+
+    #[test]
+    fn tags_select_matching_steps_and_carry_input_forward() {
+        let pipeline = tagged(&[
+            ("parser", &["core"]),
+            ("break-gens", &["optional"]),
+            ("add-storage", &["storage"]),
+            ("exporter", &["core", "export"]),
+        ]);
+
+        let selected = select_tagged_steps(pipeline, &["core".to_string(), "storage".to_string()]);
+        let wiring: Vec<(&str, Option<&str>)> = selected
+            .iter()
+            .map(|step| (step.name.as_str(), step.input.as_deref()))
+            .collect();
+        assert_eq!(
+            wiring,
+            vec![
+                ("parser", None),
+                ("add-storage", Some("parser")),
+                ("exporter", Some("add-storage")),
+            ]
+        );
+    }
+
+    #[test]
+    fn tags_matching_nothing_select_no_steps() {
+        let selected = select_tagged_steps(
+            tagged(&[("parser", &["core"]), ("exporter", &[])]),
+            &["nightly".to_string()],
+        );
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn tags_follow_repeated_step_names_by_position() {
+        let selected = select_tagged_steps(
+            tagged(&[("a", &[]), ("b", &[]), ("a", &[]), ("c", &["x"])]),
+            &["x".to_string()],
+        );
+        let wiring: Vec<(&str, Option<&str>, Option<usize>)> = selected
+            .iter()
+            .map(|step| {
+                (
+                    step.name.as_str(),
+                    step.input.as_deref(),
+                    step.input_position,
+                )
+            })
+            .collect();
+        assert_eq!(wiring, vec![("c", None, None)]);
+
+        let selected = select_tagged_steps(
+            tagged(&[("a", &["x"]), ("b", &[]), ("a", &[]), ("c", &["x"])]),
+            &["x".to_string()],
+        );
+        let wiring: Vec<(usize, Option<usize>)> = selected
+            .iter()
+            .map(|step| (step.position, step.input_position))
+            .collect();
+        assert_eq!(wiring, vec![(0, None), (3, Some(0))]);
+    }
+}
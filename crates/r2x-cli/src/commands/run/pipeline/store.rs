@@ -0,0 +1,237 @@
+use crate::commands::run::pipeline::constants::DEFAULT_OUTPUT_ROOT;
+use crate::commands::run::RunError;
+use serde_json::Value;
+use std::path::Path;
+
+/// Extensions of single-file stores, matched case-insensitively
+const FILE_STORE_EXTENSIONS: &[&str] = &[
+    ".zip", ".tar", ".tar.gz", ".tgz", ".h5", ".hdf5", ".sqlite", ".db",
+];
+
+/// How a plugin's data store is laid out on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum StoreMode {
+    /// A directory of data files (the default)
+    Folder,
+    /// A single archive or database file
+    File,
+}
+
+impl StoreMode {
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword.to_ascii_lowercase().as_str() {
+            "folder" | "dir" | "directory" => Some(StoreMode::Folder),
+            "file" => Some(StoreMode::File),
+            _ => None,
+        }
+    }
+}
+
+/// A store declared in a step's config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct StoreSpec {
+    pub mode: StoreMode,
+    /// `None` for `store: true`, which asks for a store without naming one
+    pub path: Option<String>,
+}
+
+impl StoreSpec {
+    /// Store at `path`, a file store when the extension names an archive
+    pub(super) fn from_path(path: &str) -> Self {
+        let lower = path.to_ascii_lowercase();
+        let mode = if FILE_STORE_EXTENSIONS.iter().any(|ext| lower.ends_with(ext)) {
+            StoreMode::File
+        } else {
+            StoreMode::Folder
+        };
+        Self {
+            mode,
+            path: Some(path.to_string()),
+        }
+    }
+
+    /// Read `store: true`, `store: <path>` or `store: {path: <path>, mode: file}`
+    ///
+    /// Returns `None` for anything else (including `false`), which is passed
+    /// to the plugin unchanged.
+    pub(super) fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(true) => Some(Self {
+                mode: StoreMode::Folder,
+                path: None,
+            }),
+            Value::String(path) => Some(Self::from_path(path)),
+            Value::Object(map) => {
+                let mut spec = match map.get("path") {
+                    Some(Value::String(path)) => Self::from_path(path),
+                    _ => return None,
+                };
+                if let Some(mode) = map
+                    .get("mode")
+                    .and_then(Value::as_str)
+                    .and_then(StoreMode::from_keyword)
+                {
+                    spec.mode = mode;
+                }
+                Some(spec)
+            }
+            _ => None,
+        }
+    }
+
+    /// Directory holding the store: the path itself, or a file store's parent
+    pub(super) fn folder(&self) -> Option<String> {
+        let path = self.path.as_deref()?;
+        match self.mode {
+            StoreMode::Folder => Some(path.to_string()),
+            StoreMode::File => Path::new(path)
+                .parent()
+                .map(|parent| parent.to_string_lossy().into_owned())
+                .filter(|parent| !parent.is_empty())
+                .or_else(|| Some(".".to_string())),
+        }
+    }
+}
+
+/// Store a step uses when its config declares none the builder recognizes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum StoreLocation {
+    /// Named by this step's `store_path` or `store`
+    StepConfig(String),
+    /// Named by an earlier step of the pipeline
+    Inherited(String),
+    /// `store` under the pipeline's output folder
+    OutputFolder(String),
+    /// `store` under [`DEFAULT_OUTPUT_ROOT`]
+    Default(String),
+}
+
+impl StoreLocation {
+    /// Pick the store from a step's config, then `inherited`, then the
+    /// output folder, then the default root
+    pub(super) fn resolve(
+        step_config: &Value,
+        inherited: Option<&str>,
+        output_folder: Option<&str>,
+    ) -> Self {
+        let declared = ["store_path", "store"]
+            .iter()
+            .filter_map(|key| step_config.get(*key))
+            .find_map(|value| StoreSpec::from_value(value)?.path);
+        match (declared, inherited, output_folder) {
+            (Some(path), _, _) => Self::StepConfig(path),
+            (None, Some(path), _) => Self::Inherited(path.to_string()),
+            (None, None, Some(folder)) => Self::OutputFolder(format!("{}/store", folder)),
+            (None, None, None) => Self::Default(format!("{}/store", DEFAULT_OUTPUT_ROOT)),
+        }
+    }
+
+    pub(super) fn path(&self) -> &str {
+        match self {
+            Self::StepConfig(path)
+            | Self::Inherited(path)
+            | Self::OutputFolder(path)
+            | Self::Default(path) => path,
+        }
+    }
+
+    /// Path named in the pipeline config, which later steps inherit
+    pub(super) fn declared(&self) -> Option<&str> {
+        match self {
+            Self::StepConfig(path) | Self::Inherited(path) => Some(path),
+            Self::OutputFolder(_) | Self::Default(_) => None,
+        }
+    }
+
+    /// The store path, creating the directory when r2x chose it
+    pub(super) fn prepare(&self) -> Result<&str, RunError> {
+        if self.declared().is_none() {
+            std::fs::create_dir_all(self.path()).map_err(|e| {
+                RunError::Config(format!("Failed to create store directory: {}", e))
+            })?;
+        }
+        Ok(self.path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::store::*;
+    use serde_json::json;
+
+    #[test]
+    fn boolean_store_requests_a_store_without_a_path() {
+        assert_eq!(
+            StoreSpec::from_value(&json!(true)),
+            Some(StoreSpec {
+                mode: StoreMode::Folder,
+                path: None,
+            })
+        );
+        assert_eq!(StoreSpec::from_value(&json!(false)), None);
+    }
+
+    #[test]
+    fn folder_path_store_is_a_folder() {
+        let spec = StoreSpec::from_value(&json!("inputs/reeds_case"));
+        assert_eq!(
+            spec,
+            Some(StoreSpec {
+                mode: StoreMode::Folder,
+                path: Some("inputs/reeds_case".to_string()),
+            })
+        );
+        assert_eq!(
+            spec.and_then(|spec| spec.folder()).as_deref(),
+            Some("inputs/reeds_case")
+        );
+    }
+
+    #[test]
+    fn file_path_store_is_a_file_by_extension_or_mode() {
+        let spec = StoreSpec::from_value(&json!("inputs/case.H5"));
+        assert_eq!(spec.as_ref().map(|spec| spec.mode), Some(StoreMode::File));
+        assert_eq!(
+            spec.and_then(|spec| spec.folder()).as_deref(),
+            Some("inputs")
+        );
+
+        let spec = StoreSpec::from_value(&json!({"path": "inputs/case.bin", "mode": "file"}));
+        assert_eq!(
+            spec,
+            Some(StoreSpec {
+                mode: StoreMode::File,
+                path: Some("inputs/case.bin".to_string()),
+            })
+        );
+        let spec = StoreSpec::from_value(&json!({"path": "cases.zip", "mode": "folder"}));
+        assert_eq!(spec.map(|spec| spec.mode), Some(StoreMode::Folder));
+    }
+
+    #[test]
+    fn store_location_follows_precedence() {
+        let declared = json!({"store_path": "cases/reeds", "solve_year": 2030});
+        let nested = json!({"store": {"path": "cases/reeds.h5", "mode": "file"}});
+        let undeclared = json!({"solve_year": 2030});
+
+        assert_eq!(
+            StoreLocation::resolve(&declared, Some("earlier"), Some("out")),
+            StoreLocation::StepConfig("cases/reeds".to_string())
+        );
+        assert_eq!(
+            StoreLocation::resolve(&nested, None, None),
+            StoreLocation::StepConfig("cases/reeds.h5".to_string())
+        );
+        assert_eq!(
+            StoreLocation::resolve(&undeclared, Some("earlier"), Some("out")),
+            StoreLocation::Inherited("earlier".to_string())
+        );
+        assert_eq!(
+            StoreLocation::resolve(&undeclared, None, Some("out")),
+            StoreLocation::OutputFolder("out/store".to_string())
+        );
+        let default = StoreLocation::resolve(&json!(true), None, None);
+        assert_eq!(default.path(), format!("{}/store", DEFAULT_OUTPUT_ROOT));
+        assert_eq!(default.declared(), None);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::commands::run::format_duration;
+use crate::commands::run::pipeline::report::{PipelineRunReport, StepReport, StepStatus};
+use crate::pipeline_config::ResolvedStep;
+use colored::{ColoredString, Colorize};
+use std::time::Duration;
+
+/// How a step appears in the `--summary` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SummaryStatus {
+    Ok,
+    Failed,
+    /// In the pipeline but not run, e.g. outside `--step`
+    Skipped,
+}
+
+impl SummaryStatus {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            SummaryStatus::Ok => "ok",
+            SummaryStatus::Failed => "failed",
+            SummaryStatus::Skipped => "skipped",
+        }
+    }
+
+    fn paint(self, padded: &str) -> ColoredString {
+        match self {
+            SummaryStatus::Ok => padded.green(),
+            SummaryStatus::Failed => padded.red().bold(),
+            SummaryStatus::Skipped => padded.dimmed(),
+        }
+    }
+}
+
+/// One row of the `--summary` table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SummaryRow {
+    pub name: String,
+    pub status: SummaryStatus,
+    pub wall: Option<Duration>,
+    pub python: Option<Duration>,
+    pub serialization: Option<Duration>,
+}
+
+/// The run of the pipeline step at `position`, if it ran
+pub(super) fn executed_step(report: &PipelineRunReport, position: usize) -> Option<&StepReport> {
+    report.steps.iter().find(|step| step.position == position)
+}
+
+/// A row for every step of the pipeline, in pipeline order
+pub(super) fn summary_rows(steps: &[ResolvedStep], report: &PipelineRunReport) -> Vec<SummaryRow> {
+    steps
+        .iter()
+        .map(|step| {
+            executed_step(report, step.position).map_or_else(
+                || SummaryRow {
+                    name: step.name.clone(),
+                    status: SummaryStatus::Skipped,
+                    wall: None,
+                    python: None,
+                    serialization: None,
+                },
+                row_for_step,
+            )
+        })
+        .collect()
+}
+
+fn row_for_step(step: &StepReport) -> SummaryRow {
+    let status = match step.status {
+        StepStatus::Succeeded => SummaryStatus::Ok,
+        StepStatus::Failed { .. } => SummaryStatus::Failed,
+    };
+    SummaryRow {
+        name: step.name.clone(),
+        status,
+        wall: Some(step.elapsed),
+        python: step.timings.map(|timings| timings.python_invocation),
+        serialization: step.timings.map(|timings| timings.serialization),
+    }
+}
+
+/// Render the post-run table printed by `r2x run --summary`
+pub(super) fn render_summary(steps: &[ResolvedStep], report: &PipelineRunReport) -> String {
+    let rows = summary_rows(steps, report);
+    let header = ["STEP", "STATUS", "WALL", "PYTHON", "SERIALIZATION"];
+    let cells: Vec<[String; 5]> = rows
+        .iter()
+        .map(|row| {
+            let time =
+                |duration: Option<Duration>| duration.map_or("-".to_string(), format_duration);
+            [
+                row.name.clone(),
+                row.status.label().to_string(),
+                time(row.wall),
+                time(row.python),
+                time(row.serialization),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let pad = |cell: &str, width: usize| format!("{:<width$}", cell, width = width);
+
+    let mut out = format!(
+        "{}\n",
+        header
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| pad(cell, width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .bold()
+    );
+    for (row, cells) in rows.iter().zip(&cells) {
+        // Pad before coloring so escape codes do not count toward the width.
+        let line = [
+            pad(&cells[0], widths[0]),
+            row.status.paint(&pad(&cells[1], widths[1])).to_string(),
+            pad(&cells[2], widths[2]),
+            pad(&cells[3], widths[3]),
+            cells[4].clone(),
+        ];
+        out.push_str(&line.join("  "));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::summary::*;
+    use r2x_python::plugin_invoker::PluginInvocationTimings;
+
+    fn step(name: &str, position: usize, status: StepStatus, timed: bool) -> StepReport {
+        StepReport {
+            name: name.to_string(),
+            position,
+            status,
+            elapsed: Duration::from_millis(1500),
+            timings: timed.then_some(PluginInvocationTimings {
+                python_invocation: Duration::from_millis(1200),
+                serialization: Duration::from_millis(250),
+            }),
+        }
+    }
+
+    fn report() -> PipelineRunReport {
+        PipelineRunReport {
+            pipeline: "nightly".to_string(),
+            steps: vec![
+                step("parser", 0, StepStatus::Succeeded, true),
+                step(
+                    "fails",
+                    1,
+                    StepStatus::Failed {
+                        error: "boom".to_string(),
+                    },
+                    false,
+                ),
+            ],
+            final_output: None,
+            elapsed: Duration::from_secs(3),
+        }
+    }
+
+    fn pipeline(names: &[&str]) -> Vec<ResolvedStep> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(position, name)| ResolvedStep {
+                name: (*name).to_string(),
+                position,
+                input: None,
+                input_position: None,
+                tags: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn one_row_per_pipeline_step_with_status() {
+        let rows = summary_rows(&pipeline(&["parser", "fails", "exporter"]), &report());
+        let statuses: Vec<(&str, SummaryStatus)> = rows
+            .iter()
+            .map(|row| (row.name.as_str(), row.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("parser", SummaryStatus::Ok),
+                ("fails", SummaryStatus::Failed),
+                ("exporter", SummaryStatus::Skipped),
+            ]
+        );
+        assert_eq!(rows[0].python, Some(Duration::from_millis(1200)));
+        assert_eq!(rows[1].python, None);
+        assert_eq!(rows[2].wall, None);
+    }
+
+    #[test]
+    fn table_has_header_and_a_line_per_step() {
+        colored::control::set_override(false);
+        let table = render_summary(&pipeline(&["parser", "fails", "exporter"]), &report());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "STEP      STATUS   WALL   PYTHON  SERIALIZATION");
+        assert_eq!(lines[1], "parser    ok       1.50s  1.20s   250ms");
+        assert_eq!(lines[2], "fails     failed   1.50s  -       -");
+        assert_eq!(lines[3], "exporter  skipped  -      -       -");
+    }
+
+    #[test]
+    fn repeated_steps_get_their_own_rows() {
+        let report = PipelineRunReport {
+            pipeline: "nightly".to_string(),
+            steps: vec![
+                step("parser", 0, StepStatus::Succeeded, true),
+                step("check", 1, StepStatus::Succeeded, false),
+                step(
+                    "check",
+                    3,
+                    StepStatus::Failed {
+                        error: "boom".to_string(),
+                    },
+                    false,
+                ),
+            ],
+            final_output: None,
+            elapsed: Duration::from_secs(3),
+        };
+        let rows = summary_rows(
+            &pipeline(&["parser", "check", "modifier", "check"]),
+            &report,
+        );
+        let statuses: Vec<(&str, SummaryStatus)> = rows
+            .iter()
+            .map(|row| (row.name.as_str(), row.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("parser", SummaryStatus::Ok),
+                ("check", SummaryStatus::Ok),
+                ("modifier", SummaryStatus::Skipped),
+                ("check", SummaryStatus::Failed),
+            ]
+        );
+    }
+}
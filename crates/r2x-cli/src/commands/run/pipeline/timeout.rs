@@ -0,0 +1,65 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// The call did not return within its timeout and was left running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Elapsed;
+
+/// Run `call` on a worker thread and wait at most `timeout` for its result (`--plugin-timeout`).
+///
+/// A call that overruns is abandoned, not stopped: Python code cannot be
+/// killed from outside the interpreter, so the worker keeps running (and
+/// holding the GIL) until it returns or the process exits. Callers must end
+/// the run after an [`Elapsed`] rather than start more Python work.
+///
+/// A panic in `call` is resumed on the calling thread, not reported as a timeout.
+pub(super) fn call_with_timeout<T, F>(timeout: Duration, call: F) -> Result<T, Elapsed>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        // The receiver is gone once the call has been abandoned.
+        let _ = tx.send(panic::catch_unwind(AssertUnwindSafe(call)));
+    });
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        // The worker always sends, panic or not, so only the timeout is left.
+        Err(_) => Err(Elapsed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::timeout::*;
+    use std::time::Instant;
+
+    #[test]
+    fn returns_result_of_fast_call() {
+        assert_eq!(call_with_timeout(Duration::from_secs(5), || 42), Ok(42));
+    }
+
+    #[test]
+    fn panicking_call_is_not_a_timeout() {
+        let result = panic::catch_unwind(|| {
+            call_with_timeout(Duration::from_secs(5), || -> i32 {
+                panic::resume_unwind(Box::new("plugin crashed"))
+            })
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn abandons_call_that_sleeps_past_timeout() {
+        let start = Instant::now();
+        let result = call_with_timeout(Duration::from_millis(20), || {
+            std::thread::sleep(Duration::from_secs(5));
+            42
+        });
+        assert_eq!(result, Err(Elapsed));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
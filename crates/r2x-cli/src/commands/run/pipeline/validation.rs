@@ -1,13 +1,77 @@
-use crate::manifest_lookup::resolve_plugin_ref;
+use crate::manifest_lookup::{resolve_plugin_ref, PluginRefError, ResolvedPlugin};
 use crate::pipeline_config::PipelineConfig;
-use r2x_manifest::runtime::build_runtime_bindings;
+use crate::suggestions::closest_matches;
+use r2x_logger as logger;
+use r2x_manifest::runtime::{build_runtime_bindings, RuntimeBindings};
 use r2x_manifest::types::Manifest;
 use std::collections::HashSet;
 
 use crate::commands::run::pipeline::config::resolve_plugin_config_json;
-use crate::commands::run::pipeline::constants::AUTO_PROVIDED_PARAMS;
+use crate::commands::run::pipeline::constants::{
+    AUTO_PROVIDED_PARAMS, FOLDER_FIELD_KEYS, JSON_PATH_FIELDS, STORE_FIELD_KEYS,
+};
 use crate::commands::run::RunError;
 
+/// Resolve every pipeline step against the manifest before anything runs.
+///
+/// All unknown or ambiguous references are reported together so a typo in a
+/// late step is caught without running the earlier ones first.
+pub(super) fn validate_pipeline_plugins(
+    pipeline: &[String],
+    manifest: &Manifest,
+) -> Result<(), RunError> {
+    let mut errors: Vec<String> = Vec::new();
+
+    for plugin_name in pipeline {
+        match resolve_plugin_ref(manifest, plugin_name) {
+            Ok(_) => {}
+            Err(PluginRefError::NotFound(_)) => {
+                let suggestions = suggest_plugin_refs(manifest, plugin_name);
+                if suggestions.is_empty() {
+                    errors.push(format!("{}: plugin not found", plugin_name));
+                } else {
+                    errors.push(format!(
+                        "{}: plugin not found (did you mean: {}?)",
+                        plugin_name,
+                        suggestions.join(", ")
+                    ));
+                }
+            }
+            Err(err @ (PluginRefError::Ambiguous { .. } | PluginRefError::Duplicate { .. })) => {
+                errors.push(format!("{}: {}", plugin_name, err));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(RunError::Config(format!(
+            "Pipeline references unknown plugins:\n  - {}",
+            errors.join("\n  - ")
+        )))
+    }
+}
+
+fn suggest_plugin_refs(manifest: &Manifest, plugin_ref: &str) -> Vec<String> {
+    let qualified = plugin_ref.contains('.');
+    let candidates: Vec<String> = manifest
+        .packages
+        .iter()
+        .flat_map(|package| {
+            package.plugins.iter().map(move |plugin| {
+                if qualified {
+                    format!("{}.{}", package.name, plugin.name)
+                } else {
+                    plugin.name.to_string()
+                }
+            })
+        })
+        .collect();
+
+    closest_matches(plugin_ref, candidates.iter().map(String::as_str), 3)
+}
+
 pub(super) fn validate_pipeline_configs(
     config: &PipelineConfig,
     pipeline: &[String],
@@ -25,10 +89,11 @@ pub(super) fn validate_pipeline_configs(
         let bindings = build_runtime_bindings(plugin);
 
         // Get user-provided config from YAML
-        let yaml_config = match resolve_plugin_config_json(config, plugin_name, &resolved) {
-            Ok(c) => c,
-            Err(_) => "{}".to_string(),
-        };
+        let yaml_config =
+            match resolve_plugin_config_json(config, plugin_name, &resolved, None, None) {
+                Ok(c) => c.json,
+                Err(_) => "{}".to_string(),
+            };
 
         let provided_keys: HashSet<String> =
             match serde_json::from_str::<serde_json::Value>(&yaml_config) {
@@ -39,7 +104,7 @@ pub(super) fn validate_pipeline_configs(
         // Check parameters for required ones
         for param in &bindings.parameters {
             let param_name = param.name.as_ref();
-            if param.required
+            if param.is_required()
                 && param.default.is_none()
                 && !provided_keys.contains(param_name)
                 && !is_auto_provided_param(param_name)
@@ -50,6 +115,14 @@ pub(super) fn validate_pipeline_configs(
                 ));
             }
         }
+
+        // Unknown keys only warn: plugins taking `**kwargs` accept anything.
+        let known_keys = known_config_keys(&resolved, &bindings);
+        if !known_keys.is_empty() {
+            for unknown in find_unknown_config_keys(&provided_keys, &known_keys) {
+                logger::warn(&format!("{}: {}", plugin_name, unknown));
+            }
+        }
     }
 
     if errors.is_empty() {
@@ -62,13 +135,164 @@ pub(super) fn validate_pipeline_configs(
     }
 }
 
+/// A YAML config key that matches no parameter or config field of its plugin
+#[derive(Debug, PartialEq, Eq)]
+struct UnknownConfigKey {
+    key: String,
+    suggestion: Option<String>,
+}
+
+impl std::fmt::Display for UnknownConfigKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.suggestion {
+            Some(suggestion) => write!(
+                f,
+                "unknown config key '{}' (did you mean '{}'?)",
+                self.key, suggestion
+            ),
+            None => write!(f, "unknown config key '{}'", self.key),
+        }
+    }
+}
+
+/// Parameter and config field names the plugin declares.
+///
+/// Empty when the manifest has no metadata to check against.
+fn known_config_keys(resolved: &ResolvedPlugin<'_>, bindings: &RuntimeBindings) -> Vec<String> {
+    let plugin = resolved.plugin;
+    let mut known: Vec<String> = bindings
+        .parameters
+        .iter()
+        .map(|param| param.name.to_string())
+        .chain(
+            plugin
+                .config_schema
+                .iter()
+                .map(|(name, _)| name.to_string()),
+        )
+        .collect();
+
+    if let Some(config_class) = &plugin.config_class {
+        known.extend(
+            resolved
+                .package
+                .configs
+                .iter()
+                .filter(|config| config.name == *config_class)
+                .flat_map(|config| config.fields.iter().map(|field| field.name.to_string())),
+        );
+    }
+
+    if known.is_empty() {
+        return known;
+    }
+
+    // Keys the pipeline runner consumes itself to wire stores and paths.
+    known.extend(
+        AUTO_PROVIDED_PARAMS
+            .iter()
+            .chain(STORE_FIELD_KEYS)
+            .chain(JSON_PATH_FIELDS)
+            .chain(FOLDER_FIELD_KEYS)
+            .map(ToString::to_string),
+    );
+    known.sort();
+    known.dedup();
+    known
+}
+
+fn find_unknown_config_keys(provided: &HashSet<String>, known: &[String]) -> Vec<UnknownConfigKey> {
+    let mut unknown: Vec<UnknownConfigKey> = provided
+        .iter()
+        .filter(|key| !known.contains(key))
+        .map(|key| UnknownConfigKey {
+            key: key.clone(),
+            suggestion: closest_matches(key, known.iter().map(String::as_str), 1)
+                .into_iter()
+                .next(),
+        })
+        .collect();
+    unknown.sort_by(|a, b| a.key.cmp(&b.key));
+    unknown
+}
+
 fn is_auto_provided_param(name: &str) -> bool {
     AUTO_PROVIDED_PARAMS.contains(&name)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::commands::run::pipeline::validation::is_auto_provided_param;
+    use crate::commands::run::pipeline::validation::{
+        find_unknown_config_keys, is_auto_provided_param, validate_pipeline_plugins,
+        UnknownConfigKey,
+    };
+    use crate::commands::run::RunError;
+    use r2x_manifest::types::{Manifest, Package, Plugin, PluginType};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    fn sample_manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        let mut package = Package {
+            name: Arc::from("r2x-reeds"),
+            ..Default::default()
+        };
+        package.plugins.push(Plugin {
+            name: Arc::from("reeds-parser"),
+            plugin_type: PluginType::Class,
+            module: Arc::from("r2x_reeds"),
+            class_name: Some(Arc::from("ReEDSParser")),
+            ..Default::default()
+        });
+        package.plugins.push(Plugin {
+            name: Arc::from("break-gens"),
+            plugin_type: PluginType::Function,
+            module: Arc::from("r2x_reeds.sysmod.break_gens"),
+            function_name: Some(Arc::from("break_generators")),
+            ..Default::default()
+        });
+        manifest.packages.push(package);
+        manifest.rebuild_indexes();
+        manifest
+    }
+
+    fn steps(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn validate_pipeline_plugins_accepts_known_steps() {
+        let manifest = sample_manifest();
+        let pipeline = steps(&["reeds-parser", "r2x-reeds.break-gens"]);
+        assert!(validate_pipeline_plugins(&pipeline, &manifest).is_ok());
+    }
+
+    #[test]
+    fn validate_pipeline_plugins_reports_single_unknown_step_with_suggestion() {
+        let manifest = sample_manifest();
+        let pipeline = steps(&["reeds-parser", "break-gns"]);
+        let result = validate_pipeline_plugins(&pipeline, &manifest);
+        assert!(matches!(
+            result,
+            Err(RunError::Config(ref msg))
+                if msg.contains("break-gns: plugin not found (did you mean: break-gens?)")
+                    && !msg.contains("reeds-parser:")
+        ));
+    }
+
+    #[test]
+    fn validate_pipeline_plugins_reports_all_unknown_steps_at_once() {
+        let manifest = sample_manifest();
+        let pipeline = steps(&["reeds-parsr", "break-gens", "totally-unknown"]);
+        let result = validate_pipeline_plugins(&pipeline, &manifest);
+        assert!(matches!(
+            result,
+            Err(RunError::Config(ref msg))
+                if msg.contains("reeds-parsr: plugin not found (did you mean: reeds-parser?)")
+                    && msg.contains("totally-unknown: plugin not found")
+                    && !msg.contains("break-gens:")
+        ));
+    }
 
     #[test]
     fn is_auto_provided_param_recognizes_store() {
@@ -88,4 +312,33 @@ mod tests {
         assert!(!is_auto_provided_param("system_base_power"));
         assert!(!is_auto_provided_param("model_year"));
     }
+
+    fn keys(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn unknown_config_key_suggests_closest_field() {
+        let known = keys(&["threads", "solve_year", "weather_year"]);
+        let provided: HashSet<String> = keys(&["thread", "solve_year"]).into_iter().collect();
+        let unknown = find_unknown_config_keys(&provided, &known);
+        assert_eq!(
+            unknown,
+            vec![UnknownConfigKey {
+                key: "thread".to_string(),
+                suggestion: Some("threads".to_string()),
+            }]
+        );
+        assert_eq!(
+            unknown[0].to_string(),
+            "unknown config key 'thread' (did you mean 'threads'?)"
+        );
+    }
+
+    #[test]
+    fn known_config_keys_produce_no_warnings() {
+        let known = keys(&["threads", "solve_year"]);
+        let provided: HashSet<String> = keys(&["threads", "solve_year"]).into_iter().collect();
+        assert!(find_unknown_config_keys(&provided, &known).is_empty());
+    }
 }
@@ -0,0 +1,90 @@
+use crate::errors::PipelineError;
+use std::path::{Path, PathBuf};
+
+/// Switches the process working directory for one step and restores it on drop.
+#[derive(Debug)]
+pub(super) struct WorkingDirGuard {
+    previous: PathBuf,
+}
+
+impl WorkingDirGuard {
+    pub(super) fn enter(dir: &Path) -> Result<Self, PipelineError> {
+        let previous = std::env::current_dir()?;
+        std::env::set_current_dir(dir).map_err(|e| {
+            PipelineError::InvalidConfig(format!(
+                "Failed to change working directory to {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        Ok(Self { previous })
+    }
+}
+
+impl Drop for WorkingDirGuard {
+    fn drop(&mut self) {
+        if let Err(e) = std::env::set_current_dir(&self.previous) {
+            r2x_logger::warn(&format!(
+                "Failed to restore working directory {}: {}",
+                self.previous.display(),
+                e
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::pipeline::working_dir::*;
+    use tempfile::TempDir;
+
+    /// Set in the child process that runs the CWD test
+    const CHILD_ENV: &str = "R2X_WORKING_DIR_TEST_CHILD";
+
+    // The working directory is process-wide and other tests run in parallel,
+    // so the test body runs alone in a child copy of the test binary.
+    #[test]
+    fn guard_changes_cwd_for_step_and_restores_it() {
+        if std::env::var_os(CHILD_ENV).is_none() {
+            let Ok(exe) = std::env::current_exe() else {
+                return;
+            };
+            let output = std::process::Command::new(exe)
+                .args([
+                    "--exact",
+                    "commands::run::pipeline::working_dir::tests::guard_changes_cwd_for_step_and_restores_it",
+                    "--test-threads=1",
+                ])
+                .env(CHILD_ENV, "1")
+                .output();
+            assert!(output.is_ok_and(|output| output.status.success()
+                && String::from_utf8_lossy(&output.stdout).contains("1 passed")));
+            return;
+        }
+
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let Ok(original) = std::env::current_dir() else {
+            return;
+        };
+        let Ok(target) = dir.path().canonicalize() else {
+            return;
+        };
+
+        {
+            let guard = WorkingDirGuard::enter(&target);
+            assert!(guard.is_ok());
+            let inside = std::env::current_dir().and_then(|p| p.canonicalize());
+            assert!(inside.is_ok_and(|p| p == target));
+        }
+
+        assert!(std::env::current_dir().is_ok_and(|p| p == original));
+    }
+
+    #[test]
+    fn guard_rejects_missing_directory() {
+        let result = WorkingDirGuard::enter(Path::new("/nonexistent/r2x-working-dir"));
+        assert!(matches!(result, Err(PipelineError::InvalidConfig(_))));
+    }
+}
@@ -1,40 +1,88 @@
+use crate::commands::run::picker::{pick_plugin, PickerOutcome};
 use crate::commands::run::{PluginCommand, RunError};
 use crate::common::GlobalOpts;
 use crate::help::show_plugin_help;
 use crate::manifest_lookup::resolve_plugin_ref;
 use crate::package_verification;
+use atty::Stream;
 use colored::Colorize;
+use flate2::read::GzDecoder;
+use r2x_config::Config;
 use r2x_logger as logger;
+use r2x_manifest::runtime::{build_runtime_bindings, PluginRole};
 use r2x_manifest::types::Manifest;
-use r2x_python::plugin_invoker::PluginInvocationResult;
+use r2x_python::plugin_invoker::{PluginInvocationResult, PluginOutcome};
 use r2x_python::python_bridge::Bridge;
 use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::path::Path;
 use std::time::Instant;
 
-pub(super) fn handle_plugin_command(cmd: PluginCommand, opts: &GlobalOpts) -> Result<(), RunError> {
-    match cmd.plugin_name {
-        Some(plugin_name) => {
-            if cmd.show_help {
-                show_plugin_help(&plugin_name)
-                    .map_err(|e| RunError::Config(format!("Help error: {}", e)))?;
-            } else {
-                run_plugin(&plugin_name, &cmd.args, opts)?;
-            }
+/// First two bytes of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+pub(super) fn handle_plugin_command(
+    cmd: PluginCommand,
+    no_verify: bool,
+    venv_config: Option<&Config>,
+    opts: &GlobalOpts,
+) -> Result<(), RunError> {
+    let (plugin_name, args) = match cmd.plugin_name {
+        Some(plugin_name) if cmd.show_help => {
+            show_plugin_help(&plugin_name)
+                .map_err(|e| RunError::Config(format!("Help error: {}", e)))?;
+            return Ok(());
         }
+        Some(plugin_name) => (plugin_name, cmd.args),
         None => {
-            list_available_plugins()?;
+            let manifest = Manifest::load()?;
+            let interactive = atty::is(Stream::Stdin) && atty::is(Stream::Stdout);
+            let outcome = pick_plugin(&manifest, interactive, io::stdin().lock(), io::stdout())
+                .map_err(|e| RunError::Config(format!("Plugin selection failed: {}", e)))?;
+            match outcome {
+                PickerOutcome::Run { plugin, args } => (plugin, args),
+                PickerOutcome::List => {
+                    list_available_plugins(&manifest);
+                    return Ok(());
+                }
+                PickerOutcome::Cancelled => {
+                    println!("Operation cancelled.");
+                    return Ok(());
+                }
+            }
         }
+    };
+
+    check_trailing_args(&args)?;
+    let stdin_json = cmd.stdin_file.as_deref().map(read_stdin_file).transpose()?;
+    run_plugin(
+        &plugin_name,
+        &args,
+        stdin_json.as_deref(),
+        no_verify || cmd.no_verify,
+        venv_config,
+        opts,
+    )
+}
+
+/// Reject r2x flags that ended up among the plugin arguments
+///
+/// Everything after the first plugin argument is passed to the plugin, so a
+/// trailing `--no-verify` would otherwise be silently ignored.
+fn check_trailing_args(args: &[String]) -> Result<(), RunError> {
+    if args.iter().any(|arg| arg == "--no-verify") {
+        return Err(RunError::InvalidArgs(
+            "--no-verify must come before the plugin arguments, e.g. `r2x run plugin <name> --no-verify key=value`".to_string(),
+        ));
     }
     Ok(())
 }
 
-fn list_available_plugins() -> Result<(), RunError> {
-    let manifest = Manifest::load()?;
-
+fn list_available_plugins(manifest: &Manifest) {
     if manifest.is_empty() {
         println!("No plugins installed.\n");
         println!("To install a plugin, run:\n  r2x install <package>");
-        return Ok(());
+        return;
     }
 
     println!("Available plugins:\n");
@@ -57,11 +105,16 @@ fn list_available_plugins() -> Result<(), RunError> {
 
     println!("Run a plugin with:\n  r2x run plugin <plugin-name> [args...]\n");
     println!("Show plugin help:\n  r2x run plugin <plugin-name> --show-help");
-
-    Ok(())
 }
 
-fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(), RunError> {
+fn run_plugin(
+    plugin_name: &str,
+    args: &[String],
+    stdin_json: Option<&str>,
+    no_verify: bool,
+    venv_config: Option<&Config>,
+    opts: &GlobalOpts,
+) -> Result<(), RunError> {
     logger::step(&format!("Running plugin: {}", plugin_name));
     logger::debug(&format!("Received args: {:?}", args));
 
@@ -73,7 +126,8 @@ fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(
                 crate::manifest_lookup::PluginRefError::NotFound(_) => {
                     RunError::PluginNotFound(plugin_name.to_string())
                 }
-                crate::manifest_lookup::PluginRefError::Ambiguous { .. } => {
+                crate::manifest_lookup::PluginRefError::Ambiguous { .. }
+                | crate::manifest_lookup::PluginRefError::Duplicate { .. } => {
                     RunError::Config(err.to_string())
                 }
             })
@@ -81,24 +135,22 @@ fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(
     };
     let plugin = resolved.plugin;
 
-    package_verification::verify_and_ensure_plugin(&manifest, plugin_name)
-        .map_err(|e| RunError::Verification(e.to_string()))?;
+    crate::commands::run::verify_plugins([plugin_name], no_verify, |plugin_name| {
+        package_verification::verify_and_ensure_plugin(&manifest, plugin_name, venv_config)
+    })?;
 
     let config_map = parse_plugin_args(args)?;
     let config_json = serde_json::to_string(&config_map)
         .map_err(|e| RunError::Config(format!("Failed to serialize config: {}", e)))?;
 
-    // Build target string from plugin's module and class/function name
-    let target = if let Some(ref class_name) = plugin.class_name {
-        format!("{}.{}", plugin.module, class_name)
-    } else if let Some(ref function_name) = plugin.function_name {
-        format!("{}.{}", plugin.module, function_name)
-    } else {
+    if plugin.class_name.is_none() && plugin.function_name.is_none() {
         return Err(RunError::Config(format!(
             "Plugin '{}' has no class_name or function_name",
             plugin_name
         )));
-    };
+    }
+    let bindings = build_runtime_bindings(plugin);
+    let target = crate::commands::run::build_call_target(&bindings)?;
 
     let bridge = Bridge::get()?;
     logger::debug(&format!("Invoking plugin with target: {}", target));
@@ -115,10 +167,11 @@ fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(
     }
 
     let start = Instant::now();
-    // Pass None for plugin metadata since we don't have PluginSpec (execution type)
-    let invocation_result = bridge.invoke_plugin(&target, &config_json, None, None)?;
+    let invocation_result =
+        bridge.invoke_plugin_with_bindings(&target, &config_json, stdin_json, Some(&bindings))?;
     let PluginInvocationResult {
         output: result,
+        outcome,
         timings,
     } = invocation_result;
     let elapsed = start.elapsed();
@@ -130,12 +183,16 @@ fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(
     // Clear plugin context after execution
     logger::set_current_plugin(None);
 
+    if bindings.role == PluginRole::Validator {
+        return crate::commands::run::check_validation_output(plugin_name, &result);
+    }
+
     let no_stdout = opts.no_stdout || logger::get_no_stdout();
-    if !result.is_empty() && result != "null" {
+    if let PluginOutcome::Produced(output) = &outcome {
         if opts.suppress_stdout() || no_stdout {
             logger::debug("Plugin output suppressed");
         } else {
-            println!("{}", result);
+            println!("{}", output);
         }
     }
 
@@ -153,6 +210,29 @@ fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(
     Ok(())
 }
 
+/// Read `--stdin-file`, the JSON handed to the plugin in place of piped stdin.
+///
+/// Gzip-compressed files are recognized by their header and decompressed.
+fn read_stdin_file(path: &str) -> Result<String, RunError> {
+    let bytes = std::fs::read(Path::new(path)).map_err(|e| {
+        RunError::InvalidArgs(format!("Failed to read --stdin-file '{}': {}", path, e))
+    })?;
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut text = String::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut text)
+            .map_err(|e| {
+                RunError::InvalidArgs(format!(
+                    "Failed to decompress --stdin-file '{}': {}",
+                    path, e
+                ))
+            })?;
+        return Ok(text);
+    }
+    String::from_utf8(bytes)
+        .map_err(|_| RunError::InvalidArgs(format!("--stdin-file '{}' is not UTF-8 text", path)))
+}
+
 fn parse_plugin_args(args: &[String]) -> Result<serde_json::Value, RunError> {
     let mut config = serde_json::json!({});
 
@@ -174,11 +254,40 @@ fn parse_plugin_args(args: &[String]) -> Result<serde_json::Value, RunError> {
     Ok(config)
 }
 
+/// Convert a `key=value` argument value into JSON.
+///
+/// Coercion rules, in order:
+/// - a value wrapped in matching single or double quotes is always a string
+///   (`name='123'` → `"123"`); double-quoted values may use JSON escapes
+/// - valid JSON is used as-is (`{"a": 1}`, `[1, 2]`, `42`, `null`)
+/// - other `[...]` values are lists whose comma-separated items follow these
+///   same rules (`folders=[runs/a,runs/b]` → `["runs/a", "runs/b"]`)
+/// - `true`/`false` in any case become booleans
+/// - integers and floats become numbers
+/// - anything else is a plain string
+///
+/// Shells strip one layer of quotes, so forcing a string from the command
+/// line needs nesting, e.g. `name="'123'"` or `name='"true"'`.
 fn parse_json_value(value_str: &str) -> Result<serde_json::Value, RunError> {
+    if let Some(literal) = quoted_literal(value_str) {
+        return Ok(serde_json::Value::String(literal));
+    }
+
     if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(value_str) {
         return Ok(json_val);
     }
 
+    if let Some(inner) = value_str
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        return split_list_items(inner)
+            .into_iter()
+            .map(parse_json_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map(serde_json::Value::Array);
+    }
+
     match value_str.to_lowercase().as_str() {
         "true" => return Ok(serde_json::json!(true)),
         "false" => return Ok(serde_json::json!(false)),
@@ -195,3 +304,200 @@ fn parse_json_value(value_str: &str) -> Result<serde_json::Value, RunError> {
 
     Ok(serde_json::json!(value_str))
 }
+
+/// Split the inside of a `[...]` list on commas outside quotes and nested brackets
+fn split_list_items(inner: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (idx, ch) in inner.char_indices() {
+        match (quote, ch) {
+            (Some(open), _) if ch == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(ch),
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                items.push(inner[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() || !items.is_empty() {
+        items.push(last);
+    }
+    items
+}
+
+fn quoted_literal(value_str: &str) -> Option<String> {
+    let bytes = value_str.as_bytes();
+    if bytes.len() < 2 {
+        return None;
+    }
+    let (first, last) = (bytes[0], bytes[bytes.len() - 1]);
+    if first != last || !matches!(first, b'\'' | b'"') {
+        return None;
+    }
+
+    let inner = &value_str[1..value_str.len() - 1];
+    if first == b'"' {
+        if let Ok(serde_json::Value::String(decoded)) = serde_json::from_str(value_str) {
+            return Some(decoded);
+        }
+    }
+    Some(inner.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::run::plugin::*;
+    use crate::commands::run::{RunCommand, RunSubcommand};
+    use clap::Parser;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use serde_json::json;
+    use std::io::Write;
+
+    fn temp_file(contents: &[u8]) -> Option<tempfile::NamedTempFile> {
+        let mut file = tempfile::NamedTempFile::new().ok()?;
+        file.write_all(contents).ok()?;
+        Some(file)
+    }
+
+    #[test]
+    fn stdin_file_flag_precedes_plugin_args() {
+        let cmd = RunCommand::try_parse_from([
+            "run",
+            "plugin",
+            "modify.foo",
+            "--stdin-file",
+            "system.json",
+            "key=val",
+        ]);
+        assert!(cmd.is_ok());
+        let Ok(RunCommand {
+            command: Some(RunSubcommand::Plugin(plugin)),
+            ..
+        }) = cmd
+        else {
+            return;
+        };
+        assert_eq!(plugin.plugin_name.as_deref(), Some("modify.foo"));
+        assert_eq!(plugin.stdin_file.as_deref(), Some("system.json"));
+        assert_eq!(plugin.args, vec!["key=val"]);
+    }
+
+    #[test]
+    fn no_verify_after_plugin_name_is_parsed_or_rejected() {
+        let cmd =
+            RunCommand::try_parse_from(["run", "plugin", "modify.foo", "--no-verify", "key=val"]);
+        assert!(cmd.is_ok());
+        let Ok(RunCommand {
+            command: Some(RunSubcommand::Plugin(plugin)),
+            ..
+        }) = cmd
+        else {
+            return;
+        };
+        assert!(plugin.no_verify);
+        assert_eq!(plugin.args, vec!["key=val"]);
+        assert!(check_trailing_args(&plugin.args).is_ok());
+
+        let cmd =
+            RunCommand::try_parse_from(["run", "plugin", "modify.foo", "key=val", "--no-verify"]);
+        assert!(cmd.is_ok());
+        let Ok(RunCommand {
+            command: Some(RunSubcommand::Plugin(plugin)),
+            ..
+        }) = cmd
+        else {
+            return;
+        };
+        assert!(matches!(
+            check_trailing_args(&plugin.args),
+            Err(RunError::InvalidArgs(ref msg)) if msg.contains("--no-verify")
+        ));
+    }
+
+    #[test]
+    fn stdin_file_is_read_as_plugin_stdin() {
+        let Some(file) = temp_file(br#"{"name": "system"}"#) else {
+            return;
+        };
+        let path = file.path().to_string_lossy().to_string();
+        assert!(read_stdin_file(&path).is_ok_and(|json| json == r#"{"name": "system"}"#));
+    }
+
+    #[test]
+    fn gzipped_stdin_files_are_decompressed() {
+        let system = r#"{"name": "reeds", "components": []}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        assert!(encoder.write_all(system.as_bytes()).is_ok());
+        let Ok(compressed) = encoder.finish() else {
+            return;
+        };
+        let Some(file) = temp_file(&compressed) else {
+            return;
+        };
+        let path = file.path().to_string_lossy().to_string();
+        assert!(read_stdin_file(&path).is_ok_and(|text| text == system));
+    }
+
+    #[test]
+    fn missing_stdin_files_are_rejected() {
+        assert!(matches!(
+            read_stdin_file("/nonexistent/system.json"),
+            Err(RunError::InvalidArgs(_))
+        ));
+    }
+
+    #[test]
+    fn quoted_values_stay_strings() {
+        assert!(parse_json_value("'123'").is_ok_and(|v| v == json!("123")));
+        assert!(parse_json_value("\"true\"").is_ok_and(|v| v == json!("true")));
+        assert!(parse_json_value("'a b'").is_ok_and(|v| v == json!("a b")));
+        assert!(parse_json_value("\"tab\\there\"").is_ok_and(|v| v == json!("tab\there")));
+    }
+
+    #[test]
+    fn unquoted_scalars_are_coerced() {
+        assert!(parse_json_value("123").is_ok_and(|v| v == json!(123)));
+        assert!(parse_json_value("1.5").is_ok_and(|v| v == json!(1.5)));
+        assert!(parse_json_value("True").is_ok_and(|v| v == json!(true)));
+        assert!(parse_json_value("false").is_ok_and(|v| v == json!(false)));
+        assert!(parse_json_value("reeds").is_ok_and(|v| v == json!("reeds")));
+        assert!(parse_json_value("'").is_ok_and(|v| v == json!("'")));
+    }
+
+    #[test]
+    fn json_objects_are_parsed() {
+        let value = parse_json_value(r#"{"years": [2030, 2035]}"#);
+        assert!(value.is_ok_and(|v| v == json!({"years": [2030, 2035]})));
+    }
+
+    #[test]
+    fn bracketed_values_are_lists() {
+        let value = parse_json_value("[runs/a, runs/b,'2030']");
+        assert!(value.is_ok_and(|v| v == json!(["runs/a", "runs/b", "2030"])));
+        let value = parse_json_value("[a,[1,true],'x,y']");
+        assert!(value.is_ok_and(|v| v == json!(["a", [1, true], "x,y"])));
+        assert!(parse_json_value("[]").is_ok_and(|v| v == json!([])));
+
+        let args = vec!["input-folders=[reeds/s1,reeds/s2]".to_string()];
+        let config = parse_plugin_args(&args);
+        assert!(config.is_ok_and(|c| c == json!({"input_folders": ["reeds/s1", "reeds/s2"]})));
+    }
+
+    #[test]
+    fn plugin_args_apply_forced_strings() {
+        let args = vec![
+            "model-year='2030'".to_string(),
+            "solve_year=2030".to_string(),
+        ];
+        let config = parse_plugin_args(&args);
+        assert!(config.is_ok_and(|c| c == json!({"model_year": "2030", "solve_year": 2030})));
+    }
+}
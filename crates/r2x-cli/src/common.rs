@@ -1,9 +1,10 @@
 //! Common types and utilities shared across modules
 
 use clap::{ArgAction, Parser};
+use r2x_logger::ColorChoice;
 
 /// Global CLI options available to all commands
-#[derive(Parser, Debug, Clone)]
+#[derive(Parser, Debug, Clone, Default)]
 pub struct GlobalOpts {
     #[arg(
         short = 'q',
@@ -31,6 +32,52 @@ pub struct GlobalOpts {
         help = "Disable logging stdout to file (useful with --log-python to avoid large system objects in logs)"
     )]
     pub no_stdout: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Don't prefix console messages with the plugin that emitted them"
+    )]
+    pub no_plugin_prefix: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Log each Python import, attribute lookup and call with its duration (debug level)"
+    )]
+    pub trace_calls: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Write this session's log to PATH instead of the configured log file"
+    )]
+    pub log_file: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Recreate the Python venv and reinstall manifest packages before running"
+    )]
+    pub refresh_venv: bool,
+
+    #[arg(
+        short = 'y',
+        long = "yes",
+        global = true,
+        help = "Skip confirmation prompts (e.g. for --refresh-venv)"
+    )]
+    pub yes: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "WHEN",
+        default_value = "auto",
+        help = "Color console output: auto, always or never (auto honors NO_COLOR)"
+    )]
+    pub color: ColorChoice,
 }
 
 impl GlobalOpts {
@@ -23,6 +23,42 @@ pub enum PipelineError {
 
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("Failed to read secrets file {path}: {reason}")]
+    SecretsFile { path: String, reason: String },
+
+    #[error("Secret '{key}' not found in {path}")]
+    SecretNotFound { key: String, path: String },
+
+    #[error(
+        "Secret '{0}' used but no secrets file is set (pass --secrets or set R2X_SECRETS_FILE)"
+    )]
+    NoSecretsFile(String),
+
+    #[error("Failed to load config for '{step}': {reason}")]
+    ConfigFile { step: String, reason: String },
+}
+
+/// Structural problems found by [`PipelineConfig::validate`](crate::pipeline_config::PipelineConfig::validate)
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PipelineConfigError {
+    #[error("Unknown top-level key '{0}'")]
+    UnknownKey(String),
+
+    #[error("Pipeline '{0}' is defined more than once")]
+    DuplicatePipeline(String),
+
+    #[error("Pipeline '{pipeline}' has an empty step name at position {position}")]
+    EmptyStep { pipeline: String, position: usize },
+
+    #[error("output_folder uses undefined variable '{0}'")]
+    UndefinedVariable(String),
+
+    #[error("{section} entry '{plugin}' does not match any pipeline step")]
+    UnusedPluginEntry {
+        section: &'static str,
+        plugin: String,
+    },
 }
 
 #[cfg(test)]
@@ -37,4 +73,16 @@ mod tests {
             "Pipeline 'test-pipeline' not found in YAML"
         );
     }
+
+    #[test]
+    fn test_pipeline_config_error_display() {
+        let err = PipelineConfigError::EmptyStep {
+            pipeline: "demo".to_string(),
+            position: 2,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Pipeline 'demo' has an empty step name at position 2"
+        );
+    }
 }
@@ -92,11 +92,17 @@ pub fn show_plugin_help(plugin_name: &str) -> Result<(), String> {
                 .as_ref()
                 .map(|m| format!(" ({})", m))
                 .unwrap_or_default();
+            let default_str = param
+                .default
+                .as_ref()
+                .map(|d| format!(" = {}", d))
+                .unwrap_or_default();
             println!(
-                "  --{:<20} {}{}",
+                "  --{:<20} {}{}{}",
                 param.name,
                 param.format_types(),
-                module_str
+                module_str,
+                default_str
             );
             if let Some(ref desc) = param.description {
                 println!("      {}", desc);
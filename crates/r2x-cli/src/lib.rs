@@ -10,6 +10,8 @@ pub mod manifest_lookup;
 pub mod package_verification;
 pub mod pipeline_config;
 pub mod plugins;
+pub mod secrets;
+pub mod suggestions;
 
 #[cfg(test)]
 pub(crate) mod test_support;
@@ -3,6 +3,7 @@ use r2x::commands::{
     config::{self, ConfigAction, PythonAction},
     init,
     log::{self, LogAction},
+    manifest::{self, ManifestAction},
     plugins, read, run,
 };
 use r2x::common::GlobalOpts;
@@ -43,12 +44,23 @@ enum Commands {
         #[command(subcommand)]
         action: Option<LogAction>,
     },
+    /// Manifest utilities
+    Manifest {
+        #[command(subcommand)]
+        action: ManifestAction,
+    },
     /// List installed plugins
     List {
         /// Optional plugin name to filter by (e.g., r2x-reeds)
         plugin: Option<String>,
         /// Optional module/function name to filter by (e.g., break_gens)
         module: Option<String>,
+        /// Only show plugins registered with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Print one JSON object per plugin per line, for jq or streaming consumers
+        #[arg(long)]
+        json_lines: bool,
     },
     /// Install a plugin
     Install {
@@ -59,7 +71,10 @@ enum Commands {
         /// Skip metadata cache and force rebuild
         #[arg(long)]
         no_cache: bool,
-        /// Git host (default: github.com). Use with gh:owner/repo or full URLs.
+        /// Also discover plugins under top-level `tests/` and `examples/` directories
+        #[arg(long)]
+        include_dev: bool,
+        /// Git host for gh:owner/repo (default: github.com); `git@host` or `ssh://host` clones over SSH.
         #[arg(long)]
         host: Option<String>,
         /// Install from a git branch
@@ -71,6 +86,9 @@ enum Commands {
         /// Install from a git commit hash
         #[arg(long, conflicts_with_all = ["branch", "tag"])]
         commit: Option<String>,
+        /// Print the uv command and discovery plan without installing
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Remove a plugin
     Remove { plugin: String },
@@ -79,6 +97,15 @@ enum Commands {
         /// Upgrade installed plugin packages before syncing metadata
         #[arg(long)]
         upgrade: bool,
+        /// Keep running and re-sync editable packages when their `.py` files change
+        #[arg(long)]
+        watch: bool,
+        /// Also discover plugins under top-level `tests/` and `examples/` directories
+        #[arg(long)]
+        include_dev: bool,
+        /// Print what a sync would change without writing the manifest; exit 1 on drift
+        #[arg(long, conflicts_with_all = ["upgrade", "watch"])]
+        check: bool,
     },
     /// Clean plugins and cache (removes installed plugins and cleans cache folder)
     Clean {
@@ -86,14 +113,22 @@ enum Commands {
         #[arg(short = 'y', long)]
         yes: bool,
     },
+    /// Inspect installed plugins
+    Plugin {
+        #[command(subcommand)]
+        action: plugins::PluginAction,
+    },
     /// Initialize a new pipeline file
     Init {
         /// Optional filename for the pipeline (default: pipeline.yaml)
         file: Option<String>,
+        /// Scaffold a `config:` block for this plugin, merged into the file if it exists
+        #[arg(long = "for-plugin", value_name = "PLUGIN")]
+        for_plugin: Option<String>,
     },
 
     /// Run pipelines or plugins
-    Run(run::RunCommand),
+    Run(Box<run::RunCommand>),
     /// Read a system from JSON (stdin or file) and open an interactive IPython session
     Read(read::ReadCommand),
 }
@@ -113,6 +148,13 @@ fn exit_on_plugin_error(result: Result<(), r2x::plugins::error::PluginError>) {
     }
 }
 
+/// Flush Python output and run the bridge's finalizers before the process exits
+fn finalize_python() {
+    if let Err(e) = r2x_python::python_bridge::Bridge::finalize() {
+        logger::warn(&format!("Failed to finalize Python: {}", e));
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -137,23 +179,44 @@ fn main() {
     let effective_log_python = cli.global.log_python || saved_log_python;
     let effective_no_stdout = cli.global.no_stdout || saved_no_stdout;
 
+    logger::set_color_choice(cli.global.color);
+    logger::set_plugin_prefix(!cli.global.no_plugin_prefix);
+    logger::set_quiet(cli.global.quiet > 0);
+    r2x_manifest::types::set_r2x_version(env!("CARGO_PKG_VERSION"));
+    r2x_python::plugin_invoker::set_trace_calls(cli.global.trace_calls);
+    if let Some(bytes) = startup_config
+        .as_ref()
+        .and_then(|cfg| cfg.log_max_capture_bytes)
+    {
+        logger::set_max_capture_bytes(bytes);
+    }
+
     // Initialize logger with verbosity level, log_python flag, and no_stdout flag
     if let Err(e) = logger::init_with_config(
         cli.global.verbosity_level(),
         effective_log_python,
         effective_no_stdout,
-        saved_log_path,
+        cli.global.log_file.as_deref().or(saved_log_path),
         saved_log_max_size,
     ) {
         eprintln!("Warning: Failed to initialize logger: {}", e);
     }
 
-    if let Some(cfg) = startup_config.as_mut() {
+    // `install --dry-run` must not install uv or create anything
+    let dry_run_install = matches!(cli.command, Commands::Install { dry_run: true, .. });
+    if let Some(cfg) = startup_config.as_mut().filter(|_| !dry_run_install) {
         if let Err(e) = cfg.ensure_uv_path().and_then(|_| cfg.ensure_cache_path()) {
             logger::warn(&format!("Failed to setup CLI: {}", e));
         }
     }
 
+    if cli.global.refresh_venv {
+        if let Err(e) = config::refresh_venv(cli.global.yes) {
+            logger::error(&e);
+            std::process::exit(1);
+        }
+    }
+
     match cli.command {
         Commands::Config { action } => {
             config::handle_config(action, cli.global);
@@ -164,35 +227,66 @@ fn main() {
         Commands::Log { action } => {
             log::handle_log(action);
         }
-        Commands::List { plugin, module } => {
-            exit_on_plugin_error(with_plugin_context(|ctx| {
-                plugins::list::list_plugins(&cli.global, plugin, module, ctx)
-            }));
+        Commands::Manifest { action } => {
+            manifest::handle_manifest(action);
+        }
+        Commands::List {
+            plugin,
+            module,
+            tag,
+            json_lines,
+        } => {
+            if json_lines {
+                exit_on_plugin_error(with_plugin_context(|ctx| {
+                    plugins::list::list_plugins_json_lines(
+                        &ctx.manifest,
+                        plugin.as_deref(),
+                        module.as_deref(),
+                        tag.as_deref(),
+                        std::io::stdout().lock(),
+                    )
+                    .map(|_| ())
+                }));
+            } else {
+                exit_on_plugin_error(with_plugin_context(|ctx| {
+                    plugins::list::list_plugins(&cli.global, plugin, module, tag.as_deref(), ctx)
+                }));
+            }
         }
         Commands::Install {
             plugin,
             editable,
             no_cache,
+            include_dev,
             host,
             branch,
             tag,
             commit,
+            dry_run,
         } => match plugin {
             Some(pkg) => {
-                exit_on_plugin_error(with_plugin_context(|ctx| {
-                    plugins::install::install_plugin(
-                        &pkg,
-                        editable,
-                        no_cache,
-                        plugins::install::GitOptions {
-                            host,
-                            branch,
-                            tag,
-                            commit,
-                        },
-                        ctx,
-                    )
-                }));
+                let git_opts = plugins::install::GitOptions {
+                    host,
+                    branch,
+                    tag,
+                    commit,
+                };
+                if dry_run {
+                    exit_on_plugin_error(plugins::install::print_install_plan(
+                        &pkg, editable, no_cache, &git_opts,
+                    ));
+                } else {
+                    exit_on_plugin_error(with_plugin_context(|ctx| {
+                        plugins::install::install_plugin(
+                            &pkg,
+                            editable,
+                            no_cache,
+                            include_dev,
+                            git_opts,
+                            ctx,
+                        )
+                    }));
+                }
             }
             None => {
                 if let Err(e) = plugins::install::show_install_help() {
@@ -206,28 +300,96 @@ fn main() {
                 plugins::remove::remove_plugin(&plugin, ctx)
             }));
         }
-        Commands::Sync { upgrade } => {
-            exit_on_plugin_error(with_plugin_context(|ctx| {
-                plugins::sync::sync_manifest(ctx, upgrade)
-            }));
+        Commands::Sync {
+            upgrade,
+            watch,
+            include_dev,
+            check,
+        } => {
+            if check {
+                let mut in_sync = false;
+                exit_on_plugin_error(with_plugin_context(|ctx| {
+                    in_sync = plugins::sync::check_manifest(ctx, include_dev)?;
+                    Ok(())
+                }));
+                if !in_sync {
+                    std::process::exit(1);
+                }
+            } else {
+                exit_on_plugin_error(with_plugin_context(|ctx| {
+                    plugins::sync::sync_manifest(ctx, upgrade, include_dev)?;
+                    if watch {
+                        plugins::watch::watch_manifest(ctx, include_dev)?;
+                    }
+                    Ok(())
+                }));
+            }
         }
         Commands::Clean { yes } => {
             exit_on_plugin_error(with_plugin_context(|ctx| {
                 plugins::clean::clean_manifest(yes, ctx)
             }));
         }
-        Commands::Init { file } => {
-            init::handle_init(file, cli.global);
+        Commands::Plugin { action } => match action {
+            plugins::PluginAction::Docs { name, output } => {
+                exit_on_plugin_error(plugins::docs::show_plugin_docs(&name, output.as_deref()));
+            }
+            plugins::PluginAction::Graph {
+                mermaid,
+                kind,
+                output,
+            } => {
+                let format = if mermaid {
+                    plugins::graph::GraphFormat::Mermaid
+                } else {
+                    plugins::graph::GraphFormat::Dot
+                };
+                exit_on_plugin_error(plugins::graph::show_plugin_graph(
+                    format,
+                    kind.as_deref(),
+                    output.as_deref(),
+                ));
+            }
+            plugins::PluginAction::Search { query, kind } => {
+                exit_on_plugin_error(plugins::search::search_plugins(&query, kind.as_deref()));
+            }
+            plugins::PluginAction::ValidateConfig { name, file } => {
+                exit_on_plugin_error(plugins::validate_config::validate_plugin_config(
+                    &name, &file,
+                ));
+            }
+            plugins::PluginAction::Steps { name, json, since } => {
+                exit_on_plugin_error(plugins::steps::show_plugin_steps(
+                    &name,
+                    json,
+                    since.as_deref(),
+                ));
+            }
+        },
+        Commands::Init { file, for_plugin } => {
+            if let Some(plugin_ref) = for_plugin {
+                if let Err(e) = init::handle_init_for_plugin(file, &plugin_ref) {
+                    logger::error(&e);
+                    std::process::exit(1);
+                }
+            } else {
+                init::handle_init(file, cli.global);
+            }
         }
 
         Commands::Run(cmd) => {
-            if let Err(e) = run::handle_run(cmd, cli.global) {
+            let exit_codes = cmd.exit_code_map.clone().unwrap_or_default();
+            let result = run::handle_run(*cmd, cli.global);
+            finalize_python();
+            if let Err(e) = result {
                 logger::error(&format!("Run command failed: {}", e));
-                std::process::exit(1);
+                std::process::exit(exit_codes.code_for(&e));
             }
         }
         Commands::Read(cmd) => {
-            if let Err(e) = read::handle_read(cmd, cli.global) {
+            let result = read::handle_read(cmd, cli.global);
+            finalize_python();
+            if let Err(e) = result {
                 logger::error(&format!("Read command failed: {}", e));
                 std::process::exit(1);
             }
@@ -1,7 +1,14 @@
+use once_cell::sync::Lazy;
+use r2x_logger as logger;
+use r2x_manifest::manifest::name_variants;
 use r2x_manifest::runtime::{build_runtime_bindings, PluginRole};
 use r2x_manifest::types::{Manifest, Package, Plugin};
 use std::collections::HashSet;
 use std::fmt;
+use std::sync::Mutex;
+
+/// Alias references already warned about in this process
+static WARNED_ALIASES: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
 
 #[derive(Debug)]
 pub enum PluginRefError {
@@ -11,6 +18,12 @@ pub enum PluginRefError {
         package: String,
         matches: Vec<String>,
     },
+    /// An unqualified name provided by more than one package
+    Duplicate {
+        plugin_ref: String,
+        /// Qualified `package.plugin` names
+        matches: Vec<String>,
+    },
 }
 
 impl fmt::Display for PluginRefError {
@@ -32,6 +45,17 @@ impl fmt::Display for PluginRefError {
                     matches.join(", ")
                 )
             }
+            PluginRefError::Duplicate {
+                plugin_ref,
+                matches,
+            } => {
+                write!(
+                    f,
+                    "Plugin '{}' is provided by several packages: {}. Qualify it as <package>.<plugin>",
+                    plugin_ref,
+                    matches.join(", ")
+                )
+            }
         }
     }
 }
@@ -47,20 +71,32 @@ pub fn resolve_plugin_ref<'a>(
     manifest: &'a Manifest,
     plugin_ref: &str,
 ) -> Result<ResolvedPlugin<'a>, PluginRefError> {
-    if let Some(resolved) = find_plugin_by_name(manifest, plugin_ref) {
-        return Ok(resolved);
+    for candidate in name_variants(plugin_ref) {
+        match manifest.plugin_locations(&candidate).as_slice() {
+            [] => {}
+            [(package, plugin)] => return Ok(ResolvedPlugin { package, plugin }),
+            matches => {
+                return Err(PluginRefError::Duplicate {
+                    plugin_ref: plugin_ref.to_string(),
+                    matches: matches
+                        .iter()
+                        .map(|(package, plugin)| format!("{}.{}", package.name, plugin.name))
+                        .collect(),
+                });
+            }
+        }
     }
 
     if let Some((package_part, plugin_part)) = plugin_ref.split_once('.') {
+        if let Some((package, plugin)) = manifest.find_plugin_qualified(package_part, plugin_part) {
+            return Ok(ResolvedPlugin { package, plugin });
+        }
+
         for package_name in name_variants(package_part) {
             let Some(package) = manifest.get_package(&package_name) else {
                 continue;
             };
 
-            if let Some(plugin) = find_plugin_in_package(package, plugin_part) {
-                return Ok(ResolvedPlugin { package, plugin });
-            }
-
             if let Some(role) = alias_role(plugin_part) {
                 let matches: Vec<&Plugin> = package
                     .plugins
@@ -92,56 +128,91 @@ pub fn resolve_plugin_ref<'a>(
         }
     }
 
+    if let Some(resolved) = resolve_alias(manifest, plugin_ref)? {
+        warn_alias_once(plugin_ref, &resolved);
+        return Ok(resolved);
+    }
+
     Err(PluginRefError::NotFound(plugin_ref.to_string()))
 }
 
-fn find_plugin_by_name<'a>(
+/// Find the plugin that lists `plugin_ref` (or its plugin part) among its aliases
+///
+/// Canonical names always win, so this is only consulted once they fail.
+fn resolve_alias<'a>(
     manifest: &'a Manifest,
-    plugin_name: &str,
-) -> Option<ResolvedPlugin<'a>> {
-    for candidate in name_variants(plugin_name) {
-        if let Some((package, plugin)) = manifest.packages.iter().find_map(|package| {
-            package
-                .plugins
-                .iter()
-                .find(|plugin| plugin.name.as_ref() == candidate)
-                .map(|plugin| (package, plugin))
-        }) {
-            return Some(ResolvedPlugin { package, plugin });
-        }
-    }
-    None
-}
+    plugin_ref: &str,
+) -> Result<Option<ResolvedPlugin<'a>>, PluginRefError> {
+    let ref_parts = std::iter::once((None, plugin_ref)).chain(
+        plugin_ref
+            .split_once('.')
+            .map(|(package, plugin)| (Some(package), plugin)),
+    );
 
-fn find_plugin_in_package<'a>(package: &'a Package, plugin_name: &str) -> Option<&'a Plugin> {
-    for candidate in name_variants(plugin_name) {
-        if let Some(plugin) = package
-            .plugins
+    for (package_part, alias) in ref_parts {
+        let packages = package_part.map(name_variants);
+        let aliases = name_variants(alias);
+        let matches: Vec<ResolvedPlugin<'a>> = manifest
+            .packages
             .iter()
-            .find(|plugin| plugin.name.as_ref() == candidate)
-        {
-            return Some(plugin);
+            .filter(|package| {
+                packages.as_ref().map_or(true, |names| {
+                    names.iter().any(|n| n == package.name.as_ref())
+                })
+            })
+            .flat_map(|package| {
+                package
+                    .plugins
+                    .iter()
+                    .filter(|plugin| {
+                        plugin
+                            .aliases
+                            .iter()
+                            .any(|a| aliases.iter().any(|n| n == a.as_ref()))
+                    })
+                    .map(move |plugin| ResolvedPlugin { package, plugin })
+            })
+            .collect();
+
+        match matches.len() {
+            0 => {}
+            1 => return Ok(matches.into_iter().next()),
+            _ => {
+                return Err(PluginRefError::Duplicate {
+                    plugin_ref: plugin_ref.to_string(),
+                    matches: matches
+                        .iter()
+                        .map(|r| format!("{}.{}", r.package.name, r.plugin.name))
+                        .collect(),
+                });
+            }
         }
     }
-    None
+    Ok(None)
 }
 
-fn name_variants(name: &str) -> Vec<String> {
-    let mut seen = HashSet::new();
-    let mut variants = Vec::new();
-    for candidate in [
-        name.to_string(),
-        name.replace('_', "-"),
-        name.replace('-', "_"),
-    ] {
-        if seen.insert(candidate.clone()) {
-            variants.push(candidate);
-        }
+/// Log the deprecation note for `plugin_ref` the first time it is resolved; `true` if logged
+fn warn_alias_once(plugin_ref: &str, resolved: &ResolvedPlugin<'_>) -> bool {
+    let first = WARNED_ALIASES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(plugin_ref.to_string());
+    if first {
+        logger::warn(&alias_deprecation_note(plugin_ref, resolved));
     }
-    variants
+    first
+}
+
+/// Warning logged when a pipeline still uses a plugin's former name
+fn alias_deprecation_note(plugin_ref: &str, resolved: &ResolvedPlugin<'_>) -> String {
+    format!(
+        "Plugin '{}' is a deprecated alias of '{}.{}'; update the reference",
+        plugin_ref, resolved.package.name, resolved.plugin.name
+    )
 }
 
-fn alias_role(name: &str) -> Option<PluginRole> {
+/// Map a role keyword such as `parser` or `transform` to its role
+pub(crate) fn alias_role(name: &str) -> Option<PluginRole> {
     let normalized = name.replace('-', "_").to_lowercase();
     match normalized.as_str() {
         "parser" => Some(PluginRole::Parser),
@@ -149,6 +220,7 @@ fn alias_role(name: &str) -> Option<PluginRole> {
         "upgrader" => Some(PluginRole::Upgrader),
         "modifier" | "transform" | "transformer" => Some(PluginRole::Modifier),
         "translation" | "translator" => Some(PluginRole::Translation),
+        "validator" | "validation" => Some(PluginRole::Validator),
         "utility" => Some(PluginRole::Utility),
         _ => None,
     }
@@ -213,6 +285,70 @@ mod tests {
         assert!(resolved.is_ok_and(|r| r.plugin.name.as_ref() == "break-gens"));
     }
 
+    #[test]
+    fn duplicate_name_across_packages_is_ambiguous() {
+        let mut manifest = sample_manifest();
+        let plexos = manifest.get_or_create_package("r2x-plexos");
+        plexos.add_plugin(Plugin {
+            name: Arc::from("break-gens"),
+            plugin_type: PluginType::Function,
+            module: Arc::from("r2x_plexos.sysmod"),
+            function_name: Some(Arc::from("break_generators")),
+            ..Default::default()
+        });
+
+        let resolved = resolve_plugin_ref(&manifest, "break_gens");
+        assert!(matches!(
+            resolved,
+            Err(PluginRefError::Duplicate { ref matches, .. })
+                if matches == &["r2x-reeds.break-gens", "r2x-plexos.break-gens"]
+        ));
+        let qualified = resolve_plugin_ref(&manifest, "r2x-plexos.break-gens");
+        assert!(qualified.is_ok_and(|r| r.package.name.as_ref() == "r2x-plexos"));
+    }
+
+    #[test]
+    fn resolves_plugin_by_former_name() {
+        let mut manifest = sample_manifest();
+        manifest.packages[0].plugins[1].aliases = smallvec::smallvec![Arc::from("split-gens")];
+
+        for plugin_ref in ["split-gens", "split_gens", "r2x-reeds.split-gens"] {
+            let resolved = resolve_plugin_ref(&manifest, plugin_ref);
+            assert!(resolved.is_ok_and(|r| r.plugin.name.as_ref() == "break-gens"));
+        }
+        assert!(matches!(
+            resolve_plugin_ref(&manifest, "r2x-plexos.split-gens"),
+            Err(PluginRefError::NotFound(_))
+        ));
+
+        let resolved = resolve_plugin_ref(&manifest, "split-gens");
+        assert!(resolved.is_ok_and(|r| alias_deprecation_note("split-gens", &r)
+            == "Plugin 'split-gens' is a deprecated alias of 'r2x-reeds.break-gens'; update the reference"));
+    }
+
+    #[test]
+    fn alias_warning_is_logged_once() {
+        let mut manifest = sample_manifest();
+        manifest.packages[0].plugins[1].aliases = smallvec::smallvec![Arc::from("gen-splitter")];
+        let resolved = resolve_plugin_ref(&manifest, "gen-splitter");
+        assert!(resolved.is_ok());
+        let Ok(resolved) = resolved else {
+            return;
+        };
+        // Resolving above already logged it.
+        assert!(!warn_alias_once("gen-splitter", &resolved));
+        assert!(warn_alias_once("r2x-reeds.gen-splitter", &resolved));
+        assert!(!warn_alias_once("r2x-reeds.gen-splitter", &resolved));
+    }
+
+    #[test]
+    fn canonical_name_wins_over_alias() {
+        let mut manifest = sample_manifest();
+        manifest.packages[0].plugins[1].aliases = smallvec::smallvec![Arc::from("reeds-parser")];
+        let resolved = resolve_plugin_ref(&manifest, "reeds-parser");
+        assert!(resolved.is_ok_and(|r| r.plugin.name.as_ref() == "reeds-parser"));
+    }
+
     #[test]
     fn resolves_plugin_kind_alias() {
         let manifest = sample_manifest();
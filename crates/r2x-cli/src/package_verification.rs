@@ -3,8 +3,10 @@
 use crate::manifest_lookup::resolve_plugin_ref;
 use r2x_config::Config;
 use r2x_logger as logger;
+use r2x_manifest::runtime::compare_versions;
 use r2x_manifest::types::Manifest;
 use r2x_python::utils::resolve_site_package_path;
+use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -50,6 +52,7 @@ impl std::error::Error for VerificationError {}
 /// # Arguments
 /// * `manifest` - Plugin manifest containing cached plugin metadata
 /// * `plugin_key` - Key of the plugin to verify (e.g., "parser-reeds")
+/// * `config` - Configuration naming the venv to check
 ///
 /// # Returns
 /// * `Ok(VerificationResult::Valid)` - All packages installed and valid
@@ -58,6 +61,7 @@ impl std::error::Error for VerificationError {}
 pub fn verify_plugin_packages(
     manifest: &Manifest,
     plugin_key: &str,
+    config: &Config,
 ) -> Result<VerificationResult, VerificationError> {
     logger::debug(&format!("Verifying packages for plugin: {}", plugin_key));
 
@@ -65,10 +69,6 @@ pub fn verify_plugin_packages(
         .map_err(|e| VerificationError::VerificationFailed(e.to_string()))?;
     let package_name = resolved.package.name.to_string();
 
-    // Get venv path
-    let config = Config::load().map_err(|e| {
-        VerificationError::VerificationFailed(format!("Failed to load config: {}", e))
-    })?;
     let venv_path = PathBuf::from(config.get_venv_path());
 
     if !venv_path.exists() {
@@ -228,6 +228,8 @@ pub fn ensure_packages(packages: Vec<String>, config: &Config) -> Result<(), Ver
 /// # Arguments
 /// * `manifest` - Plugin manifest
 /// * `plugin_key` - Plugin to verify (e.g., "parser-reeds")
+/// * `config` - Configuration naming the venv, e.g. from `r2x run --python`;
+///   `None` reads the saved config
 ///
 /// # Returns
 /// * `Ok(())` - Plugin packages verified and available
@@ -243,34 +245,123 @@ pub fn ensure_packages(packages: Vec<String>, config: &Config) -> Result<(), Ver
 ///
 /// // This will verify r2x-reeds is installed
 /// // If missing, it will automatically reinstall it
-/// verify_and_ensure_plugin(&manifest, "parser-reeds")?;
+/// verify_and_ensure_plugin(&manifest, "parser-reeds", None)?;
 ///
 /// // Now safe to run the plugin
 /// ```
 pub fn verify_and_ensure_plugin(
     manifest: &Manifest,
     plugin_key: &str,
+    config: Option<&Config>,
 ) -> Result<(), VerificationError> {
     logger::debug(&format!("Verifying and ensuring plugin: {}", plugin_key));
 
-    match verify_plugin_packages(manifest, plugin_key)? {
+    let saved;
+    let config = if let Some(config) = config {
+        config
+    } else {
+        saved = Config::load().map_err(|e| {
+            VerificationError::VerificationFailed(format!("Failed to load config: {}", e))
+        })?;
+        &saved
+    };
+
+    match verify_plugin_packages(manifest, plugin_key, config)? {
         VerificationResult::Valid => {
             logger::debug("All packages verified successfully");
-            Ok(())
         }
         VerificationResult::Missing(packages) => {
             logger::info(&format!(
                 "Missing {} package(s), reinstalling...",
                 packages.len()
             ));
-            let config = Config::load().map_err(|e| {
-                VerificationError::ReinstallFailed(format!("Failed to load config: {}", e))
-            })?;
-            ensure_packages(packages, &config)?;
+            ensure_packages(packages, config)?;
             logger::success("Packages verified and installed");
+        }
+    }
+
+    ensure_core_compatible(manifest, plugin_key, config)
+}
+
+/// Fail when the plugin's `requires_core=` is newer than the installed r2x-core
+fn ensure_core_compatible(
+    manifest: &Manifest,
+    plugin_key: &str,
+    config: &Config,
+) -> Result<(), VerificationError> {
+    let resolved = resolve_plugin_ref(manifest, plugin_key)
+        .map_err(|e| VerificationError::VerificationFailed(e.to_string()))?;
+    let Some(required) = resolved.plugin.min_r2x_core_version.as_deref() else {
+        return Ok(());
+    };
+
+    let site_packages = get_site_packages_dir(&PathBuf::from(config.get_venv_path()))?;
+    let installed = installed_version(&site_packages, "r2x-core");
+    check_core_version(&resolved.plugin.name, required, installed.as_deref())
+}
+
+/// Compare the installed r2x-core against a plugin's minimum
+///
+/// Only the numeric release part is compared, so `0.10.0a1` counts as
+/// `0.10.0`. An r2x-core that can't be found is left for the import to report.
+fn check_core_version(
+    plugin_name: &str,
+    required: &str,
+    installed: Option<&str>,
+) -> Result<(), VerificationError> {
+    match installed {
+        Some(installed)
+            if compare_versions(release_part(installed), release_part(required))
+                == Ordering::Less =>
+        {
+            Err(VerificationError::VerificationFailed(format!(
+                "'{}' requires r2x-core >= {}, but {} is installed; upgrade it with `uv pip install --upgrade \"r2x-core>={}\"`",
+                plugin_name, required, installed, required
+            )))
+        }
+        Some(installed) => {
+            logger::debug(&format!(
+                "r2x-core {} satisfies '{}' (>= {})",
+                installed, plugin_name, required
+            ));
             Ok(())
         }
+        None => {
+            logger::debug("r2x-core not found in site-packages; skipping version check");
+            Ok(())
+        }
+    }
+}
+
+/// Leading dotted-number release of a version, e.g. `0.10.0` of `0.10.0a1`
+fn release_part(version: &str) -> &str {
+    let version = version.trim().trim_start_matches('v');
+    let mut end = 0;
+    for (idx, part) in version.split('.').enumerate() {
+        let digits = part.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            break;
+        }
+        end += usize::from(idx > 0) + digits;
+        if digits < part.len() {
+            break;
+        }
     }
+    version[..end].trim_end_matches('.')
+}
+
+/// Version of `package` from its `<name>-<version>.dist-info` directory
+fn installed_version(site_packages: &Path, package: &str) -> Option<String> {
+    let prefix = format!("{}-", package.replace('-', "_").to_ascii_lowercase());
+    std::fs::read_dir(site_packages)
+        .ok()?
+        .flatten()
+        .find_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            name.strip_prefix(&prefix)?
+                .strip_suffix(".dist-info")
+                .map(str::to_string)
+        })
 }
 
 /// Verify all packages in the manifest (for batch operations)
@@ -357,6 +448,35 @@ mod tests {
         assert!(example_dist_info.ends_with(".dist-info"));
     }
 
+    #[test]
+    fn test_core_version_gates_plugins_needing_a_newer_core() {
+        let Ok(site_packages) = tempfile::tempdir() else {
+            return;
+        };
+        assert!(std::fs::create_dir(site_packages.path().join("r2x_core-0.4.2.dist-info")).is_ok());
+        let installed = installed_version(site_packages.path(), "r2x-core");
+        assert_eq!(installed.as_deref(), Some("0.4.2"));
+
+        assert!(check_core_version("r2x-reeds.parser", "0.4", installed.as_deref()).is_ok());
+        let err = check_core_version("r2x-reeds.parser", "0.5.1", installed.as_deref());
+        assert!(matches!(
+            err,
+            Err(VerificationError::VerificationFailed(ref msg))
+                if msg.contains("requires r2x-core >= 0.5.1, but 0.4.2 is installed")
+        ));
+        assert!(check_core_version("r2x-reeds.parser", "0.5.1", None).is_ok());
+    }
+
+    #[test]
+    fn test_core_version_ignores_pre_release_suffixes() {
+        assert_eq!(release_part("0.10.0a1"), "0.10.0");
+        assert_eq!(release_part("1.2rc1"), "1.2");
+        assert_eq!(release_part("0.5.0.dev3"), "0.5.0");
+        assert!(check_core_version("r2x-reeds.parser", "0.9", Some("0.10.0a1")).is_ok());
+        assert!(check_core_version("r2x-reeds.parser", "0.10.0", Some("0.10.0rc1")).is_ok());
+        assert!(check_core_version("r2x-reeds.parser", "0.10", Some("0.9.5b2")).is_err());
+    }
+
     #[test]
     fn test_verification_workflow() {
         // This test documents the expected verification workflow
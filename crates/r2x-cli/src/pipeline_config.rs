@@ -1,6 +1,9 @@
-use crate::errors::PipelineError;
+use crate::errors::{PipelineConfigError, PipelineError};
+use crate::secrets::{Secrets, SECRET_PREFIX};
+use serde::de::{Deserializer, IgnoredAny, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -11,9 +14,15 @@ pub struct PipelineConfig {
     #[serde(default)]
     pub variables: HashMap<String, serde_yaml::Value>,
 
-    /// Named pipelines (each is a list of plugin names)
+    /// Values to run the pipeline over with `r2x run --matrix` (keyed by variable name)
+    ///
+    /// Every combination of values runs once, set as pipeline variables.
     #[serde(default)]
-    pub pipelines: HashMap<String, Vec<String>>,
+    pub matrix: HashMap<String, Vec<serde_yaml::Value>>,
+
+    /// Named pipelines (each is a list of plugin names or dependency nodes)
+    #[serde(default)]
+    pub pipelines: HashMap<String, Pipeline>,
 
     /// Output folder for pipeline results
     #[serde(default)]
@@ -22,27 +31,461 @@ pub struct PipelineConfig {
     /// Plugin configuration (keyed by plugin name)
     #[serde(default)]
     pub config: HashMap<String, serde_yaml::Value>,
+
+    /// Machine-wide step configs from [`PIPELINE_DEFAULTS_FILE`], keyed like `config`
+    ///
+    /// Kept apart from `config` and merged under a step's entry when it is
+    /// looked up; see [`plugin_config_json_with_defaults`](Self::plugin_config_json_with_defaults).
+    #[serde(skip)]
+    pub config_defaults: HashMap<String, serde_yaml::Value>,
+
+    /// Working directory to run each step in (keyed by plugin name)
+    #[serde(default)]
+    pub working_dir: HashMap<String, String>,
+
+    /// Extra directories on `sys.path` while each step runs (keyed by plugin name)
+    #[serde(default)]
+    pub python_path: HashMap<String, Vec<String>>,
+
+    /// Commands run when the whole pipeline finishes
+    #[serde(default, flatten)]
+    pub hooks: PipelineHooks,
+
+    /// Commands run when a step finishes (keyed by plugin name)
+    #[serde(default)]
+    pub step_hooks: HashMap<String, PipelineHooks>,
+
+    /// Keys as written in the source file, for [`PipelineConfig::validate`]
+    #[serde(skip)]
+    pub source_keys: SourceKeys,
+
+    /// Values for `${SECRET:key}` references, loaded from `--secrets`
+    #[serde(skip)]
+    pub secrets: Option<Secrets>,
+}
+
+/// Starts a step config that names a file to load it from, e.g. `"@configs/reeds.yaml"`
+pub const CONFIG_FILE_PREFIX: &str = "@";
+
+/// Machine-wide step config defaults, read from the r2x config directory
+pub const PIPELINE_DEFAULTS_FILE: &str = "pipeline-defaults.yaml";
+
+/// Key of a step config mapping whose `@path` file supplies the keys not set inline
+const CONFIG_MERGE_KEY: &str = "<<";
+
+/// Top-level keys accepted in a pipeline file
+const KNOWN_KEYS: &[&str] = &[
+    "variables",
+    "matrix",
+    "pipelines",
+    "output_folder",
+    "config",
+    "working_dir",
+    "python_path",
+    "on_success",
+    "on_failure",
+    "step_hooks",
+];
+
+/// Contents of [`PIPELINE_DEFAULTS_FILE`]
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct PipelineDefaults {
+    /// Step configs keyed like a pipeline's `config`
+    #[serde(default)]
+    config: HashMap<String, serde_yaml::Value>,
+}
+
+/// Mapping keys in source order, duplicates included
+///
+/// Deserializing into a `HashMap` keeps only the last of several equal keys and
+/// drops unknown fields, so these are read separately from the raw YAML.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceKeys {
+    pub top_level: Vec<String>,
+    pub pipelines: Vec<String>,
+}
+
+impl SourceKeys {
+    /// Best effort: a document whose keys cannot be read yields no keys
+    fn read(content: &str) -> Self {
+        #[derive(Deserialize)]
+        struct PipelinesSection {
+            #[serde(default)]
+            pipelines: MapKeys,
+        }
+
+        let top_level = serde_yaml::from_str::<MapKeys>(content).unwrap_or_default();
+        let pipelines = serde_yaml::from_str::<PipelinesSection>(content)
+            .map(|section| section.pipelines)
+            .unwrap_or_default();
+        Self {
+            top_level: top_level.0,
+            pipelines: pipelines.0,
+        }
+    }
+}
+
+/// One named pipeline
+///
+/// Written as a bare step list, or as `{steps: [...], output_folder: ...}`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(from = "PipelineDefinition", into = "PipelineDefinition")]
+pub struct Pipeline {
+    pub steps: Vec<PipelineStep>,
+    /// Overrides the top-level `output_folder` when this pipeline runs
+    pub output_folder: Option<String>,
+}
+
+/// A pipeline as written in the file
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum PipelineDefinition {
+    Steps(Vec<PipelineStep>),
+    Detailed(DetailedPipeline),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DetailedPipeline {
+    steps: Vec<PipelineStep>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_folder: Option<String>,
+}
+
+impl From<PipelineDefinition> for Pipeline {
+    fn from(definition: PipelineDefinition) -> Self {
+        match definition {
+            PipelineDefinition::Steps(steps) => Self {
+                steps,
+                output_folder: None,
+            },
+            PipelineDefinition::Detailed(detailed) => Self {
+                steps: detailed.steps,
+                output_folder: detailed.output_folder,
+            },
+        }
+    }
+}
+
+impl From<Pipeline> for PipelineDefinition {
+    fn from(pipeline: Pipeline) -> Self {
+        match pipeline.output_folder {
+            None => PipelineDefinition::Steps(pipeline.steps),
+            output_folder => PipelineDefinition::Detailed(DetailedPipeline {
+                steps: pipeline.steps,
+                output_folder,
+            }),
+        }
+    }
+}
+
+#[derive(Default)]
+struct MapKeys(Vec<String>);
+
+impl<'de> Deserialize<'de> for MapKeys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeysVisitor;
+
+        impl<'de> Visitor<'de> for KeysVisitor {
+            type Value = MapKeys;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a mapping")
+            }
+
+            fn visit_unit<E>(self) -> Result<MapKeys, E> {
+                Ok(MapKeys::default())
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<MapKeys, A::Error> {
+                let mut keys = Vec::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    map.next_value::<IgnoredAny>()?;
+                    keys.push(key);
+                }
+                Ok(MapKeys(keys))
+            }
+        }
+
+        deserializer.deserialize_any(KeysVisitor)
+    }
+}
+
+/// Shell commands run after a pipeline or step finishes
+///
+/// Commands run through the system shell with the permissions of the r2x
+/// process, so a pipeline file with hooks is as trusted as a shell script:
+/// only run pipelines from sources you trust. `${pipeline}`, `${status}`
+/// (`success` or `failure`) and, for step hooks, `${step}` are substituted
+/// before the pipeline variables.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PipelineHooks {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+}
+
+impl PipelineHooks {
+    /// The hook matching the outcome, if one is configured
+    pub fn for_outcome(&self, succeeded: bool) -> Option<&str> {
+        if succeeded {
+            self.on_success.as_deref()
+        } else {
+            self.on_failure.as_deref()
+        }
+    }
+}
+
+/// A pipeline entry: either a bare plugin name or a node with explicit dependencies
+///
+/// A bare name requires the entry listed before it, so flat lists keep
+/// running top to bottom. A node with an empty `requires` starts a new branch.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PipelineStep {
+    Name(String),
+    Node {
+        name: String,
+        #[serde(default)]
+        requires: Vec<String>,
+        /// Labels `r2x run --tags` selects steps by
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+    },
+}
+
+impl PipelineStep {
+    pub fn name(&self) -> &str {
+        match self {
+            PipelineStep::Name(name) | PipelineStep::Node { name, .. } => name,
+        }
+    }
+
+    /// Tags of an object-form step; bare names have none
+    pub fn tags(&self) -> &[String] {
+        match self {
+            PipelineStep::Name(_) => &[],
+            PipelineStep::Node { tags, .. } => tags,
+        }
+    }
+}
+
+/// A pipeline step in execution order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedStep {
+    pub name: String,
+    /// Index in the pipeline's execution order, kept when `--step` or
+    /// `--tags` select a subset; tells repeated steps apart
+    pub position: usize,
+    /// Step whose output is passed to this one on stdin
+    ///
+    /// With several requirements this is the last one listed.
+    pub input: Option<String>,
+    /// [`ResolvedStep::position`] of `input`, which may repeat by name
+    pub input_position: Option<usize>,
+    /// Tags given to the step in the pipeline file
+    pub tags: Vec<String>,
 }
 
 impl PipelineConfig {
     /// Load pipeline configuration from YAML file
+    ///
+    /// Step configs written as `"@path"`, or as a mapping with a `<<: "@path"`
+    /// key, are read from that file relative to the pipeline file; see
+    /// [`load_config_files`](Self::load_config_files).
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PipelineError> {
         let path_ref = path.as_ref();
-        let content = match fs::read_to_string(path_ref) {
-            Ok(content) => content,
+        let (content, loaded_path) = match fs::read_to_string(path_ref) {
+            Ok(content) => (content, path_ref.to_path_buf()),
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
                 if let Some(fallback) = Self::resolve_fallback_path(path_ref) {
-                    fs::read_to_string(fallback)?
+                    (fs::read_to_string(&fallback)?, fallback)
                 } else {
                     return Err(PipelineError::Io(err));
                 }
             }
             Err(err) => return Err(PipelineError::Io(err)),
         };
-        let config: PipelineConfig = serde_yaml::from_str(&content)?;
+        let mut config = Self::parse(&content)?;
+        let base_dir = loaded_path.parent().unwrap_or_else(|| Path::new(""));
+        config.load_config_files(base_dir)?;
+        Ok(config)
+    }
+
+    /// Replace `@path` step configs with the YAML (or JSON) file they name
+    ///
+    /// `reeds: "@configs/reeds.yaml"` takes the whole config from the file.
+    /// `reeds: {<<: "@configs/reeds.yaml", solve_year: 2035}` starts from the
+    /// file and lets the inline keys override it. Paths are relative to
+    /// `base_dir`, or to the including file for `@path` configs inside loaded
+    /// files. Missing files and files that include themselves are errors.
+    pub fn load_config_files(&mut self, base_dir: &Path) -> Result<(), PipelineError> {
+        for (step, value) in &mut self.config {
+            let mut chain = Vec::new();
+            *value = resolve_config_file(value, base_dir, &mut chain).map_err(|reason| {
+                PipelineError::ConfigFile {
+                    step: step.clone(),
+                    reason,
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Read the step configs of a defaults file into `config_defaults`
+    ///
+    /// A missing file leaves the config unchanged and returns `false`.
+    pub fn load_defaults_file(&mut self, path: &Path) -> Result<bool, PipelineError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(PipelineError::Io(err)),
+        };
+        let defaults: PipelineDefaults = serde_yaml::from_str(&content)
+            .map_err(|e| PipelineError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        self.config_defaults = defaults.config;
+        Ok(true)
+    }
+
+    /// Parse pipeline configuration from YAML text
+    pub fn parse(content: &str) -> Result<Self, PipelineError> {
+        let mut config: PipelineConfig = serde_yaml::from_str(content)?;
+        config.source_keys = SourceKeys::read(content);
         Ok(config)
     }
 
+    /// Check the file's structure, collecting every problem found
+    ///
+    /// Reports unknown top-level keys, pipelines defined twice, empty step
+    /// names and undefined variables in `output_folder`. The first two are
+    /// only visible on configs read with [`load`](Self::load) or
+    /// [`parse`](Self::parse).
+    pub fn validate(&self) -> Result<(), Vec<PipelineConfigError>> {
+        self.validate_with(false)
+    }
+
+    /// [`validate`](Self::validate), also rejecting `config`, `working_dir`,
+    /// `python_path` and `step_hooks` entries for plugins no pipeline runs
+    pub fn validate_strict(&self) -> Result<(), Vec<PipelineConfigError>> {
+        self.validate_with(true)
+    }
+
+    fn validate_with(&self, strict: bool) -> Result<(), Vec<PipelineConfigError>> {
+        let mut errors: Vec<PipelineConfigError> = self
+            .source_keys
+            .top_level
+            .iter()
+            .filter(|key| !KNOWN_KEYS.contains(&key.as_str()))
+            .map(|key| PipelineConfigError::UnknownKey(key.clone()))
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for name in &self.source_keys.pipelines {
+            if !seen.insert(name) && reported.insert(name) {
+                errors.push(PipelineConfigError::DuplicatePipeline(name.clone()));
+            }
+        }
+
+        for name in self.list_pipelines() {
+            for (index, step) in self.pipelines[&name].steps.iter().enumerate() {
+                if step.name().trim().is_empty() {
+                    errors.push(PipelineConfigError::EmptyStep {
+                        pipeline: name.clone(),
+                        position: index + 1,
+                    });
+                }
+            }
+        }
+
+        let mut output_folders: Vec<&String> = self
+            .pipelines
+            .values()
+            .filter_map(|pipeline| pipeline.output_folder.as_ref())
+            .collect();
+        output_folders.sort();
+        for output_folder in self.output_folder.iter().chain(output_folders) {
+            errors.extend(
+                variable_references(output_folder)
+                    .into_iter()
+                    .filter(|var| {
+                        !var.starts_with(SECRET_PREFIX)
+                            && !self.variables.contains_key(*var)
+                            && !self.matrix.contains_key(*var)
+                    })
+                    .map(|var| PipelineConfigError::UndefinedVariable(var.to_string())),
+            );
+        }
+
+        if strict {
+            let steps: HashSet<&str> = self
+                .pipelines
+                .values()
+                .flat_map(|pipeline| &pipeline.steps)
+                .map(PipelineStep::name)
+                .collect();
+            let sections = [
+                ("config", self.config.keys().collect::<Vec<_>>()),
+                ("working_dir", self.working_dir.keys().collect()),
+                ("python_path", self.python_path.keys().collect()),
+                ("step_hooks", self.step_hooks.keys().collect()),
+            ];
+            for (section, mut plugins) in sections {
+                plugins.sort();
+                errors.extend(
+                    plugins
+                        .into_iter()
+                        .filter(|plugin| !steps.contains(plugin.as_str()))
+                        .map(|plugin| PipelineConfigError::UnusedPluginEntry {
+                            section,
+                            plugin: plugin.clone(),
+                        }),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Every combination of `matrix` values, variables in name order
+    ///
+    /// Empty when there is no matrix or a variable has no values.
+    pub fn matrix_combinations(&self) -> Vec<Vec<(String, serde_yaml::Value)>> {
+        let mut names: Vec<&String> = self.matrix.keys().collect();
+        if names.is_empty() {
+            return Vec::new();
+        }
+        names.sort();
+
+        let mut combinations = vec![Vec::new()];
+        for name in names {
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combination: Vec<(String, serde_yaml::Value)>| {
+                    self.matrix[name].iter().map(move |value| {
+                        let mut combination = combination.clone();
+                        combination.push((name.clone(), value.clone()));
+                        combination
+                    })
+                })
+                .collect();
+        }
+        combinations
+    }
+
+    /// This config with `variables` set (overriding any of the same name)
+    pub fn with_variables(&self, variables: &[(String, serde_yaml::Value)]) -> Self {
+        let mut config = self.clone();
+        config.variables.extend(variables.iter().cloned());
+        config
+    }
+
     /// List all available pipeline names
     pub fn list_pipelines(&self) -> Vec<String> {
         let mut names: Vec<String> = self.pipelines.keys().cloned().collect();
@@ -50,46 +493,155 @@ impl PipelineConfig {
         names
     }
 
+    /// Output folder for `pipeline`: its own `output_folder`, else the top-level one
+    ///
+    /// Variables are not substituted.
+    pub fn output_folder_for(&self, pipeline: &str) -> Option<&str> {
+        self.pipelines
+            .get(pipeline)
+            .and_then(|pipeline| pipeline.output_folder.as_ref())
+            .or(self.output_folder.as_ref())
+            .map(String::as_str)
+    }
+
     /// Get a specific pipeline by name
-    pub fn get_pipeline(&self, name: &str) -> Option<&Vec<String>> {
-        self.pipelines.get(name)
+    pub fn get_pipeline(&self, name: &str) -> Option<&Vec<PipelineStep>> {
+        self.pipelines.get(name).map(|pipeline| &pipeline.steps)
+    }
+
+    /// Step names of a pipeline in execution order
+    pub fn pipeline_steps(&self, name: &str) -> Result<Vec<String>, PipelineError> {
+        Ok(self
+            .resolve_pipeline(name)?
+            .into_iter()
+            .map(|step| step.name)
+            .collect())
+    }
+
+    /// Order a pipeline's steps so every step runs after the ones it requires
+    ///
+    /// Ready steps run in the order they are listed. A step may repeat in
+    /// the list unless another step requires it by name. Unknown or
+    /// ambiguous requirements, more than one requirement per step, and
+    /// cycles are errors.
+    pub fn resolve_pipeline(&self, name: &str) -> Result<Vec<ResolvedStep>, PipelineError> {
+        let steps = self
+            .get_pipeline(name)
+            .ok_or_else(|| PipelineError::PipelineNotFound(name.to_string()))?;
+
+        let mut index: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (idx, step) in steps.iter().enumerate() {
+            index.entry(step.name()).or_default().push(idx);
+        }
+
+        let mut requires: Vec<Vec<usize>> = Vec::with_capacity(steps.len());
+        for (idx, step) in steps.iter().enumerate() {
+            let deps = match step {
+                PipelineStep::Name(_) => idx.checked_sub(1).into_iter().collect(),
+                PipelineStep::Node { requires, .. } => {
+                    if requires.len() > 1 {
+                        return Err(PipelineError::InvalidConfig(format!(
+                            "Step '{}' in pipeline '{}' requires {} steps; a step takes the output of only one",
+                            step.name(),
+                            name,
+                            requires.len()
+                        )));
+                    }
+                    requires
+                        .iter()
+                        .map(|dep| match index.get(dep.as_str()).map(Vec::as_slice) {
+                            Some(&[dep_idx]) => Ok(dep_idx),
+                            Some(_) => Err(PipelineError::InvalidConfig(format!(
+                                "Step '{}' in pipeline '{}' requires '{}', which is listed more than once",
+                                step.name(),
+                                name,
+                                dep
+                            ))),
+                            None => Err(PipelineError::InvalidConfig(format!(
+                                "Step '{}' in pipeline '{}' requires unknown step '{}'",
+                                step.name(),
+                                name,
+                                dep
+                            ))),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?
+                }
+            };
+            requires.push(deps);
+        }
+
+        let mut done = vec![false; steps.len()];
+        let mut position_of = vec![0; steps.len()];
+        let mut order = Vec::with_capacity(steps.len());
+        while order.len() < steps.len() {
+            let ready = (0..steps.len())
+                .find(|&idx| !done[idx] && requires[idx].iter().all(|&dep| done[dep]));
+            let Some(idx) = ready else {
+                let blocked: Vec<&str> = (0..steps.len())
+                    .filter(|&idx| !done[idx])
+                    .map(|idx| steps[idx].name())
+                    .collect();
+                return Err(PipelineError::InvalidConfig(format!(
+                    "Dependency cycle in pipeline '{}' between: {}",
+                    name,
+                    blocked.join(", ")
+                )));
+            };
+            done[idx] = true;
+            position_of[idx] = order.len();
+            let input = requires[idx].last().copied();
+            order.push(ResolvedStep {
+                name: steps[idx].name().to_string(),
+                position: order.len(),
+                input: input.map(|dep| steps[dep].name().to_string()),
+                input_position: input.map(|dep| position_of[dep]),
+                tags: steps[idx].tags().to_vec(),
+            });
+        }
+
+        Ok(order)
     }
 
     /// Substitute variables in a string (supports ${var} and $(var) syntax)
+    ///
+    /// `${SECRET:key}` reads `key` from [`PipelineConfig::secrets`]. Values are
+    /// inserted as-is and never scanned again, so a value containing `${` or
+    /// `$(` is kept literally.
     pub fn substitute_string(&self, input: &str) -> Result<String, PipelineError> {
-        let mut result = input.to_string();
-
-        // Handle ${var} syntax
-        while let Some(start) = result.find("${") {
-            if let Some(end) = result[start..].find('}') {
-                let var_name = &result[start + 2..start + end];
-                let value = self.get_variable_string(var_name)?;
-                result.replace_range(start..=(start + end), &value);
-            } else {
-                return Err(PipelineError::InvalidConfig(
-                    "Unclosed variable substitution ${".to_string(),
-                ));
-            }
-        }
+        let mut result = String::with_capacity(input.len());
+        let mut rest = input;
 
-        // Handle $(var) syntax
-        while let Some(start) = result.find("$(") {
-            if let Some(end) = result[start..].find(')') {
-                let var_name = &result[start + 2..start + end];
-                let value = self.get_variable_string(var_name)?;
-                result.replace_range(start..=(start + end), &value);
+        while let Some(start) = rest.find("${").into_iter().chain(rest.find("$(")).min() {
+            let (open, close) = if rest[start..].starts_with("${") {
+                ("${", '}')
             } else {
-                return Err(PipelineError::InvalidConfig(
-                    "Unclosed variable substitution $(".to_string(),
-                ));
-            }
+                ("$(", ')')
+            };
+            let Some(end) = rest[start..].find(close) else {
+                return Err(PipelineError::InvalidConfig(format!(
+                    "Unclosed variable substitution {}",
+                    open
+                )));
+            };
+            let var_name = &rest[start + 2..start + end];
+            result.push_str(&rest[..start]);
+            result.push_str(&self.get_variable_string(var_name)?);
+            rest = &rest[start + end + 1..];
         }
+        result.push_str(rest);
 
         Ok(result)
     }
 
     /// Get a variable value as a string
-    fn get_variable_string(&self, name: &str) -> Result<String, PipelineError> {
+    pub(crate) fn get_variable_string(&self, name: &str) -> Result<String, PipelineError> {
+        if let Some(key) = name.strip_prefix(SECRET_PREFIX) {
+            return match &self.secrets {
+                Some(secrets) => secrets.get(key).map(str::to_string),
+                None => Err(PipelineError::NoSecretsFile(key.to_string())),
+            };
+        }
+
         let value = self
             .variables
             .get(name)
@@ -150,6 +702,38 @@ impl PipelineConfig {
         self.substitute_value(config)
     }
 
+    /// A step's `config` entry merged over its `config_defaults` entry, as JSON
+    ///
+    /// The two keys are found separately, so a default written as
+    /// `r2x-reeds.parser` still sits under a pipeline entry written as
+    /// `parser`. Values set by the pipeline win, explicit nulls included;
+    /// nested mappings are merged key by key. The defaults stand alone only
+    /// when the step has no entry at all.
+    pub fn plugin_config_json_with_defaults(
+        &self,
+        config_key: Option<&str>,
+        defaults_key: Option<&str>,
+    ) -> Result<String, PipelineError> {
+        let entry = config_key.and_then(|key| self.config.get(key));
+        let defaults = defaults_key.and_then(|key| self.config_defaults.get(key));
+        let value = match (entry, defaults) {
+            (Some(entry), Some(defaults)) => {
+                let mut value = entry.clone();
+                merge_under(&mut value, defaults.clone());
+                value
+            }
+            (Some(value), None) | (None, Some(value)) => value.clone(),
+            (None, None) => serde_yaml::Value::Null,
+        };
+        if value.is_null() {
+            return Ok("{}".to_string());
+        }
+        let value = self.substitute_value(&value)?;
+        serde_json::to_string(&value).map_err(|e| {
+            PipelineError::InvalidConfig(format!("Failed to serialize config to JSON: {}", e))
+        })
+    }
+
     /// Get plugin configuration as JSON string (for Python bridge)
     pub fn get_plugin_config_json(&self, plugin_name: &str) -> Result<String, PipelineError> {
         let config = self.get_plugin_config(plugin_name)?;
@@ -158,6 +742,52 @@ impl PipelineConfig {
         })
     }
 
+    /// Resolve the working directory for a step, if one is configured
+    ///
+    /// Variables are substituted before the path is checked, and the
+    /// directory must already exist.
+    pub fn get_working_dir(&self, plugin_name: &str) -> Result<Option<PathBuf>, PipelineError> {
+        let Some(raw) = self.working_dir.get(plugin_name) else {
+            return Ok(None);
+        };
+
+        let path = PathBuf::from(self.substitute_string(raw)?);
+        if !path.is_dir() {
+            return Err(PipelineError::InvalidConfig(format!(
+                "Working directory for '{}' does not exist: {}",
+                plugin_name,
+                path.display()
+            )));
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Resolve the extra `sys.path` directories for a step, in order
+    ///
+    /// Variables are substituted before each path is checked, and every
+    /// directory must already exist.
+    pub fn get_python_path(&self, plugin_name: &str) -> Result<Vec<PathBuf>, PipelineError> {
+        let Some(raw_paths) = self.python_path.get(plugin_name) else {
+            return Ok(Vec::new());
+        };
+
+        raw_paths
+            .iter()
+            .map(|raw| {
+                let path = PathBuf::from(self.substitute_string(raw)?);
+                if !path.is_dir() {
+                    return Err(PipelineError::InvalidConfig(format!(
+                        "python_path entry for '{}' does not exist: {}",
+                        plugin_name,
+                        path.display()
+                    )));
+                }
+                Ok(path)
+            })
+            .collect()
+    }
+
     /// Get all plugin configurations with variable substitution
     pub fn get_all_configs(&self) -> Result<HashMap<String, serde_yaml::Value>, PipelineError> {
         let mut configs = HashMap::new();
@@ -169,9 +799,7 @@ impl PipelineConfig {
 
     /// Resolve and print the configuration for a specific pipeline
     pub fn print_pipeline_config(&self, pipeline_name: &str) -> Result<String, PipelineError> {
-        let pipeline = self
-            .get_pipeline(pipeline_name)
-            .ok_or_else(|| PipelineError::PipelineNotFound(pipeline_name.to_string()))?;
+        let pipeline = self.pipeline_steps(pipeline_name)?;
 
         let mut output = String::new();
         output.push_str(&format!("Pipeline: {}\n", pipeline_name));
@@ -183,8 +811,22 @@ impl PipelineConfig {
         }
 
         output.push_str("\nResolved Configuration:\n");
-        for plugin_name in pipeline {
-            if let Ok(config) = self.get_plugin_config(plugin_name) {
+        for plugin_name in &pipeline {
+            let config_key =
+                Some(plugin_name.as_str()).filter(|key| self.config.contains_key(*key));
+            let defaults_key =
+                Some(plugin_name.as_str()).filter(|key| self.config_defaults.contains_key(*key));
+            if config_key.is_none() && defaults_key.is_none() {
+                continue;
+            }
+            let resolved = self
+                .plugin_config_json_with_defaults(config_key, defaults_key)
+                .and_then(|json| {
+                    serde_json::from_str::<serde_yaml::Value>(&json).map_err(|e| {
+                        PipelineError::InvalidConfig(format!("Failed to parse config JSON: {}", e))
+                    })
+                });
+            if let Ok(config) = resolved {
                 output.push_str(&format!("\n{}:\n", plugin_name));
                 let yaml_str = serde_yaml::to_string(&config).unwrap_or_else(|_| "{}".to_string());
                 for line in yaml_str.lines() {
@@ -193,7 +835,7 @@ impl PipelineConfig {
             }
         }
 
-        if let Some(output_folder) = &self.output_folder {
+        if let Some(output_folder) = self.output_folder_for(pipeline_name) {
             let resolved = self.substitute_string(output_folder)?;
             output.push_str(&format!("\nOutput Folder: {}\n", resolved));
         }
@@ -213,6 +855,119 @@ impl PipelineConfig {
     }
 }
 
+/// `value` with an `@path` reference, or a `<<: "@path"` merge key, loaded from disk
+///
+/// `chain` holds the files being loaded, outermost first, to catch cycles.
+fn resolve_config_file(
+    value: &serde_yaml::Value,
+    base_dir: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> Result<serde_yaml::Value, String> {
+    use serde_yaml::Value;
+
+    if let Some(reference) = config_file_reference(value) {
+        return load_config_file(reference, base_dir, chain);
+    }
+    let Value::Mapping(map) = value else {
+        return Ok(value.clone());
+    };
+    let merge_key = Value::String(CONFIG_MERGE_KEY.to_string());
+    let Some(reference) = map.get(&merge_key).and_then(config_file_reference) else {
+        return Ok(value.clone());
+    };
+
+    let Value::Mapping(mut merged) = load_config_file(reference, base_dir, chain)? else {
+        return Err(format!(
+            "{} must hold a mapping to be merged with inline keys",
+            reference
+        ));
+    };
+    for (key, inline) in map {
+        if *key != merge_key {
+            merged.insert(key.clone(), inline.clone());
+        }
+    }
+    Ok(Value::Mapping(merged))
+}
+
+/// Fill keys of `target` missing from it with those of `defaults`, recursively
+fn merge_under(target: &mut serde_yaml::Value, defaults: serde_yaml::Value) {
+    use serde_yaml::Value;
+
+    if let (Value::Mapping(target), Value::Mapping(defaults)) = (target, defaults) {
+        for (key, default) in defaults {
+            match target.get_mut(&key) {
+                Some(value) => merge_under(value, default),
+                None => {
+                    target.insert(key, default);
+                }
+            }
+        }
+    }
+}
+
+/// Path of an `"@path"` string
+fn config_file_reference(value: &serde_yaml::Value) -> Option<&str> {
+    value
+        .as_str()?
+        .strip_prefix(CONFIG_FILE_PREFIX)
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+}
+
+fn load_config_file(
+    reference: &str,
+    base_dir: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> Result<serde_yaml::Value, String> {
+    let path = base_dir.join(reference);
+    let canonical = path
+        .canonicalize()
+        .map_err(|_| format!("config file {} does not exist", path.display()))?;
+    if chain.contains(&canonical) {
+        let cycle: Vec<String> = chain
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|file| file.display().to_string())
+            .collect();
+        return Err(format!(
+            "config file includes itself: {}",
+            cycle.join(" -> ")
+        ));
+    }
+
+    let content = fs::read_to_string(&canonical)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    let value: serde_yaml::Value = serde_yaml::from_str(&content)
+        .map_err(|e| format!("failed to parse {}: {}", path.display(), e))?;
+
+    let nested_base = canonical
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    chain.push(canonical);
+    let resolved = resolve_config_file(&value, &nested_base, chain);
+    chain.pop();
+    resolved
+}
+
+/// Names used with `${var}` or `$(var)` in `input`, in order of appearance
+fn variable_references(input: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    for (open, close) in [("${", '}'), ("$(", ')')] {
+        let mut rest = input;
+        while let Some(start) = rest.find(open) {
+            let after = &rest[start + open.len()..];
+            let Some(end) = after.find(close) else {
+                break;
+            };
+            names.push(&after[..end]);
+            rest = &after[end + 1..];
+        }
+    }
+    names
+}
+
 #[cfg(test)]
 mod tests {
     use crate::pipeline_config::*;
@@ -230,9 +985,17 @@ mod tests {
 
         let config = PipelineConfig {
             variables: vars,
+            matrix: HashMap::new(),
+            config_defaults: HashMap::new(),
             pipelines: HashMap::new(),
             output_folder: None,
             config: HashMap::new(),
+            working_dir: HashMap::new(),
+            python_path: HashMap::new(),
+            hooks: PipelineHooks::default(),
+            step_hooks: HashMap::new(),
+            source_keys: SourceKeys::default(),
+            secrets: None,
         };
 
         let result = config.substitute_string("Year is ${year}");
@@ -249,22 +1012,50 @@ mod tests {
 
         let config = PipelineConfig {
             variables: vars,
+            matrix: HashMap::new(),
+            config_defaults: HashMap::new(),
             pipelines: HashMap::new(),
             output_folder: None,
             config: HashMap::new(),
+            working_dir: HashMap::new(),
+            python_path: HashMap::new(),
+            hooks: PipelineHooks::default(),
+            step_hooks: HashMap::new(),
+            source_keys: SourceKeys::default(),
+            secrets: None,
         };
 
         let result = config.substitute_string("Year is $(year)");
         assert!(result.is_ok_and(|r| r == "Year is 2032"));
     }
 
+    #[test]
+    fn test_self_referring_variable_is_substituted_once() {
+        let config = PipelineConfig::parse(
+            "variables:\n  loop: \"again ${loop}\"\npipelines:\n  demo: [a]\n",
+        );
+        let Ok(config) = config else {
+            return;
+        };
+        let result = config.substitute_string("${loop}!");
+        assert!(result.is_ok_and(|r| r == "again ${loop}!"));
+    }
+
     #[test]
     fn test_variable_not_found() {
         let config = PipelineConfig {
             variables: HashMap::new(),
+            matrix: HashMap::new(),
+            config_defaults: HashMap::new(),
             pipelines: HashMap::new(),
             output_folder: None,
             config: HashMap::new(),
+            working_dir: HashMap::new(),
+            python_path: HashMap::new(),
+            hooks: PipelineHooks::default(),
+            step_hooks: HashMap::new(),
+            source_keys: SourceKeys::default(),
+            secrets: None,
         };
 
         let result = config.substitute_string("Year is ${year}");
@@ -284,9 +1075,17 @@ mod tests {
 
         let config = PipelineConfig {
             variables: vars,
+            matrix: HashMap::new(),
+            config_defaults: HashMap::new(),
             pipelines: HashMap::new(),
             output_folder: None,
             config: HashMap::new(),
+            working_dir: HashMap::new(),
+            python_path: HashMap::new(),
+            hooks: PipelineHooks::default(),
+            step_hooks: HashMap::new(),
+            source_keys: SourceKeys::default(),
+            secrets: None,
         };
 
         let input = serde_yaml::Value::Mapping({
@@ -335,4 +1134,475 @@ pipelines:
         let config = PipelineConfig::load(dir.path().join("sample-pipeline"));
         assert!(config.is_ok_and(|c| c.get_pipeline("demo").is_some()));
     }
+
+    #[test]
+    fn test_step_config_loads_from_file_with_inline_overrides() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let configs = dir.path().join("configs");
+        assert!(fs::create_dir_all(&configs).is_ok());
+        assert!(fs::write(
+            configs.join("reeds.yaml"),
+            "<<: \"@base.yaml\"\nsolve_year: 2030\nweather_year: 2012\n",
+        )
+        .is_ok());
+        assert!(fs::write(
+            configs.join("base.yaml"),
+            "folder_path: ${data}\nweather_year: 2007\n"
+        )
+        .is_ok());
+        assert!(fs::write(configs.join("exporter.json"), r#"{"format": "csv"}"#).is_ok());
+        let pipeline_path = dir.path().join("pipeline.yaml");
+        assert!(fs::write(
+            &pipeline_path,
+            r#"
+variables:
+  data: /data/reeds
+pipelines:
+  demo: [reeds-parser, exporter]
+config:
+  reeds-parser:
+    <<: "@configs/reeds.yaml"
+    solve_year: 2035
+  exporter: "@configs/exporter.json"
+"#,
+        )
+        .is_ok());
+
+        let config = PipelineConfig::load(&pipeline_path);
+        assert!(config.is_ok());
+        let Ok(config) = config else {
+            return;
+        };
+        let parser = config.get_plugin_config("reeds-parser");
+        let Ok(parser) = parser else {
+            return;
+        };
+        assert_eq!(parser["solve_year"].as_i64(), Some(2035));
+        assert_eq!(parser["weather_year"].as_i64(), Some(2012));
+        assert_eq!(parser["folder_path"].as_str(), Some("/data/reeds"));
+        assert!(parser.get(CONFIG_MERGE_KEY).is_none());
+
+        let exporter = config.get_plugin_config("exporter");
+        assert!(exporter.is_ok_and(|exporter| exporter["format"].as_str() == Some("csv")));
+    }
+
+    #[test]
+    fn test_step_config_file_errors_name_the_step() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        assert!(fs::write(dir.path().join("a.yaml"), "<<: \"@b.yaml\"\n").is_ok());
+        assert!(fs::write(dir.path().join("b.yaml"), "\"@a.yaml\"\n").is_ok());
+        let load = |step_config: &str| {
+            let pipeline_path = dir.path().join("pipeline.yaml");
+            let yaml = format!(
+                "pipelines:\n  demo: [parser]\nconfig:\n  parser: {}\n",
+                step_config
+            );
+            assert!(fs::write(&pipeline_path, yaml).is_ok());
+            PipelineConfig::load(&pipeline_path)
+                .err()
+                .map(|e| e.to_string())
+        };
+
+        let missing = load("\"@missing.yaml\"");
+        assert!(missing.as_deref().is_some_and(|e| e
+            .starts_with("Failed to load config for 'parser': config file")
+            && e.ends_with("missing.yaml does not exist")));
+
+        let cycle = load("\"@a.yaml\"");
+        assert!(cycle
+            .as_deref()
+            .is_some_and(|e| e.contains("config file includes itself") && e.contains("b.yaml ->")));
+    }
+
+    #[test]
+    fn test_working_dir_substitutes_variables() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let mut vars = HashMap::new();
+        vars.insert(
+            "root".to_string(),
+            serde_yaml::Value::String(dir.path().display().to_string()),
+        );
+        let mut working_dir = HashMap::new();
+        working_dir.insert("parser".to_string(), "${root}".to_string());
+        working_dir.insert("exporter".to_string(), "${root}/missing".to_string());
+
+        let config = PipelineConfig {
+            variables: vars,
+            matrix: HashMap::new(),
+            config_defaults: HashMap::new(),
+            pipelines: HashMap::new(),
+            output_folder: None,
+            config: HashMap::new(),
+            working_dir,
+            python_path: HashMap::new(),
+            hooks: PipelineHooks::default(),
+            step_hooks: HashMap::new(),
+            source_keys: SourceKeys::default(),
+            secrets: None,
+        };
+
+        let resolved = config.get_working_dir("parser");
+        assert!(resolved.is_ok_and(|path| path.as_deref() == Some(dir.path())));
+        assert!(matches!(config.get_working_dir("other"), Ok(None)));
+        assert!(matches!(
+            config.get_working_dir("exporter"),
+            Err(PipelineError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_python_path_entries_must_exist() {
+        let Ok(dir) = TempDir::new() else {
+            return;
+        };
+        let yaml = format!(
+            "variables:\n  root: {root}\npython_path:\n  parser: [\"${{root}}\"]\n  exporter: [\"${{root}}\", \"${{root}}/missing\"]\n",
+            root = dir.path().display()
+        );
+        let Some(config) = load_yaml(&yaml) else {
+            return;
+        };
+
+        let resolved = config.get_python_path("parser");
+        assert!(resolved.is_ok_and(|paths| paths == vec![dir.path().to_path_buf()]));
+        assert!(config
+            .get_python_path("other")
+            .is_ok_and(|paths| paths.is_empty()));
+        assert!(matches!(
+            config.get_python_path("exporter"),
+            Err(PipelineError::InvalidConfig(ref msg)) if msg.contains("missing")
+        ));
+    }
+
+    fn load_yaml(yaml: &str) -> Option<PipelineConfig> {
+        serde_yaml::from_str(yaml).ok()
+    }
+
+    #[test]
+    fn test_flat_pipeline_chains_each_step() {
+        let Some(config) = load_yaml("pipelines:\n  demo: [a, b, c]\n") else {
+            return;
+        };
+        let Ok(steps) = config.resolve_pipeline("demo") else {
+            return;
+        };
+        let inputs: Vec<(&str, Option<&str>)> = steps
+            .iter()
+            .map(|s| (s.name.as_str(), s.input.as_deref()))
+            .collect();
+        assert_eq!(
+            inputs,
+            vec![("a", None), ("b", Some("a")), ("c", Some("b"))]
+        );
+    }
+
+    #[test]
+    fn test_branching_dependencies_are_ordered() {
+        let yaml = r"
+pipelines:
+  demo:
+    - name: report
+      requires: [left]
+    - name: right
+      requires: [source]
+    - name: left
+      requires: [source]
+    - name: source
+";
+        let Some(config) = load_yaml(yaml) else {
+            return;
+        };
+        let Ok(steps) = config.resolve_pipeline("demo") else {
+            return;
+        };
+        let names: Vec<&str> = steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["source", "right", "left", "report"]);
+        assert_eq!(steps[1].input.as_deref(), Some("source"));
+        assert_eq!(steps[3].input.as_deref(), Some("left"));
+    }
+
+    #[test]
+    fn test_flat_pipeline_may_repeat_a_step() {
+        let Some(config) = load_yaml("pipelines:\n  demo: [parser, upgrade, upgrade]\n") else {
+            return;
+        };
+        let Ok(steps) = config.resolve_pipeline("demo") else {
+            return;
+        };
+        let inputs: Vec<Option<&str>> = steps.iter().map(|s| s.input.as_deref()).collect();
+        assert_eq!(inputs, vec![None, Some("parser"), Some("upgrade")]);
+    }
+
+    #[test]
+    fn test_requirements_must_be_single_and_unambiguous() {
+        let repeated =
+            "pipelines:\n  demo:\n    - a\n    - a\n    - name: b\n      requires: [a]\n";
+        let two = "pipelines:\n  demo:\n    - a\n    - c\n    - name: b\n      requires: [a, c]\n";
+        for (yaml, expected) in [(repeated, "more than once"), (two, "requires 2 steps")] {
+            let Some(config) = load_yaml(yaml) else {
+                return;
+            };
+            assert!(matches!(
+                config.resolve_pipeline("demo"),
+                Err(PipelineError::InvalidConfig(ref msg)) if msg.contains(expected)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_dependency_cycle_is_an_error() {
+        let yaml = r"
+pipelines:
+  demo:
+    - name: a
+      requires: [b]
+    - name: b
+      requires: [a]
+    - name: c
+";
+        let Some(config) = load_yaml(yaml) else {
+            return;
+        };
+        let result = config.resolve_pipeline("demo");
+        assert!(matches!(
+            result,
+            Err(PipelineError::InvalidConfig(ref msg)) if msg.contains("cycle") && msg.contains("a, b")
+        ));
+    }
+
+    #[test]
+    fn test_object_steps_carry_tags() {
+        let yaml = r"
+pipelines:
+  demo:
+    - name: parser
+      tags: [core]
+    - name: break-gens
+      requires: [parser]
+      tags: [optional, cleanup]
+    - exporter
+";
+        let Some(config) = load_yaml(yaml) else {
+            return;
+        };
+        let Some(steps) = config.get_pipeline("demo") else {
+            return;
+        };
+        let tags: Vec<&[String]> = steps.iter().map(PipelineStep::tags).collect();
+        assert_eq!(tags[0], ["core"]);
+        assert_eq!(tags[1], ["optional", "cleanup"]);
+        assert!(tags[2].is_empty());
+    }
+
+    #[test]
+    fn test_unknown_requirement_is_an_error() {
+        let Some(config) =
+            load_yaml("pipelines:\n  demo:\n    - name: a\n      requires: [missing]\n")
+        else {
+            return;
+        };
+        assert!(matches!(
+            config.resolve_pipeline("demo"),
+            Err(PipelineError::InvalidConfig(_))
+        ));
+    }
+
+    fn validation_errors(yaml: &str, strict: bool) -> Vec<PipelineConfigError> {
+        let config = PipelineConfig::parse(yaml);
+        assert!(config.is_ok());
+        let Ok(config) = config else {
+            return Vec::new();
+        };
+        let result = if strict {
+            config.validate_strict()
+        } else {
+            config.validate()
+        };
+        result.err().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_valid_pipeline_file_passes_validation() {
+        let yaml = "variables:\n  year: 2030\noutput_folder: out/${year}\npipelines:\n  demo: [parser, exporter]\nconfig:\n  parser: {}\n";
+        assert!(validation_errors(yaml, true).is_empty());
+    }
+
+    #[test]
+    fn test_defaults_file_is_merged_under_pipeline_config() {
+        let Ok(dir) = tempfile::tempdir() else {
+            return;
+        };
+        let defaults = dir.path().join(PIPELINE_DEFAULTS_FILE);
+        assert!(fs::write(
+            &defaults,
+            "config:\n  r2x-reeds.parser:\n    data_root: /shared/data\n    solver: {threads: 8, name: highs}\n  exporter:\n    threads: 4\n",
+        )
+        .is_ok());
+        let Some(mut config) = load_yaml(
+            "pipelines:\n  demo: [r2x-reeds.parser]\nconfig:\n  parser:\n    solver: {threads: 2}\n",
+        ) else {
+            return;
+        };
+        assert!(config
+            .load_defaults_file(&defaults)
+            .is_ok_and(|found| found));
+        assert!(!config.config.contains_key("r2x-reeds.parser"));
+
+        let parser = config
+            .plugin_config_json_with_defaults(Some("parser"), Some("r2x-reeds.parser"))
+            .unwrap_or_default();
+        let parser: serde_json::Value = serde_json::from_str(&parser).unwrap_or_default();
+        assert_eq!(parser["data_root"], "/shared/data");
+        assert_eq!(parser["solver"]["threads"], 2);
+        assert_eq!(parser["solver"]["name"], "highs");
+        assert_eq!(
+            config
+                .plugin_config_json_with_defaults(None, Some("exporter"))
+                .ok()
+                .as_deref(),
+            Some(r#"{"threads":4}"#)
+        );
+        assert_eq!(
+            config
+                .plugin_config_json_with_defaults(None, None)
+                .ok()
+                .as_deref(),
+            Some("{}")
+        );
+
+        let Some(mut with_nulls) =
+            load_yaml("pipelines:\n  demo: [parser]\nconfig:\n  parser:\n    solver: null\n")
+        else {
+            return;
+        };
+        assert!(with_nulls
+            .load_defaults_file(&defaults)
+            .is_ok_and(|found| found));
+        let parser = with_nulls
+            .plugin_config_json_with_defaults(Some("parser"), Some("r2x-reeds.parser"))
+            .unwrap_or_default();
+        let parser: serde_json::Value = serde_json::from_str(&parser).unwrap_or_default();
+        assert!(parser["solver"].is_null());
+        assert_eq!(parser["data_root"], "/shared/data");
+        let Some(mut absent) = load_yaml("pipelines:\n  demo: [exporter]\n") else {
+            return;
+        };
+        assert!(absent
+            .load_defaults_file(&defaults)
+            .is_ok_and(|found| found));
+        let printed = absent.print_pipeline_config("demo").unwrap_or_default();
+        assert!(printed.contains("exporter:\n  threads: 4"));
+
+        let missing = dir.path().join("missing.yaml");
+        assert!(config
+            .load_defaults_file(&missing)
+            .is_ok_and(|found| !found));
+    }
+
+    #[test]
+    fn test_matrix_combines_every_value() {
+        let yaml = "matrix:\n  year: [2030, 2035]\n  scenario: [base, high]\noutput_folder: out/${scenario}/${year}\npipelines:\n  demo: [parser]\n";
+        assert!(validation_errors(yaml, false).is_empty());
+        let Some(config) = load_yaml(yaml) else {
+            return;
+        };
+
+        let folders: Vec<String> = config
+            .matrix_combinations()
+            .iter()
+            .filter_map(|combination| {
+                config
+                    .with_variables(combination)
+                    .substitute_string("out/${scenario}/${year}")
+                    .ok()
+            })
+            .collect();
+        assert_eq!(
+            folders,
+            [
+                "out/base/2030",
+                "out/base/2035",
+                "out/high/2030",
+                "out/high/2035"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validation_reports_unknown_keys_and_duplicates() {
+        let yaml =
+            "pipelines:\n  demo: [a]\n  demo: [b]\npipeline:\n  other: [c]\non_succes: echo done\n";
+        assert_eq!(
+            validation_errors(yaml, false),
+            vec![
+                PipelineConfigError::UnknownKey("pipeline".to_string()),
+                PipelineConfigError::UnknownKey("on_succes".to_string()),
+                PipelineConfigError::DuplicatePipeline("demo".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validation_reports_empty_steps_and_undefined_variables() {
+        let yaml = "variables:\n  year: 2030\noutput_folder: out/${scenario}/$(year)/$(run)\npipelines:\n  demo: [a, '', {name: ' '}]\n";
+        assert_eq!(
+            validation_errors(yaml, false),
+            vec![
+                PipelineConfigError::EmptyStep {
+                    pipeline: "demo".to_string(),
+                    position: 2,
+                },
+                PipelineConfigError::EmptyStep {
+                    pipeline: "demo".to_string(),
+                    position: 3,
+                },
+                PipelineConfigError::UndefinedVariable("scenario".to_string()),
+                PipelineConfigError::UndefinedVariable("run".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict_validation_reports_entries_for_missing_plugins() {
+        let yaml = "pipelines:\n  demo: [parser]\nconfig:\n  parser: {}\n  exporter: {}\nworking_dir:\n  upgrader: /tmp\n";
+        assert!(validation_errors(yaml, false).is_empty());
+        assert_eq!(
+            validation_errors(yaml, true),
+            vec![
+                PipelineConfigError::UnusedPluginEntry {
+                    section: "config",
+                    plugin: "exporter".to_string(),
+                },
+                PipelineConfigError::UnusedPluginEntry {
+                    section: "working_dir",
+                    plugin: "upgrader".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detailed_pipeline_rejects_unknown_fields() {
+        let config = PipelineConfig::parse(
+            "pipelines:\n  reeds:\n    steps: [parser]\n    output_folder: out/reeds\n",
+        );
+        assert!(config.is_ok());
+        let Ok(config) = config else {
+            return;
+        };
+        assert_eq!(config.output_folder_for("reeds"), Some("out/reeds"));
+        let rendered = serde_yaml::to_string(&config).unwrap_or_default();
+        assert!(rendered.contains("output_folder: out/reeds"));
+
+        assert!(PipelineConfig::parse(
+            "pipelines:\n  reeds:\n    steps: [parser]\n    output_dir: out/reeds\n"
+        )
+        .is_err());
+    }
 }
@@ -23,6 +23,8 @@ pub struct DiscoveryOptions {
     pub source_path: Option<String>,
     /// Display URI stored in manifest (git URL or local path)
     pub source_uri: Option<String>,
+    /// Also scan top-level `tests/` and `examples/`; skips plugins cached in the manifest
+    pub include_dev: bool,
 }
 
 /// Discover and register plugins from a package and its dependencies
@@ -35,7 +37,8 @@ pub fn discover_and_register_entry_points_with_deps(
     let package = &opts.package;
     let package_name_full = &opts.package_name_full;
     let dependencies = &opts.dependencies;
-    let no_cache = opts.no_cache;
+    // Cached plugins were discovered without dev directories
+    let no_cache = opts.no_cache || opts.include_dev;
     let package_version = opts.package_version.as_deref().unwrap_or("unknown");
 
     // Check if we already have this package in the manifest with plugins
@@ -62,12 +65,13 @@ pub fn discover_and_register_entry_points_with_deps(
         ));
 
         let dist_info = locator.find_dist_info_path(package_name_full);
-        AstDiscovery::discover_plugins(
+        AstDiscovery::discover_plugins_with_dev_dirs(
             &package_path,
             package_name_full,
             venv_path,
             Some(package_version),
             dist_info.as_deref(),
+            opts.include_dev,
         )
         .map_err(|e| {
             PluginError::Discovery(format!(
@@ -141,12 +145,13 @@ pub fn discover_and_register_entry_points_with_deps(
             match locator.find_package_path(&dep) {
                 Ok(dep_path) => {
                     let dep_dist_info = locator.find_dist_info_path(&dep);
-                    match AstDiscovery::discover_plugins(
+                    match AstDiscovery::discover_plugins_with_dev_dirs(
                         &dep_path,
                         &dep,
                         venv_path,
                         None,
                         dep_dist_info.as_deref(),
+                        opts.include_dev,
                     ) {
                         Ok(ast_plugins) => ast_plugins,
                         Err(e) => {
@@ -55,6 +55,41 @@ fn normalize_git_url(url: &str) -> String {
     format!("git+{url}")
 }
 
+/// Git URL for `org/repo` on `host`, as given to `--host`.
+///
+/// | `--host`                     | Output                                          |
+/// |------------------------------|-------------------------------------------------|
+/// | `gitlab.example.com`         | `git+https://gitlab.example.com/org/repo`       |
+/// | `https://gitlab.example.com` | `git+https://gitlab.example.com/org/repo`       |
+/// | `git@gitlab.example.com`     | `git+ssh://git@gitlab.example.com/org/repo.git` |
+/// | `ssh://git@host:2222`        | `git+ssh://git@host:2222/org/repo.git`          |
+fn host_repo_url(host: &str, repo_path: &str) -> String {
+    let host = host.trim().trim_end_matches('/');
+
+    let ssh_host = host
+        .strip_prefix("ssh://")
+        .or_else(|| host.contains('@').then_some(host));
+    if let Some(ssh_host) = ssh_host {
+        let ssh_host = ssh_host.trim_end_matches(':');
+        let user_host = if ssh_host.contains('@') {
+            ssh_host.to_string()
+        } else {
+            format!("git@{ssh_host}")
+        };
+        let suffix = if repo_path.ends_with(".git") {
+            ""
+        } else {
+            ".git"
+        };
+        return format!("git+ssh://{user_host}/{repo_path}{suffix}");
+    }
+
+    if host.starts_with("https://") || host.starts_with("http://") {
+        return format!("git+{host}/{repo_path}");
+    }
+    format!("git+https://{host}/{repo_path}")
+}
+
 /// Strip a trailing `@ref` from a URL, handling SSH URLs that contain `@` in `git@host`.
 fn strip_git_ref(url: &str) -> &str {
     if let Some(rest) = url.strip_prefix("git+") {
@@ -144,9 +179,96 @@ pub fn extract_package_name(package: &str) -> Result<String, PluginError> {
             PluginError::PackageSpec(format!("Failed to extract package name from {}", package))
         })
     } else {
-        // For PyPI packages, use as-is
-        Ok(pkg.to_string())
+        // For PyPI packages, drop extras and version specifiers
+        Ok(parse_requirement(pkg)?.name)
+    }
+}
+
+/// A PyPI requirement such as `r2x-reeds[viz]>=0.3,<0.4`, split into parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    pub name: String,
+    pub extras: Vec<String>,
+    /// Version specifier clauses, e.g. `[">=0.3", "<0.4"]`
+    pub specifiers: Vec<String>,
+}
+
+/// PEP 440 comparison operators, longest first so `===` wins over `==`.
+const VERSION_OPERATORS: &[&str] = &["===", "~=", "==", "!=", "<=", ">=", "<", ">"];
+
+/// Parse a PyPI requirement string of the form `name[extras]specifiers`.
+///
+/// Environment markers (`; python_version < "3.12"`) and direct references
+/// (`name @ url`) are not accepted here.
+pub fn parse_requirement(spec: &str) -> Result<Requirement, PluginError> {
+    let invalid = |reason: &str| {
+        PluginError::PackageSpec(format!("Invalid package specifier '{spec}': {reason}"))
+    };
+
+    let spec_trimmed = spec.trim();
+    let name_end = spec_trimmed
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(spec_trimmed.len());
+    let name = &spec_trimmed[..name_end];
+    if name.is_empty() {
+        return Err(invalid("missing package name"));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        || !name.ends_with(|c: char| c.is_ascii_alphanumeric())
+    {
+        return Err(invalid(
+            "package names must start and end with a letter or digit",
+        ));
     }
+
+    let mut rest = spec_trimmed[name_end..].trim_start();
+    let mut extras = Vec::new();
+    if let Some(after_bracket) = rest.strip_prefix('[') {
+        let Some(close) = after_bracket.find(']') else {
+            return Err(invalid("unclosed '[' in extras"));
+        };
+        for extra in after_bracket[..close].split(',') {
+            let extra = extra.trim();
+            if extra.is_empty()
+                || !extra
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            {
+                return Err(invalid("extras must be a comma-separated list of names"));
+            }
+            extras.push(extra.to_string());
+        }
+        rest = after_bracket[close + 1..].trim_start();
+    }
+
+    let mut specifiers = Vec::new();
+    if !rest.is_empty() {
+        for clause in rest.split(',') {
+            let clause = clause.trim();
+            let Some(op) = VERSION_OPERATORS.iter().find(|op| clause.starts_with(**op)) else {
+                return Err(invalid(&format!(
+                    "expected a version operator ({}) before '{}'",
+                    VERSION_OPERATORS.join(", "),
+                    clause
+                )));
+            };
+            let version = clause[op.len()..].trim();
+            if version.is_empty()
+                || !version.chars().all(|c| {
+                    c.is_ascii_alphanumeric() || matches!(c, '.' | '*' | '+' | '!' | '-' | '_')
+                })
+            {
+                return Err(invalid(&format!("malformed version in '{}'", clause)));
+            }
+            specifiers.push(format!("{op}{version}"));
+        }
+    }
+
+    Ok(Requirement {
+        name: name.to_string(),
+        extras,
+        specifiers,
+    })
 }
 
 /// Build package specifier for pip install.
@@ -180,8 +302,7 @@ pub fn build_package_spec(
                 "GitHub shorthand must use gh:owner/repo".to_string(),
             ));
         }
-        let git_host = host.as_deref().unwrap_or("github.com");
-        let url = format!("git+https://{git_host}/{repo_path}");
+        let url = host_repo_url(host.as_deref().unwrap_or("github.com"), repo_path);
         return Ok(add_git_ref(&url, branch, tag, commit));
     }
 
@@ -199,12 +320,14 @@ pub fn build_package_spec(
         ));
     }
 
-    // 5. PyPI package name
+    // 5. PyPI package name, optionally with extras and version specifiers.
+    // Validated here but passed through to uv unchanged.
     if branch.is_some() || tag.is_some() || commit.is_some() || host.is_some() {
         return Err(PluginError::PackageSpec(
             "Cannot use git flags with PyPI package name".to_string(),
         ));
     }
+    parse_requirement(package)?;
     Ok(package.to_string())
 }
 
@@ -441,6 +564,61 @@ mod tests {
         assert!(result.is_ok() || result.is_err());
     }
 
+    // ── parse_requirement ───────────────────────────────────────────────
+
+    #[test]
+    fn test_extract_name_pinned_version() {
+        assert!(extract_package_name("r2x-reeds==0.3.0").is_ok_and(|s| s == "r2x-reeds"));
+    }
+
+    #[test]
+    fn test_extract_name_version_range() {
+        assert!(extract_package_name("r2x-reeds>=0.3,<0.4").is_ok_and(|s| s == "r2x-reeds"));
+    }
+
+    #[test]
+    fn test_extract_name_with_extras() {
+        assert!(extract_package_name("r2x-reeds[viz]").is_ok_and(|s| s == "r2x-reeds"));
+        assert!(extract_package_name("r2x_reeds[viz, docs]~=0.3").is_ok_and(|s| s == "r2x_reeds"));
+    }
+
+    #[test]
+    fn test_parse_requirement_parts() {
+        let result = parse_requirement("r2x-reeds[viz,docs] >=0.3, <0.4");
+        assert!(result.is_ok_and(|r| r
+            == Requirement {
+                name: "r2x-reeds".to_string(),
+                extras: vec!["viz".to_string(), "docs".to_string()],
+                specifiers: vec![">=0.3".to_string(), "<0.4".to_string()],
+            }));
+    }
+
+    #[test]
+    fn test_parse_requirement_rejects_malformed() {
+        for spec in [
+            "r2x-reeds=0.3",
+            "r2x-reeds==",
+            "r2x-reeds[viz",
+            "r2x-reeds[]",
+            "r2x-reeds>=0.3,",
+            "-r2x-reeds",
+            "==0.3",
+            "r2x-reeds; python_version<'3.12'",
+        ] {
+            assert!(
+                matches!(parse_requirement(spec), Err(PluginError::PackageSpec(_))),
+                "{spec} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_spec_pypi_with_version_passthrough() {
+        let result = build_package_spec("r2x-reeds[viz]>=0.3,<0.4", None, None, None, None);
+        assert!(result.is_ok_and(|s| s == "r2x-reeds[viz]>=0.3,<0.4"));
+        assert!(build_package_spec("r2x-reeds=0.3", None, None, None, None).is_err());
+    }
+
     // ── build_package_spec ──────────────────────────────────────────────
 
     #[test]
@@ -494,6 +672,53 @@ mod tests {
         assert!(result.is_ok_and(|s| s == "git+https://github.example.com/acme/r2x-plugin@main"));
     }
 
+    #[test]
+    fn test_spec_gh_with_https_host_url() {
+        let result = build_package_spec(
+            "gh:acme/r2x-plugin",
+            Some("https://gitlab.internal/".to_string()),
+            None,
+            Some("v1.2.0".to_string()),
+            None,
+        );
+        assert!(result.is_ok_and(|s| s == "git+https://gitlab.internal/acme/r2x-plugin@v1.2.0"));
+    }
+
+    #[test]
+    fn test_spec_gh_with_ssh_host() {
+        let result = build_package_spec(
+            "gh:acme/r2x-plugin",
+            Some("git@gitlab.internal".to_string()),
+            Some("main".to_string()),
+            None,
+            None,
+        );
+        assert!(result.is_ok_and(|s| s == "git+ssh://git@gitlab.internal/acme/r2x-plugin.git@main"));
+
+        let result = build_package_spec(
+            "gh:acme/r2x-plugin.git",
+            Some("ssh://deploy@gitlab.internal:2222".to_string()),
+            None,
+            None,
+            Some("abc1234".to_string()),
+        );
+        assert!(result.is_ok_and(
+            |s| s == "git+ssh://deploy@gitlab.internal:2222/acme/r2x-plugin.git@abc1234"
+        ));
+    }
+
+    #[test]
+    fn test_spec_enterprise_ssh_url_with_tag() {
+        let result = build_package_spec(
+            "git@gitlab.internal:acme/r2x-plugin.git",
+            None,
+            None,
+            Some("v2.0".to_string()),
+            None,
+        );
+        assert!(result.is_ok_and(|s| s == "git+ssh://git@gitlab.internal/acme/r2x-plugin.git@v2.0"));
+    }
+
     #[test]
     fn test_spec_gh_without_ref() {
         let result = build_package_spec("gh:NatLabRockies/R2X", None, None, None, None);
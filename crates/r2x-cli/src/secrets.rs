@@ -0,0 +1,214 @@
+//! Secrets for `${SECRET:key}` substitution in pipeline files
+//!
+//! The secrets file is a JSON object or a TOML table (by `.toml`
+//! extension). Nested tables are addressed with dotted keys, so
+//! `[db] password = "..."` is `${SECRET:db.password}`. Every string value of
+//! at least [`MIN_REDACTED_LEN`] characters is registered with the logger so
+//! it never appears in log output; numbers, booleans and shorter strings would
+//! mask unrelated text.
+
+use crate::errors::PipelineError;
+use r2x_logger as logger;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the secrets file when `--secrets` is not given
+pub const SECRETS_FILE_ENV: &str = "R2X_SECRETS_FILE";
+
+/// Prefix of a secret reference inside `${...}` or `$(...)`
+pub const SECRET_PREFIX: &str = "SECRET:";
+
+/// Shortest string value masked in log output
+pub const MIN_REDACTED_LEN: usize = 4;
+
+/// Values loaded from a secrets file
+#[derive(Clone, Default)]
+pub struct Secrets {
+    path: PathBuf,
+    values: HashMap<String, String>,
+}
+
+impl fmt::Debug for Secrets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<&String> = self.values.keys().collect();
+        keys.sort();
+        f.debug_struct("Secrets")
+            .field("path", &self.path)
+            .field("keys", &keys)
+            .finish()
+    }
+}
+
+impl Secrets {
+    /// Read the secrets file at `path`
+    pub fn load(path: &Path) -> Result<Self, PipelineError> {
+        let file_error = |reason: String| PipelineError::SecretsFile {
+            path: path.display().to_string(),
+            reason,
+        };
+        let content = fs::read_to_string(path).map_err(|e| file_error(e.to_string()))?;
+        let document: serde_json::Value = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&content).map_err(|e| file_error(e.to_string()))?
+        } else {
+            serde_json::from_str(&content).map_err(|e| file_error(e.to_string()))?
+        };
+        if !document.is_object() {
+            return Err(file_error(
+                "expected a table of key/value pairs".to_string(),
+            ));
+        }
+
+        let mut values = HashMap::new();
+        flatten("", document, &mut values);
+        Ok(Self {
+            path: path.to_path_buf(),
+            values,
+        })
+    }
+
+    /// Load the file given on the command line, else the one named by
+    /// [`SECRETS_FILE_ENV`], else none
+    pub fn from_arg_or_env(path: Option<&Path>) -> Result<Option<Self>, PipelineError> {
+        match path {
+            Some(path) => Self::load(path).map(Some),
+            None => match std::env::var_os(SECRETS_FILE_ENV) {
+                Some(path) if !path.is_empty() => Self::load(Path::new(&path)).map(Some),
+                _ => Ok(None),
+            },
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Result<&str, PipelineError> {
+        self.values
+            .get(key)
+            .map(String::as_str)
+            .ok_or_else(|| PipelineError::SecretNotFound {
+                key: key.to_string(),
+                path: self.path.display().to_string(),
+            })
+    }
+}
+
+/// Collect leaf values under their dotted key path, redacting the string ones
+fn flatten(key: &str, value: serde_json::Value, out: &mut HashMap<String, String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, value) in map {
+                let child = if key.is_empty() {
+                    name
+                } else {
+                    format!("{}.{}", key, name)
+                };
+                flatten(&child, value, out);
+            }
+        }
+        serde_json::Value::String(text) => {
+            if text.chars().count() >= MIN_REDACTED_LEN {
+                logger::redact_value(&text);
+            }
+            out.insert(key.to_string(), text);
+        }
+        other => {
+            out.insert(key.to_string(), other.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipeline_config::PipelineConfig;
+    use crate::secrets::*;
+    use tempfile::TempDir;
+
+    fn config_with_secrets(file_name: &str, content: &str) -> Option<(TempDir, PipelineConfig)> {
+        let dir = tempfile::tempdir().ok()?;
+        let path = dir.path().join(file_name);
+        fs::write(&path, content).ok()?;
+        let mut config = PipelineConfig::parse("pipelines:\n  demo: [a]\n").ok()?;
+        config.secrets = Some(Secrets::load(&path).ok()?);
+        Some((dir, config))
+    }
+
+    #[test]
+    fn secret_tokens_resolve_from_json_and_toml() {
+        let Some((_dir, config)) =
+            config_with_secrets("secrets.json", r#"{"api_token": "abc123", "port": 5432}"#)
+        else {
+            return;
+        };
+        let resolved = config.substitute_string("token=${SECRET:api_token} port=$(SECRET:port)");
+        assert!(resolved.is_ok_and(|text| text == "token=abc123 port=5432"));
+
+        let Some((_dir, config)) =
+            config_with_secrets("secrets.toml", "[db]\npassword = \"s3cr3t-pw\"\n")
+        else {
+            return;
+        };
+        let resolved = config.substitute_string("${SECRET:db.password}");
+        assert!(resolved.is_ok_and(|text| text == "s3cr3t-pw"));
+        assert_eq!(logger::redact("pw is s3cr3t-pw"), "pw is ***");
+        assert!(!format!("{:?}", config.secrets).contains("s3cr3t-pw"));
+    }
+
+    #[test]
+    fn only_long_enough_strings_are_redacted() {
+        let Some((_dir, config)) = config_with_secrets(
+            "secrets.json",
+            r#"{"port": 61357, "debug": true, "pin": "q7z", "user": "sv-bot-91"}"#,
+        ) else {
+            return;
+        };
+        assert!(config
+            .substitute_string("${SECRET:pin}")
+            .is_ok_and(|text| text == "q7z"));
+        assert_eq!(
+            logger::redact("port 61357 debug true pin q7z user sv-bot-91"),
+            "port 61357 debug true pin q7z user ***"
+        );
+    }
+
+    #[test]
+    fn substituted_secrets_are_not_scanned_again() {
+        let Some((_dir, config)) =
+            config_with_secrets("secrets.json", r#"{"brace": "pa${ss", "paren": "x$(y)z"}"#)
+        else {
+            return;
+        };
+        let resolved = config.substitute_string("${SECRET:brace} $(SECRET:paren)");
+        assert!(resolved.is_ok_and(|text| text == "pa${ss x$(y)z"));
+    }
+
+    #[test]
+    fn missing_secret_key_is_an_error() {
+        let Some((_dir, config)) = config_with_secrets("secrets.json", r#"{"api_token": "x"}"#)
+        else {
+            return;
+        };
+        assert!(matches!(
+            config.substitute_string("${SECRET:other}"),
+            Err(PipelineError::SecretNotFound { key, .. }) if key == "other"
+        ));
+    }
+
+    #[test]
+    fn missing_secrets_file_is_an_error() {
+        let Ok(dir) = tempfile::tempdir() else {
+            return;
+        };
+        let missing = dir.path().join("absent.json");
+        assert!(matches!(
+            Secrets::from_arg_or_env(Some(&missing)),
+            Err(PipelineError::SecretsFile { .. })
+        ));
+
+        let Ok(config) = PipelineConfig::parse("pipelines:\n  demo: [a]\n") else {
+            return;
+        };
+        assert!(matches!(
+            config.substitute_string("${SECRET:api_token}"),
+            Err(PipelineError::NoSecretsFile(_))
+        ));
+    }
+}
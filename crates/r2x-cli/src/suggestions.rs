@@ -0,0 +1,86 @@
+//! "Did you mean" helpers for user-supplied names
+
+/// Levenshtein distance between two strings, compared case-insensitively.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Return up to `limit` candidates close to `target`, closest first.
+///
+/// A candidate qualifies when its edit distance is within a third of the
+/// target length (minimum 2), or when one name contains the other.
+pub fn closest_matches<'a, I>(target: &str, candidates: I, limit: usize) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let normalized_target = target.replace('_', "-").to_lowercase();
+    let threshold = (normalized_target.chars().count() / 3).max(2);
+
+    let mut scored: Vec<(usize, String)> = Vec::new();
+    for candidate in candidates {
+        if scored.iter().any(|(_, existing)| existing == candidate) {
+            continue;
+        }
+        let normalized = candidate.replace('_', "-").to_lowercase();
+        let distance = edit_distance(&normalized_target, &normalized);
+        let contains = !normalized_target.is_empty()
+            && (normalized.contains(&normalized_target) || normalized_target.contains(&normalized));
+        if distance <= threshold || contains {
+            scored.push((distance, candidate.to_string()));
+        }
+    }
+
+    scored.sort();
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::suggestions::*;
+
+    #[test]
+    fn edit_distance_counts_single_edits() {
+        assert_eq!(edit_distance("parser", "parser"), 0);
+        assert_eq!(edit_distance("parser", "parsr"), 1);
+        assert_eq!(edit_distance("parser", "PARSER"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn closest_matches_orders_by_distance() {
+        let candidates = ["reeds-parser", "reeds-exporter", "break-gens"];
+        let matches = closest_matches("reeds-parsr", candidates, 3);
+        assert_eq!(matches.first().map(String::as_str), Some("reeds-parser"));
+        assert!(!matches.iter().any(|m| m == "break-gens"));
+    }
+
+    #[test]
+    fn closest_matches_treats_underscores_as_hyphens() {
+        let matches = closest_matches("break_gen", ["break-gens"], 3);
+        assert_eq!(matches, vec!["break-gens".to_string()]);
+    }
+
+    #[test]
+    fn closest_matches_returns_empty_for_unrelated_names() {
+        assert!(closest_matches("zzzzzz", ["reeds-parser"], 3).is_empty());
+    }
+}
@@ -94,6 +94,24 @@ fn test_invalid_command() {
     r2x_cmd().arg("invalid").assert().failure();
 }
 
+#[test]
+fn test_run_failure_exit_code_follows_error_kind() {
+    let Ok(temp_dir) = TempDir::new() else {
+        return;
+    };
+    let missing = temp_dir.path().join("missing.yaml");
+    let missing = missing.to_string_lossy();
+
+    r2x_cmd()
+        .args(["run", &missing, "nightly"])
+        .assert()
+        .code(3);
+    r2x_cmd()
+        .args(["run", &missing, "nightly", "--exit-code-map", "pipeline=42"])
+        .assert()
+        .code(42);
+}
+
 #[test]
 fn test_plugins_help() {
     r2x_cmd()
@@ -343,8 +361,7 @@ fn create_real_venv(venv_path: &Path) -> io::Result<()> {
         }
     }
 
-    Err(io::Error::new(
-        io::ErrorKind::Other,
+    Err(io::Error::other(
         "failed to create test venv (uv/python not available)",
     ))
 }
@@ -2,7 +2,7 @@ pub mod venv_paths;
 
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use which::which;
 
@@ -26,47 +26,153 @@ pub struct Config {
     pub log_path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub log_max_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_max_capture_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bridge_init_timeout: Option<u64>,
+}
+
+const CONFIG_POINTER_FILE: &str = ".r2x_config_path";
+
+/// Seconds the Python bridge may spend getting ready when `bridge-init-timeout` is not configured
+pub const DEFAULT_BRIDGE_INIT_TIMEOUT_SECS: u64 = 60;
+
+/// Python version used for the venv when `python-version` is not configured
+pub const DEFAULT_PYTHON_VERSION: &str = "3.12";
+
+/// r2x-core version installed when `r2x-core-version` is not configured
+pub const DEFAULT_R2X_CORE_VERSION: &str = "0.1.0rc1";
+
+/// Keys accepted by [`Config::try_set`], in the order `r2x config show` lists them
+pub const CONFIG_KEYS: &[&str] = &[
+    "cache-path",
+    "uv-path",
+    "python-version",
+    "venv-path",
+    "r2x-core-version",
+    "log-python",
+    "no-stdout",
+    "log-path",
+    "log-max-size",
+    "log-max-capture-bytes",
+    "bridge-init-timeout",
+];
+
+/// Why a config value was not stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigValueError {
+    UnknownKey(String),
+    Invalid {
+        key: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+impl std::fmt::Display for ConfigValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigValueError::UnknownKey(key) => write!(
+                f,
+                "Unknown config key: {}. Currently supported keys: {}",
+                key,
+                CONFIG_KEYS.join(", ")
+            ),
+            ConfigValueError::Invalid {
+                key,
+                value,
+                expected,
+            } => write!(
+                f,
+                "Invalid value '{}' for {}: expected {}",
+                value, key, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigValueError {}
+
+fn invalid(key: &'static str, value: &str, expected: &'static str) -> ConfigValueError {
+    ConfigValueError::Invalid {
+        key,
+        value: value.to_string(),
+        expected,
+    }
+}
+
+fn non_empty_path(key: &'static str, value: &str) -> Result<String, ConfigValueError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(invalid(key, value, "a non-empty path"));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn parse_value<T: std::str::FromStr>(
+    key: &'static str,
+    value: &str,
+    expected: &'static str,
+) -> Result<T, ConfigValueError> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| invalid(key, value, expected))
 }
 
 impl Config {
     pub fn path() -> PathBuf {
         // Honor explicit override via R2X_CONFIG for tests / isolated runs.
         // If set and non-empty, use that path immediately.
-        if let Ok(env_path) = std::env::var("R2X_CONFIG") {
-            let trimmed = env_path.trim();
-            if !trimmed.is_empty() {
-                return PathBuf::from(trimmed);
-            }
+        if let Some(env_path) = Self::env_path() {
+            return env_path;
         }
 
-        // Default config file path (platform-appropriate).
+        // A pointer file next to the default config (e.g. ~/.config/r2x/.r2x_config_path)
+        // redirects the config file location when it holds a non-empty path.
+        read_path_pointer(&Self::pointer_path()).map_or_else(Self::default_path, PathBuf::from)
+    }
+
+    /// Platform-appropriate config file location, ignoring any overrides.
+    pub fn default_path() -> PathBuf {
         #[cfg(not(target_os = "windows"))]
-        let Some(default) =
-            dirs::home_dir().map(|h| h.join(".config").join("r2x").join("config.toml"))
-        else {
-            return PathBuf::from(".config.toml");
-        };
+        let default = dirs::home_dir().map(|h| h.join(".config").join("r2x").join("config.toml"));
 
         #[cfg(target_os = "windows")]
-        let Some(default) = dirs::config_dir().map(|c| c.join("r2x").join("config.toml")) else {
-            return PathBuf::from(".config.toml");
-        };
+        let default = dirs::config_dir().map(|c| c.join("r2x").join("config.toml"));
 
-        // Look for a pointer file next to the default config, e.g. ~/.config/r2x/.r2x_config_path
-        // If present and contains a non-empty path, use that path as the config file location.
-        if let Some(parent) = default.parent() {
-            let pointer = parent.join(".r2x_config_path");
-            if pointer.exists() {
-                if let Ok(contents) = std::fs::read_to_string(&pointer) {
-                    let trimmed = contents.trim();
-                    if !trimmed.is_empty() {
-                        return PathBuf::from(trimmed);
-                    }
-                }
-            }
-        }
+        default.unwrap_or_else(|| PathBuf::from(".config.toml"))
+    }
 
+    /// Config file named by a non-empty `R2X_CONFIG`
+    fn env_path() -> Option<PathBuf> {
+        let env_path = std::env::var("R2X_CONFIG").ok()?;
+        let trimmed = env_path.trim();
+        (!trimmed.is_empty()).then(|| PathBuf::from(trimmed))
+    }
+
+    /// Pointer file that redirects the config location, stored next to the default config.
+    pub fn pointer_path() -> PathBuf {
+        let default = Self::default_path();
         default
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(CONFIG_POINTER_FILE)
+    }
+
+    /// Custom config location recorded in the pointer file, if any.
+    pub fn path_override() -> Option<String> {
+        read_path_pointer(&Self::pointer_path())
+    }
+
+    /// Point r2x at a custom config file location.
+    pub fn set_path_override(path: &str) -> std::io::Result<()> {
+        write_path_pointer(&Self::pointer_path(), path)
+    }
+
+    /// Remove any custom config location, returning the path it pointed to.
+    pub fn reset_path_override() -> std::io::Result<Option<String>> {
+        clear_path_pointer(&Self::pointer_path())
     }
 
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
@@ -100,23 +206,121 @@ impl Config {
             "no-stdout" => self.no_stdout.map(|v| v.to_string()),
             "log-path" => self.log_path.clone(),
             "log-max-size" => self.log_max_size.map(|v| v.to_string()),
+            "log-max-capture-bytes" => self.log_max_capture_bytes.map(|v| v.to_string()),
+            "bridge-init-timeout" => self.bridge_init_timeout.map(|v| v.to_string()),
             _ => None,
         }
     }
 
-    pub fn set(&mut self, key: &str, value: String) {
+    /// Validate `value` for `key` and store it through the matching typed setter
+    ///
+    /// Unknown keys and malformed values are errors and leave the config unchanged.
+    pub fn try_set(&mut self, key: &str, value: &str) -> Result<(), ConfigValueError> {
+        const BOOL: &str = "true or false";
+        const COUNT: &str = "a non-negative whole number";
         match key {
-            "cache-path" => self.cache_path = Some(value),
-            "uv-path" => self.uv_path = Some(value),
-            "python-version" => self.python_version = Some(value),
-            "venv-path" => self.venv_path = Some(value),
-            "r2x-core-version" => self.r2x_core_version = Some(value),
-            "log-python" => self.log_python = value.parse::<bool>().ok(),
-            "no-stdout" => self.no_stdout = value.parse::<bool>().ok(),
-            "log-path" => self.log_path = Some(value),
-            "log-max-size" => self.log_max_size = value.parse::<u64>().ok(),
-            _ => {}
+            "cache-path" => self.set_cache_path(value),
+            "uv-path" => self.set_uv_path(value),
+            "python-version" => self.set_python_version(value),
+            "venv-path" => self.set_venv_path(value),
+            "r2x-core-version" => self.set_r2x_core_version(value),
+            "log-python" => {
+                self.set_log_python(parse_value("log-python", value, BOOL)?);
+                Ok(())
+            }
+            "no-stdout" => {
+                self.set_no_stdout(parse_value("no-stdout", value, BOOL)?);
+                Ok(())
+            }
+            "log-path" => self.set_log_path(value),
+            "log-max-size" => {
+                self.set_log_max_size(parse_value("log-max-size", value, COUNT)?);
+                Ok(())
+            }
+            "log-max-capture-bytes" => {
+                self.set_log_max_capture_bytes(parse_value("log-max-capture-bytes", value, COUNT)?);
+                Ok(())
+            }
+            "bridge-init-timeout" => {
+                self.set_bridge_init_timeout(parse_value("bridge-init-timeout", value, COUNT)?);
+                Ok(())
+            }
+            _ => Err(ConfigValueError::UnknownKey(key.to_string())),
+        }
+    }
+
+    pub fn set_cache_path(&mut self, path: &str) -> Result<(), ConfigValueError> {
+        self.cache_path = Some(non_empty_path("cache-path", path)?);
+        Ok(())
+    }
+
+    pub fn set_uv_path(&mut self, path: &str) -> Result<(), ConfigValueError> {
+        self.uv_path = Some(non_empty_path("uv-path", path)?);
+        Ok(())
+    }
+
+    /// Set the venv's Python version, written as `3.N` or `3.N.P`
+    pub fn set_python_version(&mut self, version: &str) -> Result<(), ConfigValueError> {
+        let version = version.trim();
+        let parts: Vec<&str> = version.split('.').collect();
+        let numeric = parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+        if !numeric || !(2..=3).contains(&parts.len()) || parts[0] != "3" {
+            return Err(invalid(
+                "python-version",
+                version,
+                "a Python 3 version such as 3.12",
+            ));
         }
+        self.python_version = Some(version.to_string());
+        Ok(())
+    }
+
+    pub fn set_venv_path(&mut self, path: &str) -> Result<(), ConfigValueError> {
+        self.venv_path = Some(non_empty_path("venv-path", path)?);
+        Ok(())
+    }
+
+    /// Set the r2x-core version: a bare version pins it, `>=0.2` and the like constrain it
+    pub fn set_r2x_core_version(&mut self, version: &str) -> Result<(), ConfigValueError> {
+        let version = version.trim();
+        let bare = version.trim_start_matches(['<', '>', '=', '!', '~']);
+        if bare.is_empty() || version.chars().any(char::is_whitespace) {
+            return Err(invalid(
+                "r2x-core-version",
+                version,
+                "a version or constraint such as 0.2.0 or >=0.2",
+            ));
+        }
+        self.r2x_core_version = Some(version.to_string());
+        Ok(())
+    }
+
+    pub fn set_log_python(&mut self, enabled: bool) {
+        self.log_python = Some(enabled);
+    }
+
+    pub fn set_no_stdout(&mut self, enabled: bool) {
+        self.no_stdout = Some(enabled);
+    }
+
+    pub fn set_log_path(&mut self, path: &str) -> Result<(), ConfigValueError> {
+        self.log_path = Some(non_empty_path("log-path", path)?);
+        Ok(())
+    }
+
+    pub fn set_log_max_size(&mut self, bytes: u64) {
+        self.log_max_size = Some(bytes);
+    }
+
+    pub fn set_log_max_capture_bytes(&mut self, bytes: u64) {
+        self.log_max_capture_bytes = Some(bytes);
+    }
+
+    /// 0 disables the timeout; see [`Config::get_bridge_init_timeout`]
+    pub fn set_bridge_init_timeout(&mut self, secs: u64) {
+        self.bridge_init_timeout = Some(secs);
     }
 
     pub fn is_empty(&self) -> bool {
@@ -129,6 +333,8 @@ impl Config {
             && self.no_stdout.is_none()
             && self.log_path.is_none()
             && self.log_max_size.is_none()
+            && self.log_max_capture_bytes.is_none()
+            && self.bridge_init_timeout.is_none()
     }
 
     pub fn values_iter(&self) -> Vec<(&str, String)> {
@@ -160,21 +366,33 @@ impl Config {
         if let Some(val) = self.log_max_size {
             values.push(("log-max-size", val.to_string()));
         }
+        if let Some(val) = self.log_max_capture_bytes {
+            values.push(("log-max-capture-bytes", val.to_string()));
+        }
+        if let Some(val) = self.bridge_init_timeout {
+            values.push(("bridge-init-timeout", val.to_string()));
+        }
         values
     }
 
+    /// Delete the config file, and the default-home pointer unless `R2X_CONFIG`
+    /// chose the file; that pointer belongs to the real config.
     pub fn reset() -> Result<(), Box<dyn std::error::Error>> {
-        let path = Self::path();
-        if path.exists() {
-            fs::remove_file(&path)?;
-        }
-        if let Some(parent) = path.parent() {
-            let pointer = parent.join(".r2x_config_path");
-            if pointer.exists() {
-                fs::remove_file(pointer)?;
-            }
+        let pointer = Self::env_path().is_none().then(Self::pointer_path);
+        remove_config(&Self::path(), pointer.as_deref())
+    }
+
+    /// How long preparing the Python bridge may take before r2x gives up
+    ///
+    /// Creating a missing venv is not counted. `None` when `bridge-init-timeout` is set to 0, which waits forever.
+    pub fn get_bridge_init_timeout(&self) -> Option<std::time::Duration> {
+        match self
+            .bridge_init_timeout
+            .unwrap_or(DEFAULT_BRIDGE_INIT_TIMEOUT_SECS)
+        {
+            0 => None,
+            secs => Some(std::time::Duration::from_secs(secs)),
         }
-        Ok(())
     }
 
     pub fn get_cache_path(&self) -> String {
@@ -258,8 +476,20 @@ impl Config {
         }
     }
 
+    pub fn get_python_version(&self) -> &str {
+        self.python_version
+            .as_deref()
+            .unwrap_or(DEFAULT_PYTHON_VERSION)
+    }
+
+    pub fn get_r2x_core_version(&self) -> &str {
+        self.r2x_core_version
+            .as_deref()
+            .unwrap_or(DEFAULT_R2X_CORE_VERSION)
+    }
+
     pub fn get_r2x_core_package_spec(&self) -> String {
-        let version = self.r2x_core_version.as_deref().unwrap_or("0.1.0rc1");
+        let version = self.get_r2x_core_version();
         // If version contains operators (>=, <=, ~=, !=, ==, <, >), use it as-is
         // Otherwise, prefix with == for exact version matching
         if version.contains(">=")
@@ -366,6 +596,29 @@ impl Config {
         }
     }
 
+    /// Directory holding the venv used by `r2x run --python <version>`
+    pub fn python_override_dir(&self, version: &str) -> PathBuf {
+        PathBuf::from(self.get_cache_path())
+            .join("python")
+            .join(version)
+    }
+
+    /// Copy of this config that uses a version-specific venv under the cache dir
+    ///
+    /// Returns `None` when `version` is already the configured Python version,
+    /// so the regular venv is used.
+    pub fn with_python_override(&self, version: &str) -> Option<Config> {
+        if version.trim() == self.get_python_version() {
+            return None;
+        }
+        let venv_path = self.python_override_dir(version).join(".venv");
+        Some(Config {
+            python_version: Some(version.to_string()),
+            venv_path: Some(venv_path.to_string_lossy().into_owned()),
+            ..self.clone()
+        })
+    }
+
     pub fn ensure_cache_path(&mut self) -> Result<String, Box<dyn std::error::Error>> {
         let cache_path = self.get_cache_path();
         fs::create_dir_all(&cache_path)?;
@@ -386,7 +639,7 @@ impl Config {
         let uv_path = self.ensure_uv_path()?;
 
         // Use the Python version from config, or default to 3.12
-        let python_version = self.python_version.as_deref().unwrap_or("3.12");
+        let python_version = self.get_python_version();
 
         // Create the venv using uv
         let output = Command::new(&uv_path)
@@ -402,6 +655,38 @@ impl Config {
     }
 }
 
+fn read_path_pointer(pointer: &Path) -> Option<String> {
+    let contents = fs::read_to_string(pointer).ok()?;
+    let trimmed = contents.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+fn write_path_pointer(pointer: &Path, path: &str) -> std::io::Result<()> {
+    if let Some(parent) = pointer.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(pointer, path.as_bytes())
+}
+
+fn clear_path_pointer(pointer: &Path) -> std::io::Result<Option<String>> {
+    if !pointer.exists() {
+        return Ok(None);
+    }
+    let previous = read_path_pointer(pointer);
+    fs::remove_file(pointer)?;
+    Ok(previous)
+}
+
+fn remove_config(path: &Path, pointer: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    if let Some(pointer) = pointer {
+        clear_path_pointer(pointer)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -415,22 +700,48 @@ mod tests {
     #[test]
     fn test_config_set_get() {
         let mut config = Config::default();
-        config.set("cache-path", "test-value".to_string());
+        assert!(config.try_set("cache-path", "test-value").is_ok());
         assert_eq!(config.get("cache-path"), Some("test-value".to_string()));
     }
 
     #[test]
     fn test_config_multiple_fields() {
         let mut config = Config::default();
-        config.set("cache-path", "/tmp/cache".to_string());
+        assert!(config.try_set("cache-path", "/tmp/cache").is_ok());
         assert_eq!(config.get("cache-path"), Some("/tmp/cache".to_string()));
         assert!(!config.is_empty());
     }
 
+    #[test]
+    fn python_override_selects_version_specific_venv() {
+        let config = Config {
+            cache_path: Some("/tmp/r2x-cache".to_string()),
+            python_version: Some("3.12".to_string()),
+            venv_path: Some("/home/user/.venv".to_string()),
+            ..Default::default()
+        };
+
+        let overridden = config.with_python_override("3.13");
+        assert_eq!(
+            overridden.as_ref().and_then(|c| c.venv_path.as_deref()),
+            Some("/tmp/r2x-cache/python/3.13/.venv")
+        );
+        assert_eq!(
+            overridden.and_then(|c| c.python_version),
+            Some("3.13".to_string())
+        );
+        assert_eq!(config.venv_path.as_deref(), Some("/home/user/.venv"));
+
+        assert!(config.with_python_override("3.12").is_none());
+        assert!(Config::default()
+            .with_python_override(DEFAULT_PYTHON_VERSION)
+            .is_none());
+    }
+
     #[test]
     fn test_config_unknown_key() {
         let mut config = Config::default();
-        config.set("unknown-key", "value".to_string());
+        assert!(config.try_set("unknown-key", "value").is_err());
         assert_eq!(config.get("unknown-key"), None);
     }
 
@@ -445,8 +756,8 @@ mod tests {
     #[test]
     fn test_config_set_get_bool_fields() {
         let mut config = Config::default();
-        config.set("no-stdout", "true".to_string());
-        config.set("log-python", "false".to_string());
+        assert!(config.try_set("no-stdout", "true").is_ok());
+        assert!(config.try_set("log-python", "false").is_ok());
         assert_eq!(config.get("no-stdout"), Some("true".to_string()));
         assert_eq!(config.get("log-python"), Some("false".to_string()));
     }
@@ -454,12 +765,164 @@ mod tests {
     #[test]
     fn test_config_set_get_log_fields() {
         let mut config = Config::default();
-        config.set("log-path", "/tmp/r2x-custom.log".to_string());
-        config.set("log-max-size", "1048576".to_string());
+        assert!(config.try_set("log-path", "/tmp/r2x-custom.log").is_ok());
+        assert!(config.try_set("log-max-size", "1048576").is_ok());
         assert_eq!(
             config.get("log-path"),
             Some("/tmp/r2x-custom.log".to_string())
         );
         assert_eq!(config.get("log-max-size"), Some("1048576".to_string()));
     }
+
+    #[test]
+    fn test_config_bridge_init_timeout() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.get_bridge_init_timeout(),
+            Some(std::time::Duration::from_secs(
+                DEFAULT_BRIDGE_INIT_TIMEOUT_SECS
+            ))
+        );
+
+        assert!(config.try_set("bridge-init-timeout", "5").is_ok());
+        assert_eq!(config.get("bridge-init-timeout"), Some("5".to_string()));
+        assert_eq!(
+            config.get_bridge_init_timeout(),
+            Some(std::time::Duration::from_secs(5))
+        );
+
+        assert!(config.try_set("bridge-init-timeout", "0").is_ok());
+        assert_eq!(config.get_bridge_init_timeout(), None);
+    }
+
+    #[test]
+    fn typed_path_setters_reject_blank_paths() {
+        let mut config = Config::default();
+        assert!(config.set_cache_path(" /tmp/r2x-cache ").is_ok());
+        assert!(config.set_uv_path("/usr/local/bin/uv").is_ok());
+        assert!(config.set_venv_path("/tmp/r2x/.venv").is_ok());
+        assert!(config.set_log_path("/tmp/r2x.log").is_ok());
+        assert_eq!(config.cache_path.as_deref(), Some("/tmp/r2x-cache"));
+
+        for result in [
+            config.set_cache_path(""),
+            config.set_uv_path("  "),
+            config.set_venv_path(""),
+            config.set_log_path("\t"),
+        ] {
+            assert!(matches!(result, Err(ConfigValueError::Invalid { .. })));
+        }
+        assert_eq!(config.cache_path.as_deref(), Some("/tmp/r2x-cache"));
+        assert_eq!(config.log_path.as_deref(), Some("/tmp/r2x.log"));
+    }
+
+    #[test]
+    fn python_version_setter_accepts_python_3_versions() {
+        let mut config = Config::default();
+        assert_eq!(config.get_python_version(), DEFAULT_PYTHON_VERSION);
+        assert!(config.set_python_version("3.13").is_ok());
+        assert!(config.set_python_version("3.11.9").is_ok());
+        assert_eq!(config.get_python_version(), "3.11.9");
+
+        for bad in ["3", "2.7", "3.x", "3.12.1.4", "python3.12", ""] {
+            assert!(config.set_python_version(bad).is_err(), "{bad}");
+        }
+        assert_eq!(config.get_python_version(), "3.11.9");
+    }
+
+    #[test]
+    fn r2x_core_version_setter_accepts_pins_and_constraints() {
+        let mut config = Config::default();
+        assert_eq!(config.get_r2x_core_version(), DEFAULT_R2X_CORE_VERSION);
+        assert!(config.set_r2x_core_version("0.2.0").is_ok());
+        assert_eq!(config.get_r2x_core_package_spec(), "r2x-core==0.2.0");
+        assert!(config.set_r2x_core_version(">=0.2").is_ok());
+        assert_eq!(config.get_r2x_core_package_spec(), "r2x-core>=0.2");
+
+        for bad in ["", ">=", "0.2 0.3"] {
+            assert!(config.set_r2x_core_version(bad).is_err(), "{bad}");
+        }
+        assert_eq!(config.get_r2x_core_version(), ">=0.2");
+    }
+
+    #[test]
+    fn try_set_coerces_values_and_rejects_bad_input() {
+        let mut config = Config::default();
+        assert!(config.try_set("log-python", "true").is_ok());
+        assert!(config.try_set("no-stdout", "false").is_ok());
+        assert!(config.try_set("log-max-size", "1048576").is_ok());
+        assert!(config.try_set("log-max-capture-bytes", "4096").is_ok());
+        assert!(config.try_set("bridge-init-timeout", "0").is_ok());
+        assert_eq!(config.log_python, Some(true));
+        assert_eq!(config.no_stdout, Some(false));
+        assert_eq!(config.log_max_size, Some(1_048_576));
+        assert_eq!(config.log_max_capture_bytes, Some(4096));
+        assert_eq!(config.get_bridge_init_timeout(), None);
+
+        assert_eq!(
+            config.try_set("log-python", "yes"),
+            Err(ConfigValueError::Invalid {
+                key: "log-python",
+                value: "yes".to_string(),
+                expected: "true or false",
+            })
+        );
+        assert!(config.try_set("log-max-size", "-1").is_err());
+        assert!(config.try_set("bridge-init-timeout", "many").is_err());
+        assert_eq!(config.log_python, Some(true));
+        assert_eq!(config.get_bridge_init_timeout(), None);
+
+        let unknown = config.try_set("verbosity", "2");
+        assert_eq!(
+            unknown,
+            Err(ConfigValueError::UnknownKey("verbosity".to_string()))
+        );
+        assert!(unknown.is_err_and(|e| e.to_string().contains("bridge-init-timeout")));
+    }
+
+    #[test]
+    fn test_config_path_pointer_set_then_reset() {
+        let Ok(dir) = tempfile::TempDir::new() else {
+            return;
+        };
+        let pointer = dir.path().join("r2x").join(CONFIG_POINTER_FILE);
+
+        assert!(write_path_pointer(&pointer, "/tmp/custom/config.toml").is_ok());
+        assert_eq!(
+            read_path_pointer(&pointer),
+            Some("/tmp/custom/config.toml".to_string())
+        );
+
+        let previous = clear_path_pointer(&pointer);
+        assert!(previous.is_ok_and(|p| p.as_deref() == Some("/tmp/custom/config.toml")));
+        assert!(!pointer.exists());
+        assert_eq!(read_path_pointer(&pointer), None);
+    }
+
+    #[test]
+    fn test_config_path_pointer_reset_without_override() {
+        let Ok(dir) = tempfile::TempDir::new() else {
+            return;
+        };
+        let pointer = dir.path().join(CONFIG_POINTER_FILE);
+        assert!(clear_path_pointer(&pointer).is_ok_and(|p| p.is_none()));
+    }
+
+    #[test]
+    fn test_reset_keeps_pointer_it_is_not_given() {
+        let Ok(dir) = tempfile::TempDir::new() else {
+            return;
+        };
+        let config = dir.path().join("config.toml");
+        let pointer = dir.path().join(CONFIG_POINTER_FILE);
+        assert!(fs::write(&config, "").is_ok());
+        assert!(write_path_pointer(&pointer, "/tmp/custom/config.toml").is_ok());
+
+        assert!(remove_config(&config, None).is_ok());
+        assert!(!config.exists());
+        assert!(pointer.exists());
+
+        assert!(remove_config(&config, Some(&pointer)).is_ok());
+        assert!(!pointer.exists());
+    }
 }
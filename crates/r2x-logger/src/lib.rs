@@ -1,9 +1,12 @@
 use colored::Colorize;
 use indicatif::ProgressBar;
+use std::cell::RefCell;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 static LOG_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
 static VERBOSITY: Mutex<u8> = Mutex::new(0);
@@ -11,8 +14,19 @@ static LOG_PYTHON: Mutex<bool> = Mutex::new(false);
 static NO_STDOUT: Mutex<bool> = Mutex::new(false);
 static FILE_LOG_LEVEL: Mutex<LogLevel> = Mutex::new(LogLevel::Info);
 static MAX_LOG_BYTES: Mutex<Option<u64>> = Mutex::new(None);
+static MAX_CAPTURE_BYTES: Mutex<u64> = Mutex::new(DEFAULT_MAX_CAPTURE_BYTES);
 static CURRENT_PLUGIN: Mutex<Option<String>> = Mutex::new(None);
+static PLUGIN_PREFIX: Mutex<bool> = Mutex::new(true);
+static QUIET: Mutex<bool> = Mutex::new(false);
 static SPINNER: Mutex<Option<ProgressBar>> = Mutex::new(None);
+static COLOR_CHOICE: Mutex<ColorChoice> = Mutex::new(ColorChoice::Auto);
+static REDACTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Replaces redacted values in log output
+pub const REDACTION_MARKER: &str = "***";
+
+/// Most bytes of each captured stdout/stderr stream written to the log file
+pub const DEFAULT_MAX_CAPTURE_BYTES: u64 = 64 * 1024;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 enum LogLevel {
@@ -23,6 +37,56 @@ enum LogLevel {
     Trace,
 }
 
+/// When console output uses ANSI colors and spinners (`--color`)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorChoice {
+    /// Color when the terminal supports it, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    Always,
+    /// Plain text and no spinners
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => Err(format!(
+                "invalid color choice '{}' (expected auto, always or never)",
+                other
+            )),
+        }
+    }
+}
+
+/// Apply a color choice to all console output
+pub fn set_color_choice(choice: ColorChoice) {
+    if let Ok(mut v) = COLOR_CHOICE.lock() {
+        *v = choice;
+    }
+    match choice {
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+        ColorChoice::Auto if no_color_requested() => colored::control::set_override(false),
+        ColorChoice::Auto => colored::control::unset_override(),
+    }
+}
+
+/// Get the active color choice
+pub fn get_color_choice() -> ColorChoice {
+    COLOR_CHOICE.lock().ok().map_or(ColorChoice::Auto, |v| *v)
+}
+
+/// `NO_COLOR` set to a non-empty value (https://no-color.org)
+fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
 /// Get the current verbosity level for use by other modules (e.g., Python bridge)
 pub fn get_verbosity() -> u8 {
     VERBOSITY.lock().ok().map_or(0, |v| *v)
@@ -52,6 +116,18 @@ pub fn set_no_stdout(disabled: bool) {
     }
 }
 
+/// Get whether `--quiet` hides spinners and step progress lines
+pub fn get_quiet() -> bool {
+    QUIET.lock().ok().is_some_and(|v| *v)
+}
+
+/// Set whether spinners and step progress lines are hidden (`--quiet`)
+pub fn set_quiet(quiet: bool) {
+    if let Ok(mut v) = QUIET.lock() {
+        *v = quiet;
+    }
+}
+
 /// Get the current plugin name being executed
 pub fn get_current_plugin() -> Option<String> {
     CURRENT_PLUGIN.lock().ok().and_then(|guard| guard.clone())
@@ -64,6 +140,32 @@ pub fn set_current_plugin(plugin_name: Option<String>) {
     }
 }
 
+/// Get whether console messages are prefixed with `[<plugin>]` while a plugin runs
+pub fn get_plugin_prefix() -> bool {
+    PLUGIN_PREFIX.lock().map_or(true, |v| *v)
+}
+
+/// Set whether console messages are prefixed with the current plugin (on by default)
+pub fn set_plugin_prefix(enabled: bool) {
+    if let Ok(mut v) = PLUGIN_PREFIX.lock() {
+        *v = enabled;
+    }
+}
+
+/// `message` with a dimmed `[<plugin>]` in front when a plugin is set
+fn plugin_prefixed(message: &str, plugin: Option<&str>) -> String {
+    match plugin {
+        Some(plugin) => format!("{} {}", format!("[{}]", plugin).dimmed(), message),
+        None => message.to_string(),
+    }
+}
+
+/// Console form of `message`; the log file keeps its own `[source]` tag
+fn console_message(message: &str) -> String {
+    let plugin = get_plugin_prefix().then(get_current_plugin).flatten();
+    plugin_prefixed(message, plugin.as_deref())
+}
+
 /// Convert verbosity level to loguru log level string
 /// 0 = warn only, 1 = debug (-v), 2 = trace (-vv)
 pub fn verbosity_to_loguru_level() -> String {
@@ -158,13 +260,61 @@ fn get_config_dir() -> Result<PathBuf, String> {
     Ok(config_dir)
 }
 
+/// Keep `value` out of every later log line, on the console and in the file
+///
+/// Use for secrets that may end up in logged configs or commands. Empty
+/// values are ignored.
+pub fn redact_value(value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    if let Ok(mut redacted) = REDACTED.lock() {
+        if !redacted.iter().any(|known| known == value) {
+            redacted.push(value.to_string());
+            // Longest first, so a secret containing another is masked whole.
+            redacted.sort_by_key(|known| std::cmp::Reverse(known.len()));
+        }
+    }
+}
+
+/// `text` with every value passed to [`redact_value`] masked
+pub fn redact(text: &str) -> String {
+    let Ok(redacted) = REDACTED.lock() else {
+        return text.to_string();
+    };
+    redacted.iter().fold(text.to_string(), |text, value| {
+        text.replace(value.as_str(), REDACTION_MARKER)
+    })
+}
+
 /// Write to log file
 fn write_to_log(level: LogLevel, message: &str) {
     write_to_log_with_source(level, message, "RUST");
 }
 
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` and return the messages it logged on this thread, redacted
+///
+/// Every level is collected, and the messages still reach the log file as
+/// usual. Meant for tests that check what was logged.
+pub fn capture_logs(f: impl FnOnce()) -> Vec<String> {
+    CAPTURED.with(|captured| *captured.borrow_mut() = Some(Vec::new()));
+    f();
+    CAPTURED.with(|captured| captured.borrow_mut().take().unwrap_or_default())
+}
+
 /// Write to log file with custom source tag
 fn write_to_log_with_source(level: LogLevel, message: &str, source: &str) {
+    let message = redact(message);
+    CAPTURED.with(|captured| {
+        if let Some(lines) = captured.borrow_mut().as_mut() {
+            lines.push(message.clone());
+        }
+    });
+
     let allowed_level = FILE_LOG_LEVEL.lock().ok().map_or(LogLevel::Info, |v| *v);
     if level > allowed_level {
         return;
@@ -210,22 +360,63 @@ fn maybe_rotate_log_file(log_path: &PathBuf, incoming_bytes: u64) {
 
 /// Log an informational message (to console if verbose >= 1, always to file)
 pub fn info(message: &str) {
+    let message = &redact(message);
     write_to_log(LogLevel::Info, &format!("INFO {}", message));
     if get_verbosity() >= 1 {
-        eprintln!("{}", message);
+        eprintln!("{}", console_message(message));
     }
 }
 
 /// Log a debug message (to console if verbose >= 1, always to file)
 pub fn debug(message: &str) {
+    let message = &redact(message);
     write_to_log(LogLevel::Debug, &format!("DEBUG {}", message));
     if get_verbosity() >= 1 {
-        eprintln!("{} {}", "DEBUG:".blue().bold(), message);
+        eprintln!("{} {}", "DEBUG:".blue().bold(), console_message(message));
+    }
+}
+
+/// Run `f` and log how long it took at debug level as `"{label} took {ms}ms"`.
+pub fn timed<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let _timer = TimedGuard::new(label);
+    f()
+}
+
+/// Logs the time between its creation and drop at debug level.
+///
+/// Use for timing a whole scope, including early returns:
+/// `let _timer = logger::TimedGuard::new("Bridge initialization");`
+pub struct TimedGuard {
+    label: String,
+    start: Instant,
+}
+
+impl TimedGuard {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            start: Instant::now(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Drop for TimedGuard {
+    fn drop(&mut self) {
+        debug(&format!(
+            "{} took {:.2}ms",
+            self.label,
+            self.start.elapsed().as_secs_f64() * 1000.0
+        ));
     }
 }
 
 /// Log a debug message to console only (not to file)
 pub fn debug_console_only(message: &str) {
+    let message = &redact(message);
     if get_verbosity() >= 1 {
         eprintln!("{} {}", "DEBUG:".blue().bold(), message);
     }
@@ -233,35 +424,89 @@ pub fn debug_console_only(message: &str) {
 
 /// Log a warning message (to both file and console)
 pub fn warn(message: &str) {
+    let message = &redact(message);
     write_to_log(LogLevel::Warn, &format!("WARN {}", message));
-    eprintln!("{} {}", "warning:".yellow().bold(), message);
+    eprintln!("{}", warning_line(&console_message(message)));
 }
 
 /// Log an error message (to both file and console)
 pub fn error(message: &str) {
+    let message = &redact(message);
     write_to_log(LogLevel::Error, &format!("ERROR {}", message));
-    eprintln!("{} {}", "Error:".red().bold(), message);
+    eprintln!("{}", error_line(&console_message(message)));
+}
+
+fn warning_line(message: &str) -> String {
+    format!("{} {}", "warning:".yellow().bold(), message)
 }
 
-/// Log a success message (to console only for user feedback)
+fn error_line(message: &str) -> String {
+    format!("{} {}", "Error:".red().bold(), message)
+}
+
+/// Log a success message (to console unless quiet, always to file)
 pub fn success(message: &str) {
+    let message = &redact(message);
     write_to_log(LogLevel::Info, &format!("SUCCESS {}", message));
-    let check = "\u{2714}".green().bold(); // 🗸 HEAVY CHECK MARK
-    eprintln!("{} {}", check, message);
+    if !get_quiet() {
+        let check = "\u{2714}".green().bold(); // 🗸 HEAVY CHECK MARK
+        eprintln!("{} {}", check, message);
+    }
 }
 
 /// Log a step message (important user-facing step)
 pub fn step(message: &str) {
+    let message = &redact(message);
     if get_verbosity() >= 2 {
         eprintln!("TRACE: {}", message);
     }
     write_to_log(LogLevel::Info, &format!("STEP: {}", message));
 }
 
+/// Set how many bytes of each captured stream are logged (`log-max-capture-bytes`)
+pub fn set_max_capture_bytes(bytes: u64) {
+    if let Ok(mut v) = MAX_CAPTURE_BYTES.lock() {
+        *v = bytes;
+    }
+}
+
+/// Get how many bytes of each captured stream are logged
+pub fn get_max_capture_bytes() -> u64 {
+    MAX_CAPTURE_BYTES
+        .lock()
+        .map_or(DEFAULT_MAX_CAPTURE_BYTES, |v| *v)
+}
+
+/// The first `max_bytes` of `text` (cut at a char boundary) and a marker
+/// counting the bytes left out
+fn truncate_capture(text: &str, max_bytes: u64) -> String {
+    let max_bytes = usize::try_from(max_bytes).unwrap_or(usize::MAX);
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n...[truncated {} bytes]",
+        &text[..end],
+        text.len() - end
+    )
+}
+
+/// Stdout and stderr of `output` as logged, each capped at [`get_max_capture_bytes`]
+fn captured_streams(output: &std::process::Output) -> (String, String) {
+    let max_bytes = get_max_capture_bytes();
+    (
+        truncate_capture(&String::from_utf8_lossy(&output.stdout), max_bytes),
+        truncate_capture(&String::from_utf8_lossy(&output.stderr), max_bytes),
+    )
+}
+
 /// Capture command output and log it
 pub fn capture_output(command_name: &str, output: &std::process::Output) {
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (stdout, stderr) = captured_streams(output);
 
     write_to_log(
         LogLevel::Debug,
@@ -284,10 +529,10 @@ pub fn capture_output(command_name: &str, output: &std::process::Output) {
 /// Capture command output and always persist it to log file at info level.
 ///
 /// This is useful for noisy subprocesses where console output is suppressed
-/// by default but full output should remain available in logs.
+/// by default but their output should remain available in logs. Each stream
+/// is capped like [`capture_output`]; the command and exit code always are.
 pub fn capture_output_always(command_name: &str, output: &std::process::Output) {
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (stdout, stderr) = captured_streams(output);
 
     write_to_log(
         LogLevel::Info,
@@ -307,6 +552,11 @@ pub fn capture_output_always(command_name: &str, output: &std::process::Output)
     }
 }
 
+/// Route this session's log output to `path`, creating it if needed.
+pub fn set_log_file_path(path: &str) -> Result<(), String> {
+    init(Some(path))
+}
+
 /// Get the log file path for display
 pub fn get_log_path() -> Option<PathBuf> {
     LOG_FILE.lock().ok().and_then(|guard| guard.clone())
@@ -334,10 +584,14 @@ pub fn show_log_path() {
     }
 }
 
-/// Start a spinner with the given message (only if not verbose)
+/// Spinners are hidden in verbose or quiet mode and with --color never
+fn spinner_enabled(verbosity: u8, quiet: bool, color: ColorChoice) -> bool {
+    verbosity == 0 && !quiet && color != ColorChoice::Never
+}
+
+/// Start a spinner with the given message (only if not verbose or quiet)
 pub fn spinner_start(message: &str) {
-    // Don't show spinner in verbose mode
-    if get_verbosity() > 0 {
+    if !spinner_enabled(get_verbosity(), get_quiet(), get_color_choice()) {
         return;
     }
 
@@ -363,8 +617,10 @@ pub fn spinner_success(message: &str) {
             spinner.finish_and_clear();
         }
     }
-    // Show success message with checkmark
-    eprintln!("{} {}", "✔".green().bold(), message);
+    // Show success message with checkmark; errors are shown even when quiet
+    if !get_quiet() {
+        eprintln!("{} {}", "✔".green().bold(), message);
+    }
 }
 
 /// Stop the spinner with an error message
@@ -386,3 +642,123 @@ pub fn spinner_stop() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn spinner_is_hidden_when_verbose_quiet_or_uncolored() {
+        assert!(spinner_enabled(0, false, ColorChoice::Auto));
+        assert!(!spinner_enabled(0, true, ColorChoice::Auto));
+        assert!(!spinner_enabled(1, false, ColorChoice::Always));
+        assert!(!spinner_enabled(0, false, ColorChoice::Never));
+    }
+
+    #[test]
+    fn redacted_values_are_masked_in_logs() {
+        redact_value("hunter2-secret");
+        redact_value("");
+        let lines = capture_logs(|| {
+            info("connecting with password hunter2-secret");
+        });
+        assert_eq!(lines, vec!["INFO connecting with password ***"]);
+        assert_eq!(redact("no secrets here"), "no secrets here");
+    }
+
+    #[test]
+    fn timed_returns_closure_value_and_logs_duration() {
+        let mut value = 0;
+        let lines = capture_logs(|| {
+            value = timed("compute answer", || 42);
+        });
+
+        assert_eq!(value, 42);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("DEBUG compute answer took "));
+        assert!(lines[0].ends_with("ms"));
+    }
+
+    #[test]
+    fn set_log_file_path_routes_messages_to_custom_file() {
+        let dir = std::env::temp_dir().join(format!("r2x-logger-test-{}", std::process::id()));
+        let log_path = dir.join("nested").join("session.log");
+        let Some(log_path_str) = log_path.to_str() else {
+            return;
+        };
+
+        assert!(set_log_file_path(log_path_str).is_ok());
+        assert_eq!(get_log_path(), Some(log_path.clone()));
+        info("custom log destination works");
+
+        let contents = fs::read_to_string(&log_path).unwrap_or_default();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(contents.contains("INFO custom log destination works"));
+    }
+
+    #[test]
+    fn color_choice_controls_ansi_in_warnings_and_errors() {
+        assert_eq!("NEVER".parse(), Ok(ColorChoice::Never));
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+
+        set_color_choice(ColorChoice::Always);
+        assert!(warning_line("disk almost full").contains('\x1b'));
+
+        set_color_choice(ColorChoice::Never);
+        assert_eq!(
+            warning_line("disk almost full"),
+            "warning: disk almost full"
+        );
+        assert_eq!(error_line("plugin failed"), "Error: plugin failed");
+        spinner_start("not drawn");
+        assert!(SPINNER.lock().is_ok_and(|spinner| spinner.is_none()));
+
+        set_color_choice(ColorChoice::Auto);
+    }
+
+    #[test]
+    fn console_messages_name_the_current_plugin() {
+        let prefixed = plugin_prefixed("capacity below zero", Some("r2x-reeds.parser"));
+        assert!(prefixed.contains("[r2x-reeds.parser]"));
+        assert!(prefixed.ends_with(" capacity below zero"));
+        assert_eq!(
+            plugin_prefixed("capacity below zero", None),
+            "capacity below zero"
+        );
+
+        set_plugin_prefix(false);
+        assert!(!get_plugin_prefix());
+        assert_eq!(console_message("no prefix"), "no prefix");
+        set_plugin_prefix(true);
+    }
+
+    #[test]
+    fn captured_output_is_truncated_past_the_cap() {
+        let output = "resolved 120 packages\n".repeat(10);
+        let truncated = truncate_capture(&output, 22);
+        assert_eq!(
+            truncated,
+            format!("resolved 120 packages\n\n...[truncated {} bytes]", 9 * 22)
+        );
+
+        assert_eq!(truncate_capture("héllo", 2), "h\n...[truncated 5 bytes]");
+    }
+
+    #[test]
+    fn short_captured_output_is_logged_in_full() {
+        let output = "Installed 3 packages in 12ms";
+        assert_eq!(truncate_capture(output, DEFAULT_MAX_CAPTURE_BYTES), output);
+        assert_eq!(truncate_capture(output, output.len() as u64), output);
+    }
+
+    #[test]
+    fn timed_guard_logs_when_scope_ends() {
+        let lines = capture_logs(|| {
+            let timer = TimedGuard::new("scoped work");
+            assert!(timer.elapsed() <= Duration::from_secs(60));
+        });
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("scoped work took "));
+    }
+}
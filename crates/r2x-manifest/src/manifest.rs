@@ -4,7 +4,10 @@
 //! including CRUD operations, dependency tracking, and persistence.
 
 use crate::errors::ManifestError;
-use crate::types::{InstallType, Manifest, Package, PackageSource, Plugin};
+use crate::types::{
+    r2x_version, DefaultValue, InstallType, Manifest, Package, PackageSource, Plugin,
+    MANIFEST_SCHEMA_VERSION,
+};
 use smallvec::SmallVec;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -52,10 +55,42 @@ impl Manifest {
 
         let content = std::fs::read_to_string(path)?;
         let mut manifest: Manifest = toml::from_str(&content)?;
+        manifest.migrate();
         manifest.rebuild_indexes();
         Ok(manifest)
     }
 
+    /// Upgrade a manifest read from an older schema to [`MANIFEST_SCHEMA_VERSION`]
+    ///
+    /// Returns whether anything was migrated. Manifests written by a newer r2x
+    /// are left untouched.
+    pub fn migrate(&mut self) -> bool {
+        if self.schema_version >= MANIFEST_SCHEMA_VERSION {
+            return false;
+        }
+        // 1 -> 2: parameter defaults went from Python source text to typed values
+        if self.schema_version < 2 {
+            self.type_legacy_parameter_defaults();
+        }
+        self.schema_version = MANIFEST_SCHEMA_VERSION;
+        true
+    }
+
+    /// Schema 1 stored parameter defaults as Python source text (`"None"`,
+    /// `"True"`, `"'x'"`); read them back as typed values
+    fn type_legacy_parameter_defaults(&mut self) {
+        let parameters = self
+            .packages
+            .iter_mut()
+            .flat_map(|package| package.plugins.iter_mut())
+            .flat_map(|plugin| plugin.parameters.iter_mut());
+        for parameter in parameters {
+            if let Some(DefaultValue::Str(source)) = &parameter.default {
+                parameter.default = Some(DefaultValue::from_python_source(source));
+            }
+        }
+    }
+
     /// Save manifest to default location with atomic write
     pub fn save(&self) -> Result<(), ManifestError> {
         let path = Self::path();
@@ -69,7 +104,7 @@ impl Manifest {
         }
 
         // Serialize
-        let content = toml::to_string_pretty(self)?;
+        let content = toml::to_string_pretty(&self.with_provenance())?;
 
         // Atomic write: write to temp file then rename
         let temp_path = path.with_extension("toml.tmp");
@@ -85,10 +120,20 @@ impl Manifest {
         Ok(())
     }
 
+    /// Copy to write to disk, stamped with the current time and r2x version
+    pub(crate) fn with_provenance(&self) -> Self {
+        Manifest {
+            generated_at: Arc::from(chrono::Utc::now().to_rfc3339()),
+            r2x_version: Some(Arc::from(r2x_version())),
+            ..self.clone()
+        }
+    }
+
     /// Clear all packages and save
     pub fn clear(&mut self) -> Result<(), ManifestError> {
         self.packages.clear();
         self.package_index.clear();
+        self.plugin_lookup.invalidate();
         self.save()
     }
 
@@ -101,12 +146,67 @@ impl Manifest {
     /// O(1) mutable package lookup by name
     #[inline]
     pub fn get_package_mut(&mut self, name: &str) -> Option<&mut Package> {
+        // The caller may add, remove or rename plugins
+        self.plugin_lookup.invalidate();
         self.package_index
             .get(name)
             .copied()
             .map(move |idx| &mut self.packages[idx])
     }
 
+    /// Find a plugin by name across all packages, along with its owning package
+    ///
+    /// Hyphen and underscore spellings of the name are treated as equivalent.
+    /// When several packages provide the name, the first package wins; use
+    /// [`Manifest::plugin_locations`] to detect that.
+    pub fn find_plugin(&self, name: &str) -> Option<(&Package, &Plugin)> {
+        name_variants(name)
+            .iter()
+            .find_map(|candidate| self.plugin_locations(candidate).into_iter().next())
+    }
+
+    /// Package that provides the plugin `name`, see [`Manifest::find_plugin`]
+    pub fn package_for_plugin(&self, name: &str) -> Option<&Package> {
+        self.find_plugin(name).map(|(package, _)| package)
+    }
+
+    /// Every plugin named exactly `name`, with its package, in package order
+    ///
+    /// Served from a reverse index built on first use and dropped by the
+    /// methods that change packages.
+    pub fn plugin_locations(&self, name: &str) -> Vec<(&Package, &Plugin)> {
+        let lookup = self.plugin_lookup.get_or_build(&self.packages);
+        let Some(entries) = lookup.entries.get(name) else {
+            return Vec::new();
+        };
+        entries
+            .iter()
+            .filter_map(|&(package_idx, plugin_idx)| {
+                let package = self.packages.get(package_idx)?;
+                Some((package, package.plugins.get(plugin_idx)?))
+            })
+            .collect()
+    }
+
+    /// Find a plugin by name within a specific package
+    pub fn find_plugin_qualified(
+        &self,
+        package_name: &str,
+        name: &str,
+    ) -> Option<(&Package, &Plugin)> {
+        let plugin_names = name_variants(name);
+        name_variants(package_name).iter().find_map(|package_name| {
+            let package = self.get_package(package_name)?;
+            plugin_names.iter().find_map(|candidate| {
+                package
+                    .plugins
+                    .iter()
+                    .find(|plugin| plugin.name.as_ref() == candidate)
+                    .map(|plugin| (package, plugin))
+            })
+        })
+    }
+
     /// Find or create a package in the manifest
     pub fn get_or_create_package(&mut self, name: &str) -> &mut Package {
         if !self.package_index.contains_key(name) {
@@ -130,6 +230,7 @@ impl Manifest {
             self.package_index.insert(name_arc, idx);
         }
 
+        self.plugin_lookup.invalidate();
         // Safety: we just inserted the package if it didn't exist, so this lookup will succeed
         let idx = self.package_index.get(name).copied().unwrap_or(0);
         &mut self.packages[idx]
@@ -347,6 +448,17 @@ impl Package {
     }
 }
 
+/// The name as given plus its hyphen and underscore spellings, without duplicates
+pub fn name_variants(name: &str) -> Vec<String> {
+    let mut variants = vec![name.to_string()];
+    for candidate in [name.replace('_', "-"), name.replace('-', "_")] {
+        if !variants.contains(&candidate) {
+            variants.push(candidate);
+        }
+    }
+    variants
+}
+
 #[cfg(test)]
 mod tests {
     use crate::manifest::*;
@@ -359,6 +471,125 @@ mod tests {
         assert_eq!(manifest.version.as_ref(), "3.0");
     }
 
+    #[test]
+    fn test_migrate_version_one_manifest() {
+        let content = r#"
+version = "3.0"
+schema_version = 1
+generated_at = "2025-01-01T00:00:00Z"
+
+[[packages]]
+name = "r2x-reeds"
+version = "1.0.0"
+source_kind = "github"
+source_uri = "git+https://github.com/NatLabRockies/r2x-reeds@main"
+install_type = "explicit"
+
+[[packages.plugins]]
+name = "break-gens"
+type = "function"
+module = "r2x_reeds.sysmod.break_gens"
+
+[[packages.plugins.parameters]]
+name = "path"
+type = ["str", "None"]
+required = false
+default = "None"
+
+[[packages]]
+name = "r2x-core"
+version = "0.5.0"
+install_type = "dependency"
+"#;
+        let parsed: Result<Manifest, _> = toml::from_str(content);
+        assert!(parsed.is_ok());
+        let Ok(mut manifest) = parsed else {
+            return;
+        };
+        assert_eq!(manifest.schema_version, 1);
+
+        assert!(manifest.migrate());
+        assert_eq!(manifest.schema_version, MANIFEST_SCHEMA_VERSION);
+        let kinds: Vec<PackageSource> = manifest.packages.iter().map(|p| p.source_kind).collect();
+        assert_eq!(kinds, vec![PackageSource::Github, PackageSource::Pypi]);
+        assert!(manifest.packages[0].plugins[0].parameters[0]
+            .default
+            .as_ref()
+            .is_some_and(DefaultValue::is_none));
+        assert!(!manifest.migrate());
+
+        let written = toml::to_string_pretty(&manifest).unwrap_or_default();
+        assert!(written.contains(&format!("schema_version = {}", MANIFEST_SCHEMA_VERSION)));
+    }
+
+    #[test]
+    fn test_load_types_string_defaults_of_version_one_manifest() {
+        use crate::types::Parameter;
+
+        // A schema 1 file, as written before defaults were typed
+        let mut plugin = Plugin {
+            name: Arc::from("break-gens"),
+            plugin_type: PluginType::Function,
+            module: Arc::from("r2x_reeds.sysmod.break_gens"),
+            ..Default::default()
+        };
+        for (name, source) in [("path", "None"), ("label", "'none'"), ("year", "0")] {
+            plugin.parameters.push(Parameter {
+                name: Arc::from(name),
+                types: SmallVec::new(),
+                module: None,
+                required: false,
+                default: Some(DefaultValue::Str(Arc::from(source))),
+                description: None,
+            });
+        }
+        let mut legacy = Manifest {
+            schema_version: 1,
+            ..Manifest::default()
+        };
+        legacy.get_or_create_package("r2x-reeds").add_plugin(plugin);
+
+        let Ok(dir) = tempfile::tempdir() else {
+            return;
+        };
+        let path = dir.path().join("manifest.toml");
+        let content = toml::to_string_pretty(&legacy).unwrap_or_default();
+        assert!(content.contains("default = \"None\""));
+        assert!(std::fs::write(&path, content).is_ok());
+
+        let loaded = Manifest::load_from_path(&path);
+        assert!(loaded.is_ok());
+        let Ok(manifest) = loaded else {
+            return;
+        };
+        let defaults: Vec<Option<DefaultValue>> = manifest.packages[0].plugins[0]
+            .parameters
+            .iter()
+            .map(|p| p.default.clone())
+            .collect();
+        assert_eq!(
+            defaults,
+            vec![
+                Some(DefaultValue::none()),
+                Some(DefaultValue::Str(Arc::from("none"))),
+                Some(DefaultValue::Int(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_schema_version_is_legacy() {
+        let parsed: Result<Manifest, _> = toml::from_str("version = \"3.0\"\n");
+        assert!(parsed.is_ok());
+        let Ok(mut manifest) = parsed else {
+            return;
+        };
+        assert_eq!(manifest.schema_version, 1);
+        assert!(manifest.migrate());
+        assert_eq!(manifest.schema_version, MANIFEST_SCHEMA_VERSION);
+        assert!(!manifest.migrate());
+    }
+
     #[test]
     fn test_get_or_create_package() {
         let mut manifest = Manifest::default();
@@ -506,4 +737,142 @@ mod tests {
         assert!(pkg.remove_plugin("test-plugin"));
         assert!(pkg.get_plugin("test-plugin").is_none());
     }
+
+    #[test]
+    fn test_parameter_defaults_round_trip_through_toml() {
+        use crate::types::Parameter;
+
+        let mut plugin = Plugin {
+            name: Arc::from("break-gens"),
+            plugin_type: PluginType::Function,
+            module: Arc::from("r2x_reeds.sysmod.break_gens"),
+            ..Default::default()
+        };
+        for (name, default) in [
+            ("label", DefaultValue::Str(Arc::from("none"))),
+            ("enabled", DefaultValue::Bool(true)),
+            ("year", DefaultValue::Int(0)),
+            ("path", DefaultValue::none()),
+        ] {
+            plugin.parameters.push(Parameter {
+                name: Arc::from(name),
+                types: SmallVec::new(),
+                module: None,
+                required: false,
+                default: Some(default),
+                description: None,
+            });
+        }
+
+        let mut manifest = Manifest::default();
+        manifest
+            .get_or_create_package("r2x-reeds")
+            .add_plugin(plugin);
+
+        let Ok(content) = toml::to_string_pretty(&manifest) else {
+            return;
+        };
+        let Ok(loaded) = toml::from_str::<Manifest>(&content) else {
+            return;
+        };
+
+        let defaults: Vec<String> = loaded.packages[0].plugins[0]
+            .parameters
+            .iter()
+            .filter_map(|p| p.default.as_ref().map(ToString::to_string))
+            .collect();
+        assert_eq!(defaults, vec!["\"none\"", "True", "0", "None"]);
+        assert!(loaded.packages[0].plugins[0].parameters[3]
+            .default
+            .as_ref()
+            .is_some_and(DefaultValue::is_none));
+    }
+
+    fn multi_package_manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        for (package_name, plugin_names) in [
+            ("r2x-reeds", ["reeds-parser", "break-gens"]),
+            ("r2x-plexos", ["plexos-exporter", "break-gens"]),
+        ] {
+            let package = manifest.get_or_create_package(package_name);
+            for plugin_name in plugin_names {
+                package.add_plugin(Plugin {
+                    name: Arc::from(plugin_name),
+                    ..Default::default()
+                });
+            }
+        }
+        manifest
+    }
+
+    #[test]
+    fn test_find_plugin_returns_owning_package() {
+        let manifest = multi_package_manifest();
+        let found = manifest.find_plugin("plexos_exporter");
+        assert!(
+            found.is_some_and(|(package, plugin)| package.name.as_ref() == "r2x-plexos"
+                && plugin.name.as_ref() == "plexos-exporter")
+        );
+        assert!(manifest.find_plugin("missing-plugin").is_none());
+    }
+
+    #[test]
+    fn test_plugin_locations_use_index_and_follow_mutations() {
+        let mut manifest = multi_package_manifest();
+        let owners = |manifest: &Manifest, name: &str| -> Vec<String> {
+            manifest
+                .plugin_locations(name)
+                .iter()
+                .map(|(package, _)| package.name.to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            owners(&manifest, "break-gens"),
+            vec!["r2x-reeds", "r2x-plexos"]
+        );
+        assert!(manifest
+            .package_for_plugin("reeds-parser")
+            .is_some_and(|package| package.name.as_ref() == "r2x-reeds"));
+        assert!(owners(&manifest, "new-modifier").is_empty());
+
+        // Added through get_package_mut, which drops the index.
+        if let Some(package) = manifest.get_package_mut("r2x-plexos") {
+            package.add_plugin(Plugin {
+                name: Arc::from("new-modifier"),
+                ..Default::default()
+            });
+        }
+        assert_eq!(owners(&manifest, "new-modifier"), vec!["r2x-plexos"]);
+
+        // Renamed through the field: rebuild_indexes drops the index.
+        manifest.packages[0].plugins[0].name = Arc::from("reeds-reader");
+        manifest.rebuild_indexes();
+        assert_eq!(owners(&manifest, "reeds-reader"), vec!["r2x-reeds"]);
+        assert!(owners(&manifest, "reeds-parser").is_empty());
+
+        manifest
+            .get_or_create_package("r2x-sienna")
+            .add_plugin(Plugin {
+                name: Arc::from("sienna-exporter"),
+                ..Default::default()
+            });
+        assert_eq!(owners(&manifest, "sienna-exporter"), vec!["r2x-sienna"]);
+
+        assert!(manifest.remove_package("r2x-reeds"));
+        assert_eq!(owners(&manifest, "break-gens"), vec!["r2x-plexos"]);
+    }
+
+    #[test]
+    fn test_find_plugin_qualified_scopes_to_package() {
+        let manifest = multi_package_manifest();
+        let found = manifest.find_plugin_qualified("r2x_plexos", "break-gens");
+        assert!(found.is_some_and(|(package, _)| package.name.as_ref() == "r2x-plexos"));
+        assert!(manifest
+            .find_plugin_qualified("r2x-reeds", "plexos-exporter")
+            .is_none());
+        assert!(manifest
+            .find_plugin_qualified("r2x-missing", "break-gens")
+            .is_none());
+    }
 }
@@ -17,7 +17,7 @@ use crate::types::Manifest;
 pub fn write_to_path(manifest: &Manifest, output_path: &Path) -> Result<()> {
     debug!("Writing manifest to custom path: {:?}", output_path);
 
-    let toml_string = toml::to_string_pretty(manifest)?;
+    let toml_string = toml::to_string_pretty(&manifest.with_provenance())?;
     fs::write(output_path, &toml_string)?;
 
     info!("Manifest written successfully to: {:?}", output_path);
@@ -31,7 +31,13 @@ pub fn read_from_path(manifest_path: &Path) -> Result<Manifest> {
     debug!("Reading manifest from custom path: {:?}", manifest_path);
 
     let content = fs::read_to_string(manifest_path)?;
-    let manifest: Manifest = toml::from_str(&content)?;
+    let mut manifest: Manifest = toml::from_str(&content)?;
+    if manifest.migrate() {
+        info!(
+            "Migrated manifest to schema version {}",
+            manifest.schema_version
+        );
+    }
 
     info!("Manifest loaded successfully");
     info!("Manifest version: {}", manifest.version);
@@ -44,7 +50,7 @@ pub fn read_from_path(manifest_path: &Path) -> Result<Manifest> {
 #[cfg(test)]
 mod tests {
     use crate::manifest_writer::*;
-    use crate::types::{Manifest, Package, Plugin, PluginType};
+    use crate::types::{r2x_version, Manifest, Package, Plugin, PluginType};
     use std::sync::Arc;
     use tempfile::TempDir;
 
@@ -95,6 +101,38 @@ mod tests {
         assert_eq!(loaded.packages[0].plugins[0].plugin_type, PluginType::Class);
     }
 
+    #[test]
+    fn test_written_manifest_records_provenance() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let manifest_path = temp_dir.path().join("manifest.toml");
+        let manifest = Manifest {
+            generated_at: Arc::from("2020-01-01T00:00:00Z"),
+            ..Manifest::default()
+        };
+
+        let before = chrono::Utc::now();
+        assert!(write_to_path(&manifest, &manifest_path).is_ok());
+        let loaded = read_from_path(&manifest_path).unwrap_or_default();
+
+        assert_eq!(loaded.r2x_version.as_deref(), Some(r2x_version()));
+        let written = chrono::DateTime::parse_from_rfc3339(&loaded.generated_at);
+        assert!(written.is_ok_and(|at| at >= before - chrono::Duration::seconds(1)));
+    }
+
+    #[test]
+    fn test_missing_provenance_still_loads() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let manifest_path = temp_dir.path().join("manifest.toml");
+        assert!(fs::write(&manifest_path, "version = \"3.0\"\n").is_ok());
+
+        let loaded = read_from_path(&manifest_path);
+        assert!(loaded.is_ok_and(|m| m.generated_at.is_empty() && m.r2x_version.is_none()));
+    }
+
     #[test]
     fn test_version_preserved() {
         let Ok(temp_dir) = TempDir::new() else {
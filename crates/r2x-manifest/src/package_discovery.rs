@@ -16,6 +16,12 @@ struct DirectUrlVcsInfo {
     commit_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct DirectUrlDirInfo {
+    #[serde(default)]
+    editable: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct DirectUrlMetadata {
     url: String,
@@ -23,6 +29,8 @@ struct DirectUrlMetadata {
     subdirectory: Option<String>,
     #[serde(default)]
     vcs_info: Option<DirectUrlVcsInfo>,
+    #[serde(default)]
+    dir_info: Option<DirectUrlDirInfo>,
 }
 
 /// Resolve installed package paths from site-packages (optionally using UV cache).
@@ -239,9 +247,21 @@ impl PackageLocator {
     }
 
     /// Locate a package root suitable for AST discovery.
+    ///
+    /// Editable installs resolve to the developer's source tree so discovery
+    /// never reads a stale copy left behind in site-packages.
     pub fn find_package_path(&self, package_name_full: &str) -> Result<PathBuf> {
         let normalized = package_name_full.replace('-', "_");
 
+        if let Some(path) = self.find_editable_source(package_name_full) {
+            debug!(
+                "Using editable source for '{}': {}",
+                package_name_full,
+                path.display()
+            );
+            return Ok(path);
+        }
+
         if let Some(path) = self.find_package_path_via_pth(&normalized) {
             return Ok(path);
         }
@@ -282,6 +302,51 @@ impl PackageLocator {
         ))
     }
 
+    /// Resolve the source directory of an editable install.
+    ///
+    /// Checks `direct_url.json` for `dir_info.editable` first (PEP 610), then
+    /// falls back to `__editable__.*.pth` / `{name}.pth` files in site-packages.
+    pub fn find_editable_source(&self, package_name: &str) -> Option<PathBuf> {
+        if let Some(metadata) = self.direct_url_metadata(package_name) {
+            if metadata.dir_info.as_ref().is_some_and(|info| info.editable) {
+                if let Some(mut path) = file_url_to_path(&metadata.url) {
+                    if let Some(subdirectory) = metadata.subdirectory.as_deref() {
+                        path = path.join(subdirectory);
+                    }
+                    if path.is_dir() {
+                        return Some(path);
+                    }
+                }
+            }
+        }
+
+        let normalized = package_name.replace('-', "_");
+        let plain_pth = format!("{}.pth", normalized);
+        let editable_prefix = format!("__editable__.{}-", normalized);
+        for (file_name, path) in &self.dir_entries {
+            let matches = file_name == &plain_pth
+                || (file_name.starts_with(&editable_prefix) && file_name.ends_with(".pth"));
+            if !matches {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            for line in content.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') || line.starts_with("import ") {
+                    continue;
+                }
+                let candidate = PathBuf::from(line);
+                if candidate.is_dir() {
+                    return Some(candidate);
+                }
+            }
+        }
+
+        None
+    }
+
     fn find_package_path_via_pth(&self, normalized_package_name: &str) -> Option<PathBuf> {
         let cache_dir = self.uv_cache_dir.as_ref()?;
         if !cache_dir.exists() {
@@ -385,6 +450,37 @@ impl PackageLocator {
     }
 }
 
+/// Convert a `file://` URL from direct_url.json into a local path.
+fn file_url_to_path(url: &str) -> Option<PathBuf> {
+    let rest = url.strip_prefix("file://")?;
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    // Windows URLs look like file:///C:/path
+    let rest = match rest.as_bytes() {
+        [b'/', _, b':', ..] => &rest[1..],
+        _ => rest,
+    };
+
+    let bytes = rest.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' && idx + 2 < bytes.len() {
+            if let Some(value) = std::str::from_utf8(&bytes[idx + 1..idx + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(value);
+                idx += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[idx]);
+        idx += 1;
+    }
+
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
 /// Information about a discovered r2x package
 #[derive(Debug, Clone)]
 pub struct DiscoveredPackage {
@@ -1112,6 +1208,100 @@ my_transform = r2x_transforms.transform:MyTransform
         );
     }
 
+    #[test]
+    fn test_find_package_path_prefers_editable_source_from_direct_url() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let site_packages = temp_dir.path().join("site-packages");
+        let project = temp_dir.path().join("my project");
+        let stale_pkg = site_packages.join("r2x_foo");
+        let live_pkg = project.join("src").join("r2x_foo");
+        let dist_info = site_packages.join("r2x_foo-0.1.0.dist-info");
+        for dir in [&stale_pkg, &live_pkg, &dist_info] {
+            if fs::create_dir_all(dir).is_err() {
+                return;
+            }
+        }
+        if fs::write(stale_pkg.join("plugins.py"), "# stale copy\n").is_err()
+            || fs::write(
+                live_pkg.join("plugins.py"),
+                "def register_plugin():\n    return 'live'\n",
+            )
+            .is_err()
+        {
+            return;
+        }
+
+        let url = format!("file://{}", project.to_string_lossy().replace(' ', "%20"));
+        let direct_url = format!(r#"{{"url": "{}", "dir_info": {{"editable": true}}}}"#, url);
+        if fs::write(dist_info.join("direct_url.json"), direct_url).is_err() {
+            return;
+        }
+
+        let Ok(locator) = PackageLocator::new(site_packages.clone(), None) else {
+            return;
+        };
+
+        assert_eq!(
+            locator.find_editable_source("r2x-foo"),
+            Some(project.clone())
+        );
+        assert!(locator
+            .find_package_path("r2x-foo")
+            .is_ok_and(|path| path == project));
+    }
+
+    #[test]
+    fn test_find_editable_source_from_editable_pth() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let site_packages = temp_dir.path().join("site-packages");
+        let source = temp_dir.path().join("checkout").join("src");
+        for dir in [&site_packages, &source] {
+            if fs::create_dir_all(dir).is_err() {
+                return;
+            }
+        }
+        let pth = site_packages.join("__editable__.r2x_foo-0.1.0.pth");
+        if fs::write(&pth, format!("{}\n", source.display())).is_err() {
+            return;
+        }
+
+        let Ok(locator) = PackageLocator::new(site_packages, None) else {
+            return;
+        };
+
+        assert_eq!(locator.find_editable_source("r2x-foo"), Some(source));
+        assert_eq!(locator.find_editable_source("r2x-bar"), None);
+    }
+
+    #[test]
+    fn test_find_editable_source_ignores_non_editable_direct_url() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let site_packages = temp_dir.path();
+        let dist_info = site_packages.join("r2x_foo-0.1.0.dist-info");
+        if fs::create_dir(&dist_info).is_err() {
+            return;
+        }
+        let direct_url = format!(
+            r#"{{"url": "file://{}", "dir_info": {{}}}}"#,
+            site_packages.display()
+        );
+        if fs::write(dist_info.join("direct_url.json"), direct_url).is_err() {
+            return;
+        }
+
+        let Ok(locator) = PackageLocator::new(site_packages.to_path_buf(), None) else {
+            return;
+        };
+
+        assert_eq!(locator.find_editable_source("r2x-foo"), None);
+    }
+
     #[test]
     fn test_detect_package_source_defaults_to_pypi_without_direct_url() {
         let Ok(temp_dir) = TempDir::new() else {
@@ -3,7 +3,8 @@
 //! This module provides utilities for building runtime bindings that are used
 //! when invoking plugins through the Python bridge.
 
-use crate::types::{Parameter, Plugin, PluginType};
+use crate::types::{DecoratorRegistration, Parameter, Plugin, PluginType, UpgradeSpec};
+use serde::{Deserialize, Serialize};
 
 /// Coarse-grained plugin role inferred from the manifest name.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,14 +14,84 @@ pub enum PluginRole {
     Modifier,
     Upgrader,
     Translation,
+    /// Reads a system and reports pass/fail with diagnostics, producing no data.
+    Validator,
     Utility,
 }
 
+/// Kind of data a plugin reads or writes.
+///
+/// Each variant's serialized name is pinned so renaming a variant never
+/// changes the manifest format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IoSlot {
+    #[serde(rename = "system")]
+    System,
+    #[serde(rename = "store_folder")]
+    StoreFolder,
+    #[serde(rename = "config_file")]
+    ConfigFile,
+    #[serde(rename = "folder")]
+    Folder,
+}
+
+/// What a plugin consumes and produces, used to reason about pipeline wiring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoContract {
+    pub consumes: &'static [IoSlot],
+    pub produces: &'static [IoSlot],
+}
+
+impl IoSlot {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IoSlot::System => "system",
+            IoSlot::StoreFolder => "store_folder",
+            IoSlot::ConfigFile => "config_file",
+            IoSlot::Folder => "folder",
+        }
+    }
+}
+
+impl PluginRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PluginRole::Parser => "parser",
+            PluginRole::Exporter => "exporter",
+            PluginRole::Modifier => "modifier",
+            PluginRole::Upgrader => "upgrader",
+            PluginRole::Translation => "translation",
+            PluginRole::Validator => "validator",
+            PluginRole::Utility => "utility",
+        }
+    }
+
+    /// IO contract implied by the role.
+    pub fn io_contract(self) -> IoContract {
+        let (consumes, produces): (&'static [IoSlot], &'static [IoSlot]) = match self {
+            PluginRole::Parser => (
+                &[IoSlot::StoreFolder, IoSlot::ConfigFile],
+                &[IoSlot::System],
+            ),
+            PluginRole::Exporter => (&[IoSlot::System], &[IoSlot::Folder]),
+            PluginRole::Modifier | PluginRole::Translation => {
+                (&[IoSlot::System], &[IoSlot::System])
+            }
+            PluginRole::Upgrader => (&[IoSlot::StoreFolder], &[IoSlot::StoreFolder]),
+            PluginRole::Validator => (&[IoSlot::System], &[]),
+            PluginRole::Utility => (&[], &[]),
+        };
+        IoContract { consumes, produces }
+    }
+}
+
 /// Minimal config metadata needed to instantiate the config class.
 #[derive(Debug, Clone)]
 pub struct RuntimeConfig {
     pub module: String,
     pub name: String,
+    /// Field names from the manifest's config schema, sorted; empty when unknown
+    pub fields: Vec<String>,
 }
 
 /// Runtime bindings for plugin invocation.
@@ -34,18 +105,33 @@ pub struct RuntimeBindings {
     pub config: Option<RuntimeConfig>,
     pub parameters: Vec<Parameter>,
     pub requires_store: bool,
+    /// Upgrader version handling from the manifest
+    pub upgrade: Option<UpgradeSpec>,
 }
 
 /// Build runtime bindings from a manifest plugin.
 pub fn build_runtime_bindings(plugin: &Plugin) -> RuntimeBindings {
     let (entry_module, entry_name) = parse_entry_point(plugin);
     let role = infer_plugin_role(&plugin.name);
-    let call_method = default_method_for_role(role, plugin.plugin_type);
+    let call_method = plugin
+        .call_method
+        .as_deref()
+        .map(str::to_string)
+        .or_else(|| default_method_for_role(role, plugin.plugin_type));
     let config = match (&plugin.config_class, &plugin.config_module) {
-        (Some(class_name), Some(module)) => Some(RuntimeConfig {
-            name: class_name.to_string(),
-            module: module.to_string(),
-        }),
+        (Some(class_name), Some(module)) => {
+            let mut fields: Vec<String> = plugin
+                .config_schema
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect();
+            fields.sort_unstable();
+            Some(RuntimeConfig {
+                module: module.to_string(),
+                name: class_name.to_string(),
+                fields,
+            })
+        }
         _ => None,
     };
 
@@ -68,6 +154,7 @@ pub fn build_runtime_bindings(plugin: &Plugin) -> RuntimeBindings {
         config,
         parameters: plugin.parameters.to_vec(),
         requires_store,
+        upgrade: plugin.upgrade.clone(),
     }
 }
 
@@ -93,6 +180,8 @@ pub fn infer_plugin_role(name: &str) -> PluginRole {
         PluginRole::Exporter
     } else if name_lower.contains("upgrade") {
         PluginRole::Upgrader
+    } else if name_lower.contains("validat") {
+        PluginRole::Validator
     } else if name_lower.contains("modifier") || name_lower.contains("transform") {
         PluginRole::Modifier
     } else if name_lower.contains("translation") || name_lower.contains("translate") {
@@ -102,6 +191,110 @@ pub fn infer_plugin_role(name: &str) -> PluginRole {
     }
 }
 
+/// Methods a class plugin is called through by convention, one per role
+const CONVENTIONAL_METHODS: [&str; 4] = ["build_system", "export", "run", "validate"];
+
+/// Method to call on a class plugin of `role` that defines `methods`.
+///
+/// A role with a conventional method only uses that one, so a parser that
+/// happens to define `export` is not called through it. A utility, whose name
+/// gives no role away, uses the conventional method its class defines when
+/// there is exactly one. `methods` are those defined in the class body;
+/// inherited methods are not seen.
+pub fn infer_call_method(role: PluginRole, methods: &[String]) -> Option<String> {
+    let defines = |method: &str| methods.iter().any(|name| name == method);
+    if let Some(method) = default_method_for_role(role, PluginType::Class) {
+        return Some(method).filter(|method| defines(method));
+    }
+    let mut defined = CONVENTIONAL_METHODS.iter().filter(|method| defines(method));
+    match (defined.next(), defined.next()) {
+        (Some(method), None) => Some((*method).to_string()),
+        _ => None,
+    }
+}
+
+/// The conventional method of `role` when a class defining `methods` lacks it
+///
+/// Upgraders are left out: they are driven by their registered steps, not
+/// a call method.
+pub fn missing_call_method(role: PluginRole, methods: &[String]) -> Option<String> {
+    if role == PluginRole::Upgrader {
+        return None;
+    }
+    default_method_for_role(role, PluginType::Class)
+        .filter(|method| !methods.iter().any(|name| name == method))
+}
+
+/// The parameter annotated with the config class
+///
+/// A type names the class exactly when one of its identifiers, without module
+/// prefix, equals `config_class_name`, so `MyConfig | dict` and
+/// `pkg.config.MyConfig` match but `MyConfigExtended` does not. Substring
+/// matching is only the last resort when no parameter matches exactly.
+pub fn find_config_param<'a>(
+    parameters: &'a [Parameter],
+    config_class_name: &str,
+) -> Option<&'a Parameter> {
+    let names_class = |annotation: &str| {
+        annotation
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+            .filter_map(|path| path.rsplit('.').next())
+            .any(|name| name == config_class_name)
+    };
+
+    parameters
+        .iter()
+        .find(|param| param.types.iter().any(|t| names_class(t)))
+        .or_else(|| {
+            parameters
+                .iter()
+                .find(|param| param.types.iter().any(|t| t.contains(config_class_name)))
+        })
+}
+
+/// Compare dotted numeric versions, falling back to string order.
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    fn parse(version: &str) -> Option<Vec<u64>> {
+        version
+            .trim()
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect()
+    }
+
+    match (parse(a), parse(b)) {
+        (Some(mut left), Some(mut right)) => {
+            let len = left.len().max(right.len());
+            left.resize(len, 0);
+            right.resize(len, 0);
+            left.cmp(&right)
+        }
+        _ => a.cmp(b),
+    }
+}
+
+/// Warnings for upgrade steps whose relative order is ambiguous
+///
+/// Steps run in priority order, so two steps with the same priority whose
+/// version ranges overlap could run either way round on the same input.
+pub fn ambiguous_step_order(registrations: &[DecoratorRegistration]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (idx, step) in registrations.iter().enumerate() {
+        for other in &registrations[idx + 1..] {
+            if step.effective_priority() == other.effective_priority() && step.overlaps(other) {
+                warnings.push(format!(
+                    "upgrade steps '{}' and '{}' share priority {} and overlapping version ranges; their order is ambiguous",
+                    step.function,
+                    other.function,
+                    step.effective_priority()
+                ));
+            }
+        }
+    }
+    warnings
+}
+
 fn default_method_for_role(role: PluginRole, plugin_type: PluginType) -> Option<String> {
     if matches!(plugin_type, PluginType::Function) {
         return None;
@@ -113,6 +306,335 @@ fn default_method_for_role(role: PluginRole, plugin_type: PluginType) -> Option<
         PluginRole::Translation | PluginRole::Modifier | PluginRole::Upgrader => {
             Some("run".to_string())
         }
+        PluginRole::Validator => Some("validate".to_string()),
         PluginRole::Utility => None,
     }
 }
+
+/// Find the end of the first argument in a comma-separated list, handling nested brackets
+pub fn find_first_arg_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '[' | '(' | '{' => depth += 1,
+            ']' | ')' | '}' => depth -= 1,
+            ',' if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    // If no comma found, the whole text is the first argument
+    Some(text.len())
+}
+
+/// Parse union types from a type annotation string
+/// Handles:
+/// - "int | str | None" -> vec!["int", "str", "None"]
+/// - "Annotated[int | str, Field(...)]" -> vec!["int", "str"]
+/// - "str" -> vec!["str"]
+pub fn parse_union_types_from_annotation(annotation: &str) -> Vec<String> {
+    let annotation = annotation.trim();
+
+    // Handle Annotated[X, Field(...)] - extract the actual type first
+    let actual_type = if annotation.starts_with("Annotated[") && annotation.ends_with(']') {
+        let inner = &annotation[10..annotation.len() - 1];
+        if let Some(type_end) = find_first_arg_end(inner) {
+            inner[..type_end].trim()
+        } else {
+            annotation
+        }
+    } else {
+        annotation
+    };
+
+    // Now parse union types
+    if actual_type.contains(" | ") {
+        actual_type
+            .split(" | ")
+            .map(|t| t.trim().to_string())
+            .collect()
+    } else {
+        vec![actual_type.to_string()]
+    }
+}
+
+/// True when an annotation admits `None`: `Optional[X]`, `X | None` or `Union[X, None]`
+///
+/// Such parameters are optional even without a default value.
+pub fn is_optional_annotation(annotation: &str) -> bool {
+    let types = parse_union_types_from_annotation(annotation);
+    types.iter().any(|ty| {
+        let ty = ty.trim().trim_start_matches("typing.");
+        ty == "None"
+            || ty.starts_with("Optional[")
+            || ty
+                .strip_prefix("Union[")
+                .and_then(|inner| inner.strip_suffix(']'))
+                .is_some_and(|inner| {
+                    split_top_level_args(inner)
+                        .iter()
+                        .any(|arg| is_optional_annotation(arg))
+                })
+    })
+}
+
+/// Split the arguments of a subscript like `int, None` on top-level commas
+fn split_top_level_args(text: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut rest = text;
+    while let Some(end) = find_first_arg_end(rest) {
+        args.push(rest[..end].trim());
+        if end >= rest.len() {
+            break;
+        }
+        rest = &rest[end + 1..];
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::*;
+    use crate::types::SchemaField;
+    use std::sync::Arc;
+
+    #[test]
+    fn parameters_admitting_none_are_not_required() {
+        let param = |ty: &str| Parameter {
+            name: Arc::from("threshold"),
+            types: ty.split(" | ").map(Arc::from).collect(),
+            module: None,
+            required: true,
+            default: None,
+            description: None,
+        };
+        assert!(!param("Optional[int]").is_required());
+        assert!(!param("int | None").is_required());
+        assert!(param("int").is_required());
+    }
+
+    #[test]
+    fn runtime_config_lists_schema_fields() {
+        let mut plugin = Plugin {
+            name: Arc::from("reeds-parser"),
+            module: Arc::from("r2x_reeds"),
+            class_name: Some(Arc::from("ReEDSParser")),
+            config_class: Some(Arc::from("ReEDSConfig")),
+            config_module: Some(Arc::from("r2x_reeds.config")),
+            ..Default::default()
+        };
+        for field in ["weather_year", "solve_year"] {
+            plugin
+                .config_schema
+                .insert(Arc::from(field), SchemaField::default());
+        }
+        let config = build_runtime_bindings(&plugin).config;
+        assert_eq!(
+            config.map(|config| config.fields),
+            Some(vec!["solve_year".to_string(), "weather_year".to_string()])
+        );
+    }
+
+    #[test]
+    fn io_slot_serializes_as_its_str() {
+        for slot in [
+            IoSlot::System,
+            IoSlot::StoreFolder,
+            IoSlot::ConfigFile,
+            IoSlot::Folder,
+        ] {
+            let json = serde_json::to_string(&slot).ok();
+            assert_eq!(json, Some(format!("\"{}\"", slot.as_str())));
+            let parsed: Option<IoSlot> = json.and_then(|json| serde_json::from_str(&json).ok());
+            assert_eq!(parsed, Some(slot));
+        }
+    }
+
+    #[test]
+    fn validator_role_is_inferred_from_name() {
+        assert_eq!(infer_plugin_role("reeds-validator"), PluginRole::Validator);
+        assert_eq!(
+            infer_plugin_role("validate_capacity"),
+            PluginRole::Validator
+        );
+        assert_eq!(infer_plugin_role("reeds-parser"), PluginRole::Parser);
+    }
+
+    #[test]
+    fn validator_classes_call_validate() {
+        let plugin = Plugin {
+            name: Arc::from("system-validator"),
+            module: Arc::from("r2x_checks.validator"),
+            class_name: Some(Arc::from("SystemValidator")),
+            ..Plugin::default()
+        };
+        let bindings = build_runtime_bindings(&plugin);
+        assert_eq!(bindings.role, PluginRole::Validator);
+        assert_eq!(bindings.call_method.as_deref(), Some("validate"));
+    }
+
+    #[test]
+    fn validator_io_contract_is_read_only() {
+        let contract = PluginRole::Validator.io_contract();
+        assert_eq!(contract.consumes, &[IoSlot::System]);
+        assert!(contract.produces.is_empty());
+
+        let exporter = PluginRole::Exporter.io_contract();
+        assert!(exporter.consumes.contains(&IoSlot::System));
+        assert!(exporter.produces.contains(&IoSlot::Folder));
+    }
+
+    #[test]
+    fn call_method_is_inferred_from_defined_methods() {
+        let methods = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| (*name).to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            infer_call_method(PluginRole::Parser, &methods(&["__init__", "build_system"]))
+                .as_deref(),
+            Some("build_system")
+        );
+        assert_eq!(
+            infer_call_method(PluginRole::Utility, &methods(&["__init__", "export"])).as_deref(),
+            Some("export")
+        );
+        assert_eq!(
+            infer_call_method(PluginRole::Utility, &methods(&["export", "run"])),
+            None
+        );
+        assert_eq!(
+            infer_call_method(PluginRole::Parser, &methods(&["helper", "export"])),
+            None
+        );
+        assert_eq!(
+            missing_call_method(PluginRole::Parser, &methods(&["helper", "export"])).as_deref(),
+            Some("build_system")
+        );
+        assert_eq!(
+            missing_call_method(PluginRole::Exporter, &methods(&["export"])),
+            None
+        );
+        assert_eq!(
+            missing_call_method(PluginRole::Upgrader, &methods(&["helper"])),
+            None
+        );
+
+        let plugin = Plugin {
+            name: Arc::from("reeds"),
+            module: Arc::from("r2x_reeds.parser"),
+            class_name: Some(Arc::from("ReEDSParser")),
+            call_method: Some(Arc::from("build_system")),
+            ..Plugin::default()
+        };
+        let bindings = build_runtime_bindings(&plugin);
+        assert_eq!(bindings.role, PluginRole::Utility);
+        assert_eq!(bindings.call_method.as_deref(), Some("build_system"));
+    }
+
+    fn step(
+        function: &str,
+        priority: i64,
+        min: Option<&str>,
+        target: Option<&str>,
+    ) -> DecoratorRegistration {
+        DecoratorRegistration {
+            function: Arc::from(function),
+            decorator: Arc::from("register_step"),
+            target_version: target.map(Arc::from),
+            min_version: min.map(Arc::from),
+            max_version: None,
+            priority: Some(priority),
+        }
+    }
+
+    #[test]
+    fn shared_priority_with_overlapping_ranges_is_ambiguous() {
+        let steps = [
+            step("rename_columns", 10, None, Some("2.0")),
+            step("move_files", 10, Some("1.0"), Some("1.5")),
+        ];
+        let warnings = ambiguous_step_order(&steps);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'rename_columns' and 'move_files' share priority 10"));
+    }
+
+    #[test]
+    fn distinct_priorities_or_disjoint_ranges_are_not_ambiguous() {
+        let steps = [
+            step("rename_columns", 10, None, Some("2.0")),
+            step("move_files", 20, None, Some("2.0")),
+            step("drop_cache", 10, Some("2.0"), Some("3.0")),
+        ];
+        assert!(ambiguous_step_order(&steps).is_empty());
+    }
+
+    fn config_param(name: &str, types: &[&str]) -> Parameter {
+        Parameter {
+            name: Arc::from(name),
+            types: types.iter().map(|t| Arc::from(*t)).collect(),
+            module: None,
+            required: true,
+            default: None,
+            description: None,
+        }
+    }
+
+    fn config_param_name(parameters: &[Parameter], class: &str) -> Option<String> {
+        find_config_param(parameters, class).map(|param| param.name.to_string())
+    }
+
+    #[test]
+    fn exact_type_wins_over_substring() {
+        let parameters = vec![
+            config_param("extended", &["MyConfigExtended"]),
+            config_param("settings", &["MyConfig", "dict"]),
+        ];
+        assert_eq!(
+            config_param_name(&parameters, "MyConfig").as_deref(),
+            Some("settings")
+        );
+        assert_eq!(
+            config_param_name(&parameters, "MyConfigExtended").as_deref(),
+            Some("extended")
+        );
+    }
+
+    #[test]
+    fn exact_match_inside_union_and_qualified_annotations() {
+        let parameters = vec![
+            config_param("extended", &["MyConfigExtended | None"]),
+            config_param("config", &["Optional[pkg.config.MyConfig]"]),
+        ];
+        assert_eq!(
+            config_param_name(&parameters, "MyConfig").as_deref(),
+            Some("config")
+        );
+    }
+
+    #[test]
+    fn substring_is_last_resort() {
+        let parameters = vec![
+            config_param("system", &["System"]),
+            config_param("cfg", &["MyConfigT"]),
+        ];
+        assert_eq!(
+            config_param_name(&parameters, "MyConfig").as_deref(),
+            Some("cfg")
+        );
+        assert_eq!(config_param_name(&parameters, "Other"), None);
+    }
+
+    #[test]
+    fn test_is_optional_annotation() {
+        assert!(is_optional_annotation("Optional[int]"));
+        assert!(is_optional_annotation("typing.Optional[Path]"));
+        assert!(is_optional_annotation("int | None"));
+        assert!(is_optional_annotation("Union[int, None]"));
+        assert!(is_optional_annotation("Annotated[int | None, Field(ge=0)]"));
+        assert!(!is_optional_annotation("int"));
+        assert!(!is_optional_annotation("Union[int, str]"));
+        assert!(!is_optional_annotation("NoneCoercingInt"));
+    }
+}
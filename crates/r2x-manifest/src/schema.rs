@@ -0,0 +1,337 @@
+//! JSON Schema for the manifest
+//!
+//! Hand-written to mirror the serde layout of the types in [`crate::types`]:
+//! renamed fields (`type`), skipped runtime fields (hashes, indexes) and
+//! defaulted fields follow the attributes on each struct. Update the matching
+//! definition here whenever a serialized field changes.
+
+use serde_json::{json, Map, Value};
+
+/// Dialect the schema is written against
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Schema describing a serialized [`Manifest`](crate::types::Manifest)
+pub fn manifest_schema() -> Value {
+    let mut schema = object(
+        &[
+            ("version", string()),
+            ("schema_version", integer()),
+            ("generated_at", string()),
+            ("r2x_version", string()),
+            ("packages", array(reference("Package"))),
+        ],
+        &["version"],
+    );
+    if let Value::Object(map) = &mut schema {
+        map.insert("$schema".to_string(), json!(SCHEMA_DIALECT));
+        map.insert("title".to_string(), json!("Manifest"));
+        map.insert("$defs".to_string(), Value::Object(definitions()));
+    }
+    schema
+}
+
+fn definitions() -> Map<String, Value> {
+    let defs = [
+        (
+            "Package",
+            object(
+                &[
+                    ("name", string()),
+                    ("version", string()),
+                    ("editable_install", boolean()),
+                    ("source_uri", string()),
+                    ("source_kind", reference("PackageSource")),
+                    ("install_type", reference("InstallType")),
+                    ("installed_by", array(string())),
+                    ("dependencies", array(string())),
+                    ("entry_point", string()),
+                    ("plugins", array(reference("Plugin"))),
+                    ("configs", array(reference("ConfigClass"))),
+                ],
+                &["name", "version", "install_type"],
+            ),
+        ),
+        (
+            "PackageSource",
+            enumeration(&["pypi", "github", "git", "local"]),
+        ),
+        ("InstallType", enumeration(&["explicit", "dependency"])),
+        (
+            "Plugin",
+            object(
+                &[
+                    ("name", string()),
+                    ("type", reference("PluginType")),
+                    ("module", string()),
+                    ("class_name", string()),
+                    ("function_name", string()),
+                    ("call_method", string()),
+                    ("config_class", string()),
+                    ("config_module", string()),
+                    ("description", string()),
+                    ("hooks", array(string())),
+                    ("tags", array(string())),
+                    ("aliases", array(string())),
+                    ("min_r2x_core_version", string()),
+                    ("parameters", array(reference("Parameter"))),
+                    ("config_schema", reference("SchemaFields")),
+                    ("registrations", array(reference("DecoratorRegistration"))),
+                    ("upgrade", reference("UpgradeSpec")),
+                    ("condition", string()),
+                ],
+                &["name", "type", "module"],
+            ),
+        ),
+        ("PluginType", enumeration(&["class", "function"])),
+        (
+            "UpgradeSpec",
+            object(
+                &[("version_strategy", string()), ("version_reader", string())],
+                &[],
+            ),
+        ),
+        (
+            "DecoratorRegistration",
+            object(
+                &[
+                    ("function", string()),
+                    ("decorator", string()),
+                    ("target_version", string()),
+                    ("min_version", string()),
+                    ("max_version", string()),
+                    ("priority", integer()),
+                ],
+                &["function", "decorator"],
+            ),
+        ),
+        (
+            "SchemaFields",
+            json!({
+                "type": "object",
+                "additionalProperties": reference("SchemaField"),
+            }),
+        ),
+        (
+            "SchemaField",
+            object(
+                &[
+                    ("type", reference("FieldType")),
+                    ("required", boolean()),
+                    ("default", reference("DefaultValue")),
+                    ("constraints", array(reference("Constraint"))),
+                    ("enum_values", array(string())),
+                    ("items", string()),
+                    ("nested", reference("NestedInfo")),
+                    ("properties", reference("SchemaFields")),
+                ],
+                &["type"],
+            ),
+        ),
+        (
+            "FieldType",
+            enumeration(&[
+                "str", "int", "float", "bool", "array", "object", "datetime", "any",
+            ]),
+        ),
+        ("Constraint", constraint()),
+        (
+            "DefaultValue",
+            json!({
+                "anyOf": [
+                    boolean(),
+                    integer(),
+                    { "type": "number" },
+                    string(),
+                    array(reference("DefaultValue")),
+                    object(&[("expr", string())], &["expr"]),
+                ],
+            }),
+        ),
+        (
+            "NestedInfo",
+            object(&[("class", string()), ("module", string())], &[]),
+        ),
+        (
+            "Parameter",
+            object(
+                &[
+                    ("name", string()),
+                    ("type", array(string())),
+                    ("module", string()),
+                    ("required", boolean()),
+                    ("default", reference("DefaultValue")),
+                    ("description", string()),
+                ],
+                &["name", "type"],
+            ),
+        ),
+        (
+            "ConfigClass",
+            object(
+                &[
+                    ("name", string()),
+                    ("module", string()),
+                    ("base", string()),
+                    ("base_module", string()),
+                    ("fields", array(reference("ConfigField"))),
+                ],
+                &["name", "module"],
+            ),
+        ),
+        (
+            "ConfigField",
+            object(
+                &[
+                    ("name", string()),
+                    ("type", string()),
+                    ("module", string()),
+                    ("required", boolean()),
+                    ("default", reference("DefaultValue")),
+                ],
+                &["name", "type"],
+            ),
+        ),
+    ];
+
+    defs.into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect()
+}
+
+/// `Constraint` is adjacently tagged: `{ "kind": "ge", "value": 0.0 }`
+fn constraint() -> Value {
+    let variant = |kinds: &[&str], value: Value| {
+        object(
+            &[("kind", enumeration(kinds)), ("value", value)],
+            &["kind", "value"],
+        )
+    };
+    json!({
+        "oneOf": [
+            variant(&["ge", "le", "gt", "lt", "multiple_of"], json!({ "type": "number" })),
+            variant(&["min_len", "max_len"], json!({ "type": "integer", "minimum": 0 })),
+            variant(&["pattern"], string()),
+        ],
+    })
+}
+
+fn object(properties: &[(&str, Value)], required: &[&str]) -> Value {
+    let properties: Map<String, Value> = properties
+        .iter()
+        .map(|(name, schema)| ((*name).to_string(), schema.clone()))
+        .collect();
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+    });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+    schema
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{}", name) })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn enumeration(values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer" })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::*;
+    use crate::types::{Manifest, Package, Plugin};
+    use std::sync::Arc;
+
+    #[test]
+    fn schema_describes_manifest_packages_and_plugins() {
+        let schema = manifest_schema();
+        assert_eq!(schema["$schema"], SCHEMA_DIALECT);
+        assert_eq!(schema["title"], "Manifest");
+        assert_eq!(
+            schema["properties"]["packages"]["items"]["$ref"],
+            "#/$defs/Package"
+        );
+        assert_eq!(
+            schema["$defs"]["Package"]["properties"]["plugins"]["items"]["$ref"],
+            "#/$defs/Plugin"
+        );
+        assert!(schema["$defs"]["Plugin"]["properties"]["type"].is_object());
+        assert!(schema["$defs"]["Plugin"]["properties"]["content_hash"].is_null());
+    }
+
+    #[test]
+    fn every_reference_resolves() {
+        fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(target)) = map.get("$ref") {
+                        refs.push(target.clone());
+                    }
+                    map.values().for_each(|v| collect_refs(v, refs));
+                }
+                Value::Array(items) => items.iter().for_each(|v| collect_refs(v, refs)),
+                _ => {}
+            }
+        }
+
+        let schema = manifest_schema();
+        let mut refs = Vec::new();
+        collect_refs(&schema, &mut refs);
+        assert!(!refs.is_empty());
+        for target in refs {
+            let name = target.trim_start_matches("#/$defs/");
+            assert!(schema["$defs"][name].is_object(), "dangling {}", target);
+        }
+    }
+
+    #[test]
+    fn serialized_manifest_uses_only_described_properties() {
+        let mut manifest = Manifest::default();
+        let mut package = Package {
+            name: Arc::from("r2x-reeds"),
+            ..Default::default()
+        };
+        package.plugins.push(Plugin {
+            name: Arc::from("reeds-parser"),
+            module: Arc::from("r2x_reeds"),
+            class_name: Some(Arc::from("ReEDSParser")),
+            ..Default::default()
+        });
+        manifest.packages.push(package);
+
+        let schema = manifest_schema();
+        let value = serde_json::to_value(&manifest).unwrap_or_default();
+        let described = |def: &Value, instance: &Value| {
+            instance
+                .as_object()
+                .is_some_and(|fields| fields.keys().all(|key| def["properties"][key].is_object()))
+        };
+        assert!(described(&schema, &value));
+        assert!(described(
+            &schema["$defs"]["Package"],
+            &value["packages"][0]
+        ));
+        assert!(described(
+            &schema["$defs"]["Plugin"],
+            &value["packages"][0]["plugins"][0]
+        ));
+    }
+}
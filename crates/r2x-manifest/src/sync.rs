@@ -36,6 +36,103 @@ pub enum Change {
     Remove(Arc<str>),
 }
 
+/// A difference between two manifests, as reported by [`Manifest::diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestDiff {
+    PackageAdded(Arc<str>),
+    PackageRemoved(Arc<str>),
+    VersionChanged {
+        package: Arc<str>,
+        from: Arc<str>,
+        to: Arc<str>,
+    },
+    PluginAdded {
+        package: Arc<str>,
+        plugin: Arc<str>,
+    },
+    PluginRemoved {
+        package: Arc<str>,
+        plugin: Arc<str>,
+    },
+    /// Same plugin name, different metadata (signature, bindings, ...)
+    PluginChanged {
+        package: Arc<str>,
+        plugin: Arc<str>,
+    },
+}
+
+impl std::fmt::Display for ManifestDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestDiff::PackageAdded(name) => write!(f, "+ package {}", name),
+            ManifestDiff::PackageRemoved(name) => write!(f, "- package {}", name),
+            ManifestDiff::VersionChanged { package, from, to } => {
+                write!(f, "~ package {}: version {} -> {}", package, from, to)
+            }
+            ManifestDiff::PluginAdded { package, plugin } => {
+                write!(f, "+ plugin {} ({})", plugin, package)
+            }
+            ManifestDiff::PluginRemoved { package, plugin } => {
+                write!(f, "- plugin {} ({})", plugin, package)
+            }
+            ManifestDiff::PluginChanged { package, plugin } => {
+                write!(f, "~ plugin {} ({})", plugin, package)
+            }
+        }
+    }
+}
+
+impl Manifest {
+    /// Differences that turn `self` into `other`, in package order
+    ///
+    /// Packages and plugins are matched by name; plugin metadata is compared
+    /// by its serialized form, so runtime-only indexes never count as a change.
+    pub fn diff(&self, other: &Manifest) -> Vec<ManifestDiff> {
+        let mut diffs = Vec::new();
+        for old_pkg in &self.packages {
+            let Some(new_pkg) = other.packages.iter().find(|p| p.name == old_pkg.name) else {
+                diffs.push(ManifestDiff::PackageRemoved(old_pkg.name.clone()));
+                continue;
+            };
+            if old_pkg.version != new_pkg.version {
+                diffs.push(ManifestDiff::VersionChanged {
+                    package: old_pkg.name.clone(),
+                    from: old_pkg.version.clone(),
+                    to: new_pkg.version.clone(),
+                });
+            }
+            for old_plugin in &old_pkg.plugins {
+                let plugin = old_plugin.name.clone();
+                let package = old_pkg.name.clone();
+                match new_pkg.plugins.iter().find(|p| p.name == old_plugin.name) {
+                    None => diffs.push(ManifestDiff::PluginRemoved { package, plugin }),
+                    Some(new_plugin)
+                        if serde_json::to_value(old_plugin).ok()
+                            != serde_json::to_value(new_plugin).ok() =>
+                    {
+                        diffs.push(ManifestDiff::PluginChanged { package, plugin });
+                    }
+                    Some(_) => {}
+                }
+            }
+            for new_plugin in &new_pkg.plugins {
+                if !old_pkg.plugins.iter().any(|p| p.name == new_plugin.name) {
+                    diffs.push(ManifestDiff::PluginAdded {
+                        package: new_pkg.name.clone(),
+                        plugin: new_plugin.name.clone(),
+                    });
+                }
+            }
+        }
+        for new_pkg in &other.packages {
+            if !self.packages.iter().any(|p| p.name == new_pkg.name) {
+                diffs.push(ManifestDiff::PackageAdded(new_pkg.name.clone()));
+            }
+        }
+        diffs
+    }
+}
+
 // =============================================================================
 // SYNC ENGINE
 // =============================================================================
@@ -343,6 +440,39 @@ mod tests {
         assert_eq!(result.packages_updated, 1);
     }
 
+    #[test]
+    fn test_manifest_diff_reports_drift() {
+        let mut manifest = Manifest::default();
+        manifest.packages.push(create_test_package("test-pkg-1"));
+        manifest.packages.push(create_test_package("test-pkg-2"));
+        assert!(manifest.diff(&manifest.clone()).is_empty());
+
+        let mut drifted = manifest.clone();
+        drifted.packages[0].version = Arc::from("2.0.0");
+        drifted.packages[0].plugins[0].class_name = Some(Arc::from("RenamedPlugin"));
+        drifted.packages.remove(1);
+        drifted.packages.push(create_test_package("test-pkg-3"));
+
+        let diffs = manifest.diff(&drifted);
+        assert_eq!(
+            diffs,
+            vec![
+                ManifestDiff::VersionChanged {
+                    package: Arc::from("test-pkg-1"),
+                    from: Arc::from("1.0.0"),
+                    to: Arc::from("2.0.0"),
+                },
+                ManifestDiff::PluginChanged {
+                    package: Arc::from("test-pkg-1"),
+                    plugin: Arc::from("test-pkg-1-plugin"),
+                },
+                ManifestDiff::PackageRemoved(Arc::from("test-pkg-2")),
+                ManifestDiff::PackageAdded(Arc::from("test-pkg-3")),
+            ]
+        );
+        assert_eq!(diffs[2].to_string(), "- package test-pkg-2");
+    }
+
     #[test]
     fn test_string_interner() {
         let interner = StringInterner::new();
@@ -7,19 +7,51 @@
 //! - Pre-computed hashes for fast comparison
 
 use ahash::AHashMap;
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 // =============================================================================
 // MANIFEST - Top-level with index for O(1) lookup
 // =============================================================================
 
+/// Layout of the manifest file; bump it together with a step in [`Manifest::migrate`]
+pub const MANIFEST_SCHEMA_VERSION: u32 = 2;
+
+static R2X_VERSION: OnceLock<&'static str> = OnceLock::new();
+
+/// Record the r2x release that writes manifests (the CLI sets this at startup)
+pub fn set_r2x_version(version: &'static str) {
+    let _ = R2X_VERSION.set(version);
+}
+
+/// r2x version recorded in written manifests; this crate's version until set
+pub fn r2x_version() -> &'static str {
+    R2X_VERSION
+        .get()
+        .copied()
+        .unwrap_or(env!("CARGO_PKG_VERSION"))
+}
+
+/// Files without a `schema_version` predate the field and are read as schema 1
+fn legacy_schema_version() -> u32 {
+    1
+}
+
 /// Top-level manifest structure for R2X plugin metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Manifest {
     pub version: Arc<str>,
+    /// Schema the file was written with; [`Manifest::migrate`] upgrades older ones
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
+    /// When the manifest was last written (RFC 3339); empty if never recorded
+    #[serde(default)]
     pub generated_at: Arc<str>,
+    /// r2x version that last wrote the manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r2x_version: Option<Arc<str>>,
     #[serde(default)]
     pub packages: Vec<Package>,
 
@@ -30,20 +62,86 @@ pub struct Manifest {
     /// Runtime only - content hash for fast equality check
     #[serde(skip)]
     pub content_hash: u64,
+    /// Runtime only - plugin name index, built on first lookup
+    #[serde(skip)]
+    pub plugin_lookup: PluginLookupCache,
 }
 
 impl Default for Manifest {
     fn default() -> Self {
         Manifest {
             version: Arc::from("3.0"),
+            schema_version: MANIFEST_SCHEMA_VERSION,
             generated_at: Arc::from(chrono::Utc::now().to_rfc3339()),
+            r2x_version: None,
             packages: Vec::new(),
             package_index: AHashMap::new(),
             content_hash: 0,
+            plugin_lookup: PluginLookupCache::default(),
         }
     }
 }
 
+/// Reverse index from plugin name to `(package, plugin)` positions
+pub(crate) struct PluginLookup {
+    pub(crate) entries: AHashMap<Arc<str>, SmallVec<[(usize, usize); 1]>>,
+}
+
+impl PluginLookup {
+    fn build(packages: &[Package]) -> Self {
+        let mut entries: AHashMap<Arc<str>, SmallVec<[(usize, usize); 1]>> = AHashMap::new();
+        for (package_idx, package) in packages.iter().enumerate() {
+            for (plugin_idx, plugin) in package.plugins.iter().enumerate() {
+                entries
+                    .entry(plugin.name.clone())
+                    .or_default()
+                    .push((package_idx, plugin_idx));
+            }
+        }
+        PluginLookup { entries }
+    }
+}
+
+/// Lazily built [`PluginLookup`] shared by lookups on a `&Manifest`
+///
+/// The `&mut Manifest` methods that hand out or change packages drop it; code
+/// that edits `Manifest::packages` directly must call
+/// [`Manifest::rebuild_indexes`] afterwards. Cloning a manifest starts it with
+/// an empty cache.
+#[derive(Default)]
+pub struct PluginLookupCache(RwLock<Option<Arc<PluginLookup>>>);
+
+impl PluginLookupCache {
+    /// Drop the index so the next lookup rebuilds it
+    pub fn invalidate(&self) {
+        *self.0.write() = None;
+    }
+
+    /// The index for `packages`, built if it was never built or was invalidated
+    pub(crate) fn get_or_build(&self, packages: &[Package]) -> Arc<PluginLookup> {
+        if let Some(lookup) = self.0.read().as_ref() {
+            return Arc::clone(lookup);
+        }
+        let lookup = Arc::new(PluginLookup::build(packages));
+        *self.0.write() = Some(Arc::clone(&lookup));
+        lookup
+    }
+}
+
+impl Clone for PluginLookupCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl std::fmt::Debug for PluginLookupCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginLookupCache")
+            .field("built", &self.0.read().is_some())
+            .finish()
+    }
+}
+
 // =============================================================================
 // PACKAGE - With pre-computed hash for fast comparison
 // =============================================================================
@@ -146,16 +244,42 @@ pub struct Plugin {
     pub class_name: Option<Arc<str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub function_name: Option<Arc<str>>,
+    /// Method `r2x run` calls on a class plugin, inferred from the methods the
+    /// class defines; the role's default method is used when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_method: Option<Arc<str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config_class: Option<Arc<str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config_module: Option<Arc<str>>,
+    /// From `description=` at registration, or the entry symbol's docstring
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Arc<str>>,
     #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
     pub hooks: SmallVec<[Arc<str>; 4]>,
     #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub tags: SmallVec<[Arc<str>; 2]>,
+    /// Former names from `aliases=` at registration; they still resolve to
+    /// this plugin, with a deprecation note
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
+    pub aliases: SmallVec<[Arc<str>; 2]>,
+    /// Oldest r2x-core the plugin supports, from `requires_core=` at registration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_r2x_core_version: Option<Arc<str>>,
+    #[serde(default, skip_serializing_if = "SmallVec::is_empty")]
     pub parameters: SmallVec<[Parameter; 4]>,
     #[serde(default, skip_serializing_if = "SchemaFields::is_empty")]
     pub config_schema: SchemaFields,
+    /// Functions registered on the plugin class through a decorator
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registrations: Vec<DecoratorRegistration>,
+    /// Version handling declared by an upgrader class
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade: Option<UpgradeSpec>,
+    /// Source text of the `if` the entry symbol is defined under, e.g.
+    /// `sys.platform == "win32"`; recorded as written, never evaluated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Arc<str>>,
 
     /// Runtime only - content hash
     #[serde(skip)]
@@ -170,16 +294,117 @@ impl Default for Plugin {
             module: Arc::from(""),
             class_name: None,
             function_name: None,
+            call_method: None,
             config_class: None,
             config_module: None,
+            description: None,
             hooks: SmallVec::new(),
+            tags: SmallVec::new(),
+            aliases: SmallVec::new(),
+            min_r2x_core_version: None,
             parameters: SmallVec::new(),
             config_schema: SchemaFields::default(),
+            registrations: Vec::new(),
+            upgrade: None,
+            condition: None,
             content_hash: 0,
         }
     }
 }
 
+/// `version_strategy` and `version_reader` of an upgrader class
+///
+/// Each is the source text of the class attribute, e.g. `SemanticVersioning`
+/// or `FileVersionReader()`, resolved in the upgrader's module at run time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpgradeSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_strategy: Option<Arc<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_reader: Option<Arc<str>>,
+}
+
+/// Priority of a registration that does not declare one
+pub const DEFAULT_REGISTRATION_PRIORITY: i64 = 100;
+
+/// Function registered through a decorator on a plugin class
+///
+/// For upgraders these are the upgrade steps, e.g.
+/// `@ReEDSUpgrader.register_step(target_version="2.0", priority=10)`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DecoratorRegistration {
+    /// Name of the decorated function
+    pub function: Arc<str>,
+    /// Decorator method on the plugin class (e.g., `register_step`)
+    pub decorator: Arc<str>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_version: Option<Arc<str>>,
+    /// Oldest input version the step accepts (inclusive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<Arc<str>>,
+    /// Newest input version the step accepts (inclusive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_version: Option<Arc<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i64>,
+}
+
+impl DecoratorRegistration {
+    /// Priority used for ordering, falling back to [`DEFAULT_REGISTRATION_PRIORITY`]
+    pub fn effective_priority(&self) -> i64 {
+        self.priority.unwrap_or(DEFAULT_REGISTRATION_PRIORITY)
+    }
+
+    /// Whether this step still applies to data at `version`
+    ///
+    /// True when `target_version` is newer than `version`; steps without a
+    /// target apply at any version.
+    pub fn applies_since(&self, version: &str) -> bool {
+        self.target_version.as_deref().map_or(true, |target| {
+            crate::runtime::compare_versions(target, version) == std::cmp::Ordering::Greater
+        })
+    }
+
+    /// Whether some input version is accepted by both this step and `other`
+    ///
+    /// A step accepts versions from `min_version` through `max_version` that
+    /// are below `target_version`; a missing bound leaves that side open.
+    pub fn overlaps(&self, other: &DecoratorRegistration) -> bool {
+        use crate::runtime::compare_versions;
+        use std::cmp::Ordering;
+
+        let Some(lower) = [self.min_version.as_deref(), other.min_version.as_deref()]
+            .into_iter()
+            .flatten()
+            .max_by(|a, b| compare_versions(a, b))
+        else {
+            return true;
+        };
+        [self.upper_bound(), other.upper_bound()]
+            .into_iter()
+            .flatten()
+            .all(|(upper, inclusive)| match compare_versions(lower, upper) {
+                Ordering::Less => true,
+                Ordering::Equal => inclusive,
+                Ordering::Greater => false,
+            })
+    }
+
+    /// Tightest of `max_version` (inclusive) and `target_version` (exclusive)
+    fn upper_bound(&self) -> Option<(&str, bool)> {
+        match (self.max_version.as_deref(), self.target_version.as_deref()) {
+            (Some(max), Some(target))
+                if crate::runtime::compare_versions(max, target) == std::cmp::Ordering::Less =>
+            {
+                Some((max, true))
+            }
+            (_, Some(target)) => Some((target, false)),
+            (Some(max), None) => Some((max, true)),
+            (None, None) => None,
+        }
+    }
+}
+
 /// Plugin implementation type
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Hash, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -319,7 +544,7 @@ pub enum Constraint {
 // DEFAULT VALUE - Inline small values
 // =============================================================================
 
-/// Default value for a schema field
+/// Default value for a schema field or parameter
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum DefaultValue {
@@ -328,6 +553,108 @@ pub enum DefaultValue {
     Float(f64),
     Str(Arc<str>),
     Array(Arc<[DefaultValue]>),
+    /// Python expression that is not a plain literal, kept as source text
+    /// (e.g. `None`, `Path("data")`)
+    Expr {
+        expr: Arc<str>,
+    },
+}
+
+impl DefaultValue {
+    /// Python's `None`
+    pub fn none() -> Self {
+        DefaultValue::Expr {
+            expr: Arc::from("None"),
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, DefaultValue::Expr { expr } if expr.as_ref() == "None")
+    }
+
+    /// Typed value of a Python literal: a string, bool, number or `[]`
+    ///
+    /// `None` and other expressions are not literals and give `None`.
+    pub fn parse_literal(value: &str) -> Option<Self> {
+        let value = value.trim();
+
+        // Boolean
+        if value == "True" || value == "true" {
+            return Some(DefaultValue::Bool(true));
+        }
+        if value == "False" || value == "false" {
+            return Some(DefaultValue::Bool(false));
+        }
+
+        // None
+        if value == "None" {
+            return None;
+        }
+
+        // String
+        if value.len() >= 2
+            && ((value.starts_with('"') && value.ends_with('"'))
+                || (value.starts_with('\'') && value.ends_with('\'')))
+        {
+            let inner = &value[1..value.len() - 1];
+            return Some(DefaultValue::Str(Arc::from(inner)));
+        }
+
+        // Empty list
+        if value == "[]" {
+            return Some(DefaultValue::Array(Arc::from([])));
+        }
+
+        // Integer
+        if let Ok(i) = value.parse::<i64>() {
+            return Some(DefaultValue::Int(i));
+        }
+
+        // Float
+        if let Ok(f) = value.parse::<f64>() {
+            return Some(DefaultValue::Float(f));
+        }
+
+        None
+    }
+
+    /// Typed value of a default written as Python source text
+    ///
+    /// Literals become their typed variants; `None` and any other expression
+    /// are kept verbatim as [`DefaultValue::Expr`].
+    pub fn from_python_source(value: &str) -> Self {
+        let value = value.trim();
+        if value == "None" {
+            return DefaultValue::none();
+        }
+        DefaultValue::parse_literal(value).unwrap_or_else(|| DefaultValue::Expr {
+            expr: Arc::from(value),
+        })
+    }
+}
+
+impl std::fmt::Display for DefaultValue {
+    /// Render the value the way it would appear in Python source.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefaultValue::Bool(true) => write!(f, "True"),
+            DefaultValue::Bool(false) => write!(f, "False"),
+            DefaultValue::Int(value) => write!(f, "{}", value),
+            DefaultValue::Float(value) => write!(f, "{:?}", value),
+            DefaultValue::Str(value) => write!(f, "{:?}", value.as_ref()),
+            DefaultValue::Array(items) => {
+                write!(f, "[")?;
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            DefaultValue::Expr { expr } => write!(f, "{}", expr),
+        }
+    }
 }
 
 // =============================================================================
@@ -348,7 +675,7 @@ pub struct NestedInfo {
 // =============================================================================
 
 /// Function parameter specification
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Parameter {
     pub name: Arc<str>,
     /// Array of type alternatives (for union types like int | str)
@@ -359,7 +686,7 @@ pub struct Parameter {
     #[serde(default)]
     pub required: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<Arc<str>>,
+    pub default: Option<DefaultValue>,
     /// Description extracted from Field(description="...")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<Arc<str>>,
@@ -374,6 +701,18 @@ impl Parameter {
             .collect::<Vec<_>>()
             .join(" | ")
     }
+
+    /// Whether the parameter must be supplied
+    ///
+    /// Types that admit `None` (`Optional[X]`, `X | None`) make a parameter
+    /// optional even when an older manifest recorded it as required.
+    pub fn is_required(&self) -> bool {
+        self.required
+            && !self
+                .types
+                .iter()
+                .any(|ty| crate::runtime::is_optional_annotation(ty))
+    }
 }
 
 // =============================================================================
@@ -414,6 +753,7 @@ pub struct ConfigField {
 impl Manifest {
     /// Rebuild all indexes after deserialization
     pub fn rebuild_indexes(&mut self) {
+        self.plugin_lookup.invalidate();
         self.package_index.clear();
         for (idx, pkg) in self.packages.iter_mut().enumerate() {
             self.package_index.insert(pkg.name.clone(), idx);
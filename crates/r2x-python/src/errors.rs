@@ -1,13 +1,36 @@
+use crate::plugin_regular::format_python_error;
+use pyo3::exceptions::{PyImportError, PyValueError};
+use pyo3::types::PyTypeMethods;
+use pyo3::{PyErr, Python};
 use std::io;
 use std::path::PathBuf;
 use thiserror::Error;
 
+/// Built-in exception a raised Python error derives from
+///
+/// Subclasses count, so pydantic's `ValidationError` is a `Value` error and
+/// `ModuleNotFoundError` an `Import` error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PyErrorKind {
+    Value,
+    Import,
+    Other,
+}
+
 /// Errors that can occur during Python bridge operations
 #[derive(Error, Debug)]
 pub enum BridgeError {
     #[error("Python error: {0}")]
     Python(String),
 
+    /// A raised Python exception, with its type name (e.g. `ImportError`)
+    #[error("Python error: {message}")]
+    PythonTyped {
+        py_type: String,
+        kind: PyErrorKind,
+        message: String,
+    },
+
     #[error("Failed to import module '{0}': {1}")]
     Import(String, String),
 
@@ -36,14 +59,141 @@ pub enum BridgeError {
     Io(#[from] io::Error),
 }
 
+impl BridgeError {
+    /// `err` with its traceback, under `context`, keeping the exception type
+    pub(crate) fn python_typed(py: Python<'_>, err: PyErr, context: &str) -> Self {
+        let py_type = exception_type_name(py, &err);
+        let kind = exception_kind(py, &err);
+        BridgeError::PythonTyped {
+            py_type,
+            kind,
+            message: format_python_error(py, err, context),
+        }
+    }
+
+    /// Type name of the Python exception behind this error, if one was kept
+    pub fn py_type(&self) -> Option<&str> {
+        match self {
+            BridgeError::PythonTyped { py_type, .. } => Some(py_type),
+            _ => None,
+        }
+    }
+
+    /// Built-in exception behind this error, if one was kept
+    pub fn py_error_kind(&self) -> Option<PyErrorKind> {
+        match self {
+            BridgeError::PythonTyped { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn exception_kind(py: Python<'_>, err: &PyErr) -> PyErrorKind {
+    if err.is_instance_of::<PyValueError>(py) {
+        PyErrorKind::Value
+    } else if err.is_instance_of::<PyImportError>(py) {
+        PyErrorKind::Import
+    } else {
+        PyErrorKind::Other
+    }
+}
+
+pub(crate) fn exception_type_name(py: Python<'_>, err: &PyErr) -> String {
+    err.get_type(py)
+        .name()
+        .map(|name| name.to_string())
+        .unwrap_or_default()
+}
+
 /// Generic conversion from PyErr to BridgeError.
 ///
 /// NOTE: This conversion loses the Python traceback information!
 /// For user-facing errors where tracebacks are important (plugin failures,
-/// config instantiation, etc.), use `format_python_error()` or
+/// config instantiation, etc.), use `BridgeError::python_typed()` or
 /// `format_exception_value()` from plugin_regular.rs instead.
-impl From<pyo3::PyErr> for BridgeError {
-    fn from(err: pyo3::PyErr) -> Self {
-        BridgeError::Python(format!("{}", err))
+impl From<PyErr> for BridgeError {
+    fn from(err: PyErr) -> Self {
+        let (py_type, kind) =
+            Python::attach(|py| (exception_type_name(py, &err), exception_kind(py, &err)));
+        BridgeError::PythonTyped {
+            py_type,
+            kind,
+            message: format!("{}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::*;
+    use pyo3::types::PyModule;
+
+    fn raised(code: &str) -> Option<BridgeError> {
+        Python::initialize();
+        Python::attach(|py| {
+            let err = py
+                .run(&std::ffi::CString::new(code).ok()?, None, None)
+                .err()?;
+            Some(BridgeError::python_typed(py, err, "Plugin call failed"))
+        })
+    }
+
+    #[test]
+    fn raised_exceptions_keep_their_type() {
+        let import_error = raised("import r2x_module_that_does_not_exist");
+        assert_eq!(
+            import_error.as_ref().and_then(BridgeError::py_type),
+            Some("ModuleNotFoundError")
+        );
+        let import_error = raised("raise ImportError('no plugin')");
+        assert_eq!(
+            import_error.as_ref().and_then(BridgeError::py_type),
+            Some("ImportError")
+        );
+
+        assert_eq!(
+            raised("import r2x_module_that_does_not_exist")
+                .as_ref()
+                .and_then(BridgeError::py_error_kind),
+            Some(PyErrorKind::Import)
+        );
+        assert_eq!(
+            raised("class ValidationError(ValueError): pass\nraise ValidationError('bad')")
+                .as_ref()
+                .and_then(BridgeError::py_error_kind),
+            Some(PyErrorKind::Value)
+        );
+        assert_eq!(
+            raised("raise KeyError('x')")
+                .as_ref()
+                .and_then(BridgeError::py_error_kind),
+            Some(PyErrorKind::Other)
+        );
+
+        let value_error = raised("raise ValueError('solve_year must be >= 2020')");
+        assert_eq!(
+            value_error.as_ref().and_then(BridgeError::py_type),
+            Some("ValueError")
+        );
+        assert!(value_error.is_some_and(|e| e
+            .to_string()
+            .starts_with("Python error: Plugin call failed")
+            && e.to_string()
+                .contains("ValueError: solve_year must be >= 2020")));
+    }
+
+    #[test]
+    fn converted_errors_keep_their_type() {
+        Python::initialize();
+        let err = Python::attach(|py| {
+            PyModule::import(py, "r2x_module_that_does_not_exist")
+                .err()
+                .map(BridgeError::from)
+        });
+        assert_eq!(
+            err.as_ref().and_then(BridgeError::py_type),
+            Some("ModuleNotFoundError")
+        );
+        assert!(err.is_some_and(|e| e.to_string().starts_with("Python error: ")));
     }
 }
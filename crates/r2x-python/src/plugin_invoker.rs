@@ -4,22 +4,170 @@ use crate::errors::BridgeError;
 use r2x_logger as logger;
 use r2x_manifest::runtime::{build_runtime_bindings, PluginRole, RuntimeBindings};
 use r2x_manifest::types::Plugin;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static TRACE_CALLS: AtomicBool = AtomicBool::new(false);
+
+/// Log every import, attribute lookup and call made into Python (`--trace-calls`)
+pub fn set_trace_calls(enabled: bool) {
+    TRACE_CALLS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn get_trace_calls() -> bool {
+    TRACE_CALLS.load(Ordering::Relaxed)
+}
+
+static UPGRADE_SINCE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Data version upgraders start from (`r2x run --since`), in place of the
+/// version their `version_reader` reports
+pub fn set_upgrade_since(version: Option<String>) {
+    if let Ok(mut since) = UPGRADE_SINCE.lock() {
+        *since = version;
+    }
+}
+
+pub fn get_upgrade_since() -> Option<String> {
+    UPGRADE_SINCE.lock().ok().and_then(|since| since.clone())
+}
+
+/// Run the Python operation `f`, logging `operation` and its duration when call tracing is on.
+///
+/// `operation` is only evaluated while tracing, so untraced runs pay for one
+/// atomic load per call boundary.
+pub(crate) fn trace_call<T>(operation: impl FnOnce() -> String, f: impl FnOnce() -> T) -> T {
+    if !get_trace_calls() {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    let message = format!(
+        "trace: {} ({:.3}ms)",
+        operation(),
+        start.elapsed().as_secs_f64() * 1000.0
+    );
+    logger::debug(&message);
+    result
+}
 
 /// Timings for a plugin invocation phase
+#[derive(Debug, Clone, Copy)]
 pub struct PluginInvocationTimings {
     pub python_invocation: Duration,
     pub serialization: Duration,
 }
 
+/// What a plugin call left for downstream steps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginOutcome {
+    /// The plugin returned data to pass on (usually a serialized system)
+    Produced(String),
+    /// The plugin is expected to return data but returned `None`
+    NullReturn,
+    /// The plugin only writes files or reports; its return value is not forwarded
+    SideEffectOnly,
+}
+
+impl PluginOutcome {
+    /// Classify serialized plugin output.
+    ///
+    /// `declares_output` is false for roles whose return value is never
+    /// forwarded (exporters, validators).
+    pub fn classify(output: &str, declares_output: bool) -> Self {
+        if !declares_output {
+            return Self::SideEffectOnly;
+        }
+        let trimmed = output.trim();
+        if trimmed.is_empty() || trimmed == "null" {
+            Self::NullReturn
+        } else {
+            Self::Produced(output.to_string())
+        }
+    }
+
+    /// Output to forward downstream, if any.
+    pub fn produced(&self) -> Option<&str> {
+        match self {
+            Self::Produced(output) => Some(output),
+            Self::NullReturn | Self::SideEffectOnly => None,
+        }
+    }
+}
+
+/// Whether a plugin's return value is meant to be forwarded on stdout.
+pub fn declares_output(runtime_bindings: Option<&RuntimeBindings>) -> bool {
+    !runtime_bindings
+        .is_some_and(|b| matches!(b.role, PluginRole::Exporter | PluginRole::Validator))
+}
+
 /// Result of running a plugin through the Python bridge
 pub struct PluginInvocationResult {
     /// JSON text emitted by the plugin (may be `"null"`)
     pub output: String,
+    /// Typed interpretation of `output`
+    pub outcome: PluginOutcome,
     /// Optional per-phase timings for diagnostics
     pub timings: Option<PluginInvocationTimings>,
 }
 
+impl PluginInvocationResult {
+    pub fn new(
+        output: String,
+        timings: Option<PluginInvocationTimings>,
+        declares_output: bool,
+    ) -> Self {
+        let outcome = PluginOutcome::classify(&output, declares_output);
+        Self {
+            output,
+            outcome,
+            timings,
+        }
+    }
+}
+
+/// Pass/fail outcome reported by a validator plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub passed: bool,
+    pub diagnostics: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Serialize as `{"passed": bool, "diagnostics": [...]}`.
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "passed": self.passed,
+            "diagnostics": self.diagnostics,
+        })
+        .to_string()
+    }
+
+    /// Parse the JSON produced by [`ValidationReport::to_json`].
+    pub fn from_json(output: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(output).ok()?;
+        let passed = value.get("passed")?.as_bool()?;
+        let diagnostics = value
+            .get("diagnostics")
+            .and_then(|d| d.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| match item.as_str() {
+                        Some(text) => text.to_string(),
+                        None => item.to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Self {
+            passed,
+            diagnostics,
+        })
+    }
+}
+
 impl crate::python_bridge::Bridge {
     pub fn invoke_plugin(
         &self,
@@ -66,13 +214,99 @@ impl crate::python_bridge::Bridge {
 #[cfg(test)]
 mod tests {
     use crate::plugin_invoker::*;
+    use crate::python_bridge::Bridge;
+    use pyo3::types::{PyAnyMethods, PyModule};
+    use std::fs;
+    use tempfile::TempDir;
 
     #[test]
-    fn plugin_invocation_result_basics() {
-        let result = PluginInvocationResult {
-            output: String::new(),
-            timings: None,
+    fn traced_invocation_logs_import_and_call_boundaries() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
         };
+        assert!(fs::write(
+            temp_dir.path().join("r2x_trace_demo.py"),
+            "def double(value):\n    return {'value': value * 2}\n",
+        )
+        .is_ok());
+
+        pyo3::Python::initialize();
+        let added = pyo3::Python::attach(|py| -> pyo3::PyResult<()> {
+            PyModule::import(py, "sys")?
+                .getattr("path")?
+                .call_method1("insert", (0, temp_dir.path().to_string_lossy().as_ref()))?;
+            Ok(())
+        });
+        assert!(added.is_ok());
+
+        set_trace_calls(true);
+        let bridge = Bridge { _marker: () };
+        let mut result = None;
+        let lines = logger::capture_logs(|| {
+            result = Some(bridge.invoke_plugin_with_bindings(
+                "r2x_trace_demo:double",
+                r#"{"value": 2}"#,
+                None,
+                None,
+            ));
+        });
+        set_trace_calls(false);
+
+        assert!(result
+            .is_some_and(|result| result.is_ok_and(|result| result.output == r#"{"value": 4}"#)));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("DEBUG trace: import r2x_trace_demo (")));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("DEBUG trace: getattr r2x_trace_demo.double (")));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("DEBUG trace: call r2x_trace_demo.double (")));
+    }
+
+    #[test]
+    fn plugin_invocation_result_basics() {
+        let result = PluginInvocationResult::new(String::new(), None, true);
         assert!(result.output.is_empty());
+        assert_eq!(result.outcome, PluginOutcome::NullReturn);
+    }
+
+    #[test]
+    fn outcome_produced_carries_output() {
+        let outcome = PluginOutcome::classify("{\"name\": \"sys\"}", true);
+        assert_eq!(
+            outcome,
+            PluginOutcome::Produced("{\"name\": \"sys\"}".to_string())
+        );
+        assert_eq!(outcome.produced(), Some("{\"name\": \"sys\"}"));
+    }
+
+    #[test]
+    fn outcome_null_return_for_null_json() {
+        assert_eq!(
+            PluginOutcome::classify("null", true),
+            PluginOutcome::NullReturn
+        );
+        assert_eq!(PluginOutcome::classify(" \n", true).produced(), None);
+    }
+
+    #[test]
+    fn outcome_side_effect_only_ignores_output() {
+        assert_eq!(
+            PluginOutcome::classify("{}", false),
+            PluginOutcome::SideEffectOnly
+        );
+        assert!(declares_output(None));
+    }
+
+    #[test]
+    fn validation_report_round_trips_through_json() {
+        let report = ValidationReport {
+            passed: false,
+            diagnostics: vec!["bus 12 has no load zone".to_string()],
+        };
+        assert_eq!(ValidationReport::from_json(&report.to_json()), Some(report));
+        assert_eq!(ValidationReport::from_json("{}"), None);
     }
 }
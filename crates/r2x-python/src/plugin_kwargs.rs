@@ -1,13 +1,14 @@
 //! Keyword argument building for plugin invocation
 
 use crate::errors::BridgeError;
-use crate::plugin_regular::format_python_error;
+use crate::plugin_invoker::trace_call;
 use crate::python_bridge::Bridge;
 use pyo3::exceptions::PyFileNotFoundError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList, PyModule};
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyModule, PyString};
 use r2x_logger as logger;
-use r2x_manifest::runtime::{PluginRole, RuntimeBindings, RuntimeConfig};
+use r2x_manifest::runtime::{find_config_param, PluginRole, RuntimeBindings, RuntimeConfig};
+use r2x_manifest::types::DefaultValue;
 use std::path::Path;
 
 impl Bridge {
@@ -84,20 +85,13 @@ impl Bridge {
             ));
 
             // Find the parameter whose type annotation matches the config class name
-            for param in &runtime.parameters {
-                let type_matches = param
-                    .types
-                    .iter()
-                    .any(|t| t.as_ref() == config_class_name || t.contains(config_class_name));
-                if type_matches {
-                    needs_config_class = true;
-                    config_param_name = param.name.to_string();
-                    logger::debug(&format!(
-                        "Config parameter detected: '{}' (type matches config class '{}')",
-                        param.name, config_class_name
-                    ));
-                    break;
-                }
+            if let Some(param) = find_config_param(&runtime.parameters, config_class_name) {
+                needs_config_class = true;
+                config_param_name = param.name.to_string();
+                logger::debug(&format!(
+                    "Config parameter detected: '{}' (type matches config class '{}')",
+                    param.name, config_class_name
+                ));
             }
 
             // Fallback: if no annotation match, look for a param explicitly named after the config
@@ -202,19 +196,15 @@ impl Bridge {
 
             // Skip parameters that are config fields when we have a config class
             // (those values are already inside the config object)
-            let is_config_field = if needs_config_class {
-                // Check the config instance directly for the attribute
-                if let Some(ref config_obj) = config_instance {
-                    config_obj
-                        .bind(py)
-                        .hasattr(param.name.as_ref())
-                        .unwrap_or(false)
-                } else {
-                    false
-                }
-            } else {
-                false
-            };
+            let is_config_field = needs_config_class
+                && is_config_field(runtime.config.as_ref(), param.name.as_ref(), || {
+                    config_instance.as_ref().is_some_and(|config_obj| {
+                        config_obj
+                            .bind(py)
+                            .hasattr(param.name.as_ref())
+                            .unwrap_or(false)
+                    })
+                });
 
             if is_config_field {
                 logger::debug(&format!(
@@ -229,6 +219,17 @@ impl Bridge {
             }
 
             if let Some(value) = config_dict.get_item(param.name.as_ref()).ok().flatten() {
+                if param
+                    .default
+                    .as_ref()
+                    .is_some_and(|default| value_matches_default(&value, default))
+                {
+                    skipped_args.push((
+                        param.name.to_string(),
+                        "matches declared default".to_string(),
+                    ));
+                    continue;
+                }
                 let path_alias = value.clone();
                 kwargs.set_item(param.name.as_ref(), value)?;
                 created_args.push(param.name.to_string());
@@ -236,7 +237,7 @@ impl Bridge {
                     kwargs.set_item("path", path_alias)?;
                     created_args.push("path (alias of folder_path)".to_string());
                 }
-            } else if param.required {
+            } else if param.is_required() {
                 let stdin_param = param.name.as_ref() == "stdin" || param.name.as_ref() == "system";
                 if stdin_param && stdin_obj.is_some() {
                     logger::debug(&format!(
@@ -311,27 +312,39 @@ impl Bridge {
         let config_meta = config_metadata
             .ok_or_else(|| BridgeError::Python("Plugin config metadata missing".to_string()))?;
 
-        let config_module = PyModule::import(py, &config_meta.module).map_err(|e| {
-            BridgeError::Python(format_python_error(
+        let config_module = trace_call(
+            || format!("import {}", config_meta.module),
+            || PyModule::import(py, &config_meta.module),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(
                 py,
                 e,
                 &format!("Failed to import config module '{}'", config_meta.module),
-            ))
+            )
         })?;
-        let config_class = config_module.getattr(&config_meta.name).map_err(|e| {
-            BridgeError::Python(format_python_error(
+        let config_class = trace_call(
+            || format!("getattr {}.{}", config_meta.module, config_meta.name),
+            || config_module.getattr(&config_meta.name),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(
                 py,
                 e,
                 &format!("Failed to get config class '{}'", config_meta.name),
-            ))
+            )
         })?;
 
-        config_class.call((), Some(config_params)).map_err(|e| {
-            BridgeError::Python(format_python_error(
+        trace_call(
+            || format!("call {}.{}", config_meta.module, config_meta.name),
+            || config_class.call((), Some(config_params)),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(
                 py,
                 e,
                 &format!("Failed to instantiate config class '{}'", config_meta.name),
-            ))
+            )
         })
     }
 
@@ -355,7 +368,10 @@ impl Bridge {
             ));
         };
 
-        let data_store_module = PyModule::import(py, "r2x_core.store")?;
+        let data_store_module = trace_call(
+            || "import r2x_core.store".to_string(),
+            || PyModule::import(py, "r2x_core.store"),
+        )?;
         let data_store_class = data_store_module.getattr("DataStore")?;
 
         if let Some(config) = config_instance {
@@ -368,7 +384,10 @@ impl Bridge {
             // path is keyword-only in from_plugin_config(plugin_config, *, path)
             let kwargs = PyDict::new(py);
             kwargs.set_item("path", &path)?;
-            match from_config.call((config,), Some(&kwargs)) {
+            match trace_call(
+                || "call r2x_core.store.DataStore.from_plugin_config".to_string(),
+                || from_config.call((config,), Some(&kwargs)),
+            ) {
                 Ok(store) => Ok(store),
                 Err(err) => {
                     logger::debug(
@@ -403,7 +422,10 @@ Verify the data folder contains all expected outputs (did you unpack the full `i
             }
         } else {
             let store_path = path.clone();
-            match data_store_class.call1((path,)) {
+            match trace_call(
+                || "call r2x_core.store.DataStore".to_string(),
+                || data_store_class.call1((path,)),
+            ) {
                 Ok(store) => Ok(store),
                 Err(err) => {
                     logger::debug(&format!(
@@ -427,6 +449,46 @@ Verify the data folder contains all expected outputs (did you unpack the full `i
     }
 }
 
+/// Whether `name` is a field of the plugin's config class
+///
+/// Decided from the manifest's config schema when it lists fields, so no
+/// reflection is needed; `has_attr` checks the config instance otherwise.
+fn is_config_field(
+    config: Option<&RuntimeConfig>,
+    name: &str,
+    has_attr: impl FnOnce() -> bool,
+) -> bool {
+    match config {
+        Some(spec) if !spec.fields.is_empty() => spec.fields.iter().any(|field| field == name),
+        _ => has_attr(),
+    }
+}
+
+/// Whether a config value is identical to the parameter's declared default,
+/// in which case the plugin's own default applies and no kwarg is needed.
+fn value_matches_default(value: &pyo3::Bound<'_, PyAny>, default: &DefaultValue) -> bool {
+    match default {
+        DefaultValue::Bool(expected) => value
+            .cast::<PyBool>()
+            .is_ok_and(|actual| actual.is_true() == *expected),
+        DefaultValue::Int(expected) => {
+            value.cast::<PyBool>().is_err()
+                && value.cast::<PyInt>().is_ok()
+                && value
+                    .extract::<i64>()
+                    .is_ok_and(|actual| actual == *expected)
+        }
+        DefaultValue::Float(expected) => value
+            .cast::<PyFloat>()
+            .is_ok_and(|actual| (actual.value() - *expected).abs() < f64::EPSILON),
+        DefaultValue::Str(expected) => value
+            .cast::<PyString>()
+            .is_ok_and(|actual| actual.to_str().is_ok_and(|s| s == expected.as_ref())),
+        DefaultValue::Expr { .. } => default.is_none() && value.is_none(),
+        DefaultValue::Array(_) => false,
+    }
+}
+
 fn transform_data_store_error(py: pyo3::Python<'_>, err: pyo3::PyErr) -> BridgeError {
     if let Some(missing) = extract_missing_data_file(py, &err) {
         BridgeError::Python(format!(
@@ -435,11 +497,7 @@ Verify the data folder contains all expected outputs (did you unpack the full `i
             missing
         ))
     } else {
-        BridgeError::Python(format_python_error(
-            py,
-            err,
-            "Failed to instantiate DataStore",
-        ))
+        BridgeError::python_typed(py, err, "Failed to instantiate DataStore")
     }
 }
 
@@ -541,20 +599,16 @@ impl Bridge {
         store_instance: Option<&pyo3::Bound<'py, PyAny>>,
         system_instance: Option<&pyo3::Bound<'py, PyAny>>,
     ) -> Result<pyo3::Bound<'py, PyAny>, BridgeError> {
-        let context_module = PyModule::import(py, "r2x_core").map_err(|e| {
-            BridgeError::Python(format_python_error(
-                py,
-                e,
-                "Failed to import r2x_core for PluginContext",
-            ))
-        })?;
-        let context_class = context_module.getattr("PluginContext").map_err(|e| {
-            BridgeError::Python(format_python_error(
-                py,
-                e,
-                "Failed to get PluginContext class",
-            ))
+        let context_module = trace_call(
+            || "import r2x_core".to_string(),
+            || PyModule::import(py, "r2x_core"),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(py, e, "Failed to import r2x_core for PluginContext")
         })?;
+        let context_class = context_module
+            .getattr("PluginContext")
+            .map_err(|e| BridgeError::python_typed(py, e, "Failed to get PluginContext class"))?;
 
         let kwargs = PyDict::new(py);
         if let Some(store) = store_instance {
@@ -565,10 +619,113 @@ impl Bridge {
         }
 
         // config is positional (first argument), rest are keyword-only
-        context_class
-            .call((config_instance,), Some(&kwargs))
-            .map_err(|e| {
-                BridgeError::Python(format_python_error(py, e, "Failed to create PluginContext"))
+        trace_call(
+            || "call r2x_core.PluginContext".to_string(),
+            || context_class.call((config_instance,), Some(&kwargs)),
+        )
+        .map_err(|e| BridgeError::python_typed(py, e, "Failed to create PluginContext"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plugin_kwargs::*;
+    use r2x_manifest::types::Parameter;
+    use std::sync::Arc;
+
+    fn param(name: &str, types: &[&str]) -> Parameter {
+        Parameter {
+            name: Arc::from(name),
+            types: types.iter().map(|t| Arc::from(*t)).collect(),
+            module: None,
+            required: true,
+            default: None,
+            description: None,
+        }
+    }
+
+    fn runtime_config(fields: &[&str]) -> RuntimeConfig {
+        RuntimeConfig {
+            module: "r2x_reeds.config".to_string(),
+            name: "ReEDSConfig".to_string(),
+            fields: fields.iter().map(|field| (*field).to_string()).collect(),
+        }
+    }
+
+    /// Parameters left as separate kwargs once config fields are removed
+    fn separate_kwargs(
+        parameters: &[Parameter],
+        config: &RuntimeConfig,
+        attributes: &[&str],
+    ) -> Vec<String> {
+        parameters
+            .iter()
+            .filter(|param| {
+                !is_config_field(Some(config), &param.name, || {
+                    attributes.contains(&param.name.as_ref())
+                })
             })
+            .map(|param| param.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn list_valued_config_reaches_python_as_a_list() {
+        let runtime = RuntimeBindings {
+            entry_module: "r2x_reeds.combine".to_string(),
+            entry_name: "combine_scenarios".to_string(),
+            plugin_type: r2x_manifest::types::PluginType::Function,
+            role: PluginRole::Modifier,
+            call_method: None,
+            config: None,
+            parameters: vec![param("folders", &["list[str]"])],
+            requires_store: false,
+            upgrade: None,
+        };
+
+        pyo3::Python::initialize();
+        let folders = pyo3::Python::attach(|py| -> Result<(bool, usize), BridgeError> {
+            let config = PyModule::import(py, "json")?
+                .getattr("loads")?
+                .call1((r#"{"folders": ["reeds/s1", "reeds/s2", "reeds/s3"]}"#,))?;
+            let config = config.cast_into::<PyDict>().map_err(pyo3::PyErr::from)?;
+            let kwargs = Bridge::build_kwargs(py, &config, None, Some(&runtime))?;
+            let folders = kwargs
+                .get_item("folders")?
+                .ok_or_else(|| BridgeError::Python("folders kwarg missing".to_string()))?;
+            Ok((folders.is_instance_of::<PyList>(), folders.len()?))
+        });
+        assert!(matches!(folders, Ok((true, 3))));
+    }
+
+    #[test]
+    fn schema_fields_and_hasattr_pick_the_same_kwargs() {
+        let parameters = vec![
+            param("system", &["System"]),
+            param("weather_year", &["int"]),
+            param("solve_year", &["int"]),
+            param("model_name", &["str"]),
+        ];
+        let fields = ["solve_year", "weather_year"];
+
+        let from_schema = separate_kwargs(&parameters, &runtime_config(&fields), &[]);
+        let from_hasattr = separate_kwargs(&parameters, &runtime_config(&[]), &fields);
+        assert_eq!(from_schema, vec!["system", "model_name"]);
+        assert_eq!(from_schema, from_hasattr);
+    }
+
+    #[test]
+    fn schema_fields_skip_reflection() {
+        let config = runtime_config(&["weather_year"]);
+        let reflected = std::cell::Cell::new(false);
+        let check = |name: &str| {
+            is_config_field(Some(&config), name, || {
+                reflected.set(true);
+                true
+            })
+        };
+        assert!(check("weather_year"));
+        assert!(!check("model_name"));
+        assert!(!reflected.get());
     }
 }
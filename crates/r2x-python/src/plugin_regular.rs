@@ -1,9 +1,14 @@
 //! Regular plugin invocation (non-upgrader)
 
-use crate::errors::BridgeError;
-use crate::plugin_invoker::{PluginInvocationResult, PluginInvocationTimings};
+use crate::errors::{exception_kind, exception_type_name, BridgeError};
+use crate::plugin_invoker::{
+    declares_output, trace_call, PluginInvocationResult, PluginInvocationTimings, ValidationReport,
+};
 use crate::python_bridge::Bridge;
-use pyo3::types::{PyAny, PyAnyMethods, PyDict, PyDictMethods, PyModule};
+use pyo3::types::{
+    PyAny, PyAnyMethods, PyBool, PyBoolMethods, PyDict, PyDictMethods, PyList, PyModule,
+    PyModuleMethods, PyTuple,
+};
 use pyo3::{Bound, PyResult};
 use r2x_logger as logger;
 use r2x_manifest::runtime::{PluginRole, RuntimeBindings};
@@ -43,6 +48,94 @@ impl Drop for StdoutGuard<'_> {
     }
 }
 
+/// Interpret a validator's return value as a pass/fail report.
+///
+/// Accepts `None` (pass), booleans, `Ok`/`Err` results, lists of diagnostic
+/// messages (pass when empty), and dicts or objects exposing
+/// `passed`/`valid` and `diagnostics`/`errors`.
+fn validation_report(
+    py: pyo3::Python<'_>,
+    result: &Bound<'_, PyAny>,
+) -> Result<ValidationReport, BridgeError> {
+    if result.is_none() {
+        return Ok(ValidationReport {
+            passed: true,
+            diagnostics: Vec::new(),
+        });
+    }
+    if let Ok(flag) = result.cast::<PyBool>() {
+        return Ok(ValidationReport {
+            passed: flag.is_true(),
+            diagnostics: Vec::new(),
+        });
+    }
+
+    let type_name: String = result
+        .get_type()
+        .getattr("__name__")
+        .and_then(|n| n.extract())
+        .unwrap_or_default();
+    if type_name == "Err" {
+        return Ok(ValidationReport {
+            passed: false,
+            diagnostics: vec![format_err_result(py, result)],
+        });
+    }
+    if type_name == "Ok" {
+        let inner = result
+            .getattr("ok_value")
+            .or_else(|_| result.getattr("value"))?;
+        return validation_report(py, &inner);
+    }
+
+    if result.cast::<PyList>().is_ok() || result.cast::<PyTuple>().is_ok() {
+        let diagnostics = diagnostic_strings(result)?;
+        return Ok(ValidationReport {
+            passed: diagnostics.is_empty(),
+            diagnostics,
+        });
+    }
+
+    let lookup = |names: &[&str]| -> PyResult<Option<Bound<'_, PyAny>>> {
+        for name in names {
+            if let Ok(dict) = result.cast::<PyDict>() {
+                if let Some(value) = dict.get_item(name)? {
+                    return Ok(Some(value));
+                }
+            } else if result.hasattr(*name)? {
+                return Ok(Some(result.getattr(*name)?));
+            }
+        }
+        Ok(None)
+    };
+
+    let diagnostics = match lookup(&["diagnostics", "errors", "messages"])? {
+        Some(value) if !value.is_none() => diagnostic_strings(&value)?,
+        _ => Vec::new(),
+    };
+    let passed = match lookup(&["passed", "valid", "is_valid"])? {
+        Some(value) => value.is_truthy()?,
+        None if result.cast::<PyDict>().is_ok() => diagnostics.is_empty(),
+        None => result.is_truthy()? && diagnostics.is_empty(),
+    };
+
+    Ok(ValidationReport {
+        passed,
+        diagnostics,
+    })
+}
+
+fn diagnostic_strings(value: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    if value.cast::<PyList>().is_ok() || value.cast::<PyTuple>().is_ok() {
+        value
+            .try_iter()?
+            .map(|item| item.and_then(|item| item.str().map(|s| s.to_string())))
+            .collect()
+    } else {
+        Ok(vec![value.str()?.to_string()])
+    }
+}
+
 impl Bridge {
     pub(crate) fn invoke_plugin_regular(
         &self,
@@ -63,8 +156,11 @@ impl Bridge {
             let callable_path = parts[1];
 
             logger::debug(&format!("Importing module: {}", module_path));
-            let module = PyModule::import(py, module_path)
-                .map_err(|e| BridgeError::Import(module_path.to_string(), format!("{}", e)))?;
+            let module = trace_call(
+                || format!("import {}", module_path),
+                || PyModule::import(py, module_path),
+            )
+            .map_err(|e| BridgeError::Import(module_path.to_string(), format!("{}", e)))?;
 
             // Re-enable loguru for this module after import.
             // Python __init__.py files call logger.disable() by convention,
@@ -130,13 +226,31 @@ impl Bridge {
 
             if is_exporter {
                 logger::debug("Exporter plugin completed, skipping result serialization");
-                return Ok(PluginInvocationResult {
-                    output: "{}".to_string(),
-                    timings: Some(PluginInvocationTimings {
+                return Ok(PluginInvocationResult::new(
+                    "{}".to_string(),
+                    Some(PluginInvocationTimings {
+                        python_invocation: call_elapsed,
+                        serialization: Duration::ZERO,
+                    }),
+                    declares_output(runtime_bindings),
+                ));
+            }
+
+            if runtime_bindings.is_some_and(|b| b.role == PluginRole::Validator) {
+                let report = validation_report(py, &result_py)?;
+                logger::debug(&format!(
+                    "Validator completed: passed={} ({} diagnostics)",
+                    report.passed,
+                    report.diagnostics.len()
+                ));
+                return Ok(PluginInvocationResult::new(
+                    report.to_json(),
+                    Some(PluginInvocationTimings {
                         python_invocation: call_elapsed,
                         serialization: Duration::ZERO,
                     }),
-                });
+                    declares_output(runtime_bindings),
+                ));
             }
 
             logger::debug("Serializing result to JSON");
@@ -199,13 +313,14 @@ impl Bridge {
                 (json_str, ser_elapsed)
             };
 
-            Ok(PluginInvocationResult {
-                output: json_str,
-                timings: Some(PluginInvocationTimings {
+            Ok(PluginInvocationResult::new(
+                json_str,
+                Some(PluginInvocationTimings {
                     python_invocation: call_elapsed,
                     serialization: ser_elapsed,
                 }),
-            })
+                declares_output(runtime_bindings),
+            ))
         })
     }
 
@@ -223,12 +338,16 @@ impl Bridge {
         }
         let (class_name, method_name) = (parts[0], parts[1]);
 
-        let class = module.getattr(class_name).map_err(|e| {
-            BridgeError::Python(format_python_error(
+        let class = trace_call(
+            || format!("getattr {}.{}", module_label(module), class_name),
+            || module.getattr(class_name),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(
                 module.py(),
                 e,
                 &format!("Failed to get class '{}'", class_name),
-            ))
+            )
         })?;
 
         let py = module.py();
@@ -316,19 +435,29 @@ impl Bridge {
         )?;
         logger::step("PluginContext created");
 
-        let from_context = class.getattr("from_context").map_err(|e| {
-            BridgeError::Python(format_python_error(
+        let from_context = trace_call(
+            || format!("getattr {}.from_context", class_name),
+            || class.getattr("from_context"),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(
                 py,
                 e,
                 &format!(
                     "Plugin class '{}' missing from_context classmethod",
                     class_name
                 ),
-            ))
+            )
         })?;
 
-        let instance = from_context.call1((ctx,)).map_err(|err| {
+        let instance = trace_call(
+            || format!("call {}.from_context", class_name),
+            || from_context.call1((ctx,)),
+        )
+        .map_err(|err| {
             let raw_msg = err.to_string();
+            let py_type = exception_type_name(py, &err);
+            let kind = exception_kind(py, &err);
             let mut formatted = format_python_error(
                 py,
                 err,
@@ -337,7 +466,11 @@ impl Bridge {
             if raw_msg.contains("missing") && raw_msg.contains("required positional argument") {
                 formatted.push_str("\n\nHint: This may happen if the plugin metadata cache is stale. Try running:\n  r2x sync");
             }
-            BridgeError::Python(formatted)
+            BridgeError::PythonTyped {
+                py_type,
+                kind,
+                message: formatted,
+            }
         })?;
         logger::step(&format!(
             "Plugin '{}' instantiated via from_context",
@@ -353,15 +486,19 @@ impl Bridge {
             "Using method '{}' for plugin '{}'",
             actual_method_name, class_name
         ));
-        let method = instance.getattr(actual_method_name).map_err(|e| {
-            BridgeError::Python(format_python_error(
+        let method = trace_call(
+            || format!("getattr {}.{}", class_name, actual_method_name),
+            || instance.getattr(actual_method_name),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(
                 instance.py(),
                 e,
                 &format!(
                     "Failed to get method '{}.{}'",
                     class_name, actual_method_name
                 ),
-            ))
+            )
         })?;
 
         let accepts_stdin = if stdin_obj.is_some() {
@@ -386,12 +523,16 @@ impl Bridge {
                     "stdin expected but not provided".to_string(),
                 ));
             };
-            method.call1((stdin,)).map_err(|e| {
-                BridgeError::Python(format_python_error(
+            trace_call(
+                || format!("call {}.{}", class_name, actual_method_name),
+                || method.call1((stdin,)),
+            )
+            .map_err(|e| {
+                BridgeError::python_typed(
                     method.py(),
                     e,
                     &format!("Method '{}.{}' failed", class_name, method_name),
-                ))
+                )
             })
         } else {
             if stdin_obj.is_some() {
@@ -400,12 +541,16 @@ impl Bridge {
                     class_name, method_name
                 ));
             }
-            method.call0().map_err(|e| {
-                BridgeError::Python(format_python_error(
+            trace_call(
+                || format!("call {}.{}", class_name, actual_method_name),
+                || method.call0(),
+            )
+            .map_err(|e| {
+                BridgeError::python_typed(
                     method.py(),
                     e,
                     &format!("Method '{}.{}' failed", class_name, method_name),
-                ))
+                )
             })
         }
     }
@@ -419,12 +564,16 @@ impl Bridge {
         json_module: &pyo3::Bound<'py, PyModule>,
     ) -> Result<pyo3::Bound<'py, PyAny>, BridgeError> {
         logger::debug(&format!("Function pattern: {}", callable_path));
-        let func = module.getattr(callable_path).map_err(|e| {
-            BridgeError::Python(format_python_error(
+        let func = trace_call(
+            || format!("getattr {}.{}", module_label(module), callable_path),
+            || module.getattr(callable_path),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(
                 module.py(),
                 e,
                 &format!("Failed to get function '{}'", callable_path),
-            ))
+            )
         })?;
 
         logger::step("Function kwargs prepared (before system injection)");
@@ -447,16 +596,28 @@ impl Bridge {
             .filter_map(|k| k.extract::<String>().ok())
             .collect();
         logger::step(&format!("Final function kwargs keys: {:?}", kwarg_keys));
-        func.call((), Some(kwargs)).map_err(|e| {
-            BridgeError::Python(format_python_error(
+        trace_call(
+            || format!("call {}.{}", module_label(module), callable_path),
+            || func.call((), Some(kwargs)),
+        )
+        .map_err(|e| {
+            BridgeError::python_typed(
                 func.py(),
                 e,
                 &format!("Function '{}' failed", callable_path),
-            ))
+            )
         })
     }
 }
 
+/// `__name__` of `module`, for call traces
+fn module_label(module: &Bound<'_, PyModule>) -> String {
+    module
+        .name()
+        .map(|name| name.to_string())
+        .unwrap_or_default()
+}
+
 fn format_duration(duration: Duration) -> String {
     let total_ms = duration.as_millis();
     if total_ms < 1000 {
@@ -590,14 +751,14 @@ fn discover_and_instantiate_config<'py>(
                                     return config_type
                                         .call((), Some(config_params))
                                         .map_err(|e| {
-                                            BridgeError::Python(format_python_error(
+                                            BridgeError::python_typed(
                                                 py,
                                                 e,
                                                 &format!(
                                                     "Failed to instantiate discovered config class '{}'",
                                                     type_name
                                                 ),
-                                            ))
+                                            )
                                         });
                                 }
                             }
@@ -612,11 +773,7 @@ fn discover_and_instantiate_config<'py>(
         if config_class.is_callable() {
             logger::debug("Discovered nested Config class");
             return config_class.call((), Some(config_params)).map_err(|e| {
-                BridgeError::Python(format_python_error(
-                    py,
-                    e,
-                    "Failed to instantiate nested Config class",
-                ))
+                BridgeError::python_typed(py, e, "Failed to instantiate nested Config class")
             });
         }
     }
@@ -632,33 +789,22 @@ fn discover_and_instantiate_config<'py>(
                 type_name
             ));
             return config_class.call((), Some(config_params)).map_err(|e| {
-                BridgeError::Python(format_python_error(
+                BridgeError::python_typed(
                     py,
                     e,
                     "Failed to instantiate config class from config_class attribute",
-                ))
+                )
             });
         }
     }
 
     logger::debug("No config class discovered, using PluginConfig from r2x_core");
-    let r2x_core = PyModule::import(py, "r2x_core").map_err(|e| {
-        BridgeError::Python(format_python_error(py, e, "Failed to import r2x_core"))
-    })?;
-    let plugin_config_class = r2x_core.getattr("PluginConfig").map_err(|e| {
-        BridgeError::Python(format_python_error(
-            py,
-            e,
-            "Failed to get PluginConfig class",
-        ))
-    })?;
+    let r2x_core = PyModule::import(py, "r2x_core")
+        .map_err(|e| BridgeError::python_typed(py, e, "Failed to import r2x_core"))?;
+    let plugin_config_class = r2x_core
+        .getattr("PluginConfig")
+        .map_err(|e| BridgeError::python_typed(py, e, "Failed to get PluginConfig class"))?;
     plugin_config_class
         .call((), Some(config_params))
-        .map_err(|e| {
-            BridgeError::Python(format_python_error(
-                py,
-                e,
-                "Failed to instantiate PluginConfig",
-            ))
-        })
+        .map_err(|e| BridgeError::python_typed(py, e, "Failed to instantiate PluginConfig"))
 }
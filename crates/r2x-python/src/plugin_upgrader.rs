@@ -1,13 +1,14 @@
 //! Upgrader plugin invocation
 
 use crate::errors::BridgeError;
-use crate::plugin_invoker::PluginInvocationResult;
+use crate::plugin_invoker::{get_upgrade_since, PluginInvocationResult};
 use crate::plugin_regular::{format_err_result, format_python_error, StdoutGuard};
 use crate::python_bridge::Bridge;
 use pyo3::types::{PyAny, PyAnyMethods, PyDict, PyDictMethods, PyModule, PyString};
 use r2x_logger as logger;
-use r2x_manifest::runtime::RuntimeBindings;
-use r2x_manifest::types::Plugin;
+use r2x_manifest::runtime::{compare_versions, RuntimeBindings};
+use r2x_manifest::types::{Plugin, UpgradeSpec};
+use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
 
 impl Bridge {
@@ -43,94 +44,158 @@ impl Bridge {
 
             let kwargs = Self::build_kwargs(py, &config_dict, None, runtime_bindings)?;
             let upgrader_class = module.getattr(callable_path).map_err(|e| {
-                BridgeError::Python(format_python_error(
+                BridgeError::python_typed(
                     py,
                     e,
                     &format!("Failed to get upgrader class '{}'", callable_path),
-                ))
+                )
             })?;
 
-            if let Some(plugin) = plugin_metadata {
-                if let Some(strategy) = find_arg_value(plugin, "version_strategy") {
-                    kwargs.set_item("version_strategy", strategy)?;
-                }
-                if let Some(reader) = find_arg_value(plugin, "version_reader") {
-                    kwargs.set_item("version_reader", reader)?;
-                }
-                if let Some(steps) = find_arg_value(plugin, "upgrade_steps") {
-                    kwargs.set_item("upgrade_steps", steps)?;
-                }
-            }
-
             let instance = upgrader_class.call((), Some(&kwargs)).map_err(|e| {
-                BridgeError::Python(format_python_error(
+                BridgeError::python_typed(
                     py,
                     e,
                     &format!("Failed to instantiate upgrader '{}'", callable_path),
-                ))
+                )
             })?;
 
+            let upgrade = runtime_bindings
+                .and_then(|bindings| bindings.upgrade.as_ref())
+                .or_else(|| plugin_metadata.and_then(|plugin| plugin.upgrade.as_ref()));
+            if let Some(spec) = upgrade {
+                apply_upgrade_spec(&module, &instance, spec);
+            }
+            let input_version = detect_input_version(&instance);
+
             if instance.hasattr("run")? {
+                if let Some(since) = get_upgrade_since() {
+                    logger::warn(&format!(
+                        "Upgrader '{}' defines run(), which selects its own steps; --since {} is ignored",
+                        callable_path, since
+                    ));
+                }
                 let output = instance.call_method0("run").map_err(|e| {
-                    BridgeError::Python(format_python_error(
+                    BridgeError::python_typed(
                         py,
                         e,
                         &format!("Failed to run upgrader '{}'", callable_path),
-                    ))
+                    )
                 })?;
                 let output = output.extract::<String>().map_err(|e| {
-                    BridgeError::Python(format_python_error(
+                    BridgeError::python_typed(
                         py,
                         e,
                         &format!("Failed to extract upgrader '{}' output", callable_path),
-                    ))
+                    )
                 })?;
-                Ok(PluginInvocationResult {
-                    output,
-                    timings: None,
-                })
+                Ok(PluginInvocationResult::new(output, None, true))
             } else {
                 logger::debug("Upgrader missing run() method, invoking registered steps directly");
-                let output = Self::invoke_registered_steps(&instance)?;
-                Ok(PluginInvocationResult {
-                    output,
-                    timings: None,
-                })
+                let output = Self::invoke_registered_steps(&instance, input_version.as_deref())?;
+                Ok(PluginInvocationResult::new(output, None, true))
             }
         })
     }
 }
 
-fn find_arg_value(_plugin: &Plugin, _name: &str) -> Option<&'static str> {
-    // Upgrade-specific metadata was removed in the manifest cleanup.
-    // Upgrader plugins now rely on their runtime configuration.
-    None
+/// Set `version_strategy`/`version_reader` from the manifest when the
+/// upgrader instance lacks them or holds `None`.
+///
+/// A value the upgrader set up itself, e.g. a reader built with arguments in
+/// `__init__`, is kept.
+fn apply_upgrade_spec(
+    module: &pyo3::Bound<'_, PyModule>,
+    instance: &pyo3::Bound<'_, PyAny>,
+    spec: &UpgradeSpec,
+) {
+    let fields = [
+        ("version_strategy", spec.version_strategy.as_deref()),
+        ("version_reader", spec.version_reader.as_deref()),
+    ];
+    for (attribute, expr) in fields {
+        let Some(expr) = expr else {
+            continue;
+        };
+        let is_set = instance
+            .getattr(attribute)
+            .is_ok_and(|current| !current.is_none());
+        if is_set {
+            continue;
+        }
+        let Some(value) = resolve_attribute_expr(module, expr) else {
+            logger::debug(&format!(
+                "Could not resolve {} '{}' in the upgrader module",
+                attribute, expr
+            ));
+            continue;
+        };
+        logger::debug(&format!("Using {} from manifest: {}", attribute, expr));
+        if let Err(e) = instance.setattr(attribute, value) {
+            logger::debug(&format!("Failed to set {}: {}", attribute, e));
+        }
+    }
+}
+
+/// A dotted name, optionally called without arguments (`Reader` or `pkg.Reader()`)
+#[derive(Debug, PartialEq, Eq)]
+struct AttributeRef<'a> {
+    path: Vec<&'a str>,
+    call: bool,
+}
+
+/// Parse the manifest text of a class attribute; anything more complex is rejected.
+fn parse_attribute_ref(expr: &str) -> Option<AttributeRef<'_>> {
+    let expr = expr.trim();
+    let (name, call) = match expr.strip_suffix("()") {
+        Some(name) => (name, true),
+        None => (expr, false),
+    };
+    let path: Vec<&str> = name.split('.').collect();
+    let is_identifier = |part: &&str| {
+        part.chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    path.iter()
+        .all(is_identifier)
+        .then_some(AttributeRef { path, call })
+}
+
+/// Look `expr` up in the upgrader's module namespace, calling it when written as a call.
+fn resolve_attribute_expr<'py>(
+    module: &pyo3::Bound<'py, PyModule>,
+    expr: &str,
+) -> Option<pyo3::Bound<'py, PyAny>> {
+    let reference = parse_attribute_ref(expr)?;
+    let (first, rest) = reference.path.split_first()?;
+    let mut value = module.getattr(*first).ok()?;
+    for part in rest {
+        value = value.getattr(*part).ok()?;
+    }
+    if reference.call {
+        value = value.call0().ok()?;
+    }
+    Some(value)
 }
 
 impl Bridge {
     fn invoke_registered_steps(
         instance: &pyo3::Bound<'_, pyo3::PyAny>,
+        input_version: Option<&str>,
     ) -> Result<String, BridgeError> {
         let py = instance.py();
 
-        let steps = instance.getattr("steps").map_err(|e| {
-            BridgeError::Python(format_python_error(
-                py,
-                e,
-                "Failed to access upgrader steps",
-            ))
-        })?;
+        let steps = instance
+            .getattr("steps")
+            .map_err(|e| BridgeError::python_typed(py, e, "Failed to access upgrader steps"))?;
 
-        let path_obj = instance.getattr("path").map_err(|e| {
-            BridgeError::Python(format_python_error(
-                py,
-                e,
-                "Upgrader missing 'path' attribute",
-            ))
-        })?;
+        let path_obj = instance
+            .getattr("path")
+            .map_err(|e| BridgeError::python_typed(py, e, "Upgrader missing 'path' attribute"))?;
         let path_str = path_obj
             .str()
-            .map_err(|e| BridgeError::Python(format_python_error(py, e, "Invalid upgrader path")))?
+            .map_err(|e| BridgeError::python_typed(py, e, "Invalid upgrader path"))?
             .to_string();
         let path_buf = PathBuf::from(path_str);
         let path_handle = path_obj.clone().unbind();
@@ -142,11 +207,11 @@ impl Bridge {
             )
         })?;
         let run_upgrade_step = upgrader_utils.getattr("run_upgrade_step").map_err(|e| {
-            BridgeError::Python(format_python_error(
+            BridgeError::python_typed(
                 py,
                 e,
                 "Failed to import r2x_core.upgrader_utils.run_upgrade_step",
-            ))
+            )
         })?;
 
         let json_module = PyModule::import(py, "json")
@@ -158,8 +223,27 @@ impl Bridge {
         let mut system_json_path: Option<PathBuf> = None;
         let mut saw_system_step = false;
 
+        let mut step_objs = Vec::new();
+        let mut step_infos = Vec::new();
         for step in steps.try_iter()? {
             let step_obj = step.map_err(|e| BridgeError::Python(format!("{}", e)))?;
+            step_infos.push(upgrade_step_info(&step_obj));
+            step_objs.push(step_obj);
+        }
+
+        if input_version.is_none() {
+            logger::debug("Input version unknown; running all upgrade steps");
+        }
+        let selection = select_upgrade_steps(&step_infos, input_version);
+        for (idx, reason) in &selection.skipped {
+            logger::info(&format!(
+                "Skipping upgrade step '{}': {}",
+                step_infos[*idx].name, reason
+            ));
+        }
+
+        for &idx in &selection.selected {
+            let step_obj = &step_objs[idx];
             let upgrade_type_obj = step_obj.getattr("upgrade_type").map_err(|e| {
                 BridgeError::Python(format!("Invalid upgrade step (missing type): {}", e))
             })?;
@@ -212,9 +296,7 @@ impl Bridge {
 
             let result = run_upgrade_step
                 .call((step_obj.clone(), data_arg), Some(&kwargs))
-                .map_err(|e| {
-                    BridgeError::Python(format_python_error(py, e, "Upgrade step execution failed"))
-                })?;
+                .map_err(|e| BridgeError::python_typed(py, e, "Upgrade step execution failed"))?;
 
             let is_err = result
                 .getattr("is_err")?
@@ -286,6 +368,137 @@ impl Bridge {
     }
 }
 
+/// Priority assumed for upgrade steps that do not declare one.
+const DEFAULT_STEP_PRIORITY: i64 = 100;
+
+/// Version gating metadata read from a registered upgrade step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UpgradeStepInfo {
+    name: String,
+    target_version: Option<String>,
+    min_version: Option<String>,
+    max_version: Option<String>,
+    priority: i64,
+}
+
+/// Steps chosen to run (in execution order) and steps skipped with a reason.
+#[derive(Debug, Default)]
+struct StepSelection {
+    selected: Vec<usize>,
+    skipped: Vec<(usize, String)>,
+}
+
+fn upgrade_step_info(step: &pyo3::Bound<'_, PyAny>) -> UpgradeStepInfo {
+    UpgradeStepInfo {
+        name: optional_str_attr(step, "name").unwrap_or_else(|| "<unknown>".to_string()),
+        target_version: optional_str_attr(step, "target_version"),
+        min_version: optional_str_attr(step, "min_version"),
+        max_version: optional_str_attr(step, "max_version"),
+        priority: step
+            .getattr("priority")
+            .and_then(|p| p.extract::<i64>())
+            .unwrap_or(DEFAULT_STEP_PRIORITY),
+    }
+}
+
+fn optional_str_attr(obj: &pyo3::Bound<'_, PyAny>, name: &str) -> Option<String> {
+    let value = obj.getattr(name).ok()?;
+    if value.is_none() {
+        return None;
+    }
+    value.str().ok().map(|s| s.to_string())
+}
+
+/// Version of the upgrader's input data: `--since` when given, otherwise
+/// what its `version_reader` reads from `path`.
+fn detect_input_version(instance: &pyo3::Bound<'_, PyAny>) -> Option<String> {
+    if let Some(since) = get_upgrade_since() {
+        logger::info(&format!(
+            "Treating input data as version {} (--since)",
+            since
+        ));
+        return Some(since);
+    }
+    let path = instance.getattr("path").ok()?;
+    let version = read_input_version(instance, &path);
+    if let Some(version) = &version {
+        logger::debug(&format!("Detected input version: {}", version));
+    }
+    version
+}
+
+/// Read the input data version through the upgrader's `version_reader`.
+fn read_input_version(
+    instance: &pyo3::Bound<'_, PyAny>,
+    path: &pyo3::Bound<'_, PyAny>,
+) -> Option<String> {
+    let reader = instance.getattr("version_reader").ok()?;
+    if reader.is_none() {
+        return None;
+    }
+    let version = match reader.call_method1("read_version", (path,)) {
+        Ok(version) => version,
+        Err(e) => {
+            logger::debug(&format!(
+                "version_reader failed to read input version: {}",
+                e
+            ));
+            return None;
+        }
+    };
+    if version.is_none() {
+        return None;
+    }
+    version.str().ok().map(|s| s.to_string())
+}
+
+/// Pick the steps that apply to `input_version`, ordered by priority.
+///
+/// A step runs when the input version lies within its inclusive
+/// `[min_version, max_version]` range and is below its `target_version`.
+/// When the input version is unknown every step runs.
+fn select_upgrade_steps(steps: &[UpgradeStepInfo], input_version: Option<&str>) -> StepSelection {
+    let mut selection = StepSelection::default();
+
+    for (idx, step) in steps.iter().enumerate() {
+        let reason = input_version.and_then(|version| {
+            if let Some(min) = &step.min_version {
+                if compare_versions(version, min) == Ordering::Less {
+                    return Some(format!(
+                        "input version {} is below min_version {}",
+                        version, min
+                    ));
+                }
+            }
+            if let Some(max) = &step.max_version {
+                if compare_versions(version, max) == Ordering::Greater {
+                    return Some(format!(
+                        "input version {} is above max_version {}",
+                        version, max
+                    ));
+                }
+            }
+            if let Some(target) = &step.target_version {
+                if compare_versions(version, target) != Ordering::Less {
+                    return Some(format!(
+                        "input version {} is already at or above target_version {}",
+                        version, target
+                    ));
+                }
+            }
+            None
+        });
+
+        match reason {
+            Some(reason) => selection.skipped.push((idx, reason)),
+            None => selection.selected.push(idx),
+        }
+    }
+
+    selection.selected.sort_by_key(|idx| steps[*idx].priority);
+    selection
+}
+
 fn resolve_system_json_path(path: &Path) -> Result<PathBuf, String> {
     if path.is_file() {
         return Ok(path.to_path_buf());
@@ -327,11 +540,11 @@ fn load_system_data<'py>(
     })?;
     let py_str = PyString::new(py, &content);
     let data = loads.call1((py_str,)).map_err(|e| {
-        BridgeError::Python(format_python_error(
+        BridgeError::python_typed(
             py,
             e,
             &format!("Failed to parse system JSON {}", json_path.display()),
-        ))
+        )
     })?;
     Ok(data.into())
 }
@@ -348,25 +561,25 @@ fn write_system_data<'py>(
     let json_str: String = dumps
         .call((data.bind(py),), Some(&kwargs))
         .map_err(|e| {
-            BridgeError::Python(format_python_error(
+            BridgeError::python_typed(
                 py,
                 e,
                 &format!(
                     "Failed to serialize upgraded system JSON {}",
                     json_path.display()
                 ),
-            ))
+            )
         })?
         .extract()
         .map_err(|e| {
-            BridgeError::Python(format_python_error(
+            BridgeError::python_typed(
                 py,
                 e,
                 &format!(
                     "Failed to convert upgraded system JSON {}",
                     json_path.display()
                 ),
-            ))
+            )
         })?;
     std::fs::write(json_path, json_str).map_err(|e| {
         BridgeError::Python(format!(
@@ -376,3 +589,188 @@ fn write_system_data<'py>(
         ))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::plugin_upgrader::*;
+
+    fn step(
+        name: &str,
+        min: Option<&str>,
+        max: Option<&str>,
+        target: Option<&str>,
+        priority: i64,
+    ) -> UpgradeStepInfo {
+        UpgradeStepInfo {
+            name: name.to_string(),
+            target_version: target.map(str::to_string),
+            min_version: min.map(str::to_string),
+            max_version: max.map(str::to_string),
+            priority,
+        }
+    }
+
+    fn sample_steps() -> Vec<UpgradeStepInfo> {
+        vec![
+            step("rename_columns", Some("1.0"), Some("1.9"), Some("2.0"), 200),
+            step("drop_legacy", None, Some("0.9"), Some("1.0"), 100),
+            step("add_units", Some("2.0"), None, Some("3.0"), 50),
+            step("normalize", None, None, Some("1.5"), 10),
+        ]
+    }
+
+    #[test]
+    fn select_upgrade_steps_excludes_out_of_range_steps() {
+        let steps = sample_steps();
+        let selection = select_upgrade_steps(&steps, Some("1.2"));
+
+        assert_eq!(selection.selected, vec![3, 0]);
+        let skipped: Vec<usize> = selection.skipped.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(skipped, vec![1, 2]);
+        assert!(selection.skipped[0].1.contains("above max_version 0.9"));
+        assert!(selection.skipped[1].1.contains("below min_version 2.0"));
+    }
+
+    #[test]
+    fn select_upgrade_steps_skips_steps_already_at_target() {
+        let steps = sample_steps();
+        let selection = select_upgrade_steps(&steps, Some("1.5"));
+
+        assert_eq!(selection.selected, vec![0]);
+        assert!(selection
+            .skipped
+            .iter()
+            .any(|(idx, reason)| *idx == 3 && reason.contains("target_version 1.5")));
+    }
+
+    #[test]
+    fn select_upgrade_steps_runs_everything_when_version_unknown() {
+        let steps = sample_steps();
+        let selection = select_upgrade_steps(&steps, None);
+
+        assert_eq!(selection.selected, vec![3, 2, 1, 0]);
+        assert!(selection.skipped.is_empty());
+    }
+
+    #[test]
+    fn compare_versions_pads_missing_components() {
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("v2.0", "1.9.9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn parses_manifest_attribute_refs() {
+        assert_eq!(
+            parse_attribute_ref("ReEDSVersionReader()"),
+            Some(AttributeRef {
+                path: vec!["ReEDSVersionReader"],
+                call: true,
+            })
+        );
+        assert_eq!(
+            parse_attribute_ref("versioning.SemanticVersioning"),
+            Some(AttributeRef {
+                path: vec!["versioning", "SemanticVersioning"],
+                call: false,
+            })
+        );
+        assert_eq!(parse_attribute_ref("Reader(path='x')"), None);
+        assert_eq!(parse_attribute_ref("lambda: '1.0'"), None);
+        assert_eq!(parse_attribute_ref(""), None);
+    }
+
+    const UPGRADER_MODULE: &str = r#"
+class OldReader:
+    def read_version(self, path):
+        return "0.1"
+
+class ManifestReader:
+    def read_version(self, path):
+        return "1.4"
+
+class Strategy:
+    pass
+
+class Upgrader:
+    version_strategy = Strategy
+    version_reader = OldReader()
+
+    def __init__(self):
+        self.path = "/data/reeds"
+
+class BareUpgrader:
+    def __init__(self):
+        self.path = "/data/reeds"
+
+class FixedReader:
+    def __init__(self, version):
+        self.version = version
+
+    def read_version(self, path):
+        return self.version
+
+class ConfiguredUpgrader:
+    version_reader = None
+
+    def __init__(self):
+        self.path = "/data/reeds"
+        self.version_reader = FixedReader("0.9")
+"#;
+
+    fn with_upgrader<T>(
+        class: &str,
+        spec: &UpgradeSpec,
+        check: impl FnOnce(&pyo3::Bound<'_, PyAny>) -> pyo3::PyResult<T>,
+    ) -> pyo3::PyResult<T> {
+        pyo3::Python::initialize();
+        pyo3::Python::attach(|py| {
+            let code = std::ffi::CString::new(UPGRADER_MODULE)?;
+            let file_name = std::ffi::CString::new("r2x_upgrade_spec_demo.py")?;
+            let module_name = std::ffi::CString::new("r2x_upgrade_spec_demo")?;
+            let module = PyModule::from_code(
+                py,
+                code.as_c_str(),
+                file_name.as_c_str(),
+                module_name.as_c_str(),
+            )?;
+            let instance = module.getattr(class)?.call0()?;
+            apply_upgrade_spec(&module, &instance, spec);
+            check(&instance)
+        })
+    }
+
+    #[test]
+    fn manifest_spec_keeps_a_reader_the_upgrader_set_up() {
+        let spec = UpgradeSpec {
+            version_strategy: Some("Strategy".into()),
+            version_reader: Some("ManifestReader()".into()),
+        };
+        for (class, expected) in [("Upgrader", "0.1"), ("ConfiguredUpgrader", "0.9")] {
+            let result = with_upgrader(class, &spec, |instance| {
+                let path = instance.getattr("path")?;
+                Ok(read_input_version(instance, &path))
+            });
+            assert!(result.is_ok_and(|version| version.as_deref() == Some(expected)));
+        }
+    }
+
+    #[test]
+    fn manifest_spec_fills_a_missing_reader() {
+        let spec = UpgradeSpec {
+            version_strategy: None,
+            version_reader: Some("ManifestReader()".into()),
+        };
+        let unset = with_upgrader("BareUpgrader", &UpgradeSpec::default(), |instance| {
+            let path = instance.getattr("path")?;
+            Ok(read_input_version(instance, &path))
+        });
+        assert!(unset.is_ok_and(|version| version.is_none()));
+
+        let result = with_upgrader("BareUpgrader", &spec, |instance| {
+            let path = instance.getattr("path")?;
+            Ok(read_input_version(instance, &path))
+        });
+        assert!(result.is_ok_and(|version| version.as_deref() == Some("1.4")));
+    }
+}
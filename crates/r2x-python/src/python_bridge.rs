@@ -12,7 +12,7 @@
 
 use crate::errors::BridgeError;
 use crate::utils::{resolve_python_path, resolve_site_package_path};
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyModule};
 use r2x_config::Config;
@@ -21,16 +21,48 @@ use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// The Python bridge for plugin execution
 pub struct Bridge {
     /// Placeholder field for future extension
-    _marker: (),
+    pub(crate) _marker: (),
 }
 
 /// Global bridge singleton
 static BRIDGE_INSTANCE: OnceCell<Result<Bridge, BridgeError>> = OnceCell::new();
 
+/// Config set by [`Bridge::use_config`], used in place of the saved one
+static BRIDGE_CONFIG: OnceCell<Config> = OnceCell::new();
+
+/// Cleanup run by [`Bridge::finalize`], e.g. closing a store or flushing a sink
+type Finalizer = Box<dyn FnMut() -> Result<(), BridgeError> + Send>;
+
+/// Registered finalizers, run in registration order
+static FINALIZERS: Lazy<Mutex<Vec<(String, Finalizer)>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Puts `sys.path` back as it was before [`Bridge::extend_sys_path`] when dropped
+#[derive(Debug)]
+pub struct SysPathGuard {
+    previous: Py<PyAny>,
+}
+
+impl Drop for SysPathGuard {
+    fn drop(&mut self) {
+        let restored = pyo3::Python::attach(|py| -> PyResult<()> {
+            let path = PyModule::import(py, "sys")?.getattr("path")?;
+            path.call_method0("clear")?;
+            path.call_method1("extend", (self.previous.bind(py),))?;
+            Ok(())
+        });
+        if let Err(e) = restored {
+            logger::warn(&format!("Failed to restore sys.path: {}", e));
+        }
+    }
+}
+
 impl Bridge {
     /// Get or initialize the bridge singleton
     pub fn get() -> Result<&'static Bridge, BridgeError> {
@@ -40,6 +72,39 @@ impl Bridge {
         }
     }
 
+    /// Raise `KeyboardInterrupt` in the thread running plugin code.
+    ///
+    /// Meant to be called from a watchdog thread; Python delivers the
+    /// interrupt at its next bytecode boundary. Does nothing before the
+    /// bridge is initialized.
+    pub fn interrupt_main() -> Result<(), BridgeError> {
+        if !matches!(BRIDGE_INSTANCE.get(), Some(Ok(_))) {
+            return Ok(());
+        }
+        pyo3::Python::attach(|py| {
+            let thread = PyModule::import(py, "_thread").map_err(|e| {
+                BridgeError::Python(format!("Failed to import _thread module: {}", e))
+            })?;
+            thread
+                .call_method0("interrupt_main")
+                .map_err(|e| BridgeError::Python(format!("Failed to interrupt Python: {}", e)))?;
+            Ok(())
+        })
+    }
+
+    /// Initialize the bridge from `config` instead of the saved config file
+    ///
+    /// Only takes effect before the first [`Bridge::get`]; later calls and
+    /// calls after initialization are ignored.
+    pub fn use_config(config: Config) {
+        let _ = BRIDGE_CONFIG.set(config);
+    }
+
+    /// Python version the embedded interpreter was built against
+    pub fn compiled_python_version() -> String {
+        get_compiled_python_version()
+    }
+
     /// Check if Python is available without initializing
     pub fn is_python_available() -> bool {
         let config = match Config::load() {
@@ -59,42 +124,61 @@ impl Bridge {
     /// 2. Resolve PYTHONHOME from venv's pyvenv.cfg
     /// 3. Set PYTHONHOME and initialize PyO3
     /// 4. Configure site-packages
+    ///
+    /// Step 1 may download Python, so `bridge-init-timeout` only covers the
+    /// steps after it and the library check (see [`run_with_deadline`]).
+    /// PyO3 initialization can't be interrupted, so it only warns when it
+    /// takes longer than whatever time is left.
     fn initialize() -> Result<Bridge, BridgeError> {
-        let start_time = std::time::Instant::now();
+        let _timer = logger::TimedGuard::new("Total bridge initialization");
 
-        let mut config = Config::load()
-            .map_err(|e| BridgeError::Initialization(format!("Failed to load config: {}", e)))?;
+        let mut config = match BRIDGE_CONFIG.get() {
+            Some(config) => config.clone(),
+            None => Config::load().map_err(|e| {
+                BridgeError::Initialization(format!("Failed to load config: {}", e))
+            })?,
+        };
+        let timeout = config.get_bridge_init_timeout();
 
-        // Ensure venv exists
         let venv_path = PathBuf::from(config.get_venv_path());
-
         if !venv_path.exists() {
             // Create venv using the compiled Python version
             Self::create_venv(&config, &venv_path)?;
         }
 
-        // Resolve PYTHONHOME from venv's pyvenv.cfg
-        let python_home = resolve_python_home(&venv_path)?;
+        let started = Instant::now();
+        let (python_home, site_packages) = run_with_deadline(timeout, move |progress| {
+            // Resolve PYTHONHOME from venv's pyvenv.cfg
+            progress.begin("reading pyvenv.cfg");
+            let python_home = resolve_python_home(&venv_path)?;
+
+            // Get site-packages path
+            progress.begin("locating site-packages");
+            let site_packages = resolve_site_package_path(&venv_path)?;
+
+            // Check if Python library is available before initializing
+            progress.begin("finding the Python library");
+            check_python_library_available()?;
+
+            Ok((python_home, site_packages))
+        })?;
+
         env::set_var("PYTHONHOME", &python_home);
         logger::debug(&format!("Set PYTHONHOME={}", python_home.display()));
 
-        // Get site-packages path
-        let site_packages = resolve_site_package_path(&venv_path)?;
-
         // Add site-packages to PYTHONPATH
         Self::configure_python_path(&site_packages);
 
-        // Check if Python library is available before initializing
-        check_python_library_available()?;
-
         // Initialize PyO3
         logger::debug("Initializing PyO3...");
-        let pyo3_start = std::time::Instant::now();
-        pyo3::Python::initialize();
-        logger::debug(&format!(
-            "pyo3::Python::initialize took: {:?}",
-            pyo3_start.elapsed()
-        ));
+        logger::timed("pyo3::Python::initialize", pyo3::Python::initialize);
+        if let Some(limit) = timeout.filter(|limit| started.elapsed() > *limit) {
+            logger::warn(&format!(
+                "Python took {:.1}s to start, over bridge-init-timeout ({}s); check for a slow filesystem under the venv",
+                started.elapsed().as_secs_f64(),
+                limit.as_secs()
+            ));
+        }
 
         // Enable bytecode generation
         pyo3::Python::attach(|py| {
@@ -127,32 +211,38 @@ impl Bridge {
             logger::warn(&format!("Python logging configuration failed: {}", e));
         }
 
-        logger::debug(&format!(
-            "Total bridge initialization took: {:?}",
-            start_time.elapsed()
-        ));
-
         Ok(Bridge { _marker: () })
     }
 
     /// Create a virtual environment
     ///
     /// Uses the compiled Python version to ensure compatibility with PyO3.
-    fn create_venv(config: &Config, venv_path: &PathBuf) -> Result<(), BridgeError> {
+    pub fn create_venv(config: &Config, venv_path: &PathBuf) -> Result<(), BridgeError> {
+        Self::create_venv_with_version(config, venv_path, &get_compiled_python_version())
+    }
+
+    /// Create a virtual environment for a specific Python version
+    ///
+    /// Used by one-off runs with `--python`; anything other than
+    /// [`Bridge::compiled_python_version`] may fail to load in the embedded
+    /// interpreter.
+    pub fn create_venv_with_version(
+        config: &Config,
+        venv_path: &PathBuf,
+        python_version: &str,
+    ) -> Result<(), BridgeError> {
         logger::step(&format!(
             "Creating Python virtual environment at: {}",
             venv_path.display()
         ));
 
-        let python_version = get_compiled_python_version();
-
         // Try uv first
         if let Some(ref uv_path) = config.uv_path {
             let output = Command::new(uv_path)
                 .arg("venv")
                 .arg(venv_path)
                 .arg("--python")
-                .arg(&python_version)
+                .arg(python_version)
                 .output()?;
 
             if output.status.success() {
@@ -343,13 +433,111 @@ def _r2x_cache_path_override():
 
         pyo3::Python::attach(|py| Self::enable_loguru_modules(py, &[&module_name]))
     }
+
+    /// Import `modules` before the first plugin call, so that call doesn't pay for them.
+    ///
+    /// Python caches imports, so each module is loaded once however often it
+    /// is listed or imported later. A failing import only logs a warning; the
+    /// plugin that needs the module reports the real error when it runs.
+    pub fn warm_up(&self, modules: &[&str]) {
+        let _timer = logger::TimedGuard::new("Python warm-up");
+        let failures = pyo3::Python::attach(|py| import_modules(py, modules));
+        for (module, error) in failures {
+            logger::warn(&format!("Could not preimport {}: {}", module, error));
+        }
+    }
+
+    /// Add `dirs` to the end of `sys.path` until the returned guard is dropped.
+    ///
+    /// Each directory goes through `site.addsitedir`, so `.pth` files in it
+    /// are honoured as they are for site-packages.
+    pub fn extend_sys_path(&self, dirs: &[PathBuf]) -> Result<SysPathGuard, BridgeError> {
+        pyo3::Python::attach(|py| {
+            let previous = PyModule::import(py, "sys")?
+                .getattr("path")?
+                .call_method0("copy")?
+                .unbind();
+            let guard = SysPathGuard { previous };
+            let site = PyModule::import(py, "site")?;
+            for dir in dirs {
+                site.call_method1("addsitedir", (dir.to_string_lossy().as_ref(),))?;
+            }
+            Ok(guard)
+        })
+    }
+
+    /// Register cleanup for [`Bridge::finalize`] under `name`.
+    ///
+    /// Registering the same name again replaces the earlier finalizer.
+    pub fn register_finalizer<F>(name: &str, finalizer: F)
+    where
+        F: FnMut() -> Result<(), BridgeError> + Send + 'static,
+    {
+        let mut finalizers = FINALIZERS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        finalizers.retain(|(existing, _)| existing != name);
+        finalizers.push((name.to_string(), Box::new(finalizer)));
+    }
+
+    /// Flush plugin resources and reset per-run state between pipeline runs.
+    ///
+    /// Completes pending loguru messages, flushes Python's stdout and stderr,
+    /// runs every registered finalizer, then clears the current plugin and
+    /// the `--since` upgrade version. The interpreter itself stays up:
+    /// PyO3 cannot re-initialize it, so the bridge remains usable afterwards.
+    /// Every finalizer runs even if one fails; the first error is returned.
+    pub fn finalize() -> Result<(), BridgeError> {
+        let mut first_error = None;
+
+        if matches!(BRIDGE_INSTANCE.get(), Some(Ok(_))) {
+            if let Err(e) = pyo3::Python::attach(Self::flush_python) {
+                logger::warn(&format!("Failed to flush Python output: {}", e));
+                first_error = Some(e);
+            }
+        }
+
+        let mut finalizers = FINALIZERS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for (name, finalizer) in finalizers.iter_mut() {
+            logger::debug(&format!("Running finalizer: {}", name));
+            if let Err(e) = finalizer() {
+                logger::warn(&format!("Finalizer '{}' failed: {}", name, e));
+                first_error.get_or_insert(e);
+            }
+        }
+        drop(finalizers);
+
+        logger::set_current_plugin(None);
+        crate::plugin_invoker::set_upgrade_since(None);
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn flush_python(py: Python<'_>) -> Result<(), BridgeError> {
+        // `complete` waits for sinks added with `enqueue=True`
+        let loguru = PyModule::import(py, "loguru")?;
+        loguru.getattr("logger")?.call_method0("complete")?;
+
+        let sys = PyModule::import(py, "sys")?;
+        for stream in ["stdout", "stderr"] {
+            let stream = sys.getattr(stream)?;
+            if !stream.is_none() {
+                stream.call_method0("flush")?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Resolve PYTHONHOME from the venv's pyvenv.cfg file.
 ///
 /// `home` in `pyvenv.cfg` is not fully consistent across creators/platforms:
 /// it may point at a prefix, a launcher dir (`bin`/`Scripts`), or an executable.
-/// We normalize it into a stable Python prefix for embedded startup.
+/// We normalize it into a stable Python prefix for embedded startup. Venvs
+/// without `home` fall back to `base-prefix`, then the parent directory of
+/// `base-executable` or `executable`.
 fn resolve_python_home(venv_path: &Path) -> Result<PathBuf, BridgeError> {
     let pyvenv_cfg = venv_path.join("pyvenv.cfg");
 
@@ -363,24 +551,45 @@ fn resolve_python_home(venv_path: &Path) -> Result<PathBuf, BridgeError> {
     let content = fs::read_to_string(&pyvenv_cfg)
         .map_err(|e| BridgeError::Initialization(format!("Failed to read pyvenv.cfg: {}", e)))?;
 
-    for line in content.lines() {
-        let line = line.trim();
-        if let Some((key, value)) = line.split_once('=') {
-            if key.trim().eq_ignore_ascii_case("home") {
-                let home_value = PathBuf::from(value.trim());
-                let python_home = normalize_python_home(&home_value);
-                logger::debug(&format!(
-                    "Resolved PYTHONHOME from pyvenv.cfg home={} -> {}",
-                    home_value.display(),
-                    python_home.display()
-                ));
-                return Ok(python_home);
-            }
-        }
+    let value_of = |wanted: &str| {
+        content.lines().find_map(|line| {
+            let (key, value) = line.trim().split_once('=')?;
+            let value = value.trim();
+            (key.trim().eq_ignore_ascii_case(wanted) && !value.is_empty())
+                .then(|| PathBuf::from(value))
+        })
+    };
+
+    // (key, whether the value is an executable rather than a directory)
+    const HOME_KEYS: &[(&str, bool)] = &[
+        ("home", false),
+        ("base-prefix", false),
+        ("base-executable", true),
+        ("executable", true),
+    ];
+    for &(key, is_executable) in HOME_KEYS {
+        let Some(home_value) = value_of(key) else {
+            continue;
+        };
+        let home_dir = if is_executable {
+            home_value
+                .parent()
+                .map_or_else(|| home_value.clone(), Path::to_path_buf)
+        } else {
+            home_value.clone()
+        };
+        let python_home = normalize_python_home(&home_dir);
+        logger::debug(&format!(
+            "Resolved PYTHONHOME from pyvenv.cfg {}={} -> {}",
+            key,
+            home_value.display(),
+            python_home.display()
+        ));
+        return Ok(python_home);
     }
 
     Err(BridgeError::Initialization(format!(
-        "Could not find 'home' in pyvenv.cfg: {}",
+        "Could not find 'home', 'base-prefix', 'base-executable' or 'executable' in pyvenv.cfg: {}",
         pyvenv_cfg.display()
     )))
 }
@@ -672,6 +881,73 @@ pub struct PythonEnvCompat {
     pub python_home: Option<PathBuf>,
 }
 
+/// Step of bridge initialization currently running, reported if it times out
+#[derive(Clone)]
+struct InitProgress(Arc<Mutex<&'static str>>);
+
+impl InitProgress {
+    fn begin(&self, step: &'static str) {
+        logger::debug(&format!("Bridge initialization: {}", step));
+        if let Ok(mut current) = self.0.lock() {
+            *current = step;
+        }
+    }
+
+    fn current(&self) -> &'static str {
+        self.0.lock().map_or("unknown", |current| *current)
+    }
+}
+
+/// Run `phase` on a worker thread and give up on it after `timeout` (`None` waits forever)
+///
+/// A timed-out phase keeps running in the background, since there is no way to
+/// stop a blocked filesystem call. The error names the last step `phase`
+/// reported through [`InitProgress::begin`].
+fn run_with_deadline<T, F>(timeout: Option<Duration>, phase: F) -> Result<T, BridgeError>
+where
+    T: Send + 'static,
+    F: FnOnce(&InitProgress) -> Result<T, BridgeError> + Send + 'static,
+{
+    let progress = InitProgress(Arc::new(Mutex::new("starting")));
+    let Some(limit) = timeout else {
+        return phase(&progress);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let worker_progress = progress.clone();
+    thread::Builder::new()
+        .name("r2x-bridge-init".to_string())
+        .spawn(move || {
+            let _ = sender.send(phase(&worker_progress));
+        })
+        .map_err(BridgeError::Io)?;
+
+    match receiver.recv_timeout(limit) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(BridgeError::Initialization(format!(
+            "Python setup did not finish within {}s (last step: {}). Check the venv's filesystem or raise `r2x config set bridge-init-timeout <secs>` (0 waits forever)",
+            limit.as_secs(),
+            progress.current()
+        ))),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(BridgeError::Initialization(format!(
+            "Python setup stopped unexpectedly (last step: {})",
+            progress.current()
+        ))),
+    }
+}
+
+/// Import each of `modules`, returning the ones that failed with their errors
+fn import_modules(py: Python<'_>, modules: &[&str]) -> Vec<(String, String)> {
+    modules
+        .iter()
+        .filter_map(|module| {
+            PyModule::import(py, *module)
+                .err()
+                .map(|e| ((*module).to_string(), e.to_string()))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::python_bridge::*;
@@ -684,6 +960,160 @@ mod tests {
         let _bridge = Bridge { _marker: () };
     }
 
+    #[test]
+    fn test_warm_up_imports_each_module_once() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        assert!(fs::write(
+            temp_dir.path().join("r2x_warm_counter.py"),
+            "import builtins\nbuiltins.r2x_warm_imports = getattr(builtins, 'r2x_warm_imports', 0) + 1\n",
+        )
+        .is_ok());
+
+        pyo3::Python::initialize();
+        let counted = pyo3::Python::attach(|py| -> PyResult<(Vec<String>, i64)> {
+            let sys = PyModule::import(py, "sys")?;
+            sys.getattr("path")?
+                .call_method1("insert", (0, temp_dir.path().to_string_lossy().as_ref()))?;
+
+            let modules = ["r2x_warm_counter", "r2x_warm_counter", "r2x_warm_missing"];
+            let mut failed: Vec<String> = import_modules(py, &modules)
+                .into_iter()
+                .map(|(module, _)| module)
+                .collect();
+            failed.extend(
+                import_modules(py, &["r2x_warm_counter"])
+                    .into_iter()
+                    .map(|(module, _)| module),
+            );
+            let imports = PyModule::import(py, "builtins")?
+                .getattr("r2x_warm_imports")?
+                .extract()?;
+            Ok((failed, imports))
+        });
+        assert!(counted.is_ok());
+        let Ok((failed, imports)) = counted else {
+            return;
+        };
+        assert_eq!(failed, vec!["r2x_warm_missing".to_string()]);
+        assert_eq!(imports, 1);
+    }
+
+    #[test]
+    fn extended_sys_path_imports_until_the_guard_drops() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        assert!(fs::write(
+            temp_dir.path().join("r2x_extra_path_demo.py"),
+            "VALUE = 7\n",
+        )
+        .is_ok());
+        let dir = temp_dir.path().to_string_lossy().to_string();
+
+        pyo3::Python::initialize();
+        let bridge = Bridge { _marker: () };
+        let on_path = |py: Python| -> PyResult<bool> {
+            PyModule::import(py, "sys")?
+                .getattr("path")?
+                .call_method1("__contains__", (dir.as_str(),))?
+                .extract()
+        };
+
+        let before = pyo3::Python::attach(|py| -> PyResult<bool> {
+            Ok(PyModule::import(py, "r2x_extra_path_demo").is_err() && !on_path(py)?)
+        });
+        assert!(before.is_ok_and(|unimportable| unimportable));
+
+        let guard = bridge.extend_sys_path(&[temp_dir.path().to_path_buf()]);
+        assert!(guard.is_ok());
+        let during = pyo3::Python::attach(|py| -> PyResult<i64> {
+            PyModule::import(py, "r2x_extra_path_demo")?
+                .getattr("VALUE")?
+                .extract()
+        });
+        assert!(during.is_ok_and(|value| value == 7));
+        drop(guard);
+
+        let after = pyo3::Python::attach(on_path);
+        assert!(after.is_ok_and(|present| !present));
+    }
+
+    #[test]
+    fn test_pre_init_phase_times_out_naming_the_last_step() {
+        let result = run_with_deadline(Some(Duration::from_millis(20)), |progress| {
+            progress.begin("reading pyvenv.cfg");
+            thread::sleep(Duration::from_millis(500));
+            Ok(())
+        });
+        assert!(matches!(
+            result,
+            Err(BridgeError::Initialization(msg)) if msg.contains("(last step: reading pyvenv.cfg)")
+        ));
+    }
+
+    #[test]
+    fn test_pre_init_phase_within_deadline_returns_its_result() {
+        let result = run_with_deadline(Some(Duration::from_secs(5)), |progress| {
+            progress.begin("locating site-packages");
+            Ok(7)
+        });
+        assert!(matches!(result, Ok(7)));
+
+        let failed: Result<(), BridgeError> = run_with_deadline(None, |_| {
+            Err(BridgeError::Initialization("no pyvenv.cfg".to_string()))
+        });
+        assert!(matches!(failed, Err(BridgeError::Initialization(msg)) if msg == "no pyvenv.cfg"));
+    }
+
+    #[test]
+    fn test_finalize_flushes_registered_sink() {
+        use std::io::Write;
+
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let sink_path = temp_dir.path().join("sink.log");
+        let Ok(file) = fs::File::create(&sink_path) else {
+            return;
+        };
+        let mut sink = std::io::BufWriter::new(file);
+        assert!(sink.write_all(b"step finished").is_ok());
+        assert_eq!(fs::read_to_string(&sink_path).ok().as_deref(), Some(""));
+
+        /// Puts back the finalizers registered before the test, dropping its own
+        struct RestoreFinalizers(Vec<(String, Finalizer)>);
+        impl Drop for RestoreFinalizers {
+            fn drop(&mut self) {
+                *FINALIZERS
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner) =
+                    std::mem::take(&mut self.0);
+            }
+        }
+        let _restore = RestoreFinalizers(std::mem::take(
+            &mut *FINALIZERS
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        ));
+
+        Bridge::register_finalizer("test-sink", move || sink.flush().map_err(BridgeError::Io));
+        Bridge::register_finalizer("test-failing", || {
+            Err(BridgeError::Python("store already closed".to_string()))
+        });
+        logger::set_current_plugin(Some("r2x-reeds.parser".to_string()));
+
+        let result = Bridge::finalize();
+        assert!(matches!(result, Err(BridgeError::Python(msg)) if msg == "store already closed"));
+        assert_eq!(
+            fs::read_to_string(&sink_path).ok().as_deref(),
+            Some("step finished")
+        );
+        assert_eq!(logger::get_current_plugin(), None);
+        assert_eq!(crate::plugin_invoker::get_upgrade_since(), None);
+    }
+
     #[test]
     fn test_get_compiled_python_version() {
         let version = get_compiled_python_version();
@@ -769,4 +1199,74 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.is_ok_and(|path| path == expected_prefix));
     }
+
+    /// Write `pyvenv_cfg` into a fresh venv and resolve its PYTHONHOME
+    fn resolve_with_cfg(
+        temp_dir: &TempDir,
+        pyvenv_cfg: &str,
+    ) -> Option<Result<PathBuf, BridgeError>> {
+        let venv_path = temp_dir.path().join(".venv");
+        fs::create_dir_all(&venv_path).ok()?;
+        fs::write(venv_path.join("pyvenv.cfg"), pyvenv_cfg).ok()?;
+        Some(resolve_python_home(&venv_path))
+    }
+
+    #[test]
+    fn test_resolve_python_home_falls_back_to_base_prefix() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let prefix = temp_dir.path().join("base");
+        let cfg = format!(
+            "include-system-site-packages = false\nbase-prefix = {}\n",
+            prefix.display()
+        );
+        let Some(result) = resolve_with_cfg(&temp_dir, &cfg) else {
+            return;
+        };
+        assert!(result.is_ok_and(|path| path == prefix));
+    }
+
+    #[test]
+    fn test_resolve_python_home_falls_back_to_base_executable() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let prefix = temp_dir.path().join("base");
+        let cfg = format!(
+            "base-executable = {}\n",
+            prefix.join("bin").join("python3.12").display()
+        );
+        let Some(result) = resolve_with_cfg(&temp_dir, &cfg) else {
+            return;
+        };
+        assert!(result.is_ok_and(|path| path == prefix));
+    }
+
+    #[test]
+    fn test_resolve_python_home_falls_back_to_executable() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let prefix = temp_dir.path().join("base");
+        let cfg = format!(
+            "executable = {}\n",
+            prefix.join("bin").join("python3").display()
+        );
+        let Some(result) = resolve_with_cfg(&temp_dir, &cfg) else {
+            return;
+        };
+        assert!(result.is_ok_and(|path| path == prefix));
+    }
+
+    #[test]
+    fn test_resolve_python_home_errors_without_any_home_key() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let Some(result) = resolve_with_cfg(&temp_dir, "version = 3.12.1\nhome =\n") else {
+            return;
+        };
+        assert!(matches!(result, Err(BridgeError::Initialization(_))));
+    }
 }
@@ -20,9 +20,9 @@ const PYTHON_BIN_DIR: &str = "Scripts";
 #[cfg(unix)]
 const PYTHON_BIN_DIR: &str = "bin";
 
-/// Candidate executable names in a venv
+/// Candidate executable names in a venv, in order of preference
 #[cfg(unix)]
-const PYTHON_EXE_CANDIDATES: &[&str] = &["python3", "python"];
+const PYTHON_EXE_CANDIDATES: &[&str] = &["python", "python3"];
 #[cfg(windows)]
 const PYTHON_EXE_CANDIDATES: &[&str] = &["python.exe", "python3.exe", "python3.12.exe"];
 
@@ -136,6 +136,11 @@ pub fn resolve_site_package_path(venv_path: &Path) -> Result<PathBuf, BridgeErro
     }
 }
 
+/// Resolve the venv's own interpreter (`bin/python` or `Scripts\python.exe`)
+///
+/// Only the venv is searched, never `PATH`, so the interpreter always matches
+/// the venv PyO3 is configured with. A missing binary, including a symlink to
+/// a base Python that was removed, is an error with instructions to rebuild.
 pub fn resolve_python_path(venv_path: &Path) -> Result<PathBuf, BridgeError> {
     // validate venv path is a valid directory
     if !venv_path.is_dir() {
@@ -143,34 +148,23 @@ pub fn resolve_python_path(venv_path: &Path) -> Result<PathBuf, BridgeError> {
     }
 
     let bin_dir = venv_path.join(PYTHON_BIN_DIR);
-    if !bin_dir.is_dir() {
-        return Err(BridgeError::Initialization(format!(
-            "Python bin directory missing: {}",
-            bin_dir.display()
-        )));
-    }
-
-    for exe in PYTHON_EXE_CANDIDATES {
-        let candidate = bin_dir.join(exe);
-        if candidate.is_file() {
-            return Ok(candidate);
-        }
-    }
-
-    if let Ok(entries) = fs::read_dir(&bin_dir) {
-        if let Some(candidate) = entries.filter_map(|e| e.ok()).map(|e| e.path()).find(|p| {
-            p.file_name()
-                .and_then(|n| n.to_str())
-                .is_some_and(|name| name.contains("python"))
-                && p.is_file()
-        }) {
-            return Ok(candidate);
-        }
+    if let Some(candidate) = PYTHON_EXE_CANDIDATES
+        .iter()
+        .map(|exe| bin_dir.join(exe))
+        .find(|candidate| candidate.is_file())
+    {
+        return Ok(candidate);
     }
 
+    let expected: Vec<String> = PYTHON_EXE_CANDIDATES
+        .iter()
+        .map(|exe| bin_dir.join(exe).display().to_string())
+        .collect();
     Err(BridgeError::Initialization(format!(
-        "Path to python binary is not valid in {}",
-        venv_path.display()
+        "No Python interpreter in venv {} (looked for {}). \
+         Recreate it with 'r2x config venv create --yes' or run with --refresh-venv.",
+        venv_path.display(),
+        expected.join(", ")
     )))
 }
 
@@ -303,6 +297,38 @@ mod tests {
         assert!(result.is_ok_and(|pp| pp.ends_with("bin/python3")));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_resolve_python_path_prefers_venv_python() {
+        let Some(temp_venv) = create_mock_venv_unix("python3.12") else {
+            return;
+        };
+        let bin_dir = temp_venv.path().join("bin");
+        assert!(fs::write(bin_dir.join("python"), "").is_ok());
+        assert!(fs::write(bin_dir.join("ipython"), "").is_ok());
+
+        let result = resolve_python_path(temp_venv.path());
+        assert!(result.is_ok_and(|pp| pp == bin_dir.join("python")));
+    }
+
+    #[test]
+    fn test_resolve_python_path_missing_interpreter() {
+        let Ok(temp_dir) = TempDir::new() else {
+            return;
+        };
+        let bin_dir = temp_dir.path().join(PYTHON_BIN_DIR);
+        assert!(fs::create_dir_all(&bin_dir).is_ok());
+        // Other tools named after python must not stand in for the interpreter
+        assert!(fs::write(bin_dir.join("python-config"), "").is_ok());
+
+        let result = resolve_python_path(temp_dir.path());
+        assert!(result.is_err_and(|e| matches!(
+            e,
+            BridgeError::Initialization(msg) if msg.contains("No Python interpreter in venv")
+                && msg.contains("r2x config venv create")
+        )));
+    }
+
     #[test]
     #[cfg(windows)]
     fn test_resolve_python_path_windows() {